@@ -0,0 +1,325 @@
+// Resolver backends: one per naming system the resolver can answer for
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use tracing::{info, warn, error};
+
+use crate::{
+    has_genesis_tld, DnsError, DnsResult, GenesisDomain, NodeLookup, ResolverType,
+    DEFAULT_TTL_SECS, MAX_RETRY_DELAY_MS,
+};
+
+/// A naming system the resolver can dispatch domains to
+pub trait ResolverBackend: Send + Sync {
+    /// Whether this backend answers for a normalized domain
+    fn handles(&self, domain: &str) -> bool;
+
+    /// Resolve a normalized domain. Caching is left to the resolver.
+    fn resolve<'a>(&'a self, domain: &'a str) -> BoxFuture<'a, Result<DnsResult, DnsError>>;
+}
+
+/// Resolves the Genesis TLDs by querying Genesis nodes, failing over between them
+pub struct GenesisBackend {
+    /// Genesis node URLs, tried in order until one answers
+    pub(crate) genesis_node_urls: Vec<String>,
+    /// Index of the last node that answered, tried first next time; shared
+    /// between clones so detached lookups fail over for the resolver too
+    pub(crate) preferred_node: Arc<AtomicUsize>,
+    /// HTTP client for API calls
+    pub(crate) client: reqwest::Client,
+    /// TLDs resolved through the Genesis blockchain
+    pub(crate) genesis_tlds: Vec<String>,
+    /// Default TTLs by TLD, for records the node serves without one
+    pub(crate) default_ttls: HashMap<String, u64>,
+    /// Retries for connection errors and 5xx responses from the node
+    pub(crate) max_retries: u32,
+    /// Initial retry delay, doubled on every attempt
+    pub(crate) base_delay_ms: u64,
+}
+
+impl Clone for GenesisBackend {
+    fn clone(&self) -> Self {
+        Self {
+            genesis_node_urls: self.genesis_node_urls.clone(),
+            preferred_node: self.preferred_node.clone(),
+            client: self.client.clone(),
+            genesis_tlds: self.genesis_tlds.clone(),
+            default_ttls: self.default_ttls.clone(),
+            max_retries: self.max_retries,
+            base_delay_ms: self.base_delay_ms,
+        }
+    }
+}
+
+impl ResolverBackend for GenesisBackend {
+    fn handles(&self, domain: &str) -> bool {
+        has_genesis_tld(domain, &self.genesis_tlds)
+    }
+
+    fn resolve<'a>(&'a self, domain: &'a str) -> BoxFuture<'a, Result<DnsResult, DnsError>> {
+        Box::pin(self.resolve_genesis_domain(domain))
+    }
+}
+
+impl GenesisBackend {
+    /// `tlds` must already be normalized with a leading dot
+    pub(crate) fn new(genesis_node_urls: Vec<String>, tlds: Vec<String>, client: reqwest::Client) -> Self {
+        Self {
+            genesis_node_urls: genesis_node_urls.into_iter()
+                .map(|url| url.trim_end_matches('/').to_string())
+                .collect(),
+            preferred_node: Arc::new(AtomicUsize::new(0)),
+            client,
+            genesis_tlds: tlds,
+            default_ttls: HashMap::new(),
+            max_retries: 3,
+            base_delay_ms: 200,
+        }
+    }
+
+    /// Resolve Genesis blockchain domain, failing over across the configured nodes
+    pub(crate) async fn resolve_genesis_domain(&self, domain: &str) -> Result<DnsResult, DnsError> {
+        info!("🌐 Resolving Genesis domain: {}", domain);
+
+        match self.query_nodes(&format!("/api/dns/resolve/{}", domain)).await? {
+            NodeLookup::Found(genesis_domain) => {
+                info!("✅ Genesis domain resolved: {}", domain);
+                self.convert_genesis_domain(genesis_domain)
+            },
+            NodeLookup::NotFound => {
+                if let Some(result) = self.resolve_wildcard(domain).await? {
+                    return Ok(result);
+                }
+                warn!("⚠️ Genesis node has no record for {}", domain);
+                Err(DnsError::UnsupportedDomain(domain.to_string()))
+            }
+        }
+    }
+
+    /// Look for a wildcard record on the parent domains, closest parent first
+    pub(crate) async fn resolve_wildcard(&self, domain: &str) -> Result<Option<DnsResult>, DnsError> {
+        for parent in self.wildcard_parents(domain) {
+            let path = format!("/api/dns/resolve/{}?wildcard=true", parent);
+            if let NodeLookup::Found(record) = self.query_nodes::<GenesisDomain>(&path).await? {
+                if record.wildcard {
+                    info!("✅ {} matched the wildcard record of {}", domain, parent);
+                    let mut result = self.convert_genesis_domain(record)?;
+                    result.domain = domain.to_string();
+                    result.original_domain = domain.to_string();
+                    return Ok(Some(result));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Parent domains that may hold a wildcard record, e.g. "api.mysite.genesis"
+    /// and "mysite.genesis" for "v1.api.mysite.genesis". The bare TLD is never included.
+    pub(crate) fn wildcard_parents(&self, domain: &str) -> Vec<String> {
+        let mut parents = Vec::new();
+        let mut rest = domain;
+
+        while let Some((_, parent)) = rest.split_once('.') {
+            if !parent.contains('.') || !self.handles(parent) {
+                break;
+            }
+            parents.push(parent.to_string());
+            rest = parent;
+        }
+
+        parents
+    }
+
+    /// Query each node in turn, starting with the last one that answered.
+    /// A 404 is a definitive answer and is returned without asking other nodes.
+    pub(crate) async fn query_nodes<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<NodeLookup<T>, DnsError> {
+        let node_count = self.genesis_node_urls.len();
+        if node_count == 0 {
+            return Err(DnsError::AllNodesFailed(vec!["no Genesis nodes configured".to_string()]));
+        }
+
+        let preferred = self.preferred_node.load(Ordering::Relaxed) % node_count;
+        let mut failures = Vec::new();
+
+        for offset in 0..node_count {
+            let index = (preferred + offset) % node_count;
+            let node_url = &self.genesis_node_urls[index];
+
+            match self.query_node(node_url, path).await {
+                Ok(lookup) => {
+                    if index != preferred {
+                        info!("🔀 Switching to Genesis node {}", node_url);
+                    }
+                    self.preferred_node.store(index, Ordering::Relaxed);
+                    return Ok(lookup);
+                },
+                Err(e) if node_count == 1 => {
+                    // No other node to fail over to, keep the original error
+                    return Err(e);
+                },
+                Err(e) => {
+                    warn!("⚠️ Genesis node {} failed: {}", node_url, e);
+                    failures.push(format!("{}: {}", node_url, e));
+                }
+            }
+        }
+
+        error!("❌ All {} Genesis nodes failed", node_count);
+        Err(DnsError::AllNodesFailed(failures))
+    }
+
+    /// Query a single Genesis node
+    pub(crate) async fn query_node<T: serde::de::DeserializeOwned>(&self, node_url: &str, path: &str) -> Result<NodeLookup<T>, DnsError> {
+        let response = self.send_with_retry(&format!("{}{}", node_url, path)).await?;
+        let status = response.status();
+
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(NodeLookup::NotFound);
+        }
+        if !status.is_success() {
+            return Err(DnsError::NodeError(status.to_string()));
+        }
+
+        match response.json::<T>().await {
+            Ok(record) => Ok(NodeLookup::Found(record)),
+            Err(e) => {
+                error!("❌ Failed to parse Genesis node response: {}", e);
+                Err(DnsError::InvalidResponse(e.to_string()))
+            }
+        }
+    }
+
+    /// Send a GET request to the Genesis node, retrying connection errors and
+    /// 5xx responses with exponential backoff. Other responses (including 4xx)
+    /// are handed back to the caller untouched since retrying won't help.
+    pub(crate) async fn send_with_retry(&self, url: &str) -> Result<reqwest::Response, DnsError> {
+        let mut attempt = 0;
+
+        loop {
+            let failure = match self.client.get(url).send().await {
+                Ok(response) if response.status().is_server_error() && attempt < self.max_retries => {
+                    format!("node returned {}", response.status())
+                },
+                Ok(response) => return Ok(response),
+                Err(e) if attempt < self.max_retries => e.to_string(),
+                Err(e) => {
+                    error!("❌ Failed to connect to Genesis node: {}", e);
+                    return Err(DnsError::ConnectionError(e.to_string()));
+                }
+            };
+
+            attempt += 1;
+            let delay = self.retry_delay(attempt);
+            warn!(
+                "⚠️ Genesis node request failed ({}), retry attempt {}/{} in {}ms",
+                failure, attempt, self.max_retries, delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Backoff delay before the given retry attempt (1-based)
+    pub(crate) fn retry_delay(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(16);
+        Duration::from_millis(self.base_delay_ms.saturating_mul(factor).min(MAX_RETRY_DELAY_MS))
+    }
+
+    /// Default TTL for a domain, preferring the longest matching TLD
+    pub(crate) fn default_ttl_for(&self, domain: &str) -> u64 {
+        let domain = domain.to_lowercase();
+        self.default_ttls.iter()
+            .filter(|(tld, _)| domain.ends_with(tld.as_str()))
+            .max_by_key(|(tld, _)| tld.len())
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(DEFAULT_TTL_SECS)
+    }
+
+    /// Convert Genesis domain to DNS result
+    pub(crate) fn convert_genesis_domain(&self, genesis_domain: GenesisDomain) -> Result<DnsResult, DnsError> {
+        #[cfg(feature = "signature-verification")]
+        Self::verify_content_hash(&genesis_domain)?;
+
+        // Accept both the legacy single `ip_address` and the `ip_addresses` list
+        let mut ip_addresses: Vec<IpAddr> = Vec::new();
+        for ip_str in genesis_domain.ip_address.iter().chain(genesis_domain.ip_addresses.iter()) {
+            match ip_str.parse() {
+                Ok(ip) if !ip_addresses.contains(&ip) => ip_addresses.push(ip),
+                Ok(_) => {},
+                Err(_) => warn!("⚠️ Ignoring invalid address for {}: {}", genesis_domain.name, ip_str),
+            }
+        }
+        let ip_address = ip_addresses.first().copied();
+
+        let ttl = genesis_domain.ttl.unwrap_or_else(|| self.default_ttl_for(&genesis_domain.name));
+
+        let heuristic = if ip_address.is_some() {
+            ResolverType::Genesis
+        } else {
+            ResolverType::IPFS
+        };
+        let resolver_type = match genesis_domain.resolver_type.as_deref() {
+            None => heuristic,
+            Some(value) => match ResolverType::parse(value) {
+                Some(ResolverType::Hybrid) if ip_address.is_none() || genesis_domain.content_hash.is_none() => {
+                    warn!("⚠️ Hybrid record for {} lacks an address or content hash", genesis_domain.name);
+                    heuristic
+                },
+                Some(resolver_type) => resolver_type,
+                None => {
+                    warn!("⚠️ Ignoring unknown resolver type for {}: {}", genesis_domain.name, value);
+                    heuristic
+                },
+            },
+        };
+
+        Ok(DnsResult {
+            original_domain: genesis_domain.name.clone(),
+            domain: genesis_domain.name,
+            ip_address,
+            ip_addresses,
+            content_hash: genesis_domain.content_hash,
+            resolver_type,
+            ttl,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            eth_address: None,
+            next_index: Arc::default(),
+        })
+    }
+
+    /// Check that the content hash was signed by the domain owner.
+    /// The owner field carries the hex-encoded ed25519 public key.
+    #[cfg(feature = "signature-verification")]
+    pub(crate) fn verify_content_hash(genesis_domain: &GenesisDomain) -> Result<(), DnsError> {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        let Some(content_hash) = &genesis_domain.content_hash else {
+            return Ok(());
+        };
+        let fail = |reason: &str| DnsError::IntegrityCheckFailed(format!("{}: {}", genesis_domain.name, reason));
+
+        let signature_hex = genesis_domain.content_hash_signature.as_deref()
+            .ok_or_else(|| fail("content hash is not signed"))?;
+        let owner_hex = genesis_domain.owner.trim_start_matches("0x");
+
+        let key_bytes: [u8; 32] = hex::decode(owner_hex).ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| fail("owner is not an ed25519 public key"))?;
+        let signature_bytes: [u8; 64] = hex::decode(signature_hex.trim_start_matches("0x")).ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| fail("malformed content hash signature"))?;
+
+        let key = VerifyingKey::from_bytes(&key_bytes)
+            .map_err(|_| fail("owner is not an ed25519 public key"))?;
+        key.verify(content_hash.as_bytes(), &Signature::from_bytes(&signature_bytes))
+            .map_err(|_| {
+                warn!("⚠️ Content hash signature mismatch for {}", genesis_domain.name);
+                fail("content hash signature does not match the owner key")
+            })
+    }
+}
@@ -0,0 +1,148 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::{DnsError, DnsResult, ResolverType};
+
+/// Cloudflare's public DNS-over-HTTPS endpoint
+pub const DEFAULT_DOH_ENDPOINT: &str = "https://cloudflare-dns.com/dns-query";
+
+/// DNS RCODE for a domain that doesn't exist
+const RCODE_NXDOMAIN: u32 = 3;
+
+/// JSON response of a DoH server (`application/dns-json`)
+#[derive(Debug, Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    #[serde(rename = "TTL")]
+    ttl: u64,
+    data: String,
+}
+
+/// DNS-over-HTTPS resolver for traditional domains, so lookups don't leak
+/// to whatever resolver the operating system is configured with
+#[derive(Debug, Clone)]
+pub struct DohResolver {
+    /// DoH endpoint URL
+    endpoint: String,
+    /// HTTP client for DoH queries
+    client: reqwest::Client,
+}
+
+impl DohResolver {
+    pub fn new(endpoint: String, client: reqwest::Client) -> Self {
+        Self { endpoint, client }
+    }
+
+    /// Get the DoH endpoint URL
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Resolve a domain over HTTPS, trying A records first and AAAA second
+    pub async fn resolve(&self, domain: &str) -> Result<DnsResult, DnsError> {
+        info!("🔒 Resolving {} over DoH ({})", domain, self.endpoint);
+
+        let (mut ip_addresses, mut ttl) = self.query(domain, "A").await?;
+        if ip_addresses.is_empty() {
+            (ip_addresses, ttl) = self.query(domain, "AAAA").await?;
+        }
+
+        let ip = *ip_addresses.first()
+            .ok_or_else(|| DnsError::NoResults(domain.to_string()))?;
+        info!("✅ DoH resolved: {} -> {}", domain, ip);
+
+        Ok(DnsResult {
+            domain: domain.to_string(),
+            original_domain: domain.to_string(),
+            ip_address: Some(ip),
+            ip_addresses,
+            content_hash: None,
+            resolver_type: ResolverType::Traditional,
+            ttl: ttl.unwrap_or(300),
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            eth_address: None,
+            next_index: Arc::default(),
+        })
+    }
+
+    /// Run a single query. The JSON flavour of DoH is only served over GET;
+    /// POST is reserved for the binary wire format.
+    async fn query(&self, domain: &str, record_type: &str) -> Result<(Vec<IpAddr>, Option<u64>), DnsError> {
+        let response = self.client
+            .get(&self.endpoint)
+            .query(&[("name", domain), ("type", record_type)])
+            .header(reqwest::header::ACCEPT, "application/dns-json")
+            .send()
+            .await
+            .map_err(|e| {
+                warn!("⚠️ DoH request for {} failed: {}", domain, e);
+                DnsError::ConnectionError(e.to_string())
+            })?;
+
+        if !response.status().is_success() {
+            warn!("⚠️ DoH server returned error for {}: {}", domain, response.status());
+            return Err(DnsError::NodeError(response.status().to_string()));
+        }
+
+        let body: DohResponse = response.json().await
+            .map_err(|e| DnsError::InvalidResponse(e.to_string()))?;
+
+        match body.status {
+            0 => {},
+            RCODE_NXDOMAIN => return Err(DnsError::NoResults(domain.to_string())),
+            rcode => return Err(DnsError::ResolutionFailed(format!("DoH RCODE {} for {}", rcode, domain))),
+        }
+
+        let mut ttl: Option<u64> = None;
+        let mut addresses = Vec::new();
+        // CNAME chains come back as extra answers, only keep address records
+        for answer in body.answer.iter().filter(|a| a.record_type == 1 || a.record_type == 28) {
+            match answer.data.parse::<IpAddr>() {
+                Ok(ip) => {
+                    addresses.push(ip);
+                    ttl = Some(ttl.map_or(answer.ttl, |t| t.min(answer.ttl)));
+                },
+                Err(_) => debug!("Skipping unparsable DoH answer: {}", answer.data),
+            }
+        }
+
+        Ok((addresses, ttl))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_doh_response() {
+        let body: DohResponse = serde_json::from_str(r#"{
+            "Status": 0,
+            "Answer": [
+                { "name": "example.com", "type": 5, "TTL": 300, "data": "cdn.example.com." },
+                { "name": "cdn.example.com", "type": 1, "TTL": 120, "data": "93.184.216.34" }
+            ]
+        }"#).unwrap();
+
+        assert_eq!(body.status, 0);
+        assert_eq!(body.answer.len(), 2);
+        assert_eq!(body.answer[1].record_type, 1);
+        assert_eq!(body.answer[1].ttl, 120);
+
+        let nxdomain: DohResponse = serde_json::from_str(r#"{ "Status": 3 }"#).unwrap();
+        assert_eq!(nxdomain.status, RCODE_NXDOMAIN);
+        assert!(nxdomain.answer.is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use serde::Deserialize;
+use tiny_keccak::{Hasher, Keccak};
+use tracing::{debug, info, warn};
+
+use crate::{DnsError, DnsResult, ResolverBackend, ResolverType};
+
+/// ENS registry, deployed at the same address on mainnet and the testnets
+pub const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+/// TLD handled by the ENS backend
+pub const ENS_TLD: &str = ".eth";
+
+/// `resolver(bytes32)` on the registry
+const RESOLVER_SELECTOR: &str = "0178b8bf";
+/// `addr(bytes32)` on a resolver
+const ADDR_SELECTOR: &str = "3b3b57de";
+/// `contenthash(bytes32)` on a resolver (EIP-1577)
+const CONTENTHASH_SELECTOR: &str = "bc1c58d1";
+
+/// Multicodec prefix of an IPFS content hash, as an unsigned varint
+const IPFS_NAMESPACE: [u8; 2] = [0xe3, 0x01];
+
+/// ENS records are cached for a fixed time, they carry no TTL of their own
+const ENS_TTL_SECS: u64 = 300;
+
+/// JSON-RPC response of an Ethereum node
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<String>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+/// Resolves `.eth` names by reading the ENS contracts through an Ethereum
+/// JSON-RPC endpoint. Names must have a content hash, which is served like
+/// an IPFS record of a Genesis domain.
+#[derive(Debug, Clone)]
+pub struct EnsBackend {
+    /// Ethereum JSON-RPC endpoint URL
+    rpc_url: String,
+    /// HTTP client for RPC calls
+    client: reqwest::Client,
+}
+
+impl EnsBackend {
+    pub fn new(rpc_url: String, client: reqwest::Client) -> Self {
+        Self { rpc_url, client }
+    }
+
+    /// Get the Ethereum RPC endpoint URL
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Look up the resolver of a name, then its content hash and address
+    pub async fn resolve_ens(&self, domain: &str) -> Result<DnsResult, DnsError> {
+        info!("🔷 Resolving ENS name: {}", domain);
+        let node = hex::encode(namehash(domain));
+
+        let resolver = decode_address(&self.eth_call(ENS_REGISTRY, RESOLVER_SELECTOR, &node).await?)?;
+        if resolver.trim_start_matches("0x").bytes().all(|b| b == b'0') {
+            return Err(DnsError::NoResults(domain.to_string()));
+        }
+        debug!("ENS resolver for {} is {}", domain, resolver);
+
+        let encoded = decode_bytes(&self.eth_call(&resolver, CONTENTHASH_SELECTOR, &node).await?)?;
+        if encoded.is_empty() {
+            warn!("⚠️ ENS name {} has no content hash", domain);
+            return Err(DnsError::NoResults(domain.to_string()));
+        }
+        let content_hash = decode_content_hash(&encoded)?;
+
+        // The address is informational, a resolver without `addr` is fine
+        let eth_address = match self.eth_call(&resolver, ADDR_SELECTOR, &node).await {
+            Ok(data) => decode_address(&data).ok()
+                .filter(|address| !address.trim_start_matches("0x").bytes().all(|b| b == b'0')),
+            Err(e) => {
+                debug!("No ENS address for {}: {}", domain, e);
+                None
+            }
+        };
+
+        info!("✅ ENS name resolved: {} -> ipfs://{}", domain, content_hash);
+        Ok(DnsResult {
+            domain: domain.to_string(),
+            original_domain: domain.to_string(),
+            ip_address: None,
+            ip_addresses: Vec::new(),
+            content_hash: Some(content_hash),
+            eth_address,
+            resolver_type: ResolverType::IPFS,
+            ttl: ENS_TTL_SECS,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            next_index: Arc::default(),
+        })
+    }
+
+    /// Call a contract function taking a single `bytes32` argument
+    async fn eth_call(&self, to: &str, selector: &str, node: &str) -> Result<Vec<u8>, DnsError> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_call",
+            "params": [{ "to": to, "data": format!("0x{}{}", selector, node) }, "latest"],
+        });
+
+        let response = self.client.post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| DnsError::ConnectionError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(DnsError::NodeError(response.status().to_string()));
+        }
+
+        let body: RpcResponse = response.json().await
+            .map_err(|e| DnsError::InvalidResponse(e.to_string()))?;
+        if let Some(error) = body.error {
+            return Err(DnsError::NodeError(error.message));
+        }
+        let result = body.result.ok_or_else(|| DnsError::InvalidResponse("empty RPC result".to_string()))?;
+        hex::decode(result.trim_start_matches("0x")).map_err(|e| DnsError::InvalidResponse(e.to_string()))
+    }
+}
+
+impl ResolverBackend for EnsBackend {
+    fn handles(&self, domain: &str) -> bool {
+        domain.ends_with(ENS_TLD)
+    }
+
+    fn resolve<'a>(&'a self, domain: &'a str) -> BoxFuture<'a, Result<DnsResult, DnsError>> {
+        Box::pin(self.resolve_ens(domain))
+    }
+}
+
+/// ENS namehash of a normalized name
+pub fn namehash(name: &str) -> [u8; 32] {
+    let mut node = [0u8; 32];
+    for label in name.rsplit('.').filter(|label| !label.is_empty()) {
+        let mut buffer = [0u8; 64];
+        buffer[..32].copy_from_slice(&node);
+        buffer[32..].copy_from_slice(&keccak256(label.as_bytes()));
+        node = keccak256(&buffer);
+    }
+    node
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut output);
+    output
+}
+
+/// ABI-decode an `address` return value
+fn decode_address(data: &[u8]) -> Result<String, DnsError> {
+    let word = data.get(..32).ok_or_else(|| DnsError::InvalidResponse("short address".to_string()))?;
+    Ok(format!("0x{}", hex::encode(&word[12..])))
+}
+
+/// ABI-decode a `bytes` return value; an empty result means no record
+fn decode_bytes(data: &[u8]) -> Result<Vec<u8>, DnsError> {
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+    let malformed = || DnsError::InvalidResponse("malformed bytes in contract response".to_string());
+    let word = |offset: usize| -> Result<usize, DnsError> {
+        let bytes = data.get(offset..offset.checked_add(32).ok_or_else(malformed)?).ok_or_else(malformed)?;
+        // Offsets and lengths beyond usize would never fit in the response anyway
+        if bytes[..24].iter().any(|&b| b != 0) {
+            return Err(malformed());
+        }
+        Ok(bytes[24..].iter().fold(0usize, |value, &b| (value << 8) | b as usize))
+    };
+
+    let offset = word(0)?;
+    let length = word(offset)?;
+    let start = offset + 32;
+    let end = start.checked_add(length).ok_or_else(malformed)?;
+    data.get(start..end).map(<[u8]>::to_vec).ok_or_else(malformed)
+}
+
+/// Turn an EIP-1577 content hash into the CIDv1 string used for IPFS paths
+fn decode_content_hash(encoded: &[u8]) -> Result<String, DnsError> {
+    let cid = encoded.strip_prefix(&IPFS_NAMESPACE[..])
+        .ok_or_else(|| DnsError::InvalidResponse(format!("unsupported content hash namespace 0x{}", hex::encode(&encoded[..encoded.len().min(2)]))))?;
+
+    // A bare sha2-256 multihash is a CIDv0; upgrade it to CIDv1 dag-pb
+    let cid = if cid.starts_with(&[0x12, 0x20]) {
+        [&[0x01, 0x70][..], cid].concat()
+    } else {
+        cid.to_vec()
+    };
+    Ok(format!("b{}", base32_lower(&cid)))
+}
+
+/// RFC 4648 base32, lowercase and unpadded as in multibase
+fn base32_lower(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut output = String::with_capacity((data.len() * 8).div_ceil(5));
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namehash() {
+        assert_eq!(namehash(""), [0u8; 32]);
+        assert_eq!(hex::encode(namehash("eth")), "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae");
+        assert_eq!(hex::encode(namehash("foo.eth")), "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f");
+    }
+
+    #[test]
+    fn test_decode_content_hash() {
+        let encoded = hex::decode("e3010170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f").unwrap();
+        assert_eq!(
+            decode_content_hash(&encoded).unwrap(),
+            "bafybeibj6lixxzqtsb45ysdjnupvqkufgdvzqbnvmhw2kf7cfkesy7r7d4"
+        );
+
+        // A CIDv0 multihash maps to the same CIDv1
+        let v0 = [&IPFS_NAMESPACE[..], &encoded[4..]].concat();
+        assert_eq!(decode_content_hash(&v0).unwrap(), decode_content_hash(&encoded).unwrap());
+
+        // Swarm and other namespaces have no IPFS path
+        assert!(decode_content_hash(&hex::decode("e40101fa011b20").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_decode_abi_bytes() {
+        let mut data = vec![0u8; 96];
+        data[31] = 0x20;
+        data[63] = 3;
+        data[64..67].copy_from_slice(b"abc");
+        assert_eq!(decode_bytes(&data).unwrap(), b"abc");
+        assert!(decode_bytes(&data[..40]).is_err());
+        assert!(decode_bytes(&[]).unwrap().is_empty());
+    }
+}
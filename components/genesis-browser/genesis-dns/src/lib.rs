@@ -1,29 +1,289 @@
 use std::net::IpAddr;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
-use tracing::{info, warn, error, debug};
+use tokio::sync::mpsc;
+use tracing::{info, warn, debug};
+
+#[cfg(feature = "doh-fallback")]
+pub mod doh;
+
+pub mod backend;
+pub use backend::{GenesisBackend, ResolverBackend};
+
+pub mod urls;
+pub use urls::{normalize_url, UrlError};
+
+#[cfg(feature = "doh-fallback")]
+pub use doh::{DohResolver, DEFAULT_DOH_ENDPOINT};
+
+#[cfg(feature = "live-updates")]
+pub mod live_updates;
+
+#[cfg(feature = "ens")]
+pub mod ens;
+#[cfg(feature = "ens")]
+pub use ens::EnsBackend;
+
+/// Top-level domains served by the Genesis blockchain
+pub const DEFAULT_GENESIS_TLDS: [&str; 5] = [".genesis", ".free", ".web", ".defi", ".dao"];
+
+/// Public gateways content hashes are served through, tried in order
+pub const DEFAULT_IPFS_GATEWAYS: [&str; 2] = ["https://ipfs.io/ipfs/", "https://cloudflare-ipfs.com/ipfs/"];
+
+/// Gateway of a locally running IPFS node, selected with the name `local`
+pub const LOCAL_IPFS_GATEWAY: &str = "http://localhost:8080/ipfs/";
+
+/// RPC API of a locally running IPFS node, used for pinning
+pub const LOCAL_IPFS_API: &str = "http://localhost:5001";
+
+/// Pinning fetches a whole site, so it may take far longer than a lookup
+#[cfg(feature = "pinned-offline")]
+const PIN_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Upper bound for the exponential backoff between node retries
+const MAX_RETRY_DELAY_MS: u64 = 5_000;
+
+/// Number of lookups `resolve_many` keeps in flight at once
+const MAX_CONCURRENT_LOOKUPS: usize = 8;
+
+/// TTL used when neither the node nor a per-TLD default provides one
+const DEFAULT_TTL_SECS: u64 = 3600;
+
+/// Default upper bound on the number of cached domains
+const DEFAULT_MAX_CACHE_ENTRIES: usize = 10_000;
+
+/// How long a single node or DoH request may take by default
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Check if a domain ends with one of the given Genesis TLDs
+pub fn has_genesis_tld<S: AsRef<str>>(domain: &str, tlds: &[S]) -> bool {
+    tlds.iter().any(|tld| domain.ends_with(tld.as_ref()))
+}
+
+/// Normalize a domain to its lowercase ASCII (punycode) form, so that
+/// `café.genesis` and `xn--caf-dma.genesis` share a cache entry
+pub fn normalize_domain(domain: &str) -> Result<String, DnsError> {
+    let trimmed = domain.trim().trim_end_matches('.');
+    if trimmed.is_empty() {
+        return Err(DnsError::InvalidDomainName(domain.to_string()));
+    }
+
+    idna::domain_to_ascii(trimmed)
+        .map_err(|e| DnsError::InvalidDomainName(format!("{}: {}", domain, e)))
+}
+
+/// Validate a proxy URL and turn it into a proxy for all requests
+pub fn parse_proxy(proxy_url: &str) -> Result<reqwest::Proxy, DnsError> {
+    let url = reqwest::Url::parse(proxy_url)
+        .map_err(|e| DnsError::InvalidProxy(format!("{}: {}", proxy_url, e)))?;
+    if !matches!(url.scheme(), "socks5" | "socks5h" | "http" | "https") {
+        return Err(DnsError::InvalidProxy(format!(
+            "{}: unsupported scheme {}, expected socks5:// or http://", proxy_url, url.scheme()
+        )));
+    }
+    if url.host_str().map_or(true, str::is_empty) {
+        return Err(DnsError::InvalidProxy(format!("{}: missing host", proxy_url)));
+    }
+
+    reqwest::Proxy::all(url).map_err(|e| DnsError::InvalidProxy(format!("{}: {}", proxy_url, e)))
+}
+
+/// Read every entry of a cache file written by `save_cache_to`, expired ones included
+pub fn read_cache_file(path: &Path) -> Result<HashMap<String, DnsResult>, DnsError> {
+    read_cache(path).map(|cache| cache.entries)
+}
+
+/// A cache file: the cached entries and the lookup counters, so the
+/// hit/miss ratio covers more than one session
+#[derive(Serialize, Deserialize)]
+struct CacheFile<E> {
+    entries: E,
+    #[serde(default)]
+    hits: u64,
+    #[serde(default)]
+    misses: u64,
+    #[serde(default)]
+    evictions: u64,
+}
+
+/// Cache files written before the counters were kept hold just the entries
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredCache {
+    WithStats(CacheFile<HashMap<String, DnsResult>>),
+    EntriesOnly(HashMap<String, DnsResult>),
+}
+
+fn read_cache(path: &Path) -> Result<CacheFile<HashMap<String, DnsResult>>, DnsError> {
+    let data = std::fs::read(path)
+        .map_err(|e| DnsError::CacheError(format!("{}: {}", path.display(), e)))?;
+    let stored = serde_json::from_slice(&data)
+        .map_err(|e| DnsError::CacheError(format!("{}: {}", path.display(), e)))?;
+    Ok(match stored {
+        StoredCache::WithStats(cache) => cache,
+        StoredCache::EntriesOnly(entries) => CacheFile { entries, hits: 0, misses: 0, evictions: 0 },
+    })
+}
+
+/// Record the domain as the caller spelled it on a successful result
+fn with_original_domain(result: Result<DnsResult, DnsError>, original: &str) -> Result<DnsResult, DnsError> {
+    result.map(|mut result| {
+        result.original_domain = original.to_string();
+        result
+    })
+}
+
+/// Start of a lookup through `GenesisDnsResolver::begin_lookup`
+pub enum Lookup {
+    /// Answered from the cache, or the domain was invalid
+    Done(Result<DnsResult, DnsError>),
+    /// Needs the network; run it and hand it to `finish_lookup`
+    Pending(PendingLookup),
+}
+
+/// A lookup that missed the cache, detached from its resolver so the network
+/// query can run while the resolver is free for other lookups
+pub struct PendingLookup {
+    resolver: GenesisDnsResolver,
+    domain: String,
+    original_domain: String,
+}
+
+impl PendingLookup {
+    /// Query the network for the domain
+    pub async fn run(self) -> FinishedLookup {
+        let result = self.resolver.lookup(&self.domain).await;
+        FinishedLookup { domain: self.domain, original_domain: self.original_domain, result }
+    }
+}
+
+/// Network answer of a `PendingLookup`, to be cached by `finish_lookup`
+pub struct FinishedLookup {
+    domain: String,
+    original_domain: String,
+    result: Result<DnsResult, DnsError>,
+}
 
 /// Genesis DNS Resolver - Blockchain-based domain resolution
 pub struct GenesisDnsResolver {
-    /// Genesis node URL
-    genesis_node_url: String,
-    /// HTTP client for API calls
-    client: reqwest::Client,
+    /// Resolves the Genesis TLDs through the configured nodes
+    genesis: GenesisBackend,
+    /// Further backends, consulted before the Genesis backend
+    backends: Vec<Arc<dyn ResolverBackend>>,
+    /// IPFS gateway URL prefixes, tried in order
+    ipfs_gateways: Vec<String>,
+    /// RPC API of the IPFS node content hashes are pinned on
+    #[cfg(feature = "pinned-offline")]
+    ipfs_api_url: Option<String>,
+    /// Pin every content hash resolved from the network
+    #[cfg(feature = "pinned-offline")]
+    auto_pin: bool,
+    /// Content hashes already pinned (or tried) this run
+    #[cfg(feature = "pinned-offline")]
+    pinned: HashSet<String>,
+    /// HTTP client for gateway probes, without the node headers
+    gateway_client: reqwest::Client,
+    /// Proxy all requests go through, if any
+    proxy: Option<reqwest::Proxy>,
+    /// Headers sent with every node query, e.g. `Authorization`
+    node_headers: reqwest::header::HeaderMap,
+    /// User-Agent of every request the resolver makes, if not reqwest's
+    user_agent: Option<reqwest::header::HeaderValue>,
+    /// Limit for each HTTP request, so a hung node can't stall a lookup forever
+    request_timeout: Duration,
     /// Cache for resolved domains
     cache: HashMap<String, DnsResult>,
+    /// Access tick of every cached domain, used to find the least recently used entry
+    cache_access: HashMap<String, u64>,
+    /// Cached domains by access tick, oldest first, so eviction doesn't scan the cache
+    cache_recency: BTreeMap<u64, String>,
+    /// Monotonic counter handed out as access ticks
+    access_clock: u64,
+    /// Maximum number of cached domains before LRU eviction kicks in
+    max_cache_entries: usize,
+    /// Entries evicted to stay within `max_cache_entries`
+    evictions: u64,
+    /// Domains known to point at an address, from reverse lookups
+    reverse_cache: HashMap<IpAddr, ReverseEntry>,
+    /// Unregistered Genesis domains and when their negative entry expires
+    negative_cache: HashMap<String, u64>,
+    /// Lifetime of negative cache entries in seconds
+    negative_ttl: u64,
     /// Enable traditional DNS fallback
     fallback_enabled: bool,
+    /// DNS-over-HTTPS resolver for traditional domains
+    #[cfg(feature = "doh-fallback")]
+    doh_resolver: DohResolver,
+    /// Lookups answered from the cache
+    cache_hits: u64,
+    /// Lookups that had to go to the network
+    cache_misses: u64,
+    /// Optional observer for resolution events
+    event_sender: Option<mpsc::Sender<ResolutionEvent>>,
+    /// Network resolutions by resolver type
+    resolution_counts: BTreeMap<&'static str, u64>,
+    /// Network failures by error kind
+    error_counts: BTreeMap<&'static str, u64>,
+    /// Serve expired entries while they are refreshed in the background
+    stale_while_revalidate: bool,
+    /// How long past expiry an entry may still be served stale
+    stale_grace_secs: u64,
+    /// Runtime that background refreshes are spawned on
+    refresh_handle: Option<tokio::runtime::Handle>,
+    /// Domains with a background refresh in flight
+    refreshing: HashSet<String>,
+    /// Completed background refreshes, applied on the next resolve
+    refresh_sender: mpsc::UnboundedSender<(String, Result<DnsResult, DnsError>)>,
+    refresh_receiver: mpsc::UnboundedReceiver<(String, Result<DnsResult, DnsError>)>,
+    /// Domains a node pushed an update for, invalidated on the next resolve
+    #[cfg(feature = "live-updates")]
+    update_sender: mpsc::UnboundedSender<String>,
+    #[cfg(feature = "live-updates")]
+    update_receiver: mpsc::UnboundedReceiver<String>,
+    /// Background task holding the live update connection
+    #[cfg(feature = "live-updates")]
+    update_task: Option<tokio::task::JoinHandle<()>>,
 }
 
 /// DNS resolution result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DnsResult {
+    /// ASCII (punycode) form of the domain, used as the cache key
     pub domain: String,
+    /// The domain as it was requested, possibly containing Unicode
+    #[serde(default)]
+    pub original_domain: String,
+    /// Primary address (the first entry of `ip_addresses`)
     pub ip_address: Option<IpAddr>,
+    /// Every address the domain points to, for load-balanced services
+    #[serde(default)]
+    pub ip_addresses: Vec<IpAddr>,
     pub content_hash: Option<String>,
+    /// Ethereum address an ENS name points to
+    #[serde(default)]
+    pub eth_address: Option<String>,
     pub resolver_type: ResolverType,
     pub ttl: u64,
     pub timestamp: u64,
+    /// Round-robin cursor, shared between clones of a cached result
+    #[serde(skip)]
+    next_index: Arc<AtomicUsize>,
+}
+
+impl DnsResult {
+    /// Get the next address in round-robin order.
+    /// Falls back to `ip_address` when no address list is available.
+    pub fn next_address(&self) -> Option<IpAddr> {
+        if self.ip_addresses.is_empty() {
+            return self.ip_address;
+        }
+        let index = self.next_index.fetch_add(1, Ordering::Relaxed);
+        Some(self.ip_addresses[index % self.ip_addresses.len()])
+    }
 }
 
 impl std::fmt::Display for DnsResult {
@@ -38,6 +298,54 @@ impl std::fmt::Display for DnsResult {
     }
 }
 
+/// State of a Genesis node, as reported by `check_node_health`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeHealth {
+    /// `/health` answered with a success status
+    pub online: bool,
+    /// Node software version from `/info`
+    pub version: Option<String>,
+    /// Latest block the node has seen, from `/info`
+    pub block_height: Option<u64>,
+    /// Round trip of the `/health` request
+    pub latency_ms: u64,
+}
+
+impl std::fmt::Display for NodeHealth {
+    /// e.g. "online, v1.4.2, block 90210, 42 ms"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.online {
+            return write!(f, "offline");
+        }
+        write!(f, "online")?;
+        if let Some(version) = &self.version {
+            write!(f, ", v{}", version.trim_start_matches('v'))?;
+        }
+        if let Some(height) = self.block_height {
+            write!(f, ", block {}", height)?;
+        }
+        write!(f, ", {} ms", self.latency_ms)
+    }
+}
+
+/// Body of a node's `/info` response; unknown fields are ignored
+#[derive(Debug, Default, Deserialize)]
+struct NodeInfo {
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default, alias = "blockHeight", alias = "height")]
+    block_height: Option<u64>,
+}
+
+/// Resolution events, for observers such as a network inspector
+#[derive(Debug, Clone)]
+pub enum ResolutionEvent {
+    CacheHit { domain: String },
+    ResolvedGenesis { domain: String, result: DnsResult },
+    ResolvedTraditional { domain: String, result: DnsResult },
+    Failed { domain: String, error: String },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ResolverType {
     Genesis,
@@ -46,6 +354,51 @@ pub enum ResolverType {
     Hybrid,
 }
 
+impl ResolverType {
+    /// Short lowercase name, e.g. for metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResolverType::Genesis => "genesis",
+            ResolverType::Traditional => "traditional",
+            ResolverType::IPFS => "ipfs",
+            ResolverType::Hybrid => "hybrid",
+        }
+    }
+
+    /// Parse the resolver type name a Genesis node sends, case-insensitively
+    fn parse(value: &str) -> Option<ResolverType> {
+        match value.trim().to_lowercase().as_str() {
+            "genesis" => Some(ResolverType::Genesis),
+            "traditional" => Some(ResolverType::Traditional),
+            "ipfs" => Some(ResolverType::IPFS),
+            "hybrid" => Some(ResolverType::Hybrid),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of a successful exchange with a Genesis node
+enum NodeLookup<T> {
+    Found(T),
+    NotFound,
+}
+
+/// Reverse lookup answer from a Genesis node
+#[derive(Debug, Deserialize)]
+struct ReverseRecord {
+    #[serde(default)]
+    domains: Vec<String>,
+    ttl: Option<u64>,
+}
+
+/// Cached reverse lookup, expiring like forward results
+#[derive(Debug, Clone)]
+struct ReverseEntry {
+    domains: Vec<String>,
+    ttl: u64,
+    timestamp: u64,
+}
+
 /// Genesis domain information from blockchain
 #[derive(Debug, Deserialize)]
 struct GenesisDomain {
@@ -53,246 +406,2033 @@ struct GenesisDomain {
     owner: String,
     resolver: String,
     content_hash: Option<String>,
+    /// Hex-encoded ed25519 signature of `content_hash` by the owner key
+    content_hash_signature: Option<String>,
     ip_address: Option<String>,
+    /// Newer nodes may return several addresses instead of a single one
+    #[serde(default)]
+    ip_addresses: Vec<String>,
     ttl: Option<u64>,
+    /// How the node recommends serving the domain, overriding the address heuristic
+    resolver_type: Option<String>,
+    /// Record also answers for every subdomain without one of its own
+    #[serde(default)]
+    wildcard: bool,
 }
 
 impl GenesisDnsResolver {
     pub fn new(genesis_node_url: String, fallback_enabled: bool) -> Self {
+        Self::new_with_nodes(vec![genesis_node_url], fallback_enabled)
+    }
+
+    /// Create a resolver that fails over across several Genesis nodes
+    pub fn new_with_nodes(genesis_node_urls: Vec<String>, fallback_enabled: bool) -> Self {
+        let tlds = DEFAULT_GENESIS_TLDS.iter().map(|tld| tld.to_string()).collect();
+        Self::build(genesis_node_urls, fallback_enabled, tlds)
+    }
+
+    /// Create a resolver for a custom set of Genesis TLDs (e.g. a private network)
+    pub fn new_with_tlds(genesis_node_url: String, fallback_enabled: bool, tlds: Vec<String>) -> Self {
+        Self::build(vec![genesis_node_url], fallback_enabled, tlds)
+    }
+
+    fn build(genesis_node_urls: Vec<String>, fallback_enabled: bool, tlds: Vec<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(DEFAULT_REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to initialize the HTTP client");
+        Self::build_with_client(genesis_node_urls, fallback_enabled, tlds, client)
+    }
+
+    fn build_with_client(
+        genesis_node_urls: Vec<String>,
+        fallback_enabled: bool,
+        tlds: Vec<String>,
+        client: reqwest::Client,
+    ) -> Self {
+        let (refresh_sender, refresh_receiver) = mpsc::unbounded_channel();
+        #[cfg(feature = "live-updates")]
+        let (update_sender, update_receiver) = mpsc::unbounded_channel();
+
         Self {
-            genesis_node_url,
-            client: reqwest::Client::new(),
+            #[cfg(feature = "doh-fallback")]
+            doh_resolver: DohResolver::new(DEFAULT_DOH_ENDPOINT.to_string(), client.clone()),
+            gateway_client: client.clone(),
+            genesis: GenesisBackend::new(genesis_node_urls, Self::normalize_tlds(tlds), client),
+            backends: Vec::new(),
+            ipfs_gateways: Self::normalize_gateways(DEFAULT_IPFS_GATEWAYS.iter().map(|gateway| gateway.to_string()).collect()),
+            #[cfg(feature = "pinned-offline")]
+            ipfs_api_url: None,
+            #[cfg(feature = "pinned-offline")]
+            auto_pin: false,
+            #[cfg(feature = "pinned-offline")]
+            pinned: HashSet::new(),
+            proxy: None,
+            node_headers: reqwest::header::HeaderMap::new(),
+            user_agent: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
             cache: HashMap::new(),
+            cache_access: HashMap::new(),
+            cache_recency: BTreeMap::new(),
+            access_clock: 0,
+            max_cache_entries: DEFAULT_MAX_CACHE_ENTRIES,
+            evictions: 0,
+            reverse_cache: HashMap::new(),
+            negative_cache: HashMap::new(),
+            negative_ttl: 30,
             fallback_enabled,
+            cache_hits: 0,
+            cache_misses: 0,
+            event_sender: None,
+            resolution_counts: BTreeMap::new(),
+            error_counts: BTreeMap::new(),
+            stale_while_revalidate: false,
+            stale_grace_secs: 0,
+            refresh_handle: None,
+            refreshing: HashSet::new(),
+            refresh_sender,
+            refresh_receiver,
+            #[cfg(feature = "live-updates")]
+            update_sender,
+            #[cfg(feature = "live-updates")]
+            update_receiver,
+            #[cfg(feature = "live-updates")]
+            update_task: None,
         }
     }
 
-    /// Initialize the DNS resolver
-    pub async fn initialize(&mut self) -> Result<(), DnsError> {
-        info!("🚀 Initializing Genesis DNS Resolver");
-        // DNS resolver initialization logic here
-        Ok(())
+    /// Serve entries up to `stale_grace_secs` past their expiry immediately and
+    /// re-resolve them in the background. Refreshes are spawned on `handle`, so the
+    /// caller must keep that Tokio runtime running; entries past the grace window
+    /// are still re-resolved before `resolve` returns.
+    pub fn enable_stale_while_revalidate(&mut self, stale_grace_secs: u64, handle: tokio::runtime::Handle) {
+        self.stale_while_revalidate = true;
+        self.stale_grace_secs = stale_grace_secs;
+        self.refresh_handle = Some(handle);
     }
 
-    /// Resolve a domain using Genesis blockchain
-    pub async fn resolve(&mut self, domain: &str) -> Result<DnsResult, DnsError> {
-        info!("🔍 Resolving domain: {}", domain);
+    /// Go back to treating expired entries as cache misses
+    pub fn disable_stale_while_revalidate(&mut self) {
+        self.stale_while_revalidate = false;
+        self.refresh_handle = None;
+    }
 
-        // Check cache first
-        if let Some(cached) = self.cache.get(domain) {
-            if !self.is_cache_expired(cached) {
-                debug!("📋 Cache hit for domain: {}", domain);
-                return Ok(cached.clone());
-            }
+    /// A cache-less copy of this resolver's network configuration, for lookups
+    /// and health checks that shouldn't hold a resolver shared behind a lock
+    /// while they wait on the network. Node failover is shared with the original.
+    pub fn detached(&self) -> Self {
+        let mut resolver = Self::build_with_client(Vec::new(), self.fallback_enabled, Vec::new(), self.gateway_client.clone());
+        resolver.genesis = self.genesis.clone();
+        resolver.backends = self.backends.clone();
+        resolver.ipfs_gateways = self.ipfs_gateways.clone();
+        resolver.gateway_client = self.gateway_client.clone();
+        #[cfg(feature = "doh-fallback")]
+        {
+            resolver.doh_resolver = self.doh_resolver.clone();
         }
+        resolver
+    }
 
-        // Determine resolver strategy
-        let result = if self.is_genesis_domain(domain) {
-            self.resolve_genesis_domain(domain).await
-        } else if self.fallback_enabled {
-            self.resolve_traditional_domain(domain).await
-        } else {
-            Err(DnsError::UnsupportedDomain(domain.to_string()))
+    /// Re-resolve a stale domain in the background, once at a time per domain
+    fn schedule_refresh(&mut self, domain: &str) {
+        let Some(handle) = &self.refresh_handle else {
+            return;
         };
+        if !self.refreshing.insert(domain.to_string()) {
+            return;
+        }
 
-        // Cache successful results
-        if let Ok(ref result) = result {
-            self.cache.insert(domain.to_string(), result.clone());
+        debug!("🔄 Refreshing stale entry in the background: {}", domain);
+        let resolver = self.detached();
+        let sender = self.refresh_sender.clone();
+        let domain = domain.to_string();
+        handle.spawn(async move {
+            let result = resolver.lookup(&domain).await;
+            let _ = sender.send((domain, result));
+        });
+    }
+
+    /// Store the results of finished background refreshes
+    fn apply_refreshes(&mut self) {
+        while let Ok((domain, result)) = self.refresh_receiver.try_recv() {
+            self.refreshing.remove(&domain);
+            self.store_result(&domain, &result);
         }
 
-        result
+        // Updated domains are dropped from the cache; the rest were never asked for
+        #[cfg(feature = "live-updates")]
+        while let Ok(domain) = self.update_receiver.try_recv() {
+            let cached = self.cache.remove(&domain).is_some();
+            let negative = self.negative_cache.remove(&domain).is_some();
+            if cached || negative {
+                self.forget_access(&domain);
+                debug!("📡 Invalidated {} after a live update", domain);
+            }
+        }
     }
 
-    /// Check if domain is a Genesis blockchain domain
-    fn is_genesis_domain(&self, domain: &str) -> bool {
-        let genesis_tlds = [".genesis", ".free", ".web", ".defi", ".dao"];
-        genesis_tlds.iter().any(|tld| domain.ends_with(tld))
+    /// Subscribe to record changes pushed by the Genesis nodes over WebSocket
+    /// (`/ws/dns`) and invalidate cached domains as soon as they change, instead
+    /// of waiting for their TTL. A background task on the current Tokio runtime
+    /// holds the connection and reconnects, rotating through the nodes, until
+    /// the resolver is dropped.
+    ///
+    /// The connection can't go through `set_proxy`, so subscribing fails while
+    /// a proxy is configured rather than bypass it.
+    #[cfg(feature = "live-updates")]
+    pub fn subscribe_updates(&mut self) -> Result<(), DnsError> {
+        if self.proxy.is_some() {
+            return Err(DnsError::InvalidProxy("live updates can't be sent through a proxy".to_string()));
+        }
+        let handle = match &self.refresh_handle {
+            Some(handle) => handle.clone(),
+            None => tokio::runtime::Handle::try_current()
+                .map_err(|_| DnsError::ConnectionError("subscribe_updates needs a Tokio runtime".to_string()))?,
+        };
+        let urls: Vec<String> = self.genesis.genesis_node_urls.iter()
+            .filter_map(|url| live_updates::live_updates_url(url))
+            .collect();
+        if urls.is_empty() {
+            return Err(DnsError::ConnectionError("no Genesis node supports live updates".to_string()));
+        }
+        let headers = self.node_headers.iter()
+            .map(|(name, value)| (name.as_str().to_string(), value.as_bytes().to_vec()))
+            .collect();
+
+        if let Some(task) = self.update_task.take() {
+            task.abort();
+        }
+        let sender = self.update_sender.clone();
+        self.update_task = Some(handle.spawn(live_updates::run_subscription(urls, headers, sender)));
+        Ok(())
     }
 
-    /// Resolve Genesis blockchain domain
-    async fn resolve_genesis_domain(&self, domain: &str) -> Result<DnsResult, DnsError> {
-        info!("🌐 Resolving Genesis domain: {}", domain);
+    /// Receive a `ResolutionEvent` for every lookup made through this resolver.
+    /// Events are dropped rather than blocking resolution if the channel is full.
+    pub fn set_event_sender(&mut self, sender: mpsc::Sender<ResolutionEvent>) {
+        self.event_sender = Some(sender);
+    }
 
-        let url = format!("{}/api/dns/resolve/{}", self.genesis_node_url, domain);
-        
-        match self.client.get(&url).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.json::<GenesisDomain>().await {
-                        Ok(genesis_domain) => {
-                            info!("✅ Genesis domain resolved: {}", domain);
-                            Ok(self.convert_genesis_domain(genesis_domain))
-                        },
-                        Err(e) => {
-                            error!("❌ Failed to parse Genesis domain response: {}", e);
-                            Err(DnsError::InvalidResponse(e.to_string()))
-                        }
-                    }
-                } else {
-                    warn!("⚠️ Genesis node returned error for {}: {}", domain, response.status());
-                    Err(DnsError::NodeError(response.status().to_string()))
-                }
-            },
-            Err(e) => {
-                error!("❌ Failed to connect to Genesis node: {}", e);
-                Err(DnsError::ConnectionError(e.to_string()))
+    /// Send an event to the observer, building it only if someone is listening
+    fn emit(&self, event: impl FnOnce() -> ResolutionEvent) {
+        if let Some(sender) = &self.event_sender {
+            if let Err(e) = sender.try_send(event()) {
+                debug!("Dropping resolution event: {}", e);
             }
         }
     }
 
-    /// Resolve traditional DNS domain (fallback)
-    async fn resolve_traditional_domain(&self, domain: &str) -> Result<DnsResult, DnsError> {
-        info!("🌍 Resolving traditional domain: {}", domain);
+    /// Configure how often and how quickly node requests are retried
+    pub fn set_retry_policy(&mut self, max_retries: u32, base_delay_ms: u64) {
+        self.genesis.max_retries = max_retries;
+        self.genesis.base_delay_ms = base_delay_ms;
+    }
 
-        #[cfg(feature = "traditional-fallback")]
-        {
-            use trust_dns_resolver::TokioAsyncResolver;
-            use trust_dns_resolver::config::*;
+    /// Limit how long each node request may take before it counts as failed
+    pub fn set_request_timeout(&mut self, timeout: Duration) {
+        self.request_timeout = timeout;
+        if let Err(e) = self.rebuild_clients() {
+            warn!("⚠️ Failed to apply request timeout: {}", e);
+        }
+    }
 
-            let resolver = TokioAsyncResolver::tokio(
-                ResolverConfig::default(),
-                ResolverOpts::default()
-            );
+    /// Replace the set of TLDs resolved through the Genesis blockchain
+    pub fn set_genesis_tlds(&mut self, tlds: Vec<String>) {
+        self.genesis.genesis_tlds = Self::normalize_tlds(tlds);
+    }
 
-            match resolver.lookup_ip(domain).await {
-                Ok(response) => {
-                    let ip = response.iter().next()
-                        .ok_or_else(|| DnsError::NoResults(domain.to_string()))?;
-                    
-                    info!("✅ Traditional DNS resolved: {} -> {}", domain, ip);
-                    
-                    Ok(DnsResult {
-                        domain: domain.to_string(),
-                        ip_address: Some(ip),
-                        content_hash: None,
-                        resolver_type: ResolverType::Traditional,
-                        ttl: 300, // 5 minutes default
-                        timestamp: chrono::Utc::now().timestamp() as u64,
-                    })
-                },
-                Err(e) => {
-                    warn!("⚠️ Traditional DNS failed for {}: {}", domain, e);
-                    Err(DnsError::ResolutionFailed(e.to_string()))
-                }
-            }
+    /// Get the TLDs resolved through the Genesis blockchain
+    pub fn genesis_tlds(&self) -> &[String] {
+        &self.genesis.genesis_tlds
+    }
+
+    /// Send node queries (and DNS-over-HTTPS lookups) through a proxy.
+    /// `socks5://`, `socks5h://`, `http://` and `https://` proxies are supported.
+    pub fn set_proxy(&mut self, proxy_url: &str) -> Result<(), DnsError> {
+        self.proxy = Some(parse_proxy(proxy_url)?);
+        self.rebuild_clients()
+            .map_err(|e| DnsError::InvalidProxy(format!("{}: {}", proxy_url, e)))?;
+        info!("🧦 Using proxy {} for Genesis node queries", proxy_url);
+        Ok(())
+    }
+
+    /// Send these headers with every Genesis node query, for nodes that need
+    /// authentication. They are never sent to the DNS-over-HTTPS endpoint.
+    pub fn set_node_headers(&mut self, headers: &HashMap<String, String>) -> Result<(), DnsError> {
+        let mut node_headers = reqwest::header::HeaderMap::new();
+        for (name, value) in headers {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| DnsError::InvalidHeader(format!("{}: {}", name, e)))?;
+            let mut value = reqwest::header::HeaderValue::from_str(value)
+                .map_err(|e| DnsError::InvalidHeader(format!("{}: {}", name, e)))?;
+            value.set_sensitive(true);
+            node_headers.insert(name, value);
         }
 
-        #[cfg(not(feature = "traditional-fallback"))]
+        self.node_headers = node_headers;
+        self.rebuild_clients()
+            .map_err(|e| DnsError::InvalidHeader(e.to_string()))?;
+        debug!("Sending {} custom headers to Genesis nodes", self.node_headers.len());
+        Ok(())
+    }
+
+    /// Identify node queries, gateway probes and DNS-over-HTTPS lookups
+    /// with this User-Agent
+    pub fn set_user_agent(&mut self, user_agent: &str) -> Result<(), DnsError> {
+        let value = reqwest::header::HeaderValue::from_str(user_agent)
+            .map_err(|e| DnsError::InvalidHeader(format!("User-Agent: {}", e)))?;
+        self.user_agent = Some(value);
+        self.rebuild_clients()
+            .map_err(|e| DnsError::InvalidHeader(e.to_string()))?;
+        Ok(())
+    }
+
+    /// User-Agent set with `set_user_agent`, if any
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_ref().and_then(|value| value.to_str().ok())
+    }
+
+    /// Client settings shared by every request: timeout, User-Agent and proxy.
+    /// Node headers are added on top for the Genesis client only.
+    fn client_builder(&self) -> reqwest::ClientBuilder {
+        let mut builder = reqwest::Client::builder().timeout(self.request_timeout);
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent.clone());
+        }
+        match &self.proxy {
+            Some(proxy) => builder.proxy(proxy.clone()),
+            None => builder,
+        }
+    }
+
+    fn rebuild_clients(&mut self) -> Result<(), reqwest::Error> {
+        #[cfg(feature = "doh-fallback")]
         {
-            Err(DnsError::UnsupportedDomain(domain.to_string()))
+            self.doh_resolver = DohResolver::new(self.doh_resolver.endpoint().to_string(), self.client_builder().build()?);
         }
+        self.gateway_client = self.client_builder().build()?;
+        self.genesis.client = self.client_builder().default_headers(self.node_headers.clone()).build()?;
+        Ok(())
     }
 
-    /// Convert Genesis domain to DNS result
-    fn convert_genesis_domain(&self, genesis_domain: GenesisDomain) -> DnsResult {
-        let ip_address = genesis_domain.ip_address
-            .and_then(|ip_str| ip_str.parse().ok());
+    /// Use a different DNS-over-HTTPS endpoint for traditional domains. The
+    /// endpoint gets its own client, so node headers never reach it.
+    #[cfg(feature = "doh-fallback")]
+    pub fn set_doh_endpoint(&mut self, endpoint: String) -> Result<(), DnsError> {
+        let client = self.client_builder().build()
+            .map_err(|e| DnsError::ConnectionError(e.to_string()))?;
+        self.doh_resolver = DohResolver::new(endpoint, client);
+        Ok(())
+    }
 
-        DnsResult {
-            domain: genesis_domain.name,
-            ip_address,
-            content_hash: genesis_domain.content_hash,
-            resolver_type: if ip_address.is_some() {
-                ResolverType::Genesis
-            } else {
-                ResolverType::IPFS
-            },
-            ttl: genesis_domain.ttl.unwrap_or(3600), // 1 hour default
-            timestamp: chrono::Utc::now().timestamp() as u64,
-        }
+    /// Get the DNS-over-HTTPS endpoint used for traditional domains
+    #[cfg(feature = "doh-fallback")]
+    pub fn doh_endpoint(&self) -> &str {
+        self.doh_resolver.endpoint()
     }
 
-    /// Check if cache entry is expired
-    fn is_cache_expired(&self, result: &DnsResult) -> bool {
-        let now = chrono::Utc::now().timestamp() as u64;
-        now > result.timestamp + result.ttl
+    /// Resolve `.eth` names through ENS, reading the contracts via an Ethereum
+    /// JSON-RPC endpoint. Picks up the current proxy, User-Agent and timeout, so call
+    /// this after configuring those; Genesis node headers are not sent.
+    #[cfg(feature = "ens")]
+    pub fn enable_ens(&mut self, rpc_url: String) -> Result<(), DnsError> {
+        let client = self.client_builder().build()
+            .map_err(|e| DnsError::ConnectionError(e.to_string()))?;
+        self.register_backend(Arc::new(EnsBackend::new(rpc_url, client)));
+        Ok(())
     }
 
-    /// Clear expired cache entries
-    pub fn cleanup_cache(&mut self) {
-        let now = chrono::Utc::now().timestamp() as u64;
-        self.cache.retain(|_, result| now <= result.timestamp + result.ttl);
+    /// Set the IPFS gateways content hashes are served through, in the order
+    /// they are tried. `local` stands for the gateway of a local IPFS node.
+    pub fn set_ipfs_gateways(&mut self, gateways: Vec<String>) {
+        self.ipfs_gateways = Self::normalize_gateways(gateways);
     }
 
-    /// Get cache statistics
-    pub fn cache_stats(&self) -> CacheStats {
-        CacheStats {
-            total_entries: self.cache.len(),
-            genesis_domains: self.cache.values()
-                .filter(|r| matches!(r.resolver_type, ResolverType::Genesis))
-                .count(),
-            traditional_domains: self.cache.values()
-                .filter(|r| matches!(r.resolver_type, ResolverType::Traditional))
-                .count(),
-        }
+    /// Get the configured IPFS gateway URL prefixes
+    pub fn ipfs_gateways(&self) -> &[String] {
+        &self.ipfs_gateways
     }
-}
 
-/// Cache statistics
-#[derive(Debug)]
-pub struct CacheStats {
-    pub total_entries: usize,
-    pub genesis_domains: usize,
-    pub traditional_domains: usize,
-}
+    /// URL of a content hash on the first gateway that answers, trying the
+    /// gateways in order. A gateway that errors or times out is skipped.
+    pub async fn ipfs_gateway_url(&self, content_hash: &str) -> Result<String, DnsError> {
+        let mut failures = Vec::new();
 
-/// DNS resolution errors
-#[derive(Debug, thiserror::Error)]
-pub enum DnsError {
-    #[error("Unsupported domain: {0}")]
-    UnsupportedDomain(String),
-    
-    #[error("Connection error: {0}")]
-    ConnectionError(String),
-    
-    #[error("Node error: {0}")]
-    NodeError(String),
-    
-    #[error("Invalid response: {0}")]
-    InvalidResponse(String),
-    
-    #[error("Resolution failed: {0}")]
-    ResolutionFailed(String),
-    
-    #[error("No results for domain: {0}")]
-    NoResults(String),
-}
+        for gateway in &self.ipfs_gateways {
+            let url = format!("{}{}", gateway, content_hash);
+            match self.gateway_client.head(&url).send().await {
+                Ok(response) if response.status().is_success() => {
+                    debug!("📦 Serving {} through {}", content_hash, gateway);
+                    return Ok(url);
+                },
+                Ok(response) => failures.push(format!("{}: {}", gateway, response.status())),
+                Err(e) => failures.push(format!("{}: {}", gateway, e)),
+            }
+            warn!("⚠️ IPFS gateway {} failed for {}", gateway, content_hash);
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        Err(DnsError::ResolutionFailed(format!("no IPFS gateway served {} ({})", content_hash, failures.join("; "))))
+    }
 
-    #[test]
-    fn test_is_genesis_domain() {
-        let resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
-        
-        assert!(resolver.is_genesis_domain("test.genesis"));
-        assert!(resolver.is_genesis_domain("freedom.free"));
-        assert!(resolver.is_genesis_domain("mysite.web"));
-        assert!(resolver.is_genesis_domain("token.defi"));
-        assert!(!resolver.is_genesis_domain("google.com"));
-        assert!(!resolver.is_genesis_domain("example.org"));
+    /// Pin content hashes through the RPC API of an IPFS node at `api_url`,
+    /// e.g. `LOCAL_IPFS_API`, or stop with `None`. With `auto_pin`, content
+    /// hashes resolved from the network are pinned in the background so their
+    /// sites stay available offline.
+    #[cfg(feature = "pinned-offline")]
+    pub fn set_ipfs_api(&mut self, api_url: Option<String>, auto_pin: bool) {
+        self.ipfs_api_url = api_url.map(|url| url.trim().trim_end_matches('/').to_string());
+        self.auto_pin = auto_pin;
     }
 
-    #[tokio::test]
-    async fn test_cache_functionality() {
-        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
-        
-        // Mock a cache entry
-        let result = DnsResult {
-            domain: "test.genesis".to_string(),
-            ip_address: Some("192.168.1.100".parse().unwrap()),
-            content_hash: None,
-            resolver_type: ResolverType::Genesis,
-            ttl: 3600,
-            timestamp: chrono::Utc::now().timestamp() as u64,
+    /// Get the RPC API content hashes are pinned through
+    #[cfg(feature = "pinned-offline")]
+    pub fn ipfs_api_url(&self) -> Option<&str> {
+        self.ipfs_api_url.as_deref()
+    }
+
+    /// Pin a content hash on the configured IPFS node
+    #[cfg(feature = "pinned-offline")]
+    pub async fn pin_content(&self, hash: &str) -> Result<(), DnsError> {
+        let Some(api_url) = &self.ipfs_api_url else {
+            return Err(DnsError::ConnectionError("no IPFS API configured for pinning".to_string()));
         };
-        
+        pin_on_node(&self.gateway_client, api_url, hash).await
+    }
+
+    /// Pin a freshly resolved content hash in the background when auto-pinning
+    /// is on. Failures are only logged; the site still loads through a gateway.
+    #[cfg(feature = "pinned-offline")]
+    fn schedule_pin(&mut self, result: &DnsResult) {
+        let (Some(api_url), Some(hash)) = (&self.ipfs_api_url, &result.content_hash) else {
+            return;
+        };
+        if !self.auto_pin || !self.pinned.insert(hash.clone()) {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let client = self.gateway_client.clone();
+        let api_url = api_url.clone();
+        let hash = hash.clone();
+        handle.spawn(async move {
+            if let Err(e) = pin_on_node(&client, &api_url, &hash).await {
+                warn!("⚠️ Failed to pin {} for offline use: {}", hash, e);
+            }
+        });
+    }
+
+    /// Give every gateway a trailing slash and expand `local`
+    fn normalize_gateways(gateways: Vec<String>) -> Vec<String> {
+        gateways.into_iter()
+            .map(|gateway| gateway.trim().to_string())
+            .filter(|gateway| !gateway.is_empty())
+            .map(|gateway| match gateway.as_str() {
+                "local" => LOCAL_IPFS_GATEWAY.to_string(),
+                _ if gateway.ends_with('/') => gateway,
+                _ => format!("{}/", gateway),
+            })
+            .collect()
+    }
+
+    /// Set the TTL used for a TLD's records when the node does not send one
+    pub fn set_default_ttl(&mut self, tld: &str, ttl: u64) {
+        if let Some(tld) = Self::normalize_tlds(vec![tld.to_string()]).pop() {
+            self.genesis.default_ttls.insert(tld, ttl);
+        }
+    }
+
+    /// Get the configured Genesis node URLs
+    pub fn genesis_node_urls(&self) -> &[String] {
+        &self.genesis.genesis_node_urls
+    }
+
+    /// Make sure every TLD carries its leading dot so "node" and ".node" behave the same
+    fn normalize_tlds(tlds: Vec<String>) -> Vec<String> {
+        tlds.into_iter()
+            .map(|tld| tld.trim().to_lowercase())
+            .filter(|tld| !tld.is_empty() && tld != ".")
+            .map(|tld| if tld.starts_with('.') { tld } else { format!(".{}", tld) })
+            .collect()
+    }
+
+    /// Initialize the DNS resolver, optionally loading a persisted cache
+    pub async fn initialize(&mut self, cache_path: Option<&Path>) -> Result<(), DnsError> {
+        info!("🚀 Initializing Genesis DNS Resolver");
+
+        if let Some(path) = cache_path {
+            if path.exists() {
+                let loaded = self.load_cache_from(path)?;
+                info!("📋 Loaded {} cached domains from {}", loaded, path.display());
+            } else {
+                debug!("📋 No DNS cache found at {}", path.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a domain using Genesis blockchain
+    pub async fn resolve(&mut self, domain: &str) -> Result<DnsResult, DnsError> {
+        info!("🔍 Resolving domain: {}", domain);
+
+        let normalized = normalize_domain(domain)?;
+        self.apply_refreshes();
+
+        // Check cache first
+        if let Some(cached) = self.lookup_cache(&normalized) {
+            return with_original_domain(cached, domain);
+        }
+
+        let result = self.lookup(&normalized).await;
+        self.store_result(&normalized, &result);
+
+        with_original_domain(result, domain)
+    }
+
+    /// First half of `resolve`, for a resolver shared behind a lock: answers
+    /// from the cache, or detaches the network query so it can run with the
+    /// lock released. Its result goes back through `finish_lookup`.
+    pub fn begin_lookup(&mut self, domain: &str) -> Lookup {
+        info!("🔍 Resolving domain: {}", domain);
+
+        let normalized = match normalize_domain(domain) {
+            Ok(normalized) => normalized,
+            Err(e) => return Lookup::Done(Err(e)),
+        };
+        self.apply_refreshes();
+
+        if let Some(cached) = self.lookup_cache(&normalized) {
+            return Lookup::Done(with_original_domain(cached, domain));
+        }
+        Lookup::Pending(PendingLookup {
+            resolver: self.detached(),
+            domain: normalized,
+            original_domain: domain.to_string(),
+        })
+    }
+
+    /// Second half of `resolve`: cache the answer of a `PendingLookup`
+    pub fn finish_lookup(&mut self, lookup: FinishedLookup) -> Result<DnsResult, DnsError> {
+        self.store_result(&lookup.domain, &lookup.result);
+        with_original_domain(lookup.result, &lookup.original_domain)
+    }
+
+    /// Resolve through a resolver shared between tasks, locking it only to
+    /// read and update the cache so a slow node doesn't hold up other lookups
+    pub async fn resolve_shared(resolver: &tokio::sync::Mutex<Self>, domain: &str) -> Result<DnsResult, DnsError> {
+        let pending = match resolver.lock().await.begin_lookup(domain) {
+            Lookup::Done(result) => return result,
+            Lookup::Pending(pending) => pending,
+        };
+        let finished = pending.run().await;
+        resolver.lock().await.finish_lookup(finished)
+    }
+
+    /// Resolve a domain without reading or updating the cache, e.g. to measure
+    /// node latency. Takes `&self` so lookups can run concurrently.
+    pub async fn resolve_uncached(&self, domain: &str) -> Result<DnsResult, DnsError> {
+        let normalized = normalize_domain(domain)?;
+        with_original_domain(self.lookup(&normalized).await, domain)
+    }
+
+    /// Resolve a domain from synchronous code, sharing the cache with `resolve`.
+    ///
+    /// This drives the lookup on a private current-thread runtime. Blocking inside
+    /// an async context would panic (or stall the executor), so when a Tokio runtime
+    /// is already running on this thread it returns `DnsError::BlockingInAsyncContext`
+    /// instead; use `resolve` there.
+    #[cfg(feature = "blocking")]
+    pub fn resolve_blocking(&mut self, domain: &str) -> Result<DnsResult, DnsError> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(DnsError::BlockingInAsyncContext(format!(
+                "resolve_blocking({}) called from within a Tokio runtime, use resolve instead",
+                domain
+            )));
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| DnsError::ResolutionFailed(format!("failed to start runtime: {}", e)))?;
+        runtime.block_on(self.resolve(domain))
+    }
+
+    /// Resolve several domains concurrently, e.g. to prefetch a bookmarks folder.
+    /// The returned results are in the same order as `domains`.
+    pub async fn resolve_many(&mut self, domains: &[String]) -> Vec<Result<DnsResult, DnsError>> {
+        use futures::stream::{self, StreamExt};
+
+        info!("🔍 Resolving {} domains", domains.len());
+        self.apply_refreshes();
+
+        let normalized: Vec<Result<String, DnsError>> = domains.iter()
+            .map(|domain| normalize_domain(domain))
+            .collect();
+        let mut results: Vec<Option<Result<DnsResult, DnsError>>> = normalized.iter()
+            .map(|domain| match domain {
+                Ok(domain) => self.lookup_cache(domain),
+                Err(e) => Some(Err(DnsError::InvalidDomainName(match e {
+                    DnsError::InvalidDomainName(reason) => reason.clone(),
+                    other => other.to_string(),
+                }))),
+            })
+            .collect();
+        let pending: Vec<usize> = results.iter()
+            .enumerate()
+            .filter(|(_, result)| result.is_none())
+            .map(|(index, _)| index)
+            .collect();
+
+        let resolver = &*self;
+        let fetched: Vec<(usize, Result<DnsResult, DnsError>)> = stream::iter(pending)
+            .map(|index| {
+                let domain = normalized[index].as_deref().unwrap_or_default();
+                async move { (index, resolver.lookup(domain).await) }
+            })
+            .buffered(MAX_CONCURRENT_LOOKUPS)
+            .collect()
+            .await;
+
+        for (index, result) in fetched {
+            if let Ok(domain) = &normalized[index] {
+                self.store_result(domain, &result);
+            }
+            results[index] = Some(result);
+        }
+
+        results.into_iter()
+            .zip(domains)
+            .map(|(result, domain)| {
+                let result = result.expect("every domain is answered from cache or network");
+                with_original_domain(result, domain)
+            })
+            .collect()
+    }
+
+    /// Answer a lookup from the positive or negative cache, counting hits and misses
+    fn lookup_cache(&mut self, domain: &str) -> Option<Result<DnsResult, DnsError>> {
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        if let Some(&expires_at) = self.negative_cache.get(domain) {
+            if now < expires_at {
+                debug!("📋 Negative cache hit for domain: {}", domain);
+                self.cache_hits += 1;
+                let error = DnsError::UnsupportedDomain(domain.to_string());
+                self.emit(|| ResolutionEvent::Failed { domain: domain.to_string(), error: error.to_string() });
+                return Some(Err(error));
+            }
+            self.negative_cache.remove(domain);
+        }
+
+        if let Some(cached) = self.cache.get(domain) {
+            if !self.is_cache_expired(cached) {
+                debug!("📋 Cache hit for domain: {}", domain);
+                let cached = cached.clone();
+                self.touch(domain);
+                self.cache_hits += 1;
+                self.emit(|| ResolutionEvent::CacheHit { domain: domain.to_string() });
+                return Some(Ok(cached));
+            }
+
+            let grace_ends = cached.timestamp + cached.ttl + self.stale_grace_secs;
+            if self.stale_while_revalidate && now <= grace_ends {
+                debug!("📋 Serving stale cache entry for domain: {}", domain);
+                let cached = cached.clone();
+                self.touch(domain);
+                self.cache_hits += 1;
+                self.emit(|| ResolutionEvent::CacheHit { domain: domain.to_string() });
+                self.schedule_refresh(domain);
+                return Some(Ok(cached));
+            }
+        }
+
+        self.cache_misses += 1;
+        None
+    }
+
+    /// Determine resolver strategy and query the network
+    async fn lookup(&self, domain: &str) -> Result<DnsResult, DnsError> {
+        let result = match self.backend_for(domain) {
+            Some(backend) => backend.resolve(domain).await,
+            None if self.fallback_enabled => self.resolve_traditional_domain(domain).await,
+            None => Err(DnsError::UnsupportedDomain(domain.to_string())),
+        };
+
+        // Key results by the normalized name regardless of how the node spells it
+        result.map(|mut result| {
+            result.domain = domain.to_string();
+            result
+        })
+    }
+
+    /// Cache successful results, and definitive "not found" answers for a short while
+    fn store_result(&mut self, domain: &str, result: &Result<DnsResult, DnsError>) {
+        self.emit(|| match result {
+            Ok(result) if self.backend_for(domain).is_some() => ResolutionEvent::ResolvedGenesis {
+                domain: domain.to_string(),
+                result: result.clone(),
+            },
+            Ok(result) => ResolutionEvent::ResolvedTraditional {
+                domain: domain.to_string(),
+                result: result.clone(),
+            },
+            Err(e) => ResolutionEvent::Failed { domain: domain.to_string(), error: e.to_string() },
+        });
+
+        match result {
+            Ok(result) => *self.resolution_counts.entry(result.resolver_type.as_str()).or_insert(0) += 1,
+            Err(e) => *self.error_counts.entry(e.kind()).or_insert(0) += 1,
+        }
+
+        match result {
+            Ok(result) => {
+                self.negative_cache.remove(domain);
+                #[cfg(feature = "pinned-offline")]
+                self.schedule_pin(result);
+                self.insert_cache_entry(domain.to_string(), result.clone());
+            },
+            Err(DnsError::UnsupportedDomain(_)) if self.negative_ttl > 0 && self.is_genesis_domain(domain) => {
+                let now = chrono::Utc::now().timestamp() as u64;
+                self.negative_cache.insert(domain.to_string(), now + self.negative_ttl);
+            },
+            Err(_) => {}
+        }
+    }
+
+    /// Limit the number of cached domains, evicting the least recently used ones
+    pub fn set_max_cache_entries(&mut self, max_entries: usize) {
+        self.max_cache_entries = max_entries;
+        while self.cache.len() > self.max_cache_entries {
+            self.evict_lru();
+        }
+    }
+
+    /// Cache a result, making room by evicting the least recently used entry
+    fn insert_cache_entry(&mut self, domain: String, result: DnsResult) {
+        if self.max_cache_entries == 0 {
+            return;
+        }
+        if !self.cache.contains_key(&domain) {
+            while self.cache.len() >= self.max_cache_entries {
+                self.evict_lru();
+            }
+        }
+        self.touch(&domain);
+        self.cache.insert(domain, result);
+    }
+
+    /// Mark a cached domain as just used
+    fn touch(&mut self, domain: &str) {
+        self.access_clock += 1;
+        if let Some(previous) = self.cache_access.insert(domain.to_string(), self.access_clock) {
+            self.cache_recency.remove(&previous);
+        }
+        self.cache_recency.insert(self.access_clock, domain.to_string());
+    }
+
+    /// Stop tracking when a domain was last used
+    fn forget_access(&mut self, domain: &str) {
+        if let Some(tick) = self.cache_access.remove(domain) {
+            self.cache_recency.remove(&tick);
+        }
+    }
+
+    /// Drop the least recently used cache entry
+    fn evict_lru(&mut self) {
+        // Every cached domain is touched on insert; fall back to any entry if one wasn't
+        let oldest = match self.cache_recency.pop_first() {
+            Some((_, domain)) => Some(domain),
+            None => self.cache.keys().next().cloned(),
+        };
+
+        if let Some(domain) = oldest {
+            debug!("🗑️ Evicting least recently used domain: {}", domain);
+            self.cache.remove(&domain);
+            self.cache_access.remove(&domain);
+            self.evictions += 1;
+        }
+    }
+
+    /// Set how long unregistered Genesis domains are remembered (0 disables negative caching)
+    pub fn set_negative_ttl(&mut self, ttl_secs: u64) {
+        self.negative_ttl = ttl_secs;
+    }
+
+    /// Check if domain is a Genesis blockchain domain
+    pub fn is_genesis_domain(&self, domain: &str) -> bool {
+        self.genesis.handles(domain)
+    }
+
+    /// Add a backend for another naming system. Registered backends are
+    /// consulted in order before the Genesis backend.
+    pub fn register_backend(&mut self, backend: Arc<dyn ResolverBackend>) {
+        self.backends.push(backend);
+    }
+
+    /// Backend responsible for a normalized domain, `None` for traditional DNS
+    fn backend_for(&self, domain: &str) -> Option<&dyn ResolverBackend> {
+        self.backends.iter()
+            .map(|backend| backend.as_ref())
+            .find(|backend| backend.handles(domain))
+            .or_else(|| self.genesis.handles(domain).then_some(&self.genesis as &dyn ResolverBackend))
+    }
+
+    /// Find the Genesis domains that point at an address. Reverse lookups
+    /// stay out of the hit/miss counters, which describe forward lookups.
+    pub async fn reverse_resolve(&mut self, ip: IpAddr) -> Result<Vec<String>, DnsError> {
+        info!("🔁 Reverse resolving address: {}", ip);
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        if let Some(entry) = self.reverse_cache.get(&ip) {
+            if now <= entry.timestamp + entry.ttl {
+                debug!("📋 Reverse cache hit for address: {}", ip);
+                return Ok(entry.domains.clone());
+            }
+        }
+
+        let record: ReverseRecord = match self.genesis.query_nodes(&format!("/api/dns/reverse/{}", ip)).await? {
+            NodeLookup::Found(record) => record,
+            NodeLookup::NotFound => return Err(DnsError::NoResults(ip.to_string())),
+        };
+        if record.domains.is_empty() {
+            return Err(DnsError::NoResults(ip.to_string()));
+        }
+
+        self.reverse_cache.insert(ip, ReverseEntry {
+            domains: record.domains.clone(),
+            ttl: record.ttl.unwrap_or(DEFAULT_TTL_SECS),
+            timestamp: now,
+        });
+
+        Ok(record.domains)
+    }
+
+    /// Ping the preferred Genesis node's `/health` endpoint
+    pub async fn check_health(&self) -> Result<(), DnsError> {
+        let Some(node_url) = self.preferred_node_url() else {
+            return Err(DnsError::AllNodesFailed(vec!["no Genesis nodes configured".to_string()]));
+        };
+
+        let response = self.genesis.send_with_retry(&format!("{}/health", node_url)).await?;
+        if response.status().is_success() {
+            info!("✅ Genesis node {} is healthy", node_url);
+            Ok(())
+        } else {
+            Err(DnsError::NodeError(response.status().to_string()))
+        }
+    }
+
+    /// Query the preferred Genesis node's `/health` and `/info` once, without
+    /// retries. An unreachable node is reported as offline rather than an error.
+    pub async fn check_node_health(&self) -> NodeHealth {
+        let Some(node_url) = self.preferred_node_url() else {
+            return NodeHealth { online: false, version: None, block_height: None, latency_ms: 0 };
+        };
+
+        let started = Instant::now();
+        let online = match self.genesis.client.get(format!("{}/health", node_url)).send().await {
+            Ok(response) => response.status().is_success(),
+            Err(e) => {
+                debug!("❌ Genesis node {} is unreachable: {}", node_url, e);
+                false
+            },
+        };
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        let info = if online {
+            self.node_info(node_url).await.unwrap_or_default()
+        } else {
+            NodeInfo::default()
+        };
+        NodeHealth { online, version: info.version, block_height: info.block_height, latency_ms }
+    }
+
+    /// Fetch a node's `/info`, or `None` if it doesn't serve one
+    async fn node_info(&self, node_url: &str) -> Option<NodeInfo> {
+        let response = self.genesis.client.get(format!("{}/info", node_url)).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.json().await.ok()
+    }
+
+    /// The node lookups start with: the last one that answered
+    fn preferred_node_url(&self) -> Option<&str> {
+        let urls = &self.genesis.genesis_node_urls;
+        if urls.is_empty() {
+            return None;
+        }
+        Some(&urls[self.genesis.preferred_node.load(Ordering::Relaxed) % urls.len()])
+    }
+
+    /// Resolve traditional DNS domain (fallback)
+    async fn resolve_traditional_domain(&self, domain: &str) -> Result<DnsResult, DnsError> {
+        info!("🌍 Resolving traditional domain: {}", domain);
+
+        // DoH takes precedence so queries never reach the system resolver
+        #[cfg(feature = "doh-fallback")]
+        {
+            self.doh_resolver.resolve(domain).await
+        }
+
+        #[cfg(all(feature = "traditional-fallback", not(feature = "doh-fallback")))]
+        {
+            use trust_dns_resolver::TokioAsyncResolver;
+            use trust_dns_resolver::config::*;
+
+            let resolver = TokioAsyncResolver::tokio(
+                ResolverConfig::default(),
+                ResolverOpts::default()
+            );
+
+            match resolver.lookup_ip(domain).await {
+                Ok(response) => {
+                    let ip_addresses: Vec<IpAddr> = response.iter().collect();
+                    let ip = *ip_addresses.first()
+                        .ok_or_else(|| DnsError::NoResults(domain.to_string()))?;
+                    
+                    info!("✅ Traditional DNS resolved: {} -> {}", domain, ip);
+                    
+                    Ok(DnsResult {
+                        domain: domain.to_string(),
+                        original_domain: domain.to_string(),
+                        ip_address: Some(ip),
+                        ip_addresses,
+                        content_hash: None,
+                        resolver_type: ResolverType::Traditional,
+                        ttl: 300, // 5 minutes default
+                        timestamp: chrono::Utc::now().timestamp() as u64,
+                        eth_address: None,
+                        next_index: Arc::default(),
+                    })
+                },
+                Err(e) => {
+                    warn!("⚠️ Traditional DNS failed for {}: {}", domain, e);
+                    Err(DnsError::ResolutionFailed(e.to_string()))
+                }
+            }
+        }
+
+        #[cfg(not(any(feature = "traditional-fallback", feature = "doh-fallback")))]
+        {
+            Err(DnsError::UnsupportedDomain(domain.to_string()))
+        }
+    }
+
+    /// Check if cache entry is expired
+    pub fn is_cache_expired(&self, result: &DnsResult) -> bool {
+        let now = chrono::Utc::now().timestamp() as u64;
+        now > result.timestamp + result.ttl
+    }
+
+    /// Clear expired cache entries
+    pub fn cleanup_cache(&mut self) {
+        let now = chrono::Utc::now().timestamp() as u64;
+        self.cache.retain(|_, result| now <= result.timestamp + result.ttl);
+        let cache = &self.cache;
+        self.cache_access.retain(|domain, _| cache.contains_key(domain));
+        self.cache_recency.retain(|_, domain| cache.contains_key(domain));
+        self.reverse_cache.retain(|_, entry| now <= entry.timestamp + entry.ttl);
+        self.negative_cache.retain(|_, expires_at| now < *expires_at);
+    }
+
+    /// Load cache entries from a JSON file, dropping expired records, and
+    /// add the saved hit/miss/eviction counters to the current ones.
+    /// Returns the number of entries that were loaded.
+    pub fn load_cache_from(&mut self, path: &Path) -> Result<usize, DnsError> {
+        let cache = read_cache(path)?;
+        self.cache_hits += cache.hits;
+        self.cache_misses += cache.misses;
+        self.evictions += cache.evictions;
+
+        let mut loaded = 0;
+        for (domain, result) in cache.entries {
+            if self.is_cache_expired(&result) {
+                continue;
+            }
+            self.insert_cache_entry(domain, result);
+            loaded += 1;
+        }
+
+        Ok(loaded)
+    }
+
+    /// Save the cache and its hit/miss/eviction counters to a JSON file.
+    /// The data is written to a temporary file first and then renamed,
+    /// so a crash mid-write never leaves a truncated cache behind.
+    pub fn save_cache_to(&self, path: &Path) -> Result<(), DnsError> {
+        let cache = CacheFile {
+            entries: &self.cache,
+            hits: self.cache_hits,
+            misses: self.cache_misses,
+            evictions: self.evictions,
+        };
+        let data = serde_json::to_vec(&cache)
+            .map_err(|e| DnsError::CacheError(e.to_string()))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| DnsError::CacheError(format!("{}: {}", parent.display(), e)))?;
+        }
+
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, data)
+            .map_err(|e| DnsError::CacheError(format!("{}: {}", tmp_path.display(), e)))?;
+        std::fs::rename(&tmp_path, path)
+            .map_err(|e| DnsError::CacheError(format!("{}: {}", path.display(), e)))?;
+
+        debug!("💾 Saved {} cached domains to {}", self.cache.len(), path.display());
+        Ok(())
+    }
+
+    /// Get cache statistics
+    pub fn cache_stats(&self) -> CacheStats {
+        CacheStats {
+            total_entries: self.cache.len(),
+            genesis_domains: self.cache.values()
+                .filter(|r| matches!(r.resolver_type, ResolverType::Genesis))
+                .count(),
+            traditional_domains: self.cache.values()
+                .filter(|r| matches!(r.resolver_type, ResolverType::Traditional))
+                .count(),
+            hits: self.cache_hits,
+            misses: self.cache_misses,
+            evictions: self.evictions,
+        }
+    }
+
+    /// Reset the hit/miss/eviction counters, e.g. to sample rates over a fixed window
+    pub fn reset_stats(&mut self) {
+        self.cache_hits = 0;
+        self.cache_misses = 0;
+        self.evictions = 0;
+    }
+
+    /// Render resolver metrics in the Prometheus text exposition format
+    pub fn metrics_text(&self) -> String {
+        use std::fmt::Write;
+
+        let stats = self.cache_stats();
+        let mut out = String::new();
+
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, u64)]| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{}{} {}", name, labels, value);
+            }
+        };
+
+        metric("genesis_dns_cache_entries", "gauge", "Number of cached domains",
+            &[(String::new(), stats.total_entries as u64)]);
+        metric("genesis_dns_cache_hits_total", "counter", "Lookups answered from the cache",
+            &[(String::new(), stats.hits)]);
+        metric("genesis_dns_cache_misses_total", "counter", "Lookups that went to the network",
+            &[(String::new(), stats.misses)]);
+        metric("genesis_dns_cache_evictions_total", "counter", "Entries evicted by the cache size limit",
+            &[(String::new(), stats.evictions)]);
+
+        let resolutions: Vec<(String, u64)> = self.resolution_counts.iter()
+            .map(|(kind, count)| (format!("{{type=\"{}\"}}", kind), *count))
+            .collect();
+        metric("genesis_dns_resolutions_total", "counter", "Successful network resolutions by resolver type",
+            &resolutions);
+
+        let errors: Vec<(String, u64)> = self.error_counts.iter()
+            .map(|(kind, count)| (format!("{{kind=\"{}\"}}", kind), *count))
+            .collect();
+        metric("genesis_dns_errors_total", "counter", "Failed network resolutions by error kind",
+            &errors);
+
+        out
+    }
+}
+
+/// Cache statistics
+#[derive(Debug)]
+pub struct CacheStats {
+    pub total_entries: usize,
+    pub genesis_domains: usize,
+    pub traditional_domains: usize,
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that fell through to the network
+    pub misses: u64,
+    /// Entries dropped to stay within the cache size limit
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups served from the cache (0.0 when nothing was looked up)
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// DNS resolution errors
+#[derive(Debug, thiserror::Error)]
+pub enum DnsError {
+    #[error("Unsupported domain: {0}")]
+    UnsupportedDomain(String),
+    
+    #[error("Connection error: {0}")]
+    ConnectionError(String),
+    
+    #[error("Node error: {0}")]
+    NodeError(String),
+    
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+    
+    #[error("Resolution failed: {0}")]
+    ResolutionFailed(String),
+    
+    #[error("No results for domain: {0}")]
+    NoResults(String),
+    
+    #[error("Cache error: {0}")]
+    CacheError(String),
+    
+    #[error("All Genesis nodes failed: {}", .0.join("; "))]
+    AllNodesFailed(Vec<String>),
+    
+    #[error("Integrity check failed: {0}")]
+    IntegrityCheckFailed(String),
+    
+    #[error("Invalid domain name: {0}")]
+    InvalidDomainName(String),
+    
+    #[error("Blocking call not allowed: {0}")]
+    BlockingInAsyncContext(String),
+    
+    #[error("Invalid proxy: {0}")]
+    InvalidProxy(String),
+    
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+}
+
+impl DnsError {
+    /// Stable short name of the error variant, e.g. for metric labels
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DnsError::UnsupportedDomain(_) => "unsupported_domain",
+            DnsError::ConnectionError(_) => "connection",
+            DnsError::NodeError(_) => "node",
+            DnsError::InvalidResponse(_) => "invalid_response",
+            DnsError::ResolutionFailed(_) => "resolution_failed",
+            DnsError::NoResults(_) => "no_results",
+            DnsError::CacheError(_) => "cache",
+            DnsError::AllNodesFailed(_) => "all_nodes_failed",
+            DnsError::IntegrityCheckFailed(_) => "integrity_check_failed",
+            DnsError::InvalidDomainName(_) => "invalid_domain_name",
+            DnsError::BlockingInAsyncContext(_) => "blocking_in_async_context",
+            DnsError::InvalidProxy(_) => "invalid_proxy",
+            DnsError::InvalidHeader(_) => "invalid_header",
+        }
+    }
+}
+
+/// Ask an IPFS node to pin a content hash through `/api/v0/pin/add`
+#[cfg(feature = "pinned-offline")]
+async fn pin_on_node(client: &reqwest::Client, api_url: &str, hash: &str) -> Result<(), DnsError> {
+    let response = client.post(format!("{}/api/v0/pin/add", api_url))
+        .query(&[("arg", hash)])
+        .timeout(PIN_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| DnsError::ConnectionError(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(DnsError::NodeError(format!("IPFS node did not pin {}: {} {}", hash, status, body.trim())));
+    }
+    info!("📌 Pinned {} on the IPFS node", hash);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genesis_result(domain: &str, ip: &str, ttl: u64, timestamp: u64) -> DnsResult {
+        let ip: IpAddr = ip.parse().unwrap();
+        DnsResult {
+            domain: domain.to_string(),
+            original_domain: domain.to_string(),
+            ip_address: Some(ip),
+            ip_addresses: vec![ip],
+            content_hash: None,
+            resolver_type: ResolverType::Genesis,
+            ttl,
+            timestamp,
+            eth_address: None,
+            next_index: Arc::default(),
+        }
+    }
+
+    /// Serve canned JSON responses by request path, and 404 for anything else
+    async fn spawn_mock_node(routes: Vec<(&'static str, &'static str)>) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0u8; 4096];
+                let read = socket.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+                let response = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn test_is_genesis_domain() {
+        let resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        
+        assert!(resolver.is_genesis_domain("test.genesis"));
+        assert!(resolver.is_genesis_domain("freedom.free"));
+        assert!(resolver.is_genesis_domain("mysite.web"));
+        assert!(resolver.is_genesis_domain("token.defi"));
+        assert!(!resolver.is_genesis_domain("google.com"));
+        assert!(!resolver.is_genesis_domain("example.org"));
+    }
+
+    #[test]
+    fn test_custom_genesis_tlds() {
+        let mut resolver = GenesisDnsResolver::new_with_tlds(
+            "http://localhost:3000".to_string(),
+            true,
+            vec!["node".to_string(), ".genesis".to_string()],
+        );
+
+        assert!(resolver.is_genesis_domain("private.node"));
+        assert!(resolver.is_genesis_domain("test.genesis"));
+        assert!(!resolver.is_genesis_domain("freedom.free"));
+
+        resolver.set_genesis_tlds(vec![".free".to_string()]);
+        assert!(resolver.is_genesis_domain("freedom.free"));
+        assert!(!resolver.is_genesis_domain("private.node"));
+        assert_eq!(resolver.genesis_tlds(), &[".free".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_cache_functionality() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        
+        // Mock a cache entry
+        let result = genesis_result(
+            "test.genesis",
+            "192.168.1.100",
+            3600,
+            chrono::Utc::now().timestamp() as u64,
+        );
+        
         resolver.cache.insert("test.genesis".to_string(), result.clone());
         
         // Test cache hit
         let stats = resolver.cache_stats();
         assert_eq!(stats.total_entries, 1);
         assert_eq!(stats.genesis_domains, 1);
+        
+        let resolved = resolver.resolve("test.genesis").await.unwrap();
+        assert_eq!(resolved.ip_address, result.ip_address);
+        
+        let stats = resolver.cache_stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 0);
+        
+        // Cleanup must not touch the counters
+        resolver.cleanup_cache();
+        assert_eq!(resolver.cache_stats().hits, 1);
+        
+        resolver.reset_stats();
+        assert_eq!(resolver.cache_stats().hits, 0);
+    }
+
+    #[test]
+    fn test_cache_persistence() {
+        let path = std::env::temp_dir()
+            .join(format!("genesis-dns-cache-{}.json", std::process::id()));
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        resolver.cache.insert(
+            "fresh.genesis".to_string(),
+            genesis_result("fresh.genesis", "10.0.0.1", 3600, now),
+        );
+        resolver.cache.insert(
+            "stale.genesis".to_string(),
+            genesis_result("stale.genesis", "10.0.0.2", 60, now - 3600),
+        );
+        resolver.cache_hits = 3;
+        resolver.cache_misses = 1;
+        resolver.save_cache_to(&path).unwrap();
+
+        let mut restored = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        assert_eq!(restored.load_cache_from(&path).unwrap(), 1);
+        assert!(restored.cache.contains_key("fresh.genesis"));
+        assert!(!restored.cache.contains_key("stale.genesis"));
+        let stats = restored.cache_stats();
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+        assert_eq!(stats.hit_ratio(), 0.75);
+
+        // Files holding only the entries still load, with no counters
+        std::fs::write(&path, serde_json::to_vec(&resolver.cache).unwrap()).unwrap();
+        let mut legacy = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        assert_eq!(legacy.load_cache_from(&path).unwrap(), 1);
+        assert_eq!(legacy.cache_stats().hits, 0);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_multiple_addresses_round_robin() {
+        let resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        let record: GenesisDomain = serde_json::from_str(r#"{
+            "name": "balanced.genesis",
+            "owner": "owner",
+            "resolver": "genesis",
+            "content_hash": null,
+            "ip_addresses": ["10.0.0.1", "10.0.0.2", "10.0.0.3"],
+            "ttl": 600
+        }"#).unwrap();
+
+        let result = resolver.genesis.convert_genesis_domain(record).unwrap();
+        assert_eq!(result.ip_addresses.len(), 3);
+        assert_eq!(result.to_string(), "10.0.0.1");
+
+        let picked: Vec<IpAddr> = (0..4).filter_map(|_| result.next_address()).collect();
+        assert_eq!(picked, vec![
+            "10.0.0.1".parse::<IpAddr>().unwrap(),
+            "10.0.0.2".parse().unwrap(),
+            "10.0.0.3".parse().unwrap(),
+            "10.0.0.1".parse().unwrap(),
+        ]);
+
+        // Legacy single-address responses still work
+        let legacy: GenesisDomain = serde_json::from_str(r#"{
+            "name": "legacy.genesis",
+            "owner": "owner",
+            "resolver": "genesis",
+            "ip_address": "10.0.0.9"
+        }"#).unwrap();
+        let result = resolver.genesis.convert_genesis_domain(legacy).unwrap();
+        assert_eq!(result.ip_addresses, vec!["10.0.0.9".parse::<IpAddr>().unwrap()]);
+        assert_eq!(result.next_address(), result.ip_address);
+    }
+
+    #[test]
+    fn test_retry_backoff_is_exponential_and_capped() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        resolver.set_retry_policy(10, 100);
+
+        assert_eq!(resolver.genesis.retry_delay(1), Duration::from_millis(100));
+        assert_eq!(resolver.genesis.retry_delay(2), Duration::from_millis(200));
+        assert_eq!(resolver.genesis.retry_delay(3), Duration::from_millis(400));
+        assert_eq!(resolver.genesis.retry_delay(10), Duration::from_millis(MAX_RETRY_DELAY_MS));
+    }
+
+    #[test]
+    fn test_multiple_genesis_nodes() {
+        let resolver = GenesisDnsResolver::new_with_nodes(
+            vec!["http://node-a:3000/".to_string(), "http://node-b:3000".to_string()],
+            false,
+        );
+        assert_eq!(resolver.genesis_node_urls(), &[
+            "http://node-a:3000".to_string(),
+            "http://node-b:3000".to_string(),
+        ]);
+
+        let single = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        assert_eq!(single.genesis_node_urls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_negative_cache() {
+        // Nothing listens on the discard port, so any network lookup fails fast
+        let mut resolver = GenesisDnsResolver::new("http://127.0.0.1:9".to_string(), false);
+        resolver.set_retry_policy(0, 0);
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        resolver.negative_cache.insert("typo.genesis".to_string(), now + 30);
+        assert!(matches!(
+            resolver.resolve("typo.genesis").await,
+            Err(DnsError::UnsupportedDomain(_))
+        ));
+        assert_eq!(resolver.cache_stats().hits, 1);
+
+        // Once the negative entry lapses the node is asked again
+        resolver.negative_cache.insert("typo.genesis".to_string(), now - 1);
+        assert!(matches!(
+            resolver.resolve("typo.genesis").await,
+            Err(DnsError::ConnectionError(_))
+        ));
+        assert!(!resolver.negative_cache.contains_key("typo.genesis"));
+
+        resolver.negative_cache.insert("old.genesis".to_string(), now - 1);
+        resolver.cleanup_cache();
+        assert!(resolver.negative_cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_many_preserves_order() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), false);
+        let now = chrono::Utc::now().timestamp() as u64;
+        resolver.cache.insert("a.genesis".to_string(), genesis_result("a.genesis", "10.0.0.1", 3600, now));
+        resolver.cache.insert("b.genesis".to_string(), genesis_result("b.genesis", "10.0.0.2", 3600, now));
+
+        let domains = vec![
+            "b.genesis".to_string(),
+            "example.com".to_string(),
+            "a.genesis".to_string(),
+        ];
+        let results = resolver.resolve_many(&domains).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().domain, "b.genesis");
+        assert!(matches!(results[1], Err(DnsError::UnsupportedDomain(_))));
+        assert_eq!(results[2].as_ref().unwrap().domain, "a.genesis");
+
+        let stats = resolver.cache_stats();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[cfg(feature = "signature-verification")]
+    #[test]
+    fn test_content_hash_signature() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        let owner = SigningKey::from_bytes(&[7u8; 32]);
+        let content_hash = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+        let signature = hex::encode(owner.sign(content_hash.as_bytes()).to_bytes());
+
+        let record = |signature: Option<String>| GenesisDomain {
+            name: "site.genesis".to_string(),
+            owner: hex::encode(owner.verifying_key().to_bytes()),
+            resolver: "ipfs".to_string(),
+            content_hash: Some(content_hash.to_string()),
+            content_hash_signature: signature,
+            ip_address: None,
+            ip_addresses: Vec::new(),
+            ttl: None,
+            resolver_type: None,
+            wildcard: false,
+        };
+
+        let result = resolver.genesis.convert_genesis_domain(record(Some(signature))).unwrap();
+        assert_eq!(result.content_hash.as_deref(), Some(content_hash));
+
+        let forged = hex::encode(owner.sign(b"QmSomethingElse").to_bytes());
+        assert!(matches!(
+            resolver.genesis.convert_genesis_domain(record(Some(forged))),
+            Err(DnsError::IntegrityCheckFailed(_))
+        ));
+        assert!(matches!(
+            resolver.genesis.convert_genesis_domain(record(None)),
+            Err(DnsError::IntegrityCheckFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolution_events() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), false);
+        let (sender, mut receiver) = mpsc::channel(8);
+        resolver.set_event_sender(sender);
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        resolver.cache.insert("a.genesis".to_string(), genesis_result("a.genesis", "10.0.0.1", 3600, now));
+
+        resolver.resolve("a.genesis").await.unwrap();
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(ResolutionEvent::CacheHit { domain }) if domain == "a.genesis"
+        ));
+
+        resolver.resolve("example.com").await.unwrap_err();
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(ResolutionEvent::Failed { domain, .. }) if domain == "example.com"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_idn_domains_share_cache_entry() {
+        assert_eq!(normalize_domain("Café.Genesis").unwrap(), "xn--caf-dma.genesis");
+        assert_eq!(normalize_domain("xn--caf-dma.genesis.").unwrap(), "xn--caf-dma.genesis");
+        assert!(matches!(normalize_domain("  "), Err(DnsError::InvalidDomainName(_))));
+
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), false);
+        let now = chrono::Utc::now().timestamp() as u64;
+        resolver.cache.insert(
+            "xn--caf-dma.genesis".to_string(),
+            genesis_result("xn--caf-dma.genesis", "10.0.0.1", 3600, now),
+        );
+
+        let unicode = resolver.resolve("café.genesis").await.unwrap();
+        assert_eq!(unicode.domain, "xn--caf-dma.genesis");
+        assert_eq!(unicode.original_domain, "café.genesis");
+
+        let punycode = resolver.resolve("xn--caf-dma.genesis").await.unwrap();
+        assert_eq!(punycode.ip_address, unicode.ip_address);
+        assert_eq!(resolver.cache_stats().hits, 2);
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        resolver.set_max_cache_entries(2);
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        resolver.store_result("a.genesis", &Ok(genesis_result("a.genesis", "10.0.0.1", 3600, now)));
+        resolver.store_result("b.genesis", &Ok(genesis_result("b.genesis", "10.0.0.2", 3600, now)));
+
+        // Touching "a" makes "b" the least recently used entry
+        assert!(matches!(resolver.lookup_cache("a.genesis"), Some(Ok(_))));
+        resolver.store_result("c.genesis", &Ok(genesis_result("c.genesis", "10.0.0.3", 3600, now)));
+
+        assert!(resolver.cache.contains_key("a.genesis"));
+        assert!(!resolver.cache.contains_key("b.genesis"));
+        assert!(resolver.cache.contains_key("c.genesis"));
+        assert_eq!(resolver.cache_stats().evictions, 1);
+        assert_eq!(resolver.cache_recency.len(), resolver.cache.len());
+
+        // Shrinking the limit evicts down to size
+        resolver.set_max_cache_entries(1);
+        assert_eq!(resolver.cache_stats().total_entries, 1);
+        assert!(resolver.cache.contains_key("c.genesis"));
+        assert_eq!(resolver.cache_stats().evictions, 2);
+    }
+
+    #[test]
+    fn test_metrics_text() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        resolver.store_result("a.genesis", &Ok(genesis_result("a.genesis", "10.0.0.1", 3600, now)));
+        resolver.store_result("b.genesis", &Err(DnsError::ConnectionError("refused".to_string())));
+        resolver.lookup_cache("a.genesis");
+
+        let metrics = resolver.metrics_text();
+        assert!(metrics.contains("# TYPE genesis_dns_cache_hits_total counter\n"));
+        assert!(metrics.contains("genesis_dns_cache_entries 1\n"));
+        assert!(metrics.contains("genesis_dns_cache_hits_total 1\n"));
+        assert!(metrics.contains("genesis_dns_resolutions_total{type=\"genesis\"} 1\n"));
+        assert!(metrics.contains("genesis_dns_errors_total{kind=\"connection\"} 1\n"));
+    }
+
+    #[tokio::test]
+    async fn test_reverse_resolve_cache() {
+        // Nothing listens on the discard port, so any network lookup fails fast
+        let mut resolver = GenesisDnsResolver::new("http://127.0.0.1:9".to_string(), false);
+        resolver.set_retry_policy(0, 0);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        resolver.reverse_cache.insert(ip, ReverseEntry {
+            domains: vec!["a.genesis".to_string(), "b.genesis".to_string()],
+            ttl: 3600,
+            timestamp: now,
+        });
+        assert_eq!(resolver.reverse_resolve(ip).await.unwrap(), vec!["a.genesis", "b.genesis"]);
+
+        // Expired entries go back to the node
+        resolver.reverse_cache.get_mut(&ip).unwrap().timestamp = now - 7200;
+        assert!(matches!(resolver.reverse_resolve(ip).await, Err(DnsError::ConnectionError(_))));
+
+        // Neither lookup skews the forward hit ratio
+        let stats = resolver.cache_stats();
+        assert_eq!((stats.hits, stats.misses), (0, 0));
+
+        resolver.cleanup_cache();
+        assert!(resolver.reverse_cache.is_empty());
+
+        let record: ReverseRecord = serde_json::from_str(r#"{"domains": []}"#).unwrap();
+        assert!(record.domains.is_empty());
+    }
+
+    #[test]
+    fn test_per_tld_default_ttl() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        resolver.set_default_ttl("defi", 60);
+        resolver.set_default_ttl(".dao", 86_400);
+
+        let record = |json: &str| -> GenesisDomain { serde_json::from_str(json).unwrap() };
+
+        let defi = resolver.genesis.convert_genesis_domain(record(
+            r#"{"name": "swap.defi", "owner": "o", "resolver": "genesis", "ip_address": "10.0.0.1"}"#,
+        )).unwrap();
+        assert_eq!(defi.ttl, 60);
+
+        let dao = resolver.genesis.convert_genesis_domain(record(
+            r#"{"name": "vote.dao", "owner": "o", "resolver": "genesis", "ip_address": "10.0.0.2"}"#,
+        )).unwrap();
+        assert_eq!(dao.ttl, 86_400);
+
+        let other = resolver.genesis.convert_genesis_domain(record(
+            r#"{"name": "site.genesis", "owner": "o", "resolver": "genesis", "ip_address": "10.0.0.3"}"#,
+        )).unwrap();
+        assert_eq!(other.ttl, DEFAULT_TTL_SECS);
+
+        // An explicit TTL from the node always wins
+        let explicit = resolver.genesis.convert_genesis_domain(record(
+            r#"{"name": "swap.defi", "owner": "o", "resolver": "genesis", "ip_address": "10.0.0.1", "ttl": 900}"#,
+        )).unwrap();
+        assert_eq!(explicit.ttl, 900);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_resolve_blocking() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), false);
+        let now = chrono::Utc::now().timestamp() as u64;
+        resolver.cache.insert("a.genesis".to_string(), genesis_result("a.genesis", "10.0.0.1", 3600, now));
+
+        let result = resolver.resolve_blocking("a.genesis").unwrap();
+        assert_eq!(result.ip_address, Some("10.0.0.1".parse().unwrap()));
+        assert_eq!(resolver.cache_stats().hits, 1);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let error = runtime.block_on(async { resolver.resolve_blocking("a.genesis") });
+        assert!(matches!(error, Err(DnsError::BlockingInAsyncContext(_))));
+    }
+
+    #[tokio::test]
+    async fn test_check_health() {
+        let healthy = spawn_mock_node(vec![("/health", r#"{"status": "ok"}"#)]).await;
+        let mut resolver = GenesisDnsResolver::new(healthy, false);
+        resolver.set_retry_policy(0, 0);
+        assert!(resolver.check_health().await.is_ok());
+
+        let unhealthy = spawn_mock_node(Vec::new()).await;
+        let mut resolver = GenesisDnsResolver::new(unhealthy, false);
+        resolver.set_retry_policy(0, 0);
+        assert!(matches!(resolver.check_health().await, Err(DnsError::NodeError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_check_node_health() {
+        let node = spawn_mock_node(vec![
+            ("/health", r#"{"status": "ok"}"#),
+            ("/info", r#"{"version": "1.4.2", "blockHeight": 90210, "peers": 12}"#),
+        ]).await;
+        let health = GenesisDnsResolver::new(node, false).check_node_health().await;
+        assert!(health.online);
+        assert_eq!(health.version.as_deref(), Some("1.4.2"));
+        assert_eq!(health.block_height, Some(90210));
+        assert!(health.to_string().starts_with("online, v1.4.2, block 90210, "));
+
+        // A node without `/info` is still online
+        let bare = spawn_mock_node(vec![("/health", r#"{"status": "ok"}"#)]).await;
+        let health = GenesisDnsResolver::new(bare, false).check_node_health().await;
+        assert!(health.online);
+        assert_eq!((health.version, health.block_height), (None, None));
+
+        let health = GenesisDnsResolver::new("http://127.0.0.1:9".to_string(), false).check_node_health().await;
+        assert!(!health.online);
+        assert_eq!(health.to_string(), "offline");
+    }
+
+    #[test]
+    fn test_set_proxy() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), false);
+        assert!(resolver.set_proxy("socks5://127.0.0.1:9050").is_ok());
+        assert!(resolver.set_proxy("http://proxy.local:8080").is_ok());
+
+        for bad in ["127.0.0.1:9050", "ftp://proxy.local", "socks5://", "not a url"] {
+            assert!(matches!(resolver.set_proxy(bad), Err(DnsError::InvalidProxy(_))), "{}", bad);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_node_headers() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // A node that only answers authenticated requests
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0u8; 4096];
+                let read = socket.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]).to_ascii_lowercase();
+                let status = if request.contains("authorization: bearer secret") { "200 OK" } else { "401 Unauthorized" };
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let mut resolver = GenesisDnsResolver::new(node, false);
+        resolver.set_retry_policy(0, 0);
+        assert!(resolver.check_health().await.is_err());
+
+        let headers = HashMap::from([("Authorization".to_string(), "Bearer secret".to_string())]);
+        resolver.set_node_headers(&headers).unwrap();
+        assert!(resolver.check_health().await.is_ok());
+
+        let bad = HashMap::from([("Bad Header".to_string(), "x".to_string())]);
+        assert!(matches!(resolver.set_node_headers(&bad), Err(DnsError::InvalidHeader(_))));
+    }
+
+    #[cfg(feature = "doh-fallback")]
+    #[tokio::test]
+    async fn test_doh_requests_skip_node_headers() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // A DoH server that reports the requests it gets
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/dns-query", listener.local_addr().unwrap());
+        let (request_sender, mut requests) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0u8; 4096];
+                let read = socket.read(&mut buffer).await.unwrap_or(0);
+                let _ = request_sender.send(String::from_utf8_lossy(&buffer[..read]).to_ascii_lowercase());
+                let body = r#"{"Status": 0, "Answer": [{"type": 1, "TTL": 60, "data": "93.184.216.34"}]}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/dns-json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        let headers = HashMap::from([("Authorization".to_string(), "Bearer secret".to_string())]);
+        resolver.set_node_headers(&headers).unwrap();
+        resolver.set_doh_endpoint(endpoint).unwrap();
+
+        let result = resolver.resolve("example.com").await.unwrap();
+        assert_eq!(result.ip_address, Some("93.184.216.34".parse().unwrap()));
+        let request = requests.recv().await.unwrap();
+        assert!(request.starts_with("get /dns-query?"));
+        assert!(!request.contains("authorization"));
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        // A node that accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+
+        let mut resolver = GenesisDnsResolver::new(node, false);
+        resolver.set_retry_policy(0, 0);
+        resolver.set_request_timeout(Duration::from_millis(200));
+
+        let started = std::time::Instant::now();
+        assert!(resolver.check_health().await.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_subdomains() {
+        let node = spawn_mock_node(vec![(
+            "/api/dns/resolve/mysite.genesis?wildcard=true",
+            r#"{"name": "*.mysite.genesis", "owner": "o", "resolver": "genesis",
+                "ip_address": "10.0.0.7", "ttl": 120, "wildcard": true}"#,
+        )]).await;
+        let mut resolver = GenesisDnsResolver::new(node, false);
+        resolver.set_retry_policy(0, 0);
+
+        assert_eq!(resolver.genesis.wildcard_parents("v1.api.mysite.genesis"), vec![
+            "api.mysite.genesis".to_string(),
+            "mysite.genesis".to_string(),
+        ]);
+        assert!(resolver.genesis.wildcard_parents("mysite.genesis").is_empty());
+
+        // Three levels: the direct parent holds the wildcard
+        let app = resolver.resolve("app.mysite.genesis").await.unwrap();
+        assert_eq!(app.domain, "app.mysite.genesis");
+        assert_eq!(app.ip_address, Some("10.0.0.7".parse().unwrap()));
+        assert_eq!(app.ttl, 120);
+
+        // Four levels: "api.mysite.genesis" has no record, "mysite.genesis" answers
+        let v1 = resolver.resolve("v1.api.mysite.genesis").await.unwrap();
+        assert_eq!(v1.domain, "v1.api.mysite.genesis");
+        assert_eq!(v1.ip_address, Some("10.0.0.7".parse().unwrap()));
+
+        assert!(resolver.cache.contains_key("app.mysite.genesis"));
+        assert!(resolver.cache.contains_key("v1.api.mysite.genesis"));
+
+        assert!(matches!(
+            resolver.resolve("missing.genesis").await,
+            Err(DnsError::UnsupportedDomain(_))
+        ));
+    }
+
+    #[cfg(feature = "live-updates")]
+    #[tokio::test]
+    async fn test_live_updates_invalidate_cached_domains() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            for update in [r#"{"domain": "a.genesis"}"#, r#"{"name": "uncached.genesis"}"#, "not json"] {
+                socket.send(Message::Text(update.to_string())).await.unwrap();
+            }
+            // Keep the connection open until the resolver goes away
+            while socket.next().await.is_some() {}
+        });
+
+        let mut resolver = GenesisDnsResolver::new(node, false);
+        let now = chrono::Utc::now().timestamp() as u64;
+        resolver.cache.insert("a.genesis".to_string(), genesis_result("a.genesis", "10.0.0.1", 3600, now));
+        resolver.cache.insert("b.genesis".to_string(), genesis_result("b.genesis", "10.0.0.2", 3600, now));
+        resolver.subscribe_updates().unwrap();
+
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            resolver.apply_refreshes();
+            if !resolver.cache.contains_key("a.genesis") {
+                break;
+            }
+        }
+        assert!(!resolver.cache.contains_key("a.genesis"), "live update never landed");
+        assert!(resolver.cache.contains_key("b.genesis"));
+        assert!(!resolver.cache.contains_key("uncached.genesis"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_uncached_bypasses_cache() {
+        let node = spawn_mock_node(vec![(
+            "/api/dns/resolve/a.genesis",
+            r#"{"name": "a.genesis", "owner": "o", "resolver": "genesis", "ip_address": "10.0.0.2", "ttl": 3600}"#,
+        )]).await;
+        let mut resolver = GenesisDnsResolver::new(node, false);
+        resolver.set_retry_policy(0, 0);
+
+        // A fresh cache entry is ignored and left untouched
+        let now = chrono::Utc::now().timestamp() as u64;
+        resolver.cache.insert("a.genesis".to_string(), genesis_result("a.genesis", "10.0.0.1", 3600, now));
+        let result = resolver.resolve_uncached("A.genesis").await.unwrap();
+        assert_eq!(result.ip_address, Some("10.0.0.2".parse().unwrap()));
+        assert_eq!(result.original_domain, "A.genesis");
+        assert_eq!(resolver.cache["a.genesis"].ip_address, Some("10.0.0.1".parse().unwrap()));
+        assert_eq!(resolver.cache_stats().hits, 0);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_shared_releases_lock_during_lookup() {
+        // A node that accepts connections and never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+
+        let mut resolver = GenesisDnsResolver::new(node, false);
+        resolver.set_retry_policy(0, 0);
+        let now = chrono::Utc::now().timestamp() as u64;
+        resolver.insert_cache_entry("cached.genesis".to_string(), genesis_result("cached.genesis", "10.0.0.1", 3600, now));
+        let resolver = Arc::new(tokio::sync::Mutex::new(resolver));
+
+        let hung = tokio::spawn({
+            let resolver = resolver.clone();
+            async move { GenesisDnsResolver::resolve_shared(&resolver, "hung.genesis").await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // The hung lookup doesn't hold the resolver
+        let cached = tokio::time::timeout(
+            Duration::from_secs(1),
+            GenesisDnsResolver::resolve_shared(&resolver, "cached.genesis"),
+        ).await.expect("cached lookup waited on the hung one").unwrap();
+        assert_eq!(cached.ip_address, Some("10.0.0.1".parse().unwrap()));
+        assert!(!hung.is_finished());
+        hung.abort();
+    }
+
+    #[tokio::test]
+    async fn test_begin_and_finish_lookup_share_the_cache() {
+        let node = spawn_mock_node(vec![(
+            "/api/dns/resolve/a.genesis",
+            r#"{"name": "a.genesis", "owner": "o", "resolver": "genesis", "ip_address": "10.0.0.2", "ttl": 3600}"#,
+        )]).await;
+        let mut resolver = GenesisDnsResolver::new(node, false);
+        resolver.set_retry_policy(0, 0);
+
+        let Lookup::Pending(pending) = resolver.begin_lookup("A.genesis") else {
+            panic!("an empty cache can't answer");
+        };
+        let result = resolver.finish_lookup(pending.run().await).unwrap();
+        assert_eq!(result.ip_address, Some("10.0.0.2".parse().unwrap()));
+        assert_eq!(result.original_domain, "A.genesis");
+
+        let Lookup::Done(Ok(cached)) = resolver.begin_lookup("a.genesis") else {
+            panic!("the finished lookup wasn't cached");
+        };
+        assert_eq!(cached.ip_address, Some("10.0.0.2".parse().unwrap()));
+        assert_eq!((resolver.cache_stats().hits, resolver.cache_stats().misses), (1, 1));
+        assert!(matches!(resolver.begin_lookup(""), Lookup::Done(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stale_while_revalidate() {
+        let node = spawn_mock_node(vec![(
+            "/api/dns/resolve/a.genesis",
+            r#"{"name": "a.genesis", "owner": "o", "resolver": "genesis", "ip_address": "10.0.0.2", "ttl": 3600}"#,
+        )]).await;
+        let mut resolver = GenesisDnsResolver::new(node, false);
+        resolver.set_retry_policy(0, 0);
+        resolver.enable_stale_while_revalidate(300, tokio::runtime::Handle::current());
+
+        // Expired 100 seconds ago, inside the grace window: served stale
+        let now = chrono::Utc::now().timestamp() as u64;
+        resolver.cache.insert("a.genesis".to_string(), genesis_result("a.genesis", "10.0.0.1", 3600, now - 3700));
+        let stale = resolver.resolve("a.genesis").await.unwrap();
+        assert_eq!(stale.ip_address, Some("10.0.0.1".parse().unwrap()));
+
+        let mut refreshed = None;
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let result = resolver.resolve("a.genesis").await.unwrap();
+            if result.ip_address == Some("10.0.0.2".parse().unwrap()) {
+                refreshed = Some(result);
+                break;
+            }
+        }
+        assert!(refreshed.is_some(), "background refresh never landed");
+        assert!(resolver.refreshing.is_empty());
+
+        // Far past the grace window the lookup blocks on the node again
+        resolver.cache.insert("a.genesis".to_string(), genesis_result("a.genesis", "10.0.0.1", 60, now - 10_000));
+        let fresh = resolver.resolve("a.genesis").await.unwrap();
+        assert_eq!(fresh.ip_address, Some("10.0.0.2".parse().unwrap()));
     }
-}
\ No newline at end of file
+
+    // The records carry unsigned content hashes
+    #[cfg(not(feature = "signature-verification"))]
+    #[test]
+    fn test_resolver_type_override() {
+        let resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), true);
+        let record = |json: &str| -> GenesisDomain { serde_json::from_str(json).unwrap() };
+
+        let ipfs = resolver.genesis.convert_genesis_domain(record(r#"{"name": "a.genesis", "owner": "o", "resolver": "genesis",
+            "ip_address": "10.0.0.1", "content_hash": "QmHash", "resolver_type": "ipfs"}"#)).unwrap();
+        assert!(matches!(ipfs.resolver_type, ResolverType::IPFS));
+
+        let hybrid = resolver.genesis.convert_genesis_domain(record(r#"{"name": "b.genesis", "owner": "o", "resolver": "genesis",
+            "ip_address": "10.0.0.2", "content_hash": "QmHash", "resolver_type": "Hybrid"}"#)).unwrap();
+        assert!(matches!(hybrid.resolver_type, ResolverType::Hybrid));
+        assert_eq!(hybrid.content_hash.as_deref(), Some("QmHash"));
+        assert_eq!(hybrid.to_string(), "10.0.0.2");
+
+        // A hybrid record without content falls back to the heuristic, as do unknown values
+        let partial = resolver.genesis.convert_genesis_domain(record(r#"{"name": "c.genesis", "owner": "o", "resolver": "genesis",
+            "ip_address": "10.0.0.3", "resolver_type": "hybrid"}"#)).unwrap();
+        assert!(matches!(partial.resolver_type, ResolverType::Genesis));
+
+        let unknown = resolver.genesis.convert_genesis_domain(record(r#"{"name": "d.genesis", "owner": "o", "resolver": "genesis",
+            "content_hash": "QmHash", "resolver_type": "carrier-pigeon"}"#)).unwrap();
+        assert!(matches!(unknown.resolver_type, ResolverType::IPFS));
+    }
+
+    /// Answers every `.test` name with a fixed content hash
+    struct StaticBackend;
+
+    impl ResolverBackend for StaticBackend {
+        fn handles(&self, domain: &str) -> bool {
+            domain.ends_with(".test")
+        }
+
+        fn resolve<'a>(&'a self, domain: &'a str) -> futures::future::BoxFuture<'a, Result<DnsResult, DnsError>> {
+            let mut result = genesis_result(domain, "10.0.0.9", 60, chrono::Utc::now().timestamp() as u64);
+            result.content_hash = Some("bafytest".to_string());
+            Box::pin(async move { Ok(result) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_backend_dispatch() {
+        let mut resolver = GenesisDnsResolver::new("http://127.0.0.1:9".to_string(), false);
+        assert!(resolver.backend_for("site.test").is_none());
+
+        resolver.register_backend(Arc::new(StaticBackend));
+        let result = resolver.resolve("Site.Test").await.unwrap();
+        assert_eq!(result.domain, "site.test");
+        assert_eq!(result.content_hash.as_deref(), Some("bafytest"));
+        assert!(resolver.cache.contains_key("site.test"));
+
+        // Genesis TLDs still go to the Genesis backend, other names stay unsupported
+        assert!(resolver.backend_for("mysite.genesis").is_some());
+        assert!(matches!(resolver.resolve("example.com").await, Err(DnsError::UnsupportedDomain(_))));
+    }
+
+    #[tokio::test]
+    async fn test_ipfs_gateway_fallthrough() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), false);
+        assert_eq!(resolver.ipfs_gateways(), DEFAULT_IPFS_GATEWAYS);
+
+        // A gateway that's down is skipped for the next one in line
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let gateway = format!("http://{}/ipfs", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let _ = socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await;
+            }
+        });
+        resolver.set_ipfs_gateways(vec!["http://127.0.0.1:9/ipfs/".to_string(), gateway.clone(), "local".to_string()]);
+        assert_eq!(resolver.ipfs_gateways()[1], format!("{}/", gateway));
+        assert_eq!(resolver.ipfs_gateways()[2], LOCAL_IPFS_GATEWAY);
+
+        assert_eq!(resolver.ipfs_gateway_url("QmHash").await.unwrap(), format!("{}/QmHash", gateway));
+
+        resolver.set_ipfs_gateways(vec!["http://127.0.0.1:9/ipfs/".to_string()]);
+        assert!(matches!(resolver.ipfs_gateway_url("QmHash").await, Err(DnsError::ResolutionFailed(_))));
+    }
+
+    #[cfg(feature = "pinned-offline")]
+    #[tokio::test]
+    async fn test_pin_content() {
+        let mut resolver = GenesisDnsResolver::new("http://localhost:3000".to_string(), false);
+        assert!(matches!(resolver.pin_content("QmHash").await, Err(DnsError::ConnectionError(_))));
+
+        let api = spawn_mock_node(vec![("/api/v0/pin/add?arg=QmHash", r#"{"Pins":["QmHash"]}"#)]).await;
+        resolver.set_ipfs_api(Some(format!("{}/", api)), true);
+        assert_eq!(resolver.ipfs_api_url(), Some(api.as_str()));
+
+        resolver.pin_content("QmHash").await.unwrap();
+        assert!(matches!(resolver.pin_content("QmMissing").await, Err(DnsError::NodeError(_))));
+    }
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+use crate::normalize_domain;
+
+/// Path of the push update endpoint on a Genesis node
+pub const LIVE_UPDATES_PATH: &str = "/ws/dns";
+
+/// First reconnection delay, doubled on every failed attempt
+const RECONNECT_BASE_DELAY_MS: u64 = 500;
+
+/// Upper bound for the delay between reconnection attempts
+const MAX_RECONNECT_DELAY_MS: u64 = 30_000;
+
+/// Message pushed by a node when a domain's record changes
+#[derive(Debug, Deserialize)]
+struct DomainUpdate {
+    #[serde(alias = "name")]
+    domain: String,
+}
+
+/// WebSocket URL of a node's update endpoint, `None` for non-HTTP node URLs
+pub fn live_updates_url(node_url: &str) -> Option<String> {
+    let node_url = node_url.trim_end_matches('/');
+    let base = if let Some(rest) = node_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else {
+        format!("ws://{}", node_url.strip_prefix("http://")?)
+    };
+    Some(format!("{}{}", base, LIVE_UPDATES_PATH))
+}
+
+/// Hold a connection to one of `urls`, moving on to the next node and backing
+/// off whenever it drops, and forward every updated domain to `sender`.
+/// Returns once the receiving resolver is gone.
+pub(crate) async fn run_subscription(
+    urls: Vec<String>,
+    headers: Vec<(String, Vec<u8>)>,
+    sender: mpsc::UnboundedSender<String>,
+) {
+    let mut node = 0;
+    let mut failures: u32 = 0;
+
+    loop {
+        let url = &urls[node % urls.len()];
+        let request = url.as_str().into_client_request().map(|mut request| {
+            for (name, value) in &headers {
+                if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_bytes(value)) {
+                    request.headers_mut().insert(name, value);
+                }
+            }
+            request
+        });
+
+        match request {
+            Ok(request) => match tokio_tungstenite::connect_async(request).await {
+                Ok((mut socket, _)) => {
+                    info!("📡 Subscribed to live domain updates from {}", url);
+                    failures = 0;
+
+                    loop {
+                        let message = tokio::select! {
+                            message = socket.next() => message,
+                            _ = sender.closed() => return,
+                        };
+                        match message {
+                            Some(Ok(Message::Text(text))) => match serde_json::from_str::<DomainUpdate>(&text) {
+                                Ok(update) => match normalize_domain(&update.domain) {
+                                    Ok(domain) => {
+                                        debug!("📡 Live update for {}", domain);
+                                        if sender.send(domain).is_err() {
+                                            return;
+                                        }
+                                    }
+                                    Err(e) => debug!("Ignoring live update: {}", e),
+                                },
+                                Err(e) => debug!("Ignoring malformed live update: {}", e),
+                            },
+                            // Pings are answered by tungstenite itself
+                            Some(Ok(_)) => {}
+                            Some(Err(e)) => {
+                                warn!("⚠️ Live update connection to {} failed: {}", url, e);
+                                break;
+                            }
+                            None => break,
+                        }
+                    }
+                    warn!("📡 Live update connection to {} closed, reconnecting", url);
+                }
+                Err(e) => {
+                    warn!("⚠️ Failed to subscribe to {}: {}", url, e);
+                    node += 1;
+                }
+            },
+            Err(e) => {
+                warn!("⚠️ Invalid live update URL {}: {}", url, e);
+                node += 1;
+            }
+        }
+
+        let delay = (RECONNECT_BASE_DELAY_MS << failures.min(6)).min(MAX_RECONNECT_DELAY_MS);
+        failures += 1;
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(delay)) => {}
+            _ = sender.closed() => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_updates_url() {
+        assert_eq!(live_updates_url("http://localhost:3000/").as_deref(), Some("ws://localhost:3000/ws/dns"));
+        assert_eq!(live_updates_url("https://node.genesis").as_deref(), Some("wss://node.genesis/ws/dns"));
+        assert_eq!(live_updates_url("ftp://node.genesis"), None);
+    }
+}
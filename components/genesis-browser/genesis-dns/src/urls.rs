@@ -0,0 +1,143 @@
+use std::net::{IpAddr, Ipv6Addr};
+
+use url::Url;
+
+use crate::{has_genesis_tld, DEFAULT_GENESIS_TLDS};
+
+/// Schemes of pages the browser serves itself, taken as typed
+const BROWSER_SCHEMES: [&str; 2] = ["genesis", "about"];
+
+/// Scheme that shows the raw source of the address after it
+pub const VIEW_SOURCE_SCHEME: &str = "view-source";
+
+/// Why typed input isn't an address to navigate to
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UrlError {
+    #[error("No address given")]
+    Empty,
+
+    /// Words to search for rather than an address
+    #[error("Not an address: {0}")]
+    SearchQuery(String),
+
+    #[error("Invalid address: {0}")]
+    Invalid(String),
+}
+
+/// Turn typed input into the URL to navigate to. Hosts without a scheme get
+/// `http://`; `genesis://`, `about:` and `view-source:` addresses are kept.
+/// Single words and anything with spaces are `UrlError::SearchQuery`.
+pub fn normalize_url(input: &str) -> Result<Url, UrlError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(UrlError::Empty);
+    }
+    if input.chars().any(char::is_whitespace) {
+        return Err(UrlError::SearchQuery(input.to_string()));
+    }
+
+    if let Some((scheme, rest)) = input.split_once(':') {
+        let scheme = scheme.to_ascii_lowercase();
+        if scheme == VIEW_SOURCE_SCHEME {
+            let target = normalize_url(rest).map_err(|_| UrlError::Invalid(input.to_string()))?;
+            return parse(&format!("{}:{}", VIEW_SOURCE_SCHEME, target));
+        }
+        if BROWSER_SCHEMES.contains(&scheme.as_str()) {
+            return parse(input);
+        }
+        if rest.starts_with("//") {
+            let url = parse(input)?;
+            if matches!(url.scheme(), "http" | "https") && url.host_str().map_or(true, str::is_empty) {
+                return Err(UrlError::Invalid(input.to_string()));
+            }
+            return Ok(url);
+        }
+    }
+
+    // Without a scheme the input has to start with something that is clearly a host
+    if input.parse::<Ipv6Addr>().is_ok() {
+        return parse(&format!("http://[{}]", input));
+    }
+    let authority = input.split(['/', '?', '#']).next().unwrap_or("");
+    if is_host(authority) {
+        parse(&format!("http://{}", input))
+    } else {
+        Err(UrlError::SearchQuery(input.to_string()))
+    }
+}
+
+fn parse(url: &str) -> Result<Url, UrlError> {
+    Url::parse(url).map_err(|e| UrlError::Invalid(format!("{} ({})", url, e)))
+}
+
+/// Whether a scheme-less authority names a host: localhost, an IP, a Genesis
+/// domain or something like `example.com`, with or without a port
+fn is_host(authority: &str) -> bool {
+    // Bracketed IPv6, with or without a port
+    if let Some(rest) = authority.strip_prefix('[') {
+        return rest.split(']').next().map_or(false, |ip| ip.parse::<Ipv6Addr>().is_ok());
+    }
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => host,
+        _ => authority,
+    };
+    let host = host.to_lowercase();
+
+    if host == "localhost" || host.parse::<IpAddr>().is_ok() {
+        return true;
+    }
+    if host.starts_with('.') {
+        return false;
+    }
+    if has_genesis_tld(&host, &DEFAULT_GENESIS_TLDS) {
+        return true;
+    }
+    match host.rsplit_once('.') {
+        Some((name, tld)) => !name.is_empty() && tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalized(input: &str) -> String {
+        normalize_url(input).unwrap_or_else(|e| panic!("{} should be an address: {}", input, e)).to_string()
+    }
+
+    #[test]
+    fn test_hosts_get_a_scheme() {
+        assert_eq!(normalized("  example.com "), "http://example.com/");
+        assert_eq!(normalized("https://Example.com/a?b=1"), "https://example.com/a?b=1");
+        assert_eq!(normalized("localhost"), "http://localhost/");
+        assert_eq!(normalized("localhost:3000/api"), "http://localhost:3000/api");
+        assert_eq!(normalized("127.0.0.1"), "http://127.0.0.1/");
+        assert_eq!(normalized("192.168.1.10:8080"), "http://192.168.1.10:8080/");
+        assert_eq!(normalized("[::1]:3000"), "http://[::1]:3000/");
+        assert_eq!(normalized("::1"), "http://[::1]/");
+        assert_eq!(normalized("wiki.genesis"), "http://wiki.genesis/");
+        assert_eq!(normalized("dex.defi/swap"), "http://dex.defi/swap");
+        assert_eq!(normalized("Shop.DAO:8443"), "http://shop.dao:8443/");
+    }
+
+    #[test]
+    fn test_browser_schemes_are_kept() {
+        assert_eq!(normalized("genesis://welcome"), "genesis://welcome");
+        assert_eq!(normalized("about:blank"), "about:blank");
+        assert_eq!(normalized("view-source:wiki.genesis"), "view-source:http://wiki.genesis/");
+        assert_eq!(normalized("view-source:https://a.free/x"), "view-source:https://a.free/x");
+    }
+
+    #[test]
+    fn test_searches_and_invalid_input() {
+        for input in ["genesis", "rust egui", "what is .genesis", "3.14", "file.", ".com", ".genesis", "foo:bar", "256.1.1.1"] {
+            assert_eq!(normalize_url(input), Err(UrlError::SearchQuery(input.to_string())), "{}", input);
+        }
+        assert_eq!(normalize_url("   "), Err(UrlError::Empty));
+        assert!(matches!(normalize_url("http://"), Err(UrlError::Invalid(_))));
+        assert!(matches!(normalize_url("http://[::1"), Err(UrlError::Invalid(_))));
+        assert!(matches!(normalize_url("view-source:"), Err(UrlError::Invalid(_))));
+        assert!(matches!(normalize_url("view-source:genesis"), Err(UrlError::Invalid(_))));
+    }
+}
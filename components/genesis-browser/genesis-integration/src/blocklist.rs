@@ -0,0 +1,268 @@
+use std::collections::HashSet;
+use std::path::Path;
+use anyhow::{Result, Context};
+use tracing::info;
+use url::Url;
+
+/// Ad and tracker block list built from EasyList-style rules.
+///
+/// Supported: `||domain^` rules, URL patterns with `*`, `^` and `|`
+/// anchors, and `@@` exceptions. Rule options after `$` are ignored and
+/// element hiding rules (`##`) are skipped. Rules are compiled once when
+/// the list is parsed, so matching a request needs no allocation beyond
+/// lowercasing the URL.
+#[derive(Debug, Default)]
+pub struct BlockList {
+    blocked_domains: HashSet<String>,
+    blocked_patterns: Vec<UrlPattern>,
+    allowed_domains: HashSet<String>,
+    allowed_patterns: Vec<UrlPattern>,
+}
+
+impl BlockList {
+    /// Load and compile a list file
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read block list {}", path.display()))?;
+        let list = Self::parse(&text);
+        
+        info!("🛡️ Loaded {} block rules from {}", list.rule_count(), path.display());
+        Ok(list)
+    }
+    
+    /// Compile rules from list text, one rule per line
+    pub fn parse(text: &str) -> Self {
+        let mut list = Self::default();
+        
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('!') || line.starts_with('[') || line.contains("##") || line.contains("#@#") {
+                continue;
+            }
+            
+            let (rule, exception) = match line.strip_prefix("@@") {
+                Some(rule) => (rule, true),
+                None => (line, false),
+            };
+            let rule = rule.split('$').next().unwrap_or_default().to_ascii_lowercase();
+            if rule.is_empty() || rule == "*" {
+                continue;
+            }
+            
+            let (domains, patterns) = if exception {
+                (&mut list.allowed_domains, &mut list.allowed_patterns)
+            } else {
+                (&mut list.blocked_domains, &mut list.blocked_patterns)
+            };
+            match domain_rule(&rule) {
+                Some(domain) => {
+                    domains.insert(domain.to_string());
+                }
+                None => patterns.push(UrlPattern::compile(&rule)),
+            }
+        }
+        
+        list
+    }
+    
+    /// Whether a request to `url` should be aborted
+    pub fn should_block(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let url = url.as_str().to_ascii_lowercase();
+        let host = host.to_ascii_lowercase();
+        
+        let blocked = matches_domain(&self.blocked_domains, &host)
+            || self.blocked_patterns.iter().any(|p| p.matches(&url, &host));
+        blocked
+            && !matches_domain(&self.allowed_domains, &host)
+            && !self.allowed_patterns.iter().any(|p| p.matches(&url, &host))
+    }
+    
+    /// Number of compiled rules
+    pub fn rule_count(&self) -> usize {
+        self.blocked_domains.len() + self.blocked_patterns.len()
+            + self.allowed_domains.len() + self.allowed_patterns.len()
+    }
+}
+
+/// The domain of a rule that is exactly `||domain^`
+fn domain_rule(rule: &str) -> Option<&str> {
+    let domain = rule.strip_prefix("||")?.strip_suffix('^')?;
+    let is_plain = !domain.is_empty()
+        && domain.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    is_plain.then_some(domain)
+}
+
+/// `host` or any of its parent domains is in `domains`
+fn matches_domain(domains: &HashSet<String>, host: &str) -> bool {
+    let mut candidate = host;
+    loop {
+        if domains.contains(candidate) {
+            return true;
+        }
+        match candidate.split_once('.') {
+            Some((_, parent)) if !parent.is_empty() => candidate = parent,
+            _ => return false,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Token {
+    Literal(String),
+    Wildcard,
+    /// `^`: one separator character, or the end of the URL
+    Separator,
+}
+
+#[derive(Debug, PartialEq)]
+enum Anchor {
+    None,
+    /// `|`: start of the URL
+    Start,
+    /// `||`: start of the host or of one of its subdomains
+    Host,
+}
+
+#[derive(Debug)]
+struct UrlPattern {
+    tokens: Vec<Token>,
+    anchor: Anchor,
+    /// Trailing `|`: the pattern must reach the end of the URL
+    anchor_end: bool,
+}
+
+impl UrlPattern {
+    fn compile(rule: &str) -> Self {
+        let (anchor, rest) = if let Some(rest) = rule.strip_prefix("||") {
+            (Anchor::Host, rest)
+        } else if let Some(rest) = rule.strip_prefix('|') {
+            (Anchor::Start, rest)
+        } else {
+            (Anchor::None, rule)
+        };
+        let (rest, anchor_end) = match rest.strip_suffix('|') {
+            Some(rest) => (rest, true),
+            None => (rest, false),
+        };
+        
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        for c in rest.chars() {
+            if c == '*' || c == '^' {
+                if !literal.is_empty() {
+                    tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                }
+                tokens.push(if c == '*' { Token::Wildcard } else { Token::Separator });
+            } else {
+                literal.push(c);
+            }
+        }
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+        
+        Self { tokens, anchor, anchor_end }
+    }
+    
+    /// Match against a lowercased URL and its host
+    fn matches(&self, url: &str, host: &str) -> bool {
+        match self.anchor {
+            Anchor::Start => self.matches_at(url),
+            Anchor::Host => {
+                let Some(host_start) = url.find(host) else {
+                    return false;
+                };
+                let at_host = &url[host_start..];
+                std::iter::once(0)
+                    .chain(host.match_indices('.').map(|(i, _)| i + 1))
+                    .any(|offset| self.matches_at(&at_host[offset..]))
+            }
+            Anchor::None => {
+                // Only try positions where the first literal actually occurs
+                match self.tokens.first() {
+                    Some(Token::Literal(first)) => url.match_indices(first.as_str())
+                        .any(|(i, _)| self.matches_at(&url[i..])),
+                    _ => url.char_indices().any(|(i, _)| self.matches_at(&url[i..])),
+                }
+            }
+        }
+    }
+    
+    fn matches_at(&self, text: &str) -> bool {
+        match_tokens(&self.tokens, text, self.anchor_end)
+    }
+}
+
+fn match_tokens(tokens: &[Token], text: &str, anchor_end: bool) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return !anchor_end || text.is_empty();
+    };
+    
+    match token {
+        Token::Literal(literal) => {
+            text.starts_with(literal.as_str()) && match_tokens(rest, &text[literal.len()..], anchor_end)
+        }
+        Token::Separator => match text.chars().next() {
+            None => match_tokens(rest, text, anchor_end),
+            Some(c) if is_separator(c) => match_tokens(rest, &text[c.len_utf8()..], anchor_end),
+            Some(_) => false,
+        },
+        Token::Wildcard => text.char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .any(|i| match_tokens(rest, &text[i..], anchor_end)),
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '%'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const RULES: &str = "\
+[Adblock Plus 2.0]
+! Title: test list
+||doubleclick.net^
+||tracker.example.com^$third-party
+/ads/banner*.gif
+|https://analytics.
+||cdn.example.org/track^
+@@||ads.example.org^
+example.com##.ad-slot
+";
+
+    fn blocked(list: &BlockList, url: &str) -> bool {
+        list.should_block(&Url::parse(url).unwrap())
+    }
+    
+    #[test]
+    fn test_domain_rules() {
+        let list = BlockList::parse(RULES);
+        assert!(blocked(&list, "https://doubleclick.net/pixel"));
+        assert!(blocked(&list, "https://stats.g.doubleclick.net/collect"));
+        assert!(blocked(&list, "https://tracker.example.com/t.js"));
+        assert!(!blocked(&list, "https://notdoubleclick.net/"));
+        assert!(!blocked(&list, "https://example.com/"));
+    }
+    
+    #[test]
+    fn test_pattern_rules_and_exceptions() {
+        let list = BlockList::parse(RULES);
+        assert!(blocked(&list, "https://news.site/ads/banner-728.gif"));
+        assert!(!blocked(&list, "https://news.site/ads/banner-728.png"));
+        assert!(blocked(&list, "https://analytics.site/collect"));
+        assert!(!blocked(&list, "http://analytics.site/collect"));
+        assert!(blocked(&list, "https://cdn.example.org/track?id=1"));
+        assert!(blocked(&list, "https://cdn.example.org/track"));
+        assert!(!blocked(&list, "https://cdn.example.org/tracking.js"));
+        
+        // The exception wins over the pattern rule
+        assert!(!blocked(&list, "https://ads.example.org/ads/banner1.gif"));
+        assert_eq!(list.rule_count(), 6);
+    }
+}
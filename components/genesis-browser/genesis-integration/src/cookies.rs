@@ -0,0 +1,249 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+use url::Url;
+
+/// A cookie set by a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    /// Host the cookie belongs to, without a leading dot
+    pub domain: String,
+    /// Also sent to subdomains of `domain` (a `Domain` attribute was given)
+    pub include_subdomains: bool,
+    pub path: String,
+    /// Unix timestamp after which the cookie is dropped; `None` for session cookies
+    pub expires: Option<u64>,
+    pub secure: bool,
+    pub http_only: bool,
+}
+
+impl Cookie {
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires.map_or(false, |expires| expires <= now)
+    }
+    
+    fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        
+        let domain_matches = host == self.domain
+            || (self.include_subdomains && host.ends_with(&format!(".{}", self.domain)));
+        let path_matches = url.path() == self.path
+            || url.path().starts_with(&format!("{}/", self.path.trim_end_matches('/')));
+        
+        domain_matches && path_matches && (!self.secure || url.scheme() == "https")
+    }
+}
+
+/// Cookie storage shared by webviews.
+///
+/// Only `Max-Age` is honoured for expiry; cookies that only carry
+/// `Expires` are kept as session cookies.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CookieJar {
+    cookies: Vec<Cookie>,
+}
+
+impl CookieJar {
+    /// Create an empty jar
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Load a jar saved with `save`, starting empty if the file doesn't exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read cookies from {}", path.display()))?;
+        let mut jar: Self = serde_json::from_slice(&data)
+            .with_context(|| format!("Invalid cookie file {}", path.display()))?;
+        jar.remove_expired();
+        
+        info!("🍪 Loaded {} cookies", jar.cookies.len());
+        Ok(jar)
+    }
+    
+    /// Write the jar to disk
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+    
+    /// Store a cookie from a `Set-Cookie` header received for `url`
+    pub fn set_cookie(&mut self, url: &Url, header: &str) -> Result<()> {
+        let host = url.host_str().context("Cookie URL has no host")?;
+        let mut parts = header.split(';').map(str::trim);
+        
+        let (name, value) = parts.next()
+            .and_then(|pair| pair.split_once('='))
+            .context("Set-Cookie header has no name=value pair")?;
+        let mut cookie = Cookie {
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+            domain: host.to_string(),
+            include_subdomains: false,
+            path: default_path(url),
+            expires: None,
+            secure: false,
+            http_only: false,
+        };
+        
+        for attribute in parts {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" => {
+                    let domain = value.trim().trim_start_matches('.').to_ascii_lowercase();
+                    // A page can only set cookies for its own domain or a parent of it
+                    if host != domain && !host.ends_with(&format!(".{}", domain)) {
+                        anyhow::bail!("{} can't set cookies for {}", host, domain);
+                    }
+                    cookie.domain = domain;
+                    cookie.include_subdomains = true;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "max-age" => {
+                    let max_age: i64 = value.trim().parse().context("Invalid Max-Age")?;
+                    cookie.expires = Some(now().saturating_add_signed(max_age));
+                }
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                _ => {}
+            }
+        }
+        
+        debug!("🍪 Setting cookie {} for {}", cookie.name, cookie.domain);
+        self.cookies.retain(|c| !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path));
+        if !cookie.is_expired(now()) {
+            self.cookies.push(cookie);
+        }
+        Ok(())
+    }
+    
+    /// Cookies to send with a request to `url`, longest path first
+    pub fn get_cookies_for(&self, url: &Url) -> Vec<Cookie> {
+        let now = now();
+        let mut cookies: Vec<Cookie> = self.cookies.iter()
+            .filter(|cookie| !cookie.is_expired(now) && cookie.matches(url))
+            .cloned()
+            .collect();
+        cookies.sort_by(|a, b| b.path.len().cmp(&a.path.len()));
+        cookies
+    }
+    
+    /// `Cookie` header value for a request to `url`
+    pub fn cookie_header(&self, url: &Url) -> Option<String> {
+        let cookies = self.get_cookies_for(url);
+        if cookies.is_empty() {
+            return None;
+        }
+        Some(cookies.iter().map(|c| format!("{}={}", c.name, c.value)).collect::<Vec<_>>().join("; "))
+    }
+    
+    /// Remove every cookie
+    pub fn clear_cookies(&mut self) {
+        self.cookies.clear();
+        info!("🧹 Cleared cookies");
+    }
+    
+    /// Number of stored cookies
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+    
+    /// Whether the jar holds no cookies
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+    
+    fn remove_expired(&mut self) {
+        let now = now();
+        self.cookies.retain(|cookie| !cookie.is_expired(now));
+    }
+}
+
+/// Directory of the request path, used when a cookie has no `Path`
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => url.path()[..index].to_string(),
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+    
+    #[test]
+    fn test_cookie_matching() {
+        let mut jar = CookieJar::new();
+        jar.set_cookie(&url("https://shop.example.com/account/login"), "session=abc; HttpOnly").unwrap();
+        jar.set_cookie(&url("https://shop.example.com/"), "theme=dark; Domain=example.com; Path=/").unwrap();
+        jar.set_cookie(&url("https://shop.example.com/"), "token=xyz; Secure").unwrap();
+        
+        assert_eq!(
+            jar.cookie_header(&url("https://shop.example.com/account/orders")).as_deref(),
+            Some("session=abc; theme=dark; token=xyz")
+        );
+        assert_eq!(jar.cookie_header(&url("http://shop.example.com/")).as_deref(), Some("theme=dark"));
+        assert_eq!(jar.cookie_header(&url("https://blog.example.com/")).as_deref(), Some("theme=dark"));
+        assert_eq!(jar.cookie_header(&url("https://other.org/")), None);
+        
+        assert!(jar.set_cookie(&url("https://shop.example.com/"), "evil=1; Domain=other.org").is_err());
+    }
+    
+    #[test]
+    fn test_cookie_replacement_and_expiry() {
+        let mut jar = CookieJar::new();
+        let site = url("https://example.com/");
+        jar.set_cookie(&site, "id=1").unwrap();
+        jar.set_cookie(&site, "id=2").unwrap();
+        assert_eq!(jar.len(), 1);
+        assert_eq!(jar.get_cookies_for(&site)[0].value, "2");
+        
+        // Max-Age=0 deletes the cookie
+        jar.set_cookie(&site, "id=2; Max-Age=0").unwrap();
+        assert!(jar.is_empty());
+        
+        jar.set_cookie(&site, "id=3; Max-Age=3600").unwrap();
+        jar.clear_cookies();
+        assert!(jar.get_cookies_for(&site).is_empty());
+    }
+    
+    #[test]
+    fn test_cookie_persistence() {
+        let path = std::env::temp_dir().join(format!("genesis-cookies-{}.json", std::process::id()));
+        let site = url("https://example.com/");
+        
+        let mut jar = CookieJar::new();
+        jar.set_cookie(&site, "session=abc").unwrap();
+        jar.set_cookie(&site, "remember=1; Max-Age=86400").unwrap();
+        jar.save(&path).unwrap();
+        
+        let loaded = CookieJar::load(&path).unwrap();
+        assert_eq!(loaded.cookie_header(&site), jar.cookie_header(&site));
+        
+        std::fs::remove_file(&path).unwrap();
+        assert!(CookieJar::load(&path).unwrap().is_empty());
+    }
+}
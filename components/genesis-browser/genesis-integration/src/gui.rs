@@ -1,4 +1,5 @@
 use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::time::Instant;
@@ -8,7 +9,7 @@ use tracing::{info, warn, error};
 use winit::event_loop::{EventLoop, ActiveEventLoop};
 use winit::window::Window;
 use winit::event::{Event, WindowEvent, ElementState, MouseButton};
-use winit::dpi::PhysicalSize;
+use winit::dpi::{LogicalPosition, LogicalSize, PhysicalSize};
 
 use egui::{
     Button, CentralPanel, Frame, Key, Label, Modifiers, TopBottomPanel, Vec2, pos2,
@@ -30,6 +31,24 @@ use glow::HasContext;
 
 use crate::servo_integration::GenesisBrowserEngine;
 use genesis_dns::GenesisDnsResolver;
+use genesis_util::window_state::{Monitor, WindowState};
+
+/// The monitors' areas in logical pixels, the primary monitor first as
+/// `WindowState::placed_on` expects
+fn monitor_layout(event_loop: &ActiveEventLoop) -> Vec<Monitor> {
+    let primary = event_loop.primary_monitor();
+    let mut monitors: Vec<(bool, Monitor)> = event_loop.available_monitors()
+        .map(|monitor| {
+            let scale = monitor.scale_factor();
+            let position = monitor.position().to_logical::<f32>(scale);
+            let size = monitor.size().to_logical::<f32>(scale);
+            let is_primary = primary.as_ref() == Some(&monitor);
+            (is_primary, Monitor { position: [position.x, position.y], size: [size.width, size.height] })
+        })
+        .collect();
+    monitors.sort_by_key(|(is_primary, _)| !is_primary);
+    monitors.into_iter().map(|(_, monitor)| monitor).collect()
+}
 
 /// Genesis Browser GUI - Custom Servo-based browser window
 pub struct GenesisBrowserGUI {
@@ -61,6 +80,10 @@ pub struct GenesisBrowserGUI {
     genesis_connected: Cell<bool>,
     /// Last update time
     last_update: Instant,
+    /// Where the window geometry is kept between runs
+    window_state_path: Option<PathBuf>,
+    /// Geometry the window was restored with
+    restored_geometry: Option<WindowState>,
 }
 
 impl GenesisBrowserGUI {
@@ -83,9 +106,17 @@ impl GenesisBrowserGUI {
             is_loading: Cell::new(false),
             genesis_connected: Cell::new(false),
             last_update: Instant::now(),
+            window_state_path: None,
+            restored_geometry: None,
         }
     }
     
+    /// Restore the window's size and position from `path` and save them
+    /// there when the window closes
+    pub fn set_window_state_path(&mut self, path: Option<PathBuf>) {
+        self.window_state_path = path;
+    }
+    
     /// Initialize the GUI with window and browser engine
     pub async fn initialize(&mut self, browser_engine: GenesisBrowserEngine) -> Result<()> {
         info!("🚀 Initializing Genesis Browser GUI");
@@ -100,10 +131,30 @@ impl GenesisBrowserGUI {
     pub fn create_window(&mut self, event_loop: &ActiveEventLoop) -> Result<()> {
         info!("🪟 Creating Genesis Browser window");
         
-        let window_attributes = Window::default_attributes()
+        let mut window_attributes = Window::default_attributes()
             .with_title("Genesis Browser - Decentralized Web Freedom")
             .with_inner_size(PhysicalSize::new(1200, 800))
             .with_min_inner_size(PhysicalSize::new(800, 600));
+        
+        // Reopen where the window was left; the first run keeps the defaults
+        let saved = self.window_state_path.as_deref()
+            .filter(|path| path.exists())
+            .and_then(|path| WindowState::load(path)
+                .map_err(|e| warn!("⚠️ Failed to load window state from {}: {}", path.display(), e))
+                .ok());
+        if let Some(saved) = saved {
+            let geometry = saved.placed_on(&monitor_layout(event_loop));
+            if geometry != saved {
+                warn!("🪟 Saved window position is off-screen, moving it back");
+            }
+            window_attributes = window_attributes
+                .with_inner_size(LogicalSize::new(geometry.width, geometry.height))
+                .with_maximized(geometry.maximized);
+            if let Some([x, y]) = geometry.position {
+                window_attributes = window_attributes.with_position(LogicalPosition::new(x, y));
+            }
+            self.restored_geometry = Some(geometry);
+        }
             
         let window = event_loop.create_window(window_attributes)?;
         
@@ -201,8 +252,42 @@ impl GenesisBrowserGUI {
         Ok(())
     }
     
+    /// Write the window's geometry to the window state file, if there is one
+    pub fn save_window_state(&self) {
+        let (Some(path), Some(window)) = (&self.window_state_path, &self.window) else {
+            return;
+        };
+        let scale = window.scale_factor();
+        let maximized = window.is_maximized();
+        let size = window.inner_size().to_logical::<f32>(scale);
+        // A maximized window keeps the size it had before, when it's known
+        let (width, height, position) = match &self.restored_geometry {
+            Some(restored) if maximized => (restored.width, restored.height, restored.position),
+            _ => (
+                size.width,
+                size.height,
+                window.outer_position().ok().map(|position| {
+                    let position = position.to_logical::<f32>(scale);
+                    [position.x, position.y]
+                }),
+            ),
+        };
+        let monitor_size = window.current_monitor().map(|monitor| {
+            let size = monitor.size().to_logical::<f32>(monitor.scale_factor());
+            [size.width, size.height]
+        });
+        
+        let geometry = WindowState { width, height, position, maximized, monitor_size };
+        if let Err(e) = geometry.save(path) {
+            warn!("⚠️ Failed to save window state to {}: {}", path.display(), e);
+        }
+    }
+    
     /// Handle window events
     pub fn handle_event(&mut self, event: &WindowEvent) -> EventResponse {
+        if matches!(event, WindowEvent::CloseRequested) {
+            self.save_window_state();
+        }
         if let (Some(egui_winit), Some(window)) = (self.egui_winit.as_mut(), self.window.as_ref()) {
             egui_winit.on_window_event(window, event)
         } else {
@@ -380,8 +465,9 @@ impl GenesisBrowserGUI {
     
     /// Check if URL is a Genesis domain
     fn is_genesis_domain(&self, url: &str) -> bool {
-        let genesis_tlds = [".genesis", ".free", ".web", ".defi", ".dao"];
-        genesis_tlds.iter().any(|tld| url.contains(tld))
+        genesis_dns::normalize_url(url).ok()
+            .and_then(|url| url.host_str().map(|host| genesis_dns::has_genesis_tld(host, &genesis_dns::DEFAULT_GENESIS_TLDS)))
+            .unwrap_or(false)
     }
     
     /// Get window reference
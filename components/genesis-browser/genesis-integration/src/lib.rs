@@ -1,15 +1,26 @@
 // Genesis Integration - Servo engine and blockchain integration layer
 
+pub mod blocklist;
+pub mod cookies;
 pub mod servo_engine;
 pub mod servo_integration;
 pub mod webview;
 pub mod gui;
 
+#[cfg(feature = "remote-control")]
+pub mod remote_control;
+
 // Re-export main types
+pub use blocklist::BlockList;
+pub use cookies::{Cookie, CookieJar};
 pub use servo_engine::{ServoEngine, ServoConfig};
-pub use servo_integration::{GenesisBrowserEngine, BrowserConfig};
+pub use servo_integration::{GenesisBrowserEngine, BrowserConfig, BrowserStatus, TabInfo};
 pub use webview::{
     GenesisWebView, WebViewManager, WebViewConfig, WebViewEvent, 
-    SecurityState, ConsoleMessage, ConsoleLevel
+    WebViewEventSender, WebViewEventReceiver, StopHandle,
+    SecurityState, ConsoleMessage, ConsoleLevel, NetworkRequest
 };
-pub use gui::GenesisBrowserGUI;
\ No newline at end of file
+pub use gui::GenesisBrowserGUI;
+
+#[cfg(feature = "remote-control")]
+pub use remote_control::{ControlCommand, ControlRequest, ControlServer};
\ No newline at end of file
@@ -0,0 +1,280 @@
+// Remote control over a Unix socket, for driving the browser from scripts
+
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, info, warn};
+
+use crate::servo_integration::{GenesisBrowserEngine, RUN_LOOP_INTERVAL};
+
+/// Commands waiting for the engine; connections block once this many queue up
+const COMMAND_QUEUE_SIZE: usize = 32;
+
+/// One command per line, e.g. `{"cmd":"navigate","url":"x.genesis"}`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum ControlCommand {
+    Navigate { url: String },
+    NewTab,
+    /// `id` as returned by `new_tab` or `list_tabs`
+    CloseTab { id: String },
+    SwitchTab { id: String },
+    ListTabs,
+    GetStatus,
+    GoBack,
+    GoForward,
+    Reload,
+    /// Reload every tab; answers with each tab's outcome
+    ReloadAll,
+}
+
+/// A command from a client, answered through `reply`
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    reply: oneshot::Sender<Value>,
+}
+
+impl ControlRequest {
+    /// Send the response back to the client
+    pub fn respond(self, response: Value) {
+        // The client may have hung up in the meantime
+        let _ = self.reply.send(response);
+    }
+}
+
+/// Listening control socket. Connections are served on their own tasks and
+/// their commands queued for the engine; the socket file is removed on drop.
+pub struct ControlServer {
+    path: PathBuf,
+    requests: mpsc::Receiver<ControlRequest>,
+}
+
+impl ControlServer {
+    /// Listen on `path`, replacing a stale socket left by a crashed browser.
+    /// Must be called inside a Tokio runtime.
+    pub async fn bind(path: &Path) -> Result<Self> {
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                anyhow::bail!("{} exists and is not a socket", path.display());
+            }
+            if UnixStream::connect(path).await.is_ok() {
+                anyhow::bail!("{} is in use by another browser", path.display());
+            }
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+        }
+        
+        let listener = UnixListener::bind(path)
+            .with_context(|| format!("Failed to listen on {}", path.display()))?;
+        let (sender, requests) = mpsc::channel(COMMAND_QUEUE_SIZE);
+        tokio::spawn(accept_connections(listener, sender));
+        
+        info!("🎛️ Remote control listening on {}", path.display());
+        Ok(Self { path: path.to_path_buf(), requests })
+    }
+    
+    /// Path of the socket
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// Next command from any client
+    pub async fn next_request(&mut self) -> Option<ControlRequest> {
+        self.requests.recv().await
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+async fn accept_connections(listener: UnixListener, sender: mpsc::Sender<ControlRequest>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                debug!("🎛️ Remote control client connected");
+                tokio::spawn(serve_connection(stream, sender.clone()));
+            }
+            Err(e) => warn!("⚠️ Failed to accept remote control client: {}", e),
+        }
+        if sender.is_closed() {
+            return;
+        }
+    }
+}
+
+/// Answer each line a client sends with one line of JSON
+async fn serve_connection(stream: UnixStream, sender: mpsc::Sender<ControlRequest>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<ControlCommand>(&line) {
+            Ok(command) => {
+                let (reply, response) = oneshot::channel();
+                if sender.send(ControlRequest { command, reply }).await.is_err() {
+                    return;
+                }
+                response.await.unwrap_or_else(|_| error_response("browser is shutting down"))
+            }
+            Err(e) => error_response(&format!("invalid command: {}", e)),
+        };
+        
+        let mut line = response.to_string();
+        line.push('\n');
+        if writer.write_all(line.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+    debug!("🎛️ Remote control client disconnected");
+}
+
+fn error_response(message: &str) -> Value {
+    json!({ "ok": false, "error": message })
+}
+
+impl GenesisBrowserEngine {
+    /// Like `run_until_shutdown`, also executing commands from the control
+    /// socket. Commands run between engine iterations, one at a time.
+    pub async fn run_with_control(
+        &mut self,
+        mut shutdown: oneshot::Receiver<()>,
+        mut control: ControlServer,
+    ) -> Result<()> {
+        info!("🏃 Running with remote control on {}", control.path().display());
+        
+        let result = async {
+            let mut interval = tokio::time::interval(RUN_LOOP_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown => {
+                        info!("🛑 Shutdown requested");
+                        return Ok(());
+                    }
+                    Some(request) = control.next_request() => {
+                        let response = self.execute_command(request.command.clone()).await;
+                        request.respond(response);
+                    }
+                    _ = interval.tick() => self.tick()?,
+                }
+            }
+        }.await;
+        
+        let stopped = self.stop().await;
+        result.and(stopped)
+    }
+    
+    /// Execute a control command, returning `{"ok": true, ...}` or `{"ok": false, "error": ...}`
+    pub async fn execute_command(&mut self, command: ControlCommand) -> Value {
+        debug!("🎛️ Remote control command: {:?}", command);
+        
+        let result: Result<Value> = match command {
+            ControlCommand::Navigate { url } => self.navigate(&url).await.map(|()| json!({})),
+            ControlCommand::NewTab => self.new_tab().await.map(|id| json!({ "id": id.0.to_string() })),
+            ControlCommand::CloseTab { id } => match self.find_tab(&id).await {
+                Ok(id) => self.close_tab(id).await.map(|()| json!({})),
+                Err(e) => Err(e),
+            },
+            ControlCommand::SwitchTab { id } => match self.find_tab(&id).await {
+                Ok(id) => self.switch_tab(id).await.map(|()| json!({})),
+                Err(e) => Err(e),
+            },
+            ControlCommand::ListTabs => {
+                let tabs: Vec<Value> = self.list_tabs().await.into_iter()
+                    .map(|tab| json!({
+                        "id": tab.id.0.to_string(),
+                        "url": tab.current_url,
+                        "title": tab.title,
+                        "active": tab.is_active,
+                    }))
+                    .collect();
+                Ok(json!({ "tabs": tabs }))
+            }
+            ControlCommand::GetStatus => serde_json::to_value(self.get_status().await)
+                .map(|status| json!({ "status": status }))
+                .map_err(Into::into),
+            ControlCommand::GoBack => self.go_back().await.map(|()| json!({})),
+            ControlCommand::GoForward => self.go_forward().await.map(|()| json!({})),
+            ControlCommand::Reload => self.reload().await.map(|()| json!({})),
+            ControlCommand::ReloadAll => {
+                let tabs: Vec<Value> = self.reload_all_tabs().await.into_iter()
+                    .map(|(id, result)| match result {
+                        Ok(()) => json!({ "id": id.0.to_string(), "ok": true }),
+                        Err(e) => json!({ "id": id.0.to_string(), "ok": false, "error": e.to_string() }),
+                    })
+                    .collect();
+                Ok(json!({ "tabs": tabs }))
+            }
+        };
+        
+        match result {
+            Ok(mut response) => {
+                response["ok"] = json!(true);
+                response
+            }
+            Err(e) => error_response(&e.to_string()),
+        }
+    }
+    
+    /// Tab with the given ID string
+    async fn find_tab(&self, id: &str) -> Result<base::id::WebViewId> {
+        self.list_tabs().await.into_iter()
+            .map(|tab| tab.id)
+            .find(|tab_id| tab_id.0.to_string() == id)
+            .with_context(|| format!("No tab with ID {}", id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_commands() {
+        let parse = |line: &str| serde_json::from_str::<ControlCommand>(line);
+        assert_eq!(parse(r#"{"cmd":"navigate","url":"x.genesis"}"#).unwrap(), ControlCommand::Navigate { url: "x.genesis".to_string() });
+        assert_eq!(parse(r#"{"cmd":"new_tab"}"#).unwrap(), ControlCommand::NewTab);
+        assert_eq!(parse(r#"{"cmd":"reload_all"}"#).unwrap(), ControlCommand::ReloadAll);
+        assert_eq!(parse(r#"{"cmd":"close_tab","id":"(1,2)"}"#).unwrap(), ControlCommand::CloseTab { id: "(1,2)".to_string() });
+        assert!(parse(r#"{"cmd":"navigate"}"#).is_err());
+        assert!(parse(r#"{"cmd":"format_disk"}"#).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_socket_round_trip() {
+        let path = std::env::temp_dir().join(format!("genesis-control-{}.sock", std::process::id()));
+        let mut server = ControlServer::bind(&path).await.unwrap();
+        // A second browser can't take over a live socket
+        assert!(ControlServer::bind(&path).await.is_err());
+        
+        tokio::spawn(async move {
+            while let Some(request) = server.next_request().await {
+                let response = json!({ "ok": true, "echo": format!("{:?}", request.command) });
+                request.respond(response);
+            }
+        });
+        
+        let stream = UnixStream::connect(&path).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        
+        writer.write_all(b"{\"cmd\":\"list_tabs\"}\nnot json\n").await.unwrap();
+        let reply: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(reply, json!({ "ok": true, "echo": "ListTabs" }));
+        let reply: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(reply["ok"], json!(false));
+        assert!(reply["error"].as_str().unwrap().starts_with("invalid command"));
+        
+        let _ = std::fs::remove_file(&path);
+    }
+}
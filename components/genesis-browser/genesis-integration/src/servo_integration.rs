@@ -1,14 +1,22 @@
 use std::sync::{Arc, Mutex};
-use tokio::sync::RwLock;
-use tracing::{info, error, debug};
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::sync::{oneshot, RwLock};
+use tracing::{info, error, debug, trace, warn};
 use anyhow::Result;
+use serde::Serialize;
 use url::Url;
 
 use crate::servo_engine::{ServoEngine, ServoConfig};
-use crate::webview::{WebViewManager, WebViewConfig};
+use crate::webview::{PendingNavigation, WebViewManager, WebViewConfig};
 use base::id::WebViewId;
 use genesis_dns::GenesisDnsResolver;
 
+/// How often `run_until_shutdown` lets the Servo engine process its work
+pub(crate) const RUN_LOOP_INTERVAL: std::time::Duration = std::time::Duration::from_millis(16);
+
+/// Pause between tabs in `reload_all_tabs`, so they don't all hit the Genesis node at once
+const RELOAD_ALL_STAGGER: std::time::Duration = std::time::Duration::from_millis(250);
+
 /// Servo-based browser engine integration for Genesis Browser
 pub struct GenesisBrowserEngine {
     /// Servo engine instance
@@ -21,6 +29,9 @@ pub struct GenesisBrowserEngine {
     config: BrowserConfig,
     /// Active webview ID
     active_webview: Option<WebViewId>,
+    /// Mirrors of engine state, so `get_status` doesn't wait on the engine lock
+    is_running: bool,
+    window_size: (u32, u32),
 }
 
 /// Browser configuration
@@ -30,8 +41,35 @@ pub struct BrowserConfig {
     pub enable_traditional_fallback: bool,
     pub genesis_node_url: String,
     pub user_agent: String,
+    /// User-Agent for Genesis domains and Genesis node queries; `None` uses `user_agent`
+    pub genesis_user_agent: Option<String>,
+    /// User-Agent for traditional domains; `None` uses `user_agent`
+    pub traditional_user_agent: Option<String>,
     pub enable_javascript: bool,
+    pub enable_images: bool,
     pub enable_webgl: bool,
+    /// Run page content in sandboxed child processes
+    pub multiprocess: bool,
+    /// TLDs resolved through the Genesis node
+    pub genesis_tlds: Vec<String>,
+    /// Initial window size in pixels
+    pub window_size: (u32, u32),
+    /// `socks5://` or `http://` proxy for page loads and Genesis node queries
+    pub proxy_url: Option<String>,
+    /// How long a navigation may take before `navigate` gives up
+    pub navigation_timeout: std::time::Duration,
+    /// Navigations resolved at once across all tabs; further ones wait
+    pub max_concurrent_navigations: usize,
+    /// IPFS gateways for content-hash domains, tried in order.
+    /// `local` stands for the gateway of a local IPFS node.
+    pub ipfs_gateways: Vec<String>,
+    /// RPC API of an IPFS node content hashes are pinned on, used with the
+    /// `pinned-offline` feature
+    pub ipfs_api_url: Option<String>,
+    /// Pin every content hash a page resolves to, so it loads offline
+    pub auto_pin: bool,
+    /// Private window: cookies stay in memory and nothing is pinned
+    pub private_mode: bool,
 }
 
 impl Default for BrowserConfig {
@@ -41,8 +79,21 @@ impl Default for BrowserConfig {
             enable_traditional_fallback: true,
             genesis_node_url: "http://localhost:3000".to_string(),
             user_agent: "Genesis Browser/1.0 (Servo; Decentralized Web)".to_string(),
+            genesis_user_agent: None,
+            traditional_user_agent: None,
             enable_javascript: true,
+            enable_images: true,
             enable_webgl: true,
+            multiprocess: false,
+            genesis_tlds: genesis_dns::DEFAULT_GENESIS_TLDS.iter().map(|tld| tld.to_string()).collect(),
+            window_size: (1200, 800),
+            proxy_url: None,
+            navigation_timeout: crate::webview::DEFAULT_NAVIGATION_TIMEOUT,
+            max_concurrent_navigations: crate::webview::DEFAULT_MAX_CONCURRENT_NAVIGATIONS,
+            ipfs_gateways: genesis_dns::DEFAULT_IPFS_GATEWAYS.iter().map(|gateway| gateway.to_string()).collect(),
+            ipfs_api_url: None,
+            auto_pin: false,
+            private_mode: false,
         }
     }
 }
@@ -51,34 +102,62 @@ impl GenesisBrowserEngine {
     /// Create new Genesis Browser Engine with Servo
     pub async fn new(config: BrowserConfig) -> Result<Self> {
         info!("🚀 Initializing Genesis Browser Engine with Servo");
+        
+        // Reject a bad proxy up front instead of silently browsing without it
+        if let Some(proxy_url) = &config.proxy_url {
+            genesis_dns::parse_proxy(proxy_url)
+                .map_err(|e| anyhow::anyhow!("Invalid proxy_url in browser config: {}", e))?;
+        }
 
         // Create Servo configuration
+        let window_size = config.window_size;
         let servo_config = ServoConfig {
-            window_width: 1200,
-            window_height: 800,
+            window_width: window_size.0,
+            window_height: window_size.1,
             enable_webgl: config.enable_webgl,
             enable_webrender: true,
             enable_javascript: config.enable_javascript,
             user_agent: config.user_agent.clone(),
+            genesis_user_agent: config.genesis_user_agent.clone(),
             genesis_node_url: config.genesis_node_url.clone(),
+            genesis_tlds: config.genesis_tlds.clone(),
+            enable_traditional_fallback: config.enable_traditional_fallback,
             enable_dev_tools: false,
-            multiprocess: false,
+            multiprocess: config.multiprocess,
             ..Default::default()
         };
 
         // Initialize Servo engine
         let servo_engine = Arc::new(Mutex::new(
-            ServoEngine::new(servo_config.title.clone())?
+            ServoEngine::new(servo_config)?
         ));
+        if let Some(proxy_url) = &config.proxy_url {
+            servo_engine.lock().unwrap().set_proxy(proxy_url);
+        }
 
         // Initialize WebView manager
         let webview_config = WebViewConfig {
             javascript_enabled: config.enable_javascript,
-            images_enabled: true,
+            images_enabled: config.enable_images,
             webgl_enabled: config.enable_webgl,
             user_agent: config.user_agent.clone(),
+            genesis_user_agent: config.genesis_user_agent.clone(),
+            traditional_user_agent: config.traditional_user_agent.clone(),
             developer_tools: false,
-            private_mode: false,
+            private_mode: config.private_mode,
+            cookie_store: None,
+            blocklist: None,
+            genesis_node_url: config.genesis_node_url.clone(),
+            genesis_tlds: config.genesis_tlds.clone(),
+            enable_traditional_fallback: config.enable_traditional_fallback,
+            proxy_url: config.proxy_url.clone(),
+            custom_headers: Default::default(),
+            node_headers: Default::default(),
+            navigation_timeout: config.navigation_timeout,
+            max_concurrent_navigations: config.max_concurrent_navigations,
+            ipfs_gateways: config.ipfs_gateways.clone(),
+            ipfs_api_url: config.ipfs_api_url.clone(),
+            auto_pin: config.auto_pin,
         };
         
         let webview_manager = Arc::new(Mutex::new(
@@ -86,12 +165,19 @@ impl GenesisBrowserEngine {
         ));
 
         // Initialize DNS resolver
-        let dns_resolver = Arc::new(RwLock::new(
-            GenesisDnsResolver::new(
-                config.genesis_node_url.clone(),
-                config.enable_traditional_fallback,
-            )
-        ));
+        let mut resolver = GenesisDnsResolver::new(
+            config.genesis_node_url.clone(),
+            config.enable_traditional_fallback,
+        );
+        resolver.set_genesis_tlds(config.genesis_tlds.clone());
+        resolver.set_user_agent(config.genesis_user_agent.as_deref().unwrap_or(&config.user_agent))?;
+        resolver.set_ipfs_gateways(config.ipfs_gateways.clone());
+        #[cfg(feature = "pinned-offline")]
+        resolver.set_ipfs_api(config.ipfs_api_url.clone(), config.auto_pin);
+        if let Some(proxy_url) = &config.proxy_url {
+            resolver.set_proxy(proxy_url)?;
+        }
+        let dns_resolver = Arc::new(RwLock::new(resolver));
 
         let engine = Self {
             servo_engine,
@@ -99,6 +185,8 @@ impl GenesisBrowserEngine {
             dns_resolver,
             config,
             active_webview: None,
+            is_running: false,
+            window_size,
         };
 
         info!("✅ Genesis Browser Engine with Servo initialized successfully");
@@ -116,10 +204,11 @@ impl GenesisBrowserEngine {
         info!("🔧 Servo namespace installed for browser engine");
 
         // Start Servo engine
-        {
+        self.is_running = {
             let mut engine = self.servo_engine.lock().unwrap();
-            engine.start().await?;
-        }
+            engine.start()?;
+            engine.is_running()
+        };
 
         // Create initial webview
         let webview_id = {
@@ -137,14 +226,43 @@ impl GenesisBrowserEngine {
     pub async fn navigate(&mut self, url: &str) -> Result<()> {
         info!("🔍 Navigating to: {}", url);
 
-        // Navigate in active webview
-        let mut manager = self.webview_manager.lock().unwrap();
-        manager.navigate(url).await?;
+        // Resolve without holding the manager, so other tabs and
+        // `get_status` aren't held up by a slow Genesis node
+        let pending = self.webview_manager.lock().unwrap().begin_active_navigation(url)?;
+        let resolved = pending.resolve().await;
+        self.webview_manager.lock().unwrap().finish_navigation(resolved)?;
 
         info!("✅ Navigation initiated: {}", url);
         Ok(())
     }
 
+    /// Navigate several tabs at once. Hosts resolve concurrently and each tab
+    /// is handed to Servo as soon as its host is resolved; the manager is
+    /// only locked to start and finish each navigation.
+    pub async fn navigate_all(&self, urls: Vec<(WebViewId, String)>) -> Vec<(WebViewId, Result<()>)> {
+        let mut results = Vec::with_capacity(urls.len());
+        let mut resolutions = FuturesUnordered::new();
+        {
+            let mut manager = self.webview_manager.lock().unwrap();
+            for (id, url) in urls {
+                match manager.begin_navigation(id, &url) {
+                    Ok(pending) => resolutions.push(pending.resolve()),
+                    Err(e) => results.push((id, Err(e))),
+                }
+            }
+        }
+        
+        while let Some(resolved) = resolutions.next().await {
+            let id = resolved.webview_id();
+            let result = self.webview_manager.lock().unwrap().finish_navigation(resolved);
+            if let Err(e) = &result {
+                warn!("⚠️ Failed to navigate tab {}: {}", id.0, e);
+            }
+            results.push((id, result));
+        }
+        results
+    }
+
     /// Create new tab
     pub async fn new_tab(&mut self) -> Result<WebViewId> {
         let mut manager = self.webview_manager.lock().unwrap();
@@ -178,60 +296,137 @@ impl GenesisBrowserEngine {
 
     /// Go back in history
     pub async fn go_back(&mut self) -> Result<()> {
-        let mut manager = self.webview_manager.lock().unwrap();
-        manager.go_back().await?;
-        Ok(())
+        let pending = self.webview_manager.lock().unwrap().begin_go_back()?;
+        self.finish_pending(pending).await
     }
 
     /// Go forward in history
     pub async fn go_forward(&mut self) -> Result<()> {
-        let mut manager = self.webview_manager.lock().unwrap();
-        manager.go_forward().await?;
-        Ok(())
+        let pending = self.webview_manager.lock().unwrap().begin_go_forward()?;
+        self.finish_pending(pending).await
     }
 
     /// Reload current page
     pub async fn reload(&mut self) -> Result<()> {
-        let mut manager = self.webview_manager.lock().unwrap();
-        manager.reload().await?;
+        let pending = self.webview_manager.lock().unwrap().active_webview()
+            .ok_or_else(|| anyhow::anyhow!("No active webview"))?
+            .begin_reload();
+        self.finish_pending(pending).await
+    }
+
+    /// Resolve a navigation started with the manager locked, without holding
+    /// it, then apply it. `None` means there was nothing to navigate to.
+    async fn finish_pending(&self, pending: Option<PendingNavigation>) -> Result<()> {
+        if let Some(pending) = pending {
+            let resolved = pending.resolve().await;
+            self.webview_manager.lock().unwrap().finish_navigation(resolved)?;
+        }
         Ok(())
     }
 
-    /// Run the browser event loop
-    pub async fn run(&mut self) -> Result<()> {
-        info!("🏃 Starting browser event loop");
+    /// Reload every tab, one at a time and `RELOAD_ALL_STAGGER` apart.
+    /// A failing tab doesn't stop the others; each tab's outcome is returned.
+    pub async fn reload_all_tabs(&mut self) -> Vec<(WebViewId, Result<()>)> {
+        let ids = self.webview_manager.lock().unwrap().webview_ids();
+        info!("🔄 Reloading {} tabs", ids.len());
         
-        let mut engine = self.servo_engine.lock().unwrap();
-        engine.run().await?;
+        let mut results = Vec::with_capacity(ids.len());
+        for (position, id) in ids.into_iter().enumerate() {
+            if position > 0 {
+                tokio::time::sleep(RELOAD_ALL_STAGGER).await;
+            }
+            let pending = self.webview_manager.lock().unwrap().begin_reload(id);
+            let result = match pending {
+                Ok(pending) => self.finish_pending(pending).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = &result {
+                warn!("⚠️ Failed to reload tab {}: {}", id.0, e);
+            }
+            results.push((id, result));
+        }
+        results
+    }
+
+    /// The window was resized
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.servo_engine.lock().unwrap().set_window_size(width, height);
+        self.window_size = (width, height);
+    }
+
+    /// One iteration of the browser event loop: let the Servo engine process
+    /// its work, then drain the webview events nobody subscribed to, so the
+    /// channel never fills up and starts dropping them
+    pub fn tick(&mut self) -> Result<()> {
+        self.servo_engine.lock().unwrap().tick()?;
         
+        for (id, event) in self.webview_manager.lock().unwrap().drain_events() {
+            trace!("WebView {} event: {:?}", id.0, event);
+        }
         Ok(())
     }
 
+    /// Keep processing the Servo engine until `shutdown` fires (or its
+    /// sender is dropped), then stop the engine. The engine is stopped on
+    /// error paths too, so Servo resources are always released.
+    pub async fn run_until_shutdown(&mut self, mut shutdown: oneshot::Receiver<()>) -> Result<()> {
+        info!("🏃 Running until shutdown is requested");
+        
+        let result = async {
+            let mut interval = tokio::time::interval(RUN_LOOP_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown => {
+                        info!("🛑 Shutdown requested");
+                        return Ok(());
+                    }
+                    _ = interval.tick() => self.tick()?,
+                }
+            }
+        }.await;
+        
+        let stopped = self.stop().await;
+        result.and(stopped)
+    }
+
     /// Get current browser status
     pub async fn get_status(&self) -> BrowserStatus {
-        let engine = self.servo_engine.lock().unwrap();
         let mut manager = self.webview_manager.lock().unwrap();
-        
-        let current_url = if let Some(webview) = manager.active_webview() {
-            webview.current_url().map(|u| u.to_string())
-        } else {
-            None
-        };
+        let active = manager.active_webview();
 
         BrowserStatus {
-            is_running: engine.is_running(),
-            current_url,
-            is_loading: false,
-            window_size: (1200, 800),
+            is_running: self.is_running,
+            current_url: active.as_ref().and_then(|webview| webview.current_url()).map(|u| u.to_string()),
+            title: active.as_ref().map(|webview| webview.title().to_string()),
+            is_loading: active.as_ref().map_or(false, |webview| webview.is_loading()),
+            window_size: self.window_size,
+            resident_memory_bytes: genesis_util::memory::resident_memory_bytes(),
+            webview_count: manager.webview_count(),
         }
     }
 
+    /// Every open tab, in no particular order
+    pub async fn list_tabs(&self) -> Vec<TabInfo> {
+        let manager = self.webview_manager.lock().unwrap();
+        manager.webview_ids().into_iter()
+            .filter_map(|id| manager.get_webview(id).map(|webview| TabInfo {
+                id,
+                current_url: webview.current_url().map(|u| u.to_string()),
+                title: webview.title().to_string(),
+                is_active: self.active_webview == Some(id),
+            }))
+            .collect()
+    }
+
     /// Stop the browser engine
     pub async fn stop(&mut self) -> Result<()> {
         info!("🛑 Stopping Genesis Browser Engine");
 
-        let mut engine = self.servo_engine.lock().unwrap();
-        engine.stop().await?;
+        self.is_running = {
+            let mut engine = self.servo_engine.lock().unwrap();
+            engine.stop()?;
+            engine.is_running()
+        };
 
         info!("✅ Genesis Browser Engine stopped");
         Ok(())
@@ -239,12 +434,26 @@ impl GenesisBrowserEngine {
 }
 
 /// Browser status information
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct BrowserStatus {
     pub is_running: bool,
     pub current_url: Option<String>,
+    /// Title of the active webview
+    pub title: Option<String>,
     pub is_loading: bool,
     pub window_size: (u32, u32),
+    /// Resident memory of the browser process, 0 if the platform can't report it
+    pub resident_memory_bytes: u64,
+    pub webview_count: usize,
+}
+
+/// An open tab, as listed by `list_tabs`
+#[derive(Debug, Clone)]
+pub struct TabInfo {
+    pub id: WebViewId,
+    pub current_url: Option<String>,
+    pub title: String,
+    pub is_active: bool,
 }
 
 #[cfg(test)]
@@ -253,8 +462,161 @@ mod tests {
 
     #[tokio::test]
     async fn test_browser_engine_creation() {
-        let config = BrowserConfig::default();
-        let engine = GenesisBrowserEngine::new(config).await;
-        assert!(engine.is_ok());
+        let config = BrowserConfig {
+            genesis_node_url: "http://node.example:3000".to_string(),
+            user_agent: "Genesis Test".to_string(),
+            genesis_user_agent: Some("Genesis Node Test".to_string()),
+            genesis_tlds: vec!["lab".to_string()],
+            window_size: (1024, 768),
+            ..BrowserConfig::default()
+        };
+        let engine = GenesisBrowserEngine::new(config).await.unwrap();
+        
+        // The Servo engine keeps the settings instead of falling back to defaults
+        let servo_engine = engine.servo_engine.lock().unwrap();
+        assert_eq!(servo_engine.user_agent(), "Genesis Test");
+        assert_eq!(servo_engine.window_size(), (1024, 768));
+        assert!(!servo_engine.is_running());
+        
+        let dns_resolver = servo_engine.dns_resolver();
+        let resolver = dns_resolver.read().await;
+        assert_eq!(resolver.genesis_node_urls(), ["http://node.example:3000".to_string()]);
+        assert_eq!(resolver.user_agent(), Some("Genesis Node Test"));
+        assert_eq!(engine.dns_resolver.read().await.genesis_tlds(), [".lab".to_string()]);
+        assert_eq!(engine.dns_resolver.read().await.user_agent(), Some("Genesis Node Test"));
+    }
+
+    #[tokio::test]
+    async fn test_status_reflects_engine_state() {
+        let mut engine = GenesisBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let status = engine.get_status().await;
+        assert!(!status.is_running);
+        assert_eq!(status.title, None);
+        
+        engine.start().await.unwrap();
+        engine.resize(800, 600);
+        let status = engine.get_status().await;
+        assert!(status.is_running);
+        assert!(!status.is_loading);
+        assert_eq!(status.title.as_deref(), Some("New Tab"));
+        assert_eq!(status.window_size, (800, 600));
+        assert_eq!(status.webview_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_until_shutdown_stops_engine() {
+        let mut engine = GenesisBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        engine.start().await.unwrap();
+        engine.navigate("example.com").await.unwrap();
+        
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            let _ = shutdown_sender.send(());
+        });
+        
+        tokio::time::timeout(std::time::Duration::from_secs(5), engine.run_until_shutdown(shutdown_receiver))
+            .await
+            .expect("shutdown signal was ignored")
+            .unwrap();
+        assert!(!engine.get_status().await.is_running);
+        // The loop consumed the navigation's events
+        assert!(engine.webview_manager.lock().unwrap().drain_events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hung_resolution_does_not_block_other_tabs() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        
+        // A Genesis node that accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let slow_node = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+        
+        // And one that answers straight away
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let fast_node = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0u8; 4096];
+                let _ = socket.read(&mut buffer).await;
+                let body = r#"{"name": "fast.genesis", "owner": "o", "resolver": "genesis", "ip_address": "10.0.0.7"}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        
+        let config = BrowserConfig {
+            genesis_node_url: slow_node,
+            enable_traditional_fallback: false,
+            navigation_timeout: std::time::Duration::from_secs(2),
+            ..BrowserConfig::default()
+        };
+        let mut engine = GenesisBrowserEngine::new(config).await.unwrap();
+        engine.start().await.unwrap();
+        let slow_tab = engine.active_webview.unwrap();
+        let fast_tab = engine.new_tab().await.unwrap();
+        
+        let fast_resolver = engine.webview_manager.lock().unwrap().get_webview(fast_tab).unwrap().dns_resolver.clone();
+        let mut resolver = GenesisDnsResolver::new(fast_node, false);
+        resolver.set_retry_policy(0, 0);
+        *fast_resolver.write().await = resolver;
+        
+        let slow = engine.navigate_all(vec![(slow_tab, "slow.genesis".to_string())]);
+        let others = tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            // Let the slow navigation reach the node first
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            let status = engine.get_status().await;
+            assert_eq!(status.webview_count, 2);
+            
+            let results = engine.navigate_all(vec![(fast_tab, "fast.genesis".to_string())]).await;
+            assert!(results[0].1.is_ok());
+            
+            let manager = engine.webview_manager.lock().unwrap();
+            assert_eq!(manager.get_webview(fast_tab).unwrap().current_url().unwrap().as_str(), "http://10.0.0.7/");
+            assert!(manager.get_webview(slow_tab).unwrap().is_loading());
+        });
+        
+        let (slow_results, others) = tokio::join!(slow, others);
+        others.expect("the other tab's Genesis lookup waited on the hung resolution");
+        let error = slow_results[0].1.as_ref().unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_history_navigation_through_engine() {
+        let mut engine = GenesisBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        engine.start().await.unwrap();
+        engine.navigate("example.com/a").await.unwrap();
+        engine.navigate("example.com/b").await.unwrap();
+        let current_path = |engine: &GenesisBrowserEngine| {
+            let mut manager = engine.webview_manager.lock().unwrap();
+            manager.active_webview().unwrap().current_url().unwrap().path().to_string()
+        };
+        
+        engine.go_back().await.unwrap();
+        assert_eq!(current_path(&engine), "/a");
+        engine.reload().await.unwrap();
+        engine.go_forward().await.unwrap();
+        assert_eq!(current_path(&engine), "/b");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_proxy_is_rejected() {
+        let config = BrowserConfig {
+            proxy_url: Some("socks5//127.0.0.1:9050".to_string()),
+            ..BrowserConfig::default()
+        };
+        let error = GenesisBrowserEngine::new(config).await.err().expect("proxy should be rejected");
+        assert!(error.to_string().contains("proxy"));
     }
 }
\ No newline at end of file
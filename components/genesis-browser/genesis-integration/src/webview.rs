@@ -1,9 +1,11 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 use anyhow::{Result, Context};
 use tracing::{info, error, debug, warn};
 use url::Url;
-use tokio::sync::{mpsc, oneshot};
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::sync::{mpsc, oneshot, watch, Semaphore};
 
 #[cfg(feature = "servo-integration")]
 use {
@@ -15,10 +17,48 @@ use {
     servo_url::ServoUrl,
 };
 
+use crate::blocklist::BlockList;
+use crate::cookies::CookieJar;
 use crate::servo_engine::{ServoEngine, ServoConfig};
 use genesis_dns::GenesisDnsResolver;
 
-/// Genesis Browser WebView implementation
+/// How long `execute_script` waits for the script thread by default
+pub const DEFAULT_SCRIPT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long a navigation may take, resolution included, by default
+pub const DEFAULT_NAVIGATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Navigations a manager resolves at once by default; further ones wait for a slot
+pub const DEFAULT_MAX_CONCURRENT_NAVIGATIONS: usize = 8;
+
+/// Smallest and largest page zoom a webview accepts
+pub const MIN_ZOOM: f32 = 0.25;
+pub const MAX_ZOOM: f32 = 5.0;
+
+/// Load progress reported when a navigation starts, once its host is
+/// resolved and once Servo has the request; 1.0 comes with `LoadFinished`
+const PROGRESS_STARTED: f32 = 0.1;
+const PROGRESS_RESOLVED: f32 = 0.4;
+const PROGRESS_REQUESTED: f32 = 0.7;
+
+/// Headers whose values never appear in logs
+const SENSITIVE_HEADERS: [&str; 4] = ["authorization", "proxy-authorization", "cookie", "x-api-key"];
+
+/// Events buffered until someone drains them; further events are dropped
+pub const EVENT_CHANNEL_CAPACITY: usize = 100;
+
+/// Sending half of the event channel, shared by every webview of a manager
+pub type WebViewEventSender = mpsc::Sender<(WebViewId, WebViewEvent)>;
+
+/// Receiving half of the event channel
+pub type WebViewEventReceiver = mpsc::Receiver<(WebViewId, WebViewEvent)>;
+
+/// Genesis Browser WebView implementation.
+///
+/// A webview only holds the sending half of the event channel. The receiver
+/// belongs to the `WebViewManager`, which drains it or hands it to the UI,
+/// so events from all tabs arrive in one place. Sending never blocks:
+/// if nobody drains the channel, new events are dropped.
 pub struct GenesisWebView {
     /// Unique ID for this webview
     id: WebViewId,
@@ -42,15 +82,107 @@ pub struct GenesisWebView {
     /// JavaScript enabled
     javascript_enabled: bool,
     
+    /// Page zoom, 1.0 is 100%
+    zoom_level: f32,
+    
+    /// Whether the page's audio is silenced
+    muted: bool,
+    
+    /// How long to wait for a script result
+    script_timeout: std::time::Duration,
+    
+    /// How long a navigation may take before it's abandoned
+    navigation_timeout: std::time::Duration,
+    
     /// User agent
     user_agent: String,
     
+    /// User agents for Genesis and traditional domains, overriding `user_agent`
+    genesis_user_agent: Option<String>,
+    traditional_user_agent: Option<String>,
+    
     /// Genesis DNS resolver
-    dns_resolver: Arc<tokio::sync::RwLock<GenesisDnsResolver>>,
+    pub(crate) dns_resolver: Arc<tokio::sync::RwLock<GenesisDnsResolver>>,
     
-    /// Event channel
-    event_sender: mpsc::Sender<WebViewEvent>,
-    event_receiver: mpsc::Receiver<WebViewEvent>,
+    /// Genesis TLDs, taken from the resolver
+    genesis_tlds: Vec<String>,
+    
+    /// Event channel, drained by the manager
+    event_sender: WebViewEventSender,
+    
+    /// Stop flag of the latest navigation, replaced by every new one
+    stop_sender: Arc<Mutex<watch::Sender<bool>>>,
+    
+    /// Limits concurrent resolutions, shared by the webviews of a manager
+    navigation_slots: Arc<Semaphore>,
+    
+    /// Bumped by every navigation and by `stop`, so a resolution that
+    /// finishes after it was superseded is dropped
+    navigation_seq: u64,
+    
+    /// Cookies, shared with other webviews unless in private mode
+    cookie_jar: Arc<Mutex<CookieJar>>,
+    
+    /// Headers sent with every navigation
+    custom_headers: HashMap<String, String>,
+}
+
+/// Stops a webview's in-flight navigation from another task.
+/// Navigation holds `&mut GenesisWebView`, so `stop` can't be called meanwhile.
+#[derive(Clone)]
+pub struct StopHandle {
+    sender: Arc<Mutex<watch::Sender<bool>>>,
+}
+
+impl StopHandle {
+    /// Cancel the navigation in progress; does nothing if the webview is idle.
+    /// The flag stays set, so a navigation that hasn't started waiting for
+    /// it yet still sees the stop.
+    pub fn stop(&self) {
+        self.sender.lock().unwrap().send_replace(true);
+    }
+}
+
+/// A navigation whose host is still to be resolved. It owns everything
+/// resolution needs, so `resolve` runs without borrowing the webview, its
+/// manager or the engine, and tabs don't wait on each other's lookups.
+pub struct PendingNavigation {
+    id: WebViewId,
+    seq: u64,
+    url: String,
+    history_index: Option<usize>,
+    headers: HashMap<String, String>,
+    dns_resolver: Arc<tokio::sync::RwLock<GenesisDnsResolver>>,
+    genesis_tlds: Vec<String>,
+    event_sender: WebViewEventSender,
+    stop_receiver: watch::Receiver<bool>,
+    navigation_timeout: std::time::Duration,
+    navigation_slots: Arc<Semaphore>,
+}
+
+/// A navigation after resolution, for `GenesisWebView::finish_navigation`
+pub struct ResolvedNavigation {
+    id: WebViewId,
+    seq: u64,
+    url: String,
+    history_index: Option<usize>,
+    headers: HashMap<String, String>,
+    outcome: ResolutionOutcome,
+    stop_receiver: watch::Receiver<bool>,
+}
+
+enum ResolutionOutcome {
+    Resolved(Url),
+    Failed(anyhow::Error),
+    TimedOut(std::time::Duration),
+    Stopped,
+}
+
+impl ResolvedNavigation {
+    /// Webview the navigation belongs to
+    pub fn webview_id(&self) -> WebViewId {
+        self.id
+    }
 }
 
 /// WebView events
@@ -66,6 +198,9 @@ pub enum WebViewEvent {
     ProgressUpdate(f32),
     SecurityStateChanged(SecurityState),
     ConsoleMessage(ConsoleMessage),
+    NetworkRequest(NetworkRequest),
+    /// The page started or stopped playing sound
+    AudibleChanged(bool),
 }
 
 /// Security state of the page
@@ -94,6 +229,23 @@ pub enum ConsoleLevel {
     Error,
 }
 
+/// A finished request, for the network log in developer tools
+#[derive(Debug, Clone)]
+pub struct NetworkRequest {
+    /// HTTP method, or "DNS" for a Genesis domain resolution
+    pub method: String,
+    pub url: Url,
+    /// `None` for DNS lookups and requests that got no response
+    pub status: Option<u16>,
+    /// Response body size in bytes
+    pub size: Option<u64>,
+    pub duration: std::time::Duration,
+    pub request_headers: Vec<(String, String)>,
+    pub response_headers: Vec<(String, String)>,
+    /// Why the request failed, if it did
+    pub error: Option<String>,
+}
+
 /// WebView manager for multiple tabs
 pub struct WebViewManager {
     /// All webviews
@@ -110,6 +262,23 @@ pub struct WebViewManager {
     
     /// Configuration
     config: WebViewConfig,
+    
+    /// Cookies shared by all non-private webviews
+    cookie_jar: Arc<Mutex<CookieJar>>,
+    
+    /// Ad and tracker rules, `None` when blocking is off
+    blocklist: Option<Arc<BlockList>>,
+    
+    /// Requests blocked since the counter was last reset
+    blocked_requests: Arc<AtomicUsize>,
+    
+    /// Shared by all webviews, so at most `max_concurrent_navigations` resolve at once
+    navigation_slots: Arc<Semaphore>,
+    
+    /// Events from all webviews
+    event_sender: WebViewEventSender,
+    /// `None` once the UI has taken it with `take_event_receiver`
+    event_receiver: Option<WebViewEventReceiver>,
 }
 
 /// WebView configuration
@@ -118,9 +287,43 @@ pub struct WebViewConfig {
     pub javascript_enabled: bool,
     pub images_enabled: bool,
     pub webgl_enabled: bool,
+    /// User-Agent sent to sites without one of their own below
     pub user_agent: String,
+    /// User-Agent for Genesis domains and Genesis node queries
+    pub genesis_user_agent: Option<String>,
+    /// User-Agent for traditional domains, e.g. a Chrome-compatible one for
+    /// sites that block unknown browsers
+    pub traditional_user_agent: Option<String>,
     pub developer_tools: bool,
+    /// Private webviews get their own cookie jar, which is never saved
     pub private_mode: bool,
+    /// Where the shared cookie jar is persisted, if anywhere
+    pub cookie_store: Option<std::path::PathBuf>,
+    /// EasyList-style block list to load, if ad blocking is on
+    pub blocklist: Option<std::path::PathBuf>,
+    /// Genesis node webviews resolve domains through
+    pub genesis_node_url: String,
+    /// TLDs webviews resolve through the Genesis node
+    pub genesis_tlds: Vec<String>,
+    /// Resolve other domains through traditional DNS
+    pub enable_traditional_fallback: bool,
+    /// Proxy for Genesis DNS lookups made by webviews
+    pub proxy_url: Option<String>,
+    /// Headers sent with every page navigation
+    pub custom_headers: HashMap<String, String>,
+    /// Headers sent to the Genesis node, kept apart so page credentials
+    /// never reach the node and vice versa
+    pub node_headers: HashMap<String, String>,
+    /// How long a navigation may take, resolution included
+    pub navigation_timeout: std::time::Duration,
+    /// Navigations resolved at once across all webviews
+    pub max_concurrent_navigations: usize,
+    /// IPFS gateways for content-hash domains, tried in order
+    pub ipfs_gateways: Vec<String>,
+    /// RPC API of an IPFS node content hashes are pinned on
+    pub ipfs_api_url: Option<String>,
+    /// Pin the content hash of every IPFS page loaded, except in private mode
+    pub auto_pin: bool,
 }
 
 impl Default for WebViewConfig {
@@ -130,9 +333,125 @@ impl Default for WebViewConfig {
             images_enabled: true,
             webgl_enabled: true,
             user_agent: "Genesis Browser/1.0 (Servo; Decentralized Web)".to_string(),
+            genesis_user_agent: None,
+            traditional_user_agent: None,
             developer_tools: false,
             private_mode: false,
+            cookie_store: None,
+            blocklist: None,
+            genesis_node_url: "http://localhost:3000".to_string(),
+            genesis_tlds: genesis_dns::DEFAULT_GENESIS_TLDS.iter().map(|tld| tld.to_string()).collect(),
+            enable_traditional_fallback: true,
+            proxy_url: None,
+            custom_headers: HashMap::new(),
+            node_headers: HashMap::new(),
+            navigation_timeout: DEFAULT_NAVIGATION_TIMEOUT,
+            max_concurrent_navigations: DEFAULT_MAX_CONCURRENT_NAVIGATIONS,
+            ipfs_gateways: genesis_dns::DEFAULT_IPFS_GATEWAYS.iter().map(|gateway| gateway.to_string()).collect(),
+            ipfs_api_url: None,
+            auto_pin: false,
+        }
+    }
+}
+
+impl PendingNavigation {
+    /// Webview the navigation is for
+    pub fn webview_id(&self) -> WebViewId {
+        self.id
+    }
+    
+    /// Resolve the URL's host once a navigation slot is free. Stopping the
+    /// webview or running out of time ends the wait for a slot as well.
+    pub async fn resolve(self) -> ResolvedNavigation {
+        let outcome = tokio::select! {
+            result = tokio::time::timeout(self.navigation_timeout, async {
+                let _slot = self.navigation_slots.acquire().await
+                    .context("Navigation slots were closed")?;
+                let parsed_url = self.parse_and_resolve_url().await?;
+                self.emit(WebViewEvent::ProgressUpdate(PROGRESS_RESOLVED));
+                Ok::<Url, anyhow::Error>(parsed_url)
+            }) => match result {
+                Ok(Ok(parsed_url)) => ResolutionOutcome::Resolved(parsed_url),
+                Ok(Err(e)) => ResolutionOutcome::Failed(e),
+                Err(_) => ResolutionOutcome::TimedOut(self.navigation_timeout),
+            },
+            _ = stopped(self.stop_receiver.clone()) => ResolutionOutcome::Stopped,
+        };
+        
+        ResolvedNavigation {
+            id: self.id,
+            seq: self.seq,
+            url: self.url,
+            history_index: self.history_index,
+            headers: self.headers,
+            outcome,
+            stop_receiver: self.stop_receiver,
+        }
+    }
+    
+    /// Parse and resolve URL (including Genesis domains)
+    async fn parse_and_resolve_url(&self) -> Result<Url> {
+        let mut parsed_url = genesis_dns::normalize_url(&self.url)?;
+        
+        // Check if it's a Genesis domain
+        if let Some(host) = parsed_url.host_str() {
+            if genesis_dns::has_genesis_tld(host, &self.genesis_tlds) {
+                parsed_url = self.resolve_genesis_domain(parsed_url).await?;
+            }
         }
+        
+        Ok(parsed_url)
+    }
+    
+    /// Resolve Genesis domain
+    async fn resolve_genesis_domain(&self, mut url: Url) -> Result<Url> {
+        if let Some(host) = url.host_str() {
+            info!("🌐 Resolving Genesis domain: {}", host);
+            
+            let mut resolver = self.dns_resolver.write().await;
+            let started = std::time::Instant::now();
+            let resolution = resolver.resolve(host).await;
+            self.emit(WebViewEvent::NetworkRequest(NetworkRequest {
+                method: "DNS".to_string(),
+                url: url.clone(),
+                status: None,
+                size: None,
+                duration: started.elapsed(),
+                request_headers: Vec::new(),
+                response_headers: Vec::new(),
+                error: resolution.as_ref().err().map(|e| e.to_string()),
+            }));
+            match resolution {
+                Ok(dns_result) => {
+                    // Update security state for Genesis domain
+                    self.emit(WebViewEvent::SecurityStateChanged(SecurityState::Genesis));
+                    
+                    if let Some(ip) = dns_result.ip_address {
+                        info!("✅ Resolved to IP: {}", ip);
+                        url.set_host(Some(&ip.to_string()))?;
+                    } else if let Some(content_hash) = dns_result.content_hash {
+                        info!("📦 Resolved to IPFS: {}", content_hash);
+                        match resolver.ipfs_gateway_url(&content_hash).await {
+                            Ok(gateway_url) => url = Url::parse(&gateway_url)?,
+                            Err(e) => {
+                                warn!("⚠️ {}", e);
+                                return Err(anyhow::Error::from(e));
+                            }
+                        }
+                    }
+                },
+                Err(e) => {
+                    warn!("⚠️ Failed to resolve Genesis domain: {:?}", e);
+                    return Err(anyhow::Error::from(e));
+                }
+            }
+        }
+        
+        Ok(url)
+    }
+    
+    fn emit(&self, event: WebViewEvent) {
+        emit_event(&self.event_sender, self.id, event);
     }
 }
 
@@ -142,8 +461,10 @@ impl GenesisWebView {
         id: WebViewId,
         engine: Arc<Mutex<ServoEngine>>,
         dns_resolver: Arc<tokio::sync::RwLock<GenesisDnsResolver>>,
+        event_sender: WebViewEventSender,
+        cookie_jar: Arc<Mutex<CookieJar>>,
     ) -> Result<Self> {
-        let (event_sender, event_receiver) = mpsc::channel(100);
+        let genesis_tlds = dns_resolver.read().await.genesis_tlds().to_vec();
         
         Ok(Self {
             id,
@@ -154,134 +475,291 @@ impl GenesisWebView {
             history: Vec::new(),
             history_index: 0,
             javascript_enabled: true,
+            zoom_level: 1.0,
+            muted: false,
+            script_timeout: DEFAULT_SCRIPT_TIMEOUT,
+            navigation_timeout: DEFAULT_NAVIGATION_TIMEOUT,
             user_agent: "Genesis Browser/1.0".to_string(),
+            genesis_user_agent: None,
+            traditional_user_agent: None,
             dns_resolver,
+            genesis_tlds,
             event_sender,
-            event_receiver,
+            stop_sender: Arc::new(Mutex::new(watch::channel(false).0)),
+            navigation_slots: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_NAVIGATIONS)),
+            navigation_seq: 0,
+            cookie_jar,
+            custom_headers: HashMap::new(),
         })
     }
     
     /// Navigate to URL
     pub async fn navigate(&mut self, url: &str) -> Result<()> {
+        let headers = self.custom_headers.clone();
+        self.load(url, None, headers).await
+    }
+    
+    /// Navigate with extra headers for this load only; they override
+    /// the configured headers of the same name
+    pub async fn navigate_with_headers(&mut self, url: &str, headers: HashMap<String, String>) -> Result<()> {
+        let headers = merge_headers(&self.custom_headers, headers);
+        self.load(url, None, headers).await
+    }
+    
+    /// Load a URL. A history index means we're moving through existing
+    /// history, so the entries stay as they are and only the position moves.
+    async fn load(&mut self, url: &str, history_index: Option<usize>, headers: HashMap<String, String>) -> Result<()> {
+        let pending = self.begin_load(url, history_index, headers);
+        let resolved = pending.resolve().await;
+        self.finish_navigation(resolved)
+    }
+    
+    /// Start navigating to `url` with the configured headers. The returned
+    /// resolution doesn't borrow the webview; hand its result back to
+    /// `finish_navigation`.
+    pub fn begin_navigation(&mut self, url: &str) -> PendingNavigation {
+        let headers = self.custom_headers.clone();
+        self.begin_load(url, None, headers)
+    }
+    
+    fn begin_load(&mut self, url: &str, history_index: Option<usize>, headers: HashMap<String, String>) -> PendingNavigation {
         info!("🔍 WebView {} navigating to: {}", self.id.0, url);
+        if !headers.is_empty() {
+            debug!("📨 Request headers: {}", redact_headers(&headers));
+        }
+        self.is_loading = true;
+        self.navigation_seq += 1;
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        *self.stop_sender.lock().unwrap() = stop_sender;
+        self.emit(WebViewEvent::ProgressUpdate(PROGRESS_STARTED));
         
-        // Parse URL
-        let parsed_url = self.parse_and_resolve_url(url).await?;
+        // A User-Agent among the headers wins over the configured ones
+        let mut headers = headers;
+        if !headers.keys().any(|name| name.eq_ignore_ascii_case("user-agent")) {
+            headers.insert("User-Agent".to_string(), self.user_agent_for(url).to_string());
+        }
+        
+        PendingNavigation {
+            id: self.id,
+            seq: self.navigation_seq,
+            url: url.to_string(),
+            history_index,
+            headers,
+            dns_resolver: self.dns_resolver.clone(),
+            genesis_tlds: self.genesis_tlds.clone(),
+            event_sender: self.event_sender.clone(),
+            stop_receiver,
+            navigation_timeout: self.navigation_timeout,
+            navigation_slots: self.navigation_slots.clone(),
+        }
+    }
+    
+    /// Finish a navigation once its host is resolved: hand it to Servo and
+    /// update history. Only the handoff holds the engine lock. A navigation
+    /// superseded by a newer one or by `stop` is dropped.
+    pub fn finish_navigation(&mut self, resolved: ResolvedNavigation) -> Result<()> {
+        let ResolvedNavigation { id, seq, url, history_index, headers, outcome, stop_receiver } = resolved;
+        if id != self.id {
+            anyhow::bail!("Navigation to {} belongs to WebView {}", url, id.0);
+        }
+        if seq != self.navigation_seq {
+            debug!("WebView {} dropping superseded navigation to {}", self.id.0, url);
+            return Ok(());
+        }
+        
+        // A stop that came after resolution still keeps the page from loading
+        let outcome = if *stop_receiver.borrow() { ResolutionOutcome::Stopped } else { outcome };
+        let parsed_url = match outcome {
+            ResolutionOutcome::Resolved(parsed_url) => parsed_url,
+            ResolutionOutcome::Failed(e) => {
+                self.is_loading = false;
+                self.emit(WebViewEvent::LoadError(e.to_string()));
+                return Err(e);
+            }
+            ResolutionOutcome::TimedOut(navigation_timeout) => {
+                warn!("⏱️ WebView {} navigation to {} timed out", self.id.0, url);
+                self.is_loading = false;
+                self.emit(WebViewEvent::LoadError("navigation timed out".to_string()));
+                return Err(anyhow::anyhow!("Navigation to {} timed out after {:?}", url, navigation_timeout));
+            }
+            ResolutionOutcome::Stopped => {
+                // Dropping the resolution future aborted the pending lookup
+                info!("⏹️ WebView {} navigation to {} stopped", self.id.0, url);
+                self.finish_stopped();
+                return Ok(());
+            }
+        };
         
         // Send load started event
-        self.event_sender.send(WebViewEvent::LoadStarted(parsed_url.clone())).await?;
-        self.is_loading = true;
+        self.emit(WebViewEvent::LoadStarted(parsed_url.clone()));
         
-        // Navigate using Servo engine
+        // Hand the load to the Servo engine, with this webview's cookies attached
         #[cfg(feature = "servo-integration")]
         {
-            let mut engine = self.engine.lock().unwrap();
-            engine.navigate(&parsed_url.to_string()).await?;
+            let handoff = {
+                let cookies = self.cookie_jar.lock().unwrap().cookie_header(&parsed_url);
+                let mut engine = self.engine.lock().unwrap();
+                engine.set_request_cookies(&parsed_url, cookies);
+                engine.set_request_headers(&parsed_url, &headers);
+                engine.load_resolved(&parsed_url)
+            };
+            if let Err(e) = handoff {
+                self.is_loading = false;
+                self.emit(WebViewEvent::LoadError(e.to_string()));
+                return Err(e);
+            }
+        }
+        // Servo's resource loader reports its own requests; without it
+        // there's only the document request to show
+        #[cfg(not(feature = "servo-integration"))]
+        {
+            let mut request_headers: Vec<(String, String)> = headers.into_iter().collect();
+            request_headers.sort();
+            self.emit(WebViewEvent::NetworkRequest(NetworkRequest {
+                method: "GET".to_string(),
+                url: parsed_url.clone(),
+                status: None,
+                size: None,
+                duration: std::time::Duration::ZERO,
+                request_headers,
+                response_headers: Vec::new(),
+                error: None,
+            }));
         }
+        self.emit(WebViewEvent::ProgressUpdate(PROGRESS_REQUESTED));
         
         // Update state
         self.current_url = Some(parsed_url.clone());
-        self.add_to_history(parsed_url.clone());
+        match history_index {
+            Some(index) => self.history_index = index,
+            None => self.add_to_history(parsed_url.clone()),
+        }
         
         // Send load finished event (in real implementation, this would be async)
-        self.event_sender.send(WebViewEvent::LoadFinished(parsed_url)).await?;
+        self.emit(WebViewEvent::ProgressUpdate(1.0));
+        self.emit(WebViewEvent::LoadFinished(parsed_url));
         self.is_loading = false;
         
         Ok(())
     }
     
-    /// Parse and resolve URL (including Genesis domains)
-    async fn parse_and_resolve_url(&self, url: &str) -> Result<Url> {
-        let url_str = if !url.starts_with("http://") && !url.starts_with("https://") {
-            format!("http://{}", url)
-        } else {
-            url.to_string()
-        };
-        
-        let mut parsed_url = Url::parse(&url_str)?;
-        
-        // Check if it's a Genesis domain
-        if let Some(host) = parsed_url.host_str() {
-            if self.is_genesis_domain(host) {
-                parsed_url = self.resolve_genesis_domain(parsed_url).await?;
-            }
-        }
-        
-        Ok(parsed_url)
-    }
-    
     /// Check if domain is Genesis
-    fn is_genesis_domain(&self, host: &str) -> bool {
-        let genesis_tlds = [".genesis", ".free", ".web", ".defi", ".dao"];
-        genesis_tlds.iter().any(|tld| host.ends_with(tld))
+    pub fn is_genesis_domain(&self, host: &str) -> bool {
+        genesis_dns::has_genesis_tld(host, &self.genesis_tlds)
     }
     
-    /// Resolve Genesis domain
-    async fn resolve_genesis_domain(&self, mut url: Url) -> Result<Url> {
-        if let Some(host) = url.host_str() {
-            info!("🌐 Resolving Genesis domain: {}", host);
-            
-            let mut resolver = self.dns_resolver.write().await;
-            match resolver.resolve(host).await {
-                Ok(dns_result) => {
-                    // Update security state for Genesis domain
-                    self.event_sender.send(WebViewEvent::SecurityStateChanged(
-                        SecurityState::Genesis
-                    )).await?;
-                    
-                    if let Some(ip) = dns_result.ip_address {
-                        info!("✅ Resolved to IP: {}", ip);
-                        url.set_host(Some(&ip.to_string()))?;
-                    } else if let Some(content_hash) = dns_result.content_hash {
-                        info!("📦 Resolved to IPFS: {}", content_hash);
-                        url = Url::parse(&format!("https://ipfs.io/ipfs/{}", content_hash))?;
-                    }
-                },
-                Err(e) => {
-                    warn!("⚠️ Failed to resolve Genesis domain: {:?}", e);
-                    self.event_sender.send(WebViewEvent::LoadError(
-                        format!("Failed to resolve Genesis domain: {}", e)
-                    )).await?;
-                }
-            }
-        }
-        
-        Ok(url)
+    /// User-Agent sent when loading `url`: the Genesis one for Genesis
+    /// domains, the traditional one otherwise, `user_agent` if unset
+    pub fn user_agent_for(&self, url: &str) -> &str {
+        let is_genesis = genesis_dns::normalize_url(url).ok()
+            .and_then(|url| url.host_str().map(|host| self.is_genesis_domain(host)))
+            .unwrap_or(false);
+        let user_agent = if is_genesis { &self.genesis_user_agent } else { &self.traditional_user_agent };
+        user_agent.as_deref().unwrap_or(&self.user_agent)
     }
     
     /// Go back in history
     pub async fn go_back(&mut self) -> Result<()> {
-        if self.can_go_back() {
-            self.history_index -= 1;
-            let url = self.history[self.history_index].clone();
-            self.navigate(&url.to_string()).await?;
+        match self.begin_go_back() {
+            Some(pending) => self.finish_navigation(pending.resolve().await),
+            None => Ok(()),
         }
-        Ok(())
     }
     
     /// Go forward in history
     pub async fn go_forward(&mut self) -> Result<()> {
-        if self.can_go_forward() {
-            self.history_index += 1;
-            let url = self.history[self.history_index].clone();
-            self.navigate(&url.to_string()).await?;
+        match self.begin_go_forward() {
+            Some(pending) => self.finish_navigation(pending.resolve().await),
+            None => Ok(()),
         }
-        Ok(())
+    }
+    
+    /// Start going back, see `begin_navigation`; `None` at the oldest entry
+    pub fn begin_go_back(&mut self) -> Option<PendingNavigation> {
+        if !self.can_go_back() {
+            return None;
+        }
+        self.begin_history_navigation(self.history_index - 1).ok()
+    }
+    
+    /// Start going forward, see `begin_navigation`; `None` at the newest entry
+    pub fn begin_go_forward(&mut self) -> Option<PendingNavigation> {
+        if !self.can_go_forward() {
+            return None;
+        }
+        self.begin_history_navigation(self.history_index + 1).ok()
+    }
+    
+    /// Jump to any entry of `history_entries`
+    pub async fn go_to_history_index(&mut self, index: usize) -> Result<()> {
+        let pending = self.begin_history_navigation(index)?;
+        self.finish_navigation(pending.resolve().await)
+    }
+    
+    /// Start a navigation to an entry of `history_entries`, leaving the entries as they are
+    pub fn begin_history_navigation(&mut self, index: usize) -> Result<PendingNavigation> {
+        let url = self.history.get(index)
+            .with_context(|| format!("History index {} out of range ({} entries)", index, self.history.len()))?
+            .clone();
+        let headers = self.custom_headers.clone();
+        Ok(self.begin_load(url.as_str(), Some(index), headers))
+    }
+    
+    /// Every page in this webview's history, oldest first
+    pub fn history_entries(&self) -> &[Url] {
+        &self.history
+    }
+    
+    /// Index of the current page in `history_entries`
+    pub fn history_position(&self) -> usize {
+        self.history_index
     }
     
     /// Reload current page
     pub async fn reload(&mut self) -> Result<()> {
-        if let Some(url) = &self.current_url {
-            let url_str = url.to_string();
-            self.navigate(&url_str).await?;
+        match self.begin_reload() {
+            Some(pending) => self.finish_navigation(pending.resolve().await),
+            None => Ok(()),
         }
-        Ok(())
+    }
+    
+    /// Start reloading the current page in place, see `begin_navigation`;
+    /// `None` before anything was loaded
+    pub fn begin_reload(&mut self) -> Option<PendingNavigation> {
+        let url = self.current_url.as_ref()?.to_string();
+        let headers = self.custom_headers.clone();
+        Some(self.begin_load(&url, Some(self.history_index), headers))
     }
     
     /// Stop loading
     pub fn stop(&mut self) {
+        if !self.is_loading {
+            return;
+        }
+        
+        #[cfg(feature = "servo-integration")]
+        self.engine.lock().unwrap().cancel_navigation();
+        
+        // The resolution runs outside this webview and may finish before it
+        // sees the flag, so its result is invalidated as well
+        self.navigation_seq += 1;
+        self.stop_sender.lock().unwrap().send_replace(true);
+        self.finish_stopped();
+    }
+    
+    /// Handle for stopping navigation from another task
+    pub fn stop_handle(&self) -> StopHandle {
+        StopHandle { sender: self.stop_sender.clone() }
+    }
+    
+    /// Whether a navigation is in progress
+    pub fn is_loading(&self) -> bool {
+        self.is_loading
+    }
+    
+    fn finish_stopped(&mut self) {
         self.is_loading = false;
-        // Send stop message to Servo
+        self.emit(WebViewEvent::LoadError("stopped by user".to_string()));
     }
     
     /// Execute JavaScript
@@ -293,10 +771,67 @@ impl GenesisWebView {
         
         debug!("📜 Executing JavaScript in WebView {}", self.id.0);
         
-        // Execute script via Servo
-        // This would involve sending a message to the script thread
+        // Hand the script to Servo's script thread and wait for its reply
+        let (reply_sender, reply_receiver) = oneshot::channel();
+        self.engine.lock().unwrap().evaluate_script(script.to_string(), reply_sender);
+        
+        match tokio::time::timeout(self.script_timeout, reply_receiver).await {
+            Ok(Ok(Ok(value))) => Ok(value),
+            Ok(Ok(Err(message))) => Err(anyhow::anyhow!("Script error: {}", message)),
+            Ok(Err(_)) => Err(anyhow::anyhow!("Script thread dropped the request")),
+            Err(_) => Err(anyhow::anyhow!("Script timed out after {:?}", self.script_timeout)),
+        }
+    }
+    
+    /// Capture the rendered page as a PNG. Not supported yet: the engine has
+    /// no compositor rendering context to read pages back from.
+    pub async fn capture_png(&self) -> Result<Vec<u8>> {
+        Err(anyhow::anyhow!("Page capture isn't supported yet: the engine can't read pages back"))
+    }
+    
+    /// Set how long a navigation may take
+    pub fn set_navigation_timeout(&mut self, timeout: std::time::Duration) {
+        self.navigation_timeout = timeout;
+    }
+    
+    /// Set how long `execute_script` waits for a result
+    pub fn set_script_timeout(&mut self, timeout: std::time::Duration) {
+        self.script_timeout = timeout;
+    }
+    
+    /// Set page zoom, clamped to `MIN_ZOOM..=MAX_ZOOM`
+    pub fn set_zoom(&mut self, level: f32) {
+        self.zoom_level = level.clamp(MIN_ZOOM, MAX_ZOOM);
+        
+        #[cfg(feature = "servo-integration")]
+        self.engine.lock().unwrap().set_page_zoom(self.id, self.zoom_level);
         
-        Ok(String::new())
+        debug!("🔍 WebView {} zoom set to {:.0}%", self.id.0, self.zoom_level * 100.0);
+    }
+    
+    /// Current page zoom
+    pub fn zoom(&self) -> f32 {
+        self.zoom_level
+    }
+    
+    /// Silence or restore the page's audio output
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+        
+        #[cfg(feature = "servo-integration")]
+        self.engine.lock().unwrap().set_webview_muted(self.id, muted);
+        
+        debug!("🔇 WebView {} {}", self.id.0, if muted { "muted" } else { "unmuted" });
+    }
+    
+    /// Whether the page's audio is silenced
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+    
+    /// Set the headers sent with every navigation
+    pub fn set_custom_headers(&mut self, headers: HashMap<String, String>) {
+        self.custom_headers = headers;
     }
     
     /// Set user agent
@@ -304,6 +839,13 @@ impl GenesisWebView {
         self.user_agent = user_agent;
     }
     
+    /// Set the user agents for Genesis and traditional domains; `None`
+    /// falls back to `set_user_agent`'s
+    pub fn set_domain_user_agents(&mut self, genesis: Option<String>, traditional: Option<String>) {
+        self.genesis_user_agent = genesis;
+        self.traditional_user_agent = traditional;
+    }
+    
     /// Enable/disable JavaScript
     pub fn set_javascript_enabled(&mut self, enabled: bool) {
         self.javascript_enabled = enabled;
@@ -326,13 +868,14 @@ impl GenesisWebView {
     
     /// Check if can go forward
     pub fn can_go_forward(&self) -> bool {
-        self.history_index < self.history.len() - 1
+        // Written this way so an empty history can't underflow
+        self.history_index + 1 < self.history.len()
     }
     
     /// Add URL to history
     fn add_to_history(&mut self, url: Url) {
         // Remove forward history if we're not at the end
-        if self.history_index < self.history.len() - 1 {
+        if self.can_go_forward() {
             self.history.truncate(self.history_index + 1);
         }
         
@@ -340,35 +883,120 @@ impl GenesisWebView {
         self.history_index = self.history.len() - 1;
     }
     
+    /// Store a cookie the page set through a `Set-Cookie` header
+    pub fn on_set_cookie(&mut self, url: &Url, header: &str) -> Result<()> {
+        self.cookie_jar.lock().unwrap().set_cookie(url, header)
+    }
+    
     /// Handle page title change
     pub async fn on_title_changed(&mut self, title: String) -> Result<()> {
         self.title = title.clone();
-        self.event_sender.send(WebViewEvent::TitleChanged(title)).await?;
+        self.emit(WebViewEvent::TitleChanged(title));
+        Ok(())
+    }
+    
+    /// Handle the page starting or stopping audio playback
+    pub async fn on_audible_changed(&mut self, audible: bool) -> Result<()> {
+        self.emit(WebViewEvent::AudibleChanged(audible));
         Ok(())
     }
     
     /// Handle console message
     pub async fn on_console_message(&mut self, message: ConsoleMessage) -> Result<()> {
-        self.event_sender.send(WebViewEvent::ConsoleMessage(message)).await?;
+        self.emit(WebViewEvent::ConsoleMessage(message));
         Ok(())
     }
     
-    /// Get next event
-    pub async fn next_event(&mut self) -> Option<WebViewEvent> {
-        self.event_receiver.recv().await
+    fn emit(&self, event: WebViewEvent) {
+        emit_event(&self.event_sender, self.id, event);
     }
 }
 
 impl WebViewManager {
     /// Create new webview manager
     pub async fn new(engine: Arc<Mutex<ServoEngine>>, config: WebViewConfig) -> Result<Self> {
-        Ok(Self {
+        let (event_sender, event_receiver) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let cookie_jar = match &config.cookie_store {
+            Some(path) => CookieJar::load(path)?,
+            None => CookieJar::new(),
+        };
+        let blocklist = config.blocklist.as_deref().map(BlockList::load).transpose()?;
+        
+        // Requests Servo's resource loader finishes show up as webview events
+        #[cfg(feature = "servo-integration")]
+        {
+            let sender = event_sender.clone();
+            let observer: crate::servo_engine::RequestObserver = Arc::new(move |id, request| {
+                let _ = sender.try_send((id, WebViewEvent::NetworkRequest(request)));
+            });
+            engine.lock().unwrap().set_request_observer(Some(observer));
+        }
+        
+        let max_navigations = config.max_concurrent_navigations.max(1);
+        let mut manager = Self {
             webviews: HashMap::new(),
             active_id: None,
             engine,
             next_id: 1,
             config,
-        })
+            cookie_jar: Arc::new(Mutex::new(cookie_jar)),
+            blocklist: None,
+            blocked_requests: Arc::new(AtomicUsize::new(0)),
+            navigation_slots: Arc::new(Semaphore::new(max_navigations)),
+            event_sender,
+            event_receiver: Some(event_receiver),
+        };
+        manager.set_blocklist(blocklist);
+        Ok(manager)
+    }
+    
+    /// Replace the block list; `None` turns blocking off
+    pub fn set_blocklist(&mut self, blocklist: Option<BlockList>) {
+        self.blocklist = blocklist.map(Arc::new);
+        
+        // Servo's resource loader asks the engine before every request
+        #[cfg(feature = "servo-integration")]
+        {
+            let filter = self.blocklist.clone().map(|blocklist| {
+                let blocked_requests = self.blocked_requests.clone();
+                Arc::new(move |url: &Url| !check_blocked(&blocklist, &blocked_requests, url))
+                    as crate::servo_engine::RequestFilter
+            });
+            self.engine.lock().unwrap().set_request_filter(filter);
+        }
+    }
+    
+    /// Whether a request should be aborted; blocked requests are counted
+    pub fn should_block(&self, url: &Url) -> bool {
+        self.blocklist.as_ref()
+            .map_or(false, |blocklist| check_blocked(blocklist, &self.blocked_requests, url))
+    }
+    
+    /// Requests blocked since the last reset, for the "N trackers blocked" badge
+    pub fn blocked_count(&self) -> usize {
+        self.blocked_requests.load(Ordering::Relaxed)
+    }
+    
+    /// Start counting blocked requests from zero, e.g. on a new page load
+    pub fn reset_blocked_count(&self) {
+        self.blocked_requests.store(0, Ordering::Relaxed);
+    }
+    
+    /// Take the event receiver so the UI can subscribe to webview events.
+    /// After this, `drain_events` returns nothing.
+    pub fn take_event_receiver(&mut self) -> Option<WebViewEventReceiver> {
+        self.event_receiver.take()
+    }
+    
+    /// All events queued since the last call, without waiting
+    pub fn drain_events(&mut self) -> Vec<(WebViewId, WebViewEvent)> {
+        let mut events = Vec::new();
+        if let Some(receiver) = self.event_receiver.as_mut() {
+            while let Ok(event) = receiver.try_recv() {
+                events.push(event);
+            }
+        }
+        events
     }
     
     /// Create new webview tab
@@ -376,11 +1004,34 @@ impl WebViewManager {
         let id = WebViewId(BrowsingContextId::new());
         self.next_id += 1;
         
-        let dns_resolver = Arc::new(tokio::sync::RwLock::new(
-            GenesisDnsResolver::new("http://localhost:3000".to_string(), true)
-        ));
+        let mut resolver = GenesisDnsResolver::new(self.config.genesis_node_url.clone(), self.config.enable_traditional_fallback);
+        resolver.set_genesis_tlds(self.config.genesis_tlds.clone());
+        if let Some(proxy_url) = &self.config.proxy_url {
+            resolver.set_proxy(proxy_url)?;
+        }
+        if !self.config.node_headers.is_empty() {
+            resolver.set_node_headers(&self.config.node_headers)?;
+        }
+        resolver.set_user_agent(self.config.genesis_user_agent.as_deref().unwrap_or(&self.config.user_agent))?;
+        resolver.set_ipfs_gateways(self.config.ipfs_gateways.clone());
+        #[cfg(feature = "pinned-offline")]
+        resolver.set_ipfs_api(self.config.ipfs_api_url.clone(), self.config.auto_pin && !self.config.private_mode);
+        let dns_resolver = Arc::new(tokio::sync::RwLock::new(resolver));
         
-        let webview = GenesisWebView::new(id, self.engine.clone(), dns_resolver).await?;
+        let cookie_jar = if self.config.private_mode {
+            Arc::new(Mutex::new(CookieJar::new()))
+        } else {
+            self.cookie_jar.clone()
+        };
+        let mut webview = GenesisWebView::new(id, self.engine.clone(), dns_resolver, self.event_sender.clone(), cookie_jar).await?;
+        webview.set_custom_headers(self.config.custom_headers.clone());
+        webview.set_user_agent(self.config.user_agent.clone());
+        webview.set_domain_user_agents(
+            self.config.genesis_user_agent.clone(),
+            self.config.traditional_user_agent.clone(),
+        );
+        webview.set_navigation_timeout(self.config.navigation_timeout);
+        webview.navigation_slots = self.navigation_slots.clone();
         
         self.webviews.insert(id, webview);
         self.active_id = Some(id);
@@ -416,6 +1067,16 @@ impl WebViewManager {
         }
     }
     
+    /// Number of open webviews
+    pub fn webview_count(&self) -> usize {
+        self.webviews.len()
+    }
+    
+    /// Get a webview by ID
+    pub fn get_webview(&self, id: WebViewId) -> Option<&GenesisWebView> {
+        self.webviews.get(&id)
+    }
+    
     /// Get all webview IDs
     pub fn webview_ids(&self) -> Vec<WebViewId> {
         self.webviews.keys().cloned().collect()
@@ -430,6 +1091,70 @@ impl WebViewManager {
         }
     }
     
+    /// Start a navigation in any webview. Resolve the result and pass it to
+    /// `finish_navigation`; the manager needn't be held in between.
+    pub fn begin_navigation(&mut self, id: WebViewId, url: &str) -> Result<PendingNavigation> {
+        let webview = self.webviews.get_mut(&id)
+            .with_context(|| format!("No webview with ID {}", id.0))?;
+        Ok(webview.begin_navigation(url))
+    }
+    
+    /// Start a navigation in the active webview, see `begin_navigation`
+    pub fn begin_active_navigation(&mut self, url: &str) -> Result<PendingNavigation> {
+        self.active_webview()
+            .map(|webview| webview.begin_navigation(url))
+            .ok_or_else(|| anyhow::anyhow!("No active webview"))
+    }
+    
+    /// Apply a resolved navigation; fails if its webview was closed meanwhile
+    pub fn finish_navigation(&mut self, resolved: ResolvedNavigation) -> Result<()> {
+        let id = resolved.webview_id();
+        self.webviews.get_mut(&id)
+            .with_context(|| format!("No webview with ID {}", id.0))?
+            .finish_navigation(resolved)
+    }
+    
+    /// Navigate several webviews at once. Hosts resolve concurrently, at most
+    /// `max_concurrent_navigations` at a time, and each navigation is applied
+    /// as soon as its host is resolved. Outcomes come in the order they finish.
+    pub async fn navigate_all(&mut self, urls: Vec<(WebViewId, String)>) -> Vec<(WebViewId, Result<()>)> {
+        let mut results = Vec::with_capacity(urls.len());
+        let mut resolutions = FuturesUnordered::new();
+        for (id, url) in urls {
+            match self.begin_navigation(id, &url) {
+                Ok(pending) => resolutions.push(pending.resolve()),
+                Err(e) => results.push((id, Err(e))),
+            }
+        }
+        
+        while let Some(resolved) = resolutions.next().await {
+            let id = resolved.webview_id();
+            results.push((id, self.finish_navigation(resolved)));
+        }
+        results
+    }
+    
+    /// Start going back in the active webview; `None` if there's nowhere to go
+    pub fn begin_go_back(&mut self) -> Result<Option<PendingNavigation>> {
+        self.active_webview()
+            .map(|webview| webview.begin_go_back())
+            .ok_or_else(|| anyhow::anyhow!("No active webview"))
+    }
+    
+    /// Start going forward in the active webview; `None` if there's nowhere to go
+    pub fn begin_go_forward(&mut self) -> Result<Option<PendingNavigation>> {
+        self.active_webview()
+            .map(|webview| webview.begin_go_forward())
+            .ok_or_else(|| anyhow::anyhow!("No active webview"))
+    }
+    
+    /// Start reloading any webview; `None` if it has no page yet
+    pub fn begin_reload(&mut self, id: WebViewId) -> Result<Option<PendingNavigation>> {
+        let webview = self.webviews.get_mut(&id)
+            .with_context(|| format!("No webview with ID {}", id.0))?;
+        Ok(webview.begin_reload())
+    }
+    
     /// Go back in active webview
     pub async fn go_back(&mut self) -> Result<()> {
         if let Some(webview) = self.active_webview() {
@@ -456,6 +1181,137 @@ impl WebViewManager {
             Err(anyhow::anyhow!("No active webview"))
         }
     }
+    
+    /// Reload any webview, e.g. a background tab
+    pub async fn reload_webview(&mut self, id: WebViewId) -> Result<()> {
+        let webview = self.webviews.get_mut(&id)
+            .with_context(|| format!("No webview with ID {}", id.0))?;
+        webview.reload().await
+    }
+    
+    /// Cookie jar of the active webview, or the shared one if there's none
+    fn active_cookie_jar(&mut self) -> Arc<Mutex<CookieJar>> {
+        let shared = self.cookie_jar.clone();
+        self.active_webview().map_or(shared, |webview| webview.cookie_jar.clone())
+    }
+    
+    /// Store a cookie for the active webview
+    pub fn set_cookie(&mut self, url: &Url, header: &str) -> Result<()> {
+        self.active_cookie_jar().lock().unwrap().set_cookie(url, header)?;
+        self.save_cookies()
+    }
+    
+    /// Cookies the active webview sends to `url`
+    pub fn get_cookies_for(&mut self, url: &Url) -> Vec<crate::cookies::Cookie> {
+        self.active_cookie_jar().lock().unwrap().get_cookies_for(url)
+    }
+    
+    /// Remove all cookies, including those of private webviews
+    pub fn clear_cookies(&mut self) -> Result<()> {
+        self.cookie_jar.lock().unwrap().clear_cookies();
+        for webview in self.webviews.values() {
+            webview.cookie_jar.lock().unwrap().clear_cookies();
+        }
+        self.save_cookies()
+    }
+    
+    /// Persist the shared cookie jar; private-mode cookies are never written
+    pub fn save_cookies(&self) -> Result<()> {
+        if let Some(path) = &self.config.cookie_store {
+            self.cookie_jar.lock().unwrap().save(path)?;
+        }
+        Ok(())
+    }
+    
+    /// Capture the active webview as a PNG
+    pub async fn capture_active(&mut self) -> Result<Vec<u8>> {
+        if let Some(webview) = self.active_webview() {
+            webview.capture_png().await
+        } else {
+            Err(anyhow::anyhow!("No active webview"))
+        }
+    }
+    
+    /// Set page zoom of the active webview
+    pub fn set_active_zoom(&mut self, level: f32) -> Result<()> {
+        if let Some(webview) = self.active_webview() {
+            webview.set_zoom(level);
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("No active webview"))
+        }
+    }
+    
+    /// Mute or unmute any webview, e.g. a background tab
+    pub fn set_webview_muted(&mut self, id: WebViewId, muted: bool) -> Result<()> {
+        let webview = self.webviews.get_mut(&id)
+            .with_context(|| format!("No webview with ID {}", id.0))?;
+        webview.set_muted(muted);
+        Ok(())
+    }
+    
+    /// Stop loading in active webview
+    pub fn stop(&mut self) -> Result<()> {
+        if let Some(webview) = self.active_webview() {
+            webview.stop();
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("No active webview"))
+        }
+    }
+}
+
+/// Configured headers with per-navigation overrides applied.
+/// Header names are case-insensitive, so `authorization` replaces `Authorization`.
+fn merge_headers(defaults: &HashMap<String, String>, overrides: HashMap<String, String>) -> HashMap<String, String> {
+    let mut merged: HashMap<String, String> = defaults.iter()
+        .filter(|(name, _)| !overrides.keys().any(|o| o.eq_ignore_ascii_case(name)))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    merged.extend(overrides);
+    merged
+}
+
+/// Headers for logging, with credentials replaced
+fn redact_headers(headers: &HashMap<String, String>) -> String {
+    let mut lines: Vec<String> = headers.iter()
+        .map(|(name, value)| {
+            let sensitive = SENSITIVE_HEADERS.iter().any(|s| s.eq_ignore_ascii_case(name));
+            format!("{}: {}", name, if sensitive { "<redacted>" } else { value })
+        })
+        .collect();
+    lines.sort();
+    lines.join(", ")
+}
+
+/// Resolves once the navigation's stop flag is set. A navigation superseded
+/// by a newer one is never stopped this way; `finish_navigation` drops it.
+async fn stopped(mut stop_receiver: watch::Receiver<bool>) {
+    if stop_receiver.wait_for(|stopped| *stopped).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Queue an event without blocking, dropping it when the channel is full
+fn emit_event(sender: &WebViewEventSender, id: WebViewId, event: WebViewEvent) {
+    match sender.try_send((id, event)) {
+        Ok(()) => {}
+        Err(mpsc::error::TrySendError::Full((_, event))) => {
+            warn!("⚠️ WebView {} event queue full, dropping {:?}", id.0, event);
+        }
+        Err(mpsc::error::TrySendError::Closed(_)) => {
+            debug!("WebView {} has no event subscriber", id.0);
+        }
+    }
+}
+
+fn check_blocked(blocklist: &BlockList, blocked_requests: &AtomicUsize, url: &Url) -> bool {
+    let blocked = blocklist.should_block(url);
+    if blocked {
+        blocked_requests.fetch_add(1, Ordering::Relaxed);
+        debug!("🛡️ Blocked request to {}", url);
+    }
+    blocked
 }
 
 // Use Servo's WebViewId instead of defining our own
@@ -464,45 +1320,471 @@ impl WebViewManager {
 mod tests {
     use super::*;
     
-    #[tokio::test]
-    async fn test_webview_creation() {
-        let config = ServoConfig::default();
-        let engine = Arc::new(Mutex::new(
-            ServoEngine::new(config).await.unwrap()
-        ));
-        
+    /// An engine with the default config
+    fn test_engine() -> Arc<Mutex<ServoEngine>> {
+        Arc::new(Mutex::new(ServoEngine::new(ServoConfig::default()).unwrap()))
+    }
+    
+    /// A webview on a default engine resolving through `node_url`, with its
+    /// engine and the receiving end of its events
+    async fn test_webview(node_url: &str, fallback: bool) -> (Arc<Mutex<ServoEngine>>, GenesisWebView, WebViewEventReceiver) {
+        let engine = test_engine();
         let dns_resolver = Arc::new(tokio::sync::RwLock::new(
-            GenesisDnsResolver::new("http://localhost:3000".to_string(), true)
+            GenesisDnsResolver::new(node_url.to_string(), fallback)
         ));
+        let (event_sender, event_receiver) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
         
         let webview = GenesisWebView::new(
             WebViewId(1),
-            engine,
+            engine.clone(),
             dns_resolver,
-        ).await;
+            event_sender,
+            Arc::new(Mutex::new(CookieJar::new())),
+        ).await.unwrap();
+        (engine, webview, event_receiver)
+    }
+    
+    #[tokio::test]
+    async fn test_webview_creation() {
+        let (_engine, webview, _event_receiver) = test_webview("http://localhost:3000", true).await;
         
-        assert!(webview.is_ok());
+        assert!(!webview.is_loading());
+        assert!(webview.current_url().is_none());
     }
     
     #[tokio::test]
     async fn test_genesis_domain_detection() {
-        let config = ServoConfig::default();
-        let engine = Arc::new(Mutex::new(
-            ServoEngine::new(config).await.unwrap()
+        let (_engine, webview, _event_receiver) = test_webview("http://localhost:3000", true).await;
+        
+        assert!(webview.is_genesis_domain("test.genesis"));
+        assert!(webview.is_genesis_domain("freedom.free"));
+        assert!(!webview.is_genesis_domain("google.com"));
+    }
+    
+    #[tokio::test]
+    async fn test_webviews_use_configured_tlds() {
+        let engine = test_engine();
+        let config = WebViewConfig {
+            genesis_tlds: vec![".free".to_string()],
+            ..WebViewConfig::default()
+        };
+        let mut manager = WebViewManager::new(engine, config).await.unwrap();
+        let id = manager.create_webview().await.unwrap();
+        let webview = manager.get_webview(id).unwrap();
+        
+        assert!(webview.is_genesis_domain("freedom.free"));
+        assert!(!webview.is_genesis_domain("test.genesis"));
+    }
+    
+    #[tokio::test]
+    async fn test_fresh_webview_has_no_history() {
+        let (_engine, mut webview, _event_receiver) = test_webview("http://localhost:3000", true).await;
+        
+        assert!(!webview.can_go_back());
+        assert!(!webview.can_go_forward());
+        
+        // Going forward with nothing to go forward to is a no-op
+        webview.go_forward().await.unwrap();
+        assert!(webview.current_url().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_history_navigation_keeps_entries() {
+        let engine = test_engine();
+        let mut manager = WebViewManager::new(engine, WebViewConfig::default()).await.unwrap();
+        manager.create_webview().await.unwrap();
+        for page in ["example.com/a", "example.com/b", "example.com/c"] {
+            manager.navigate(page).await.unwrap();
+        }
+        
+        let webview = manager.active_webview().unwrap();
+        webview.go_back().await.unwrap();
+        webview.go_back().await.unwrap();
+        assert_eq!(webview.history_entries().len(), 3);
+        assert_eq!(webview.history_position(), 0);
+        assert_eq!(webview.current_url().unwrap().path(), "/a");
+        
+        webview.go_forward().await.unwrap();
+        assert_eq!(webview.history_position(), 1);
+        webview.go_to_history_index(2).await.unwrap();
+        assert_eq!(webview.current_url().unwrap().path(), "/c");
+        assert_eq!(webview.history_entries().len(), 3);
+        
+        assert!(webview.go_to_history_index(3).await.is_err());
+        assert_eq!(webview.history_position(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_reload_keeps_forward_history() {
+        let engine = test_engine();
+        let mut manager = WebViewManager::new(engine, WebViewConfig::default()).await.unwrap();
+        manager.create_webview().await.unwrap();
+        for page in ["example.com/a", "example.com/b"] {
+            manager.navigate(page).await.unwrap();
+        }
+        
+        let webview = manager.active_webview().unwrap();
+        webview.go_back().await.unwrap();
+        webview.reload().await.unwrap();
+        assert_eq!(webview.history_entries().len(), 2);
+        assert_eq!(webview.current_url().unwrap().path(), "/a");
+        assert!(webview.can_go_forward());
+        
+        webview.go_forward().await.unwrap();
+        assert_eq!(webview.current_url().unwrap().path(), "/b");
+    }
+    
+    #[tokio::test]
+    async fn test_navigation_limit_follows_config() {
+        let engine = test_engine();
+        let manager = WebViewManager::new(engine.clone(), WebViewConfig::default()).await.unwrap();
+        assert_eq!(manager.navigation_slots.available_permits(), DEFAULT_MAX_CONCURRENT_NAVIGATIONS);
+        
+        let config = WebViewConfig { max_concurrent_navigations: 2, ..WebViewConfig::default() };
+        let manager = WebViewManager::new(engine, config).await.unwrap();
+        assert_eq!(manager.navigation_slots.available_permits(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_user_agent_follows_domain_class() {
+        let engine = test_engine();
+        let config = WebViewConfig {
+            user_agent: "Genesis Default".to_string(),
+            genesis_user_agent: Some("Genesis Decentralized".to_string()),
+            traditional_user_agent: Some("Mozilla/5.0 Chrome/120.0".to_string()),
+            ..WebViewConfig::default()
+        };
+        let mut manager = WebViewManager::new(engine.clone(), config).await.unwrap();
+        let id = manager.create_webview().await.unwrap();
+        let webview = manager.get_webview(id).unwrap();
+        assert_eq!(webview.user_agent_for("wiki.genesis"), "Genesis Decentralized");
+        assert_eq!(webview.user_agent_for("https://dex.defi/swap"), "Genesis Decentralized");
+        assert_eq!(webview.user_agent_for("example.com"), "Mozilla/5.0 Chrome/120.0");
+        assert_eq!(webview.user_agent_for("http://127.0.0.1:8080/"), "Mozilla/5.0 Chrome/120.0");
+        
+        // The chosen User-Agent goes out with the navigation, unless one was given
+        let pending = manager.begin_navigation(id, "example.com").unwrap();
+        assert_eq!(pending.headers["User-Agent"], "Mozilla/5.0 Chrome/120.0");
+        let pending = manager.active_webview().unwrap().begin_load("wiki.genesis", None, headers(&[("user-agent", "Custom")]));
+        assert_eq!(pending.headers, headers(&[("user-agent", "Custom")]));
+        
+        // Without per-class agents both use the single one
+        let config = WebViewConfig { user_agent: "Genesis Default".to_string(), ..WebViewConfig::default() };
+        let mut manager = WebViewManager::new(engine, config).await.unwrap();
+        let id = manager.create_webview().await.unwrap();
+        let webview = manager.get_webview(id).unwrap();
+        assert_eq!(webview.user_agent_for("wiki.genesis"), "Genesis Default");
+        assert_eq!(webview.user_agent_for("example.com"), "Genesis Default");
+    }
+    
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+    
+    #[test]
+    fn test_navigation_headers_override_defaults() {
+        let defaults = headers(&[("Authorization", "Bearer default"), ("X-Client", "genesis")]);
+        let merged = merge_headers(&defaults, headers(&[("authorization", "Bearer once"), ("X-Trace", "1")]));
+        
+        assert_eq!(merged, headers(&[
+            ("authorization", "Bearer once"),
+            ("X-Client", "genesis"),
+            ("X-Trace", "1"),
+        ]));
+        assert_eq!(merge_headers(&defaults, HashMap::new()), defaults);
+    }
+    
+    #[test]
+    fn test_sensitive_headers_are_redacted() {
+        let logged = redact_headers(&headers(&[("Authorization", "Bearer secret"), ("Accept", "text/html")]));
+        assert_eq!(logged, "Accept: text/html, Authorization: <redacted>");
+        assert!(!logged.contains("secret"));
+    }
+    
+    #[tokio::test]
+    async fn test_blocked_requests_are_counted() {
+        let engine = test_engine();
+        let mut manager = WebViewManager::new(engine, WebViewConfig::default()).await.unwrap();
+        let tracker = Url::parse("https://stats.doubleclick.net/pixel.gif").unwrap();
+        assert!(!manager.should_block(&tracker));
+        
+        manager.set_blocklist(Some(BlockList::parse("||doubleclick.net^")));
+        assert!(manager.should_block(&tracker));
+        assert!(!manager.should_block(&Url::parse("https://example.com/").unwrap()));
+        assert!(manager.should_block(&tracker));
+        assert_eq!(manager.blocked_count(), 2);
+        
+        manager.reset_blocked_count();
+        assert_eq!(manager.blocked_count(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_zoom_is_clamped() {
+        let engine = test_engine();
+        let mut manager = WebViewManager::new(engine, WebViewConfig::default()).await.unwrap();
+        assert!(manager.set_active_zoom(2.0).is_err());
+        
+        manager.create_webview().await.unwrap();
+        assert_eq!(manager.active_webview().unwrap().zoom(), 1.0);
+        
+        manager.set_active_zoom(1.5).unwrap();
+        assert_eq!(manager.active_webview().unwrap().zoom(), 1.5);
+        manager.set_active_zoom(10.0).unwrap();
+        assert_eq!(manager.active_webview().unwrap().zoom(), MAX_ZOOM);
+        manager.set_active_zoom(0.0).unwrap();
+        assert_eq!(manager.active_webview().unwrap().zoom(), MIN_ZOOM);
+    }
+    
+    #[tokio::test]
+    async fn test_stop_cancels_pending_resolution() {
+        // A Genesis node that accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+        
+        let (_engine, mut webview, mut event_receiver) = test_webview(&node_url, false).await;
+        
+        let stop_handle = webview.stop_handle();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            stop_handle.stop();
+        });
+        
+        tokio::time::timeout(std::time::Duration::from_secs(5), webview.navigate("slow.genesis"))
+            .await
+            .expect("stop didn't cancel the navigation")
+            .unwrap();
+        
+        assert!(!webview.is_loading());
+        assert!(webview.current_url().is_none());
+        // The navigation got as far as reporting its start
+        assert!(matches!(
+            event_receiver.try_recv(),
+            Ok((_, WebViewEvent::ProgressUpdate(progress))) if progress == PROGRESS_STARTED
+        ));
+        assert!(matches!(
+            event_receiver.try_recv(),
+            Ok((_, WebViewEvent::LoadError(message))) if message == "stopped by user"
         ));
         
-        let dns_resolver = Arc::new(tokio::sync::RwLock::new(
-            GenesisDnsResolver::new("http://localhost:3000".to_string(), true)
+        // Stopping an idle webview doesn't emit anything
+        webview.stop();
+        assert!(event_receiver.try_recv().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_stop_before_resolution_starts_is_kept() {
+        let (_engine, mut webview, mut event_receiver) = test_webview("http://localhost:3000", true).await;
+        
+        // The stop comes before the resolution is first polled
+        let pending = webview.begin_navigation("example.com");
+        webview.stop_handle().stop();
+        webview.finish_navigation(pending.resolve().await).unwrap();
+        
+        assert!(!webview.is_loading());
+        assert!(webview.current_url().is_none());
+        let mut events = Vec::new();
+        while let Ok((_, event)) = event_receiver.try_recv() {
+            events.push(event);
+        }
+        assert!(events.iter().any(|event| matches!(event, WebViewEvent::LoadError(message) if message == "stopped by user")));
+        assert!(!events.iter().any(|event| matches!(event, WebViewEvent::LoadFinished(_))));
+        
+        // The next navigation isn't affected
+        webview.navigate("example.com").await.unwrap();
+        assert_eq!(webview.current_url().unwrap().as_str(), "http://example.com/");
+    }
+    
+    #[tokio::test]
+    async fn test_navigation_times_out() {
+        // A Genesis node that accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+        
+        let (_engine, mut webview, mut event_receiver) = test_webview(&node_url, false).await;
+        webview.set_navigation_timeout(std::time::Duration::from_millis(200));
+        
+        let result = webview.navigate("slow.genesis").await;
+        assert!(result.unwrap_err().to_string().contains("timed out"));
+        assert!(!webview.is_loading());
+        // The navigation got as far as reporting its start
+        assert!(matches!(
+            event_receiver.try_recv(),
+            Ok((_, WebViewEvent::ProgressUpdate(progress))) if progress == PROGRESS_STARTED
+        ));
+        assert!(matches!(
+            event_receiver.try_recv(),
+            Ok((_, WebViewEvent::LoadError(message))) if message == "navigation timed out"
         ));
+    }
+    
+    #[tokio::test]
+    async fn test_failed_lookup_reports_error_without_loading() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
         
-        let webview = GenesisWebView::new(
-            WebViewId(1),
-            engine,
-            dns_resolver,
-        ).await.unwrap();
+        // A Genesis node with no records at all
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0u8; 4096];
+                let _ = socket.read(&mut buffer).await;
+                let _ = socket.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await;
+            }
+        });
         
-        assert!(webview.is_genesis_domain("test.genesis"));
-        assert!(webview.is_genesis_domain("freedom.free"));
-        assert!(!webview.is_genesis_domain("google.com"));
+        let (_engine, mut webview, mut event_receiver) = test_webview(&node_url, false).await;
+        
+        let error = webview.navigate("typo.genesis").await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<genesis_dns::DnsError>(), Some(genesis_dns::DnsError::UnsupportedDomain(_))));
+        assert!(!webview.is_loading());
+        assert!(webview.current_url().is_none());
+        assert!(webview.history_entries().is_empty());
+        
+        let mut events = Vec::new();
+        while let Ok((_, event)) = event_receiver.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(events.iter().filter(|event| matches!(event, WebViewEvent::LoadError(_))).count(), 1);
+        assert!(!events.iter().any(|event| matches!(event, WebViewEvent::LoadStarted(_) | WebViewEvent::LoadFinished(_))));
+        // Progress never got past the start
+        assert!(events.iter().all(|event| match event {
+            WebViewEvent::ProgressUpdate(progress) => *progress == PROGRESS_STARTED,
+            _ => true,
+        }));
     }
-}
\ No newline at end of file
+    
+    #[cfg(feature = "servo-integration")]
+    #[tokio::test]
+    async fn test_failed_handoff_reports_error() {
+        let (engine, mut webview, mut event_receiver) = test_webview("http://localhost:3000", true).await;
+        engine.lock().unwrap().set_request_filter(Some(Arc::new(|_: &Url| false)));
+        
+        assert!(webview.navigate("example.com").await.is_err());
+        assert!(!webview.is_loading());
+        assert!(webview.current_url().is_none());
+        
+        let mut events = Vec::new();
+        while let Ok((_, event)) = event_receiver.try_recv() {
+            events.push(event);
+        }
+        assert!(events.iter().any(|event| matches!(event, WebViewEvent::LoadError(message) if message.contains("Blocked"))));
+        assert!(!events.iter().any(|event| matches!(event, WebViewEvent::LoadFinished(_))));
+    }
+    
+    #[cfg(feature = "servo-integration")]
+    #[tokio::test]
+    async fn test_execute_script_round_trip() {
+        let (engine, mut webview, _event_receiver) = test_webview("http://localhost:3000", true).await;
+        let mut scripts = engine.lock().unwrap().take_script_receiver().unwrap();
+        webview.set_script_timeout(std::time::Duration::from_millis(200));
+        
+        // Answer the first script, never answer the second
+        tokio::spawn(async move {
+            let request = scripts.recv().await.unwrap();
+            assert_eq!(request.script, "document.title");
+            let _ = request.reply.send(Ok("\"Genesis\"".to_string()));
+            
+            let _ignored = scripts.recv().await;
+            std::future::pending::<()>().await;
+        });
+        
+        assert_eq!(webview.execute_script("document.title").await.unwrap(), "\"Genesis\"");
+        
+        let error = webview.execute_script("while (true) {}").await.unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+        
+        webview.set_javascript_enabled(false);
+        assert!(webview.execute_script("1 + 1").await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_private_webviews_keep_cookies_apart() {
+        let engine = test_engine();
+        let site = Url::parse("https://example.com/").unwrap();
+        
+        let mut manager = WebViewManager::new(engine.clone(), WebViewConfig::default()).await.unwrap();
+        manager.create_webview().await.unwrap();
+        manager.set_cookie(&site, "session=shared").unwrap();
+        manager.create_webview().await.unwrap();
+        assert_eq!(manager.get_cookies_for(&site).len(), 1);
+        
+        let private_config = WebViewConfig { private_mode: true, ..WebViewConfig::default() };
+        let mut private_manager = WebViewManager::new(engine, private_config).await.unwrap();
+        private_manager.create_webview().await.unwrap();
+        private_manager.set_cookie(&site, "session=first").unwrap();
+        private_manager.create_webview().await.unwrap();
+        assert!(private_manager.get_cookies_for(&site).is_empty());
+        
+        manager.clear_cookies().unwrap();
+        assert!(manager.get_cookies_for(&site).is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_navigate_all_reports_each_webview() {
+        let engine = test_engine();
+        let mut manager = WebViewManager::new(engine, WebViewConfig::default()).await.unwrap();
+        let first = manager.create_webview().await.unwrap();
+        let second = manager.create_webview().await.unwrap();
+        let closed = manager.create_webview().await.unwrap();
+        manager.close_webview(closed).unwrap();
+        
+        let mut results = manager.navigate_all(vec![
+            (first, "example.com/a".to_string()),
+            (second, "example.com/b".to_string()),
+            (closed, "example.com/c".to_string()),
+        ]).await;
+        results.sort_by_key(|(id, _)| [first, second, closed].iter().position(|other| other == id));
+        
+        assert!(results[0].1.is_ok() && results[1].1.is_ok());
+        assert!(results[2].1.as_ref().unwrap_err().to_string().contains("No webview"));
+        assert_eq!(manager.get_webview(first).unwrap().current_url().unwrap().path(), "/a");
+        assert_eq!(manager.get_webview(second).unwrap().current_url().unwrap().path(), "/b");
+        
+        // A navigation overtaken by a newer one in the same webview is dropped
+        manager.set_active(first).unwrap();
+        let stale = manager.begin_navigation(first, "example.com/stale").unwrap();
+        manager.navigate("example.com/c").await.unwrap();
+        manager.finish_navigation(stale.resolve().await).unwrap();
+        assert_eq!(manager.get_webview(first).unwrap().current_url().unwrap().path(), "/c");
+        assert_eq!(manager.get_webview(first).unwrap().history_entries().len(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_navigation_without_draining_does_not_block() {
+        let (_engine, mut webview, mut event_receiver) = test_webview("http://localhost:3000", true).await;
+        
+        // Every navigation emits several events, far more than the channel holds
+        let navigations = async {
+            for i in 0..200 {
+                webview.navigate(&format!("example.com/page/{}", i)).await.unwrap();
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(10), navigations)
+            .await
+            .expect("navigation blocked on a full event channel");
+        
+        let mut queued = Vec::new();
+        while let Ok((_, event)) = event_receiver.try_recv() {
+            queued.push(event);
+        }
+        assert_eq!(queued.len(), EVENT_CHANNEL_CAPACITY);
+        
+        // The oldest events were kept and the overflow dropped
+        assert!(matches!(queued[0], WebViewEvent::ProgressUpdate(progress) if progress == PROGRESS_STARTED));
+        assert!(queued.iter().any(|event| matches!(event, WebViewEvent::LoadStarted(url) if url.path() == "/page/0")));
+        assert!(queued.iter().any(|event| matches!(event, WebViewEvent::LoadFinished(url) if url.path() == "/page/0")));
+        assert!(!queued.iter().any(|event| matches!(event, WebViewEvent::LoadFinished(url) if url.path() == "/page/199")));
+    }
+}
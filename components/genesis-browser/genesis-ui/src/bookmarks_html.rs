@@ -0,0 +1,239 @@
+// Netscape bookmark file format, as exported by Chrome, Firefox and Safari
+
+use std::fmt::Write;
+
+use chrono::TimeZone;
+
+use crate::enhanced_browser::Bookmark;
+
+/// Separator between nested folder names in `Bookmark::folder`
+pub const FOLDER_SEPARATOR: &str = "/";
+
+/// Bookmarks parsed from a bookmark file, and how many entries were malformed
+pub struct ParsedBookmarks {
+    pub bookmarks: Vec<Bookmark>,
+    pub skipped: usize,
+}
+
+/// Parse a Netscape bookmark file. Folders nest through `<DL>` lists and are
+/// flattened into `Bookmark::folder` as `Parent/Child`. Entries without a
+/// usable `HREF` are skipped instead of failing the whole file.
+pub fn parse(html: &str) -> ParsedBookmarks {
+    let mut parsed = ParsedBookmarks { bookmarks: Vec::new(), skipped: 0 };
+    // One entry per open <DL>: the folder it belongs to, if it has one
+    let mut folders: Vec<Option<String>> = Vec::new();
+    let mut pending_folder = None;
+    let mut rest = html;
+    
+    while let Some(start) = rest.find('<') {
+        let Some(length) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + length];
+        rest = &rest[start + length + 1..];
+        
+        match tag_name(tag).as_str() {
+            "h3" => {
+                if let Some((text, after)) = split_at_close(rest, "</h3>") {
+                    pending_folder = Some(decode_text(text));
+                    rest = after;
+                }
+            }
+            "dl" => folders.push(pending_folder.take()),
+            "/dl" => {
+                folders.pop();
+            }
+            "a" => {
+                let Some((text, after)) = split_at_close(rest, "</a>") else {
+                    parsed.skipped += 1;
+                    continue;
+                };
+                rest = after;
+                
+                let folder = folders.iter().flatten().cloned().collect::<Vec<_>>();
+                let folder = (!folder.is_empty()).then(|| folder.join(FOLDER_SEPARATOR));
+                match parse_entry(tag, text, folder) {
+                    Some(bookmark) => parsed.bookmarks.push(bookmark),
+                    None => parsed.skipped += 1,
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    parsed
+}
+
+/// Render bookmarks as a Netscape bookmark file
+pub fn render(bookmarks: &[Bookmark]) -> String {
+    let mut sorted: Vec<(Vec<&str>, &Bookmark)> = bookmarks.iter()
+        .map(|bookmark| (folder_path(bookmark), bookmark))
+        .collect();
+    // Stable, so bookmarks keep their order within a folder
+    sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+    
+    let mut html = String::from(
+        "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n\
+         <!-- This is an automatically generated file.\n     It will be read and overwritten.\n     DO NOT EDIT! -->\n\
+         <META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">\n\
+         <TITLE>Bookmarks</TITLE>\n\
+         <H1>Bookmarks</H1>\n\
+         <DL><p>\n"
+    );
+    let mut open: Vec<&str> = Vec::new();
+    
+    for (path, bookmark) in sorted {
+        let common = open.iter().zip(&path).take_while(|(a, b)| a == b).count();
+        while open.len() > common {
+            open.pop();
+            let _ = writeln!(html, "{}</DL><p>", indent(open.len() + 1));
+        }
+        for name in &path[common..] {
+            let _ = writeln!(html, "{}<DT><H3>{}</H3>", indent(open.len() + 1), html_escape::encode_text(name));
+            let _ = writeln!(html, "{}<DL><p>", indent(open.len() + 1));
+            open.push(*name);
+        }
+        
+        let _ = writeln!(
+            html,
+            "{}<DT><A HREF=\"{}\" ADD_DATE=\"{}\">{}</A>",
+            indent(open.len() + 1),
+            html_escape::encode_double_quoted_attribute(&bookmark.url),
+            bookmark.created_at.timestamp(),
+            html_escape::encode_text(&bookmark.title)
+        );
+    }
+    while !open.is_empty() {
+        open.pop();
+        let _ = writeln!(html, "{}</DL><p>", indent(open.len() + 1));
+    }
+    
+    html.push_str("</DL><p>\n");
+    html
+}
+
+fn parse_entry(tag: &str, text: &str, folder: Option<String>) -> Option<Bookmark> {
+    let attributes = attributes(tag);
+    let attribute = |name: &str| attributes.iter().find(|(key, _)| key == name).map(|(_, value)| *value);
+    
+    let url = html_escape::decode_html_entities(attribute("href")?.trim()).into_owned();
+    url::Url::parse(&url).ok()?;
+    let title = decode_text(text);
+    let created_at = attribute("add_date")
+        .and_then(|date| date.trim().parse::<i64>().ok())
+        .and_then(|seconds| chrono::Utc.timestamp_opt(seconds, 0).single())
+        .unwrap_or_else(chrono::Utc::now);
+    
+    Some(Bookmark {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: if title.is_empty() { url.clone() } else { title },
+        url,
+        folder,
+        created_at,
+    })
+}
+
+/// Lowercased tag name, with a leading `/` for closing tags
+fn tag_name(tag: &str) -> String {
+    tag.split(|c: char| c.is_whitespace() || c == '>')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Split `text` at a closing tag, matched case-insensitively
+fn split_at_close<'a>(text: &'a str, close: &str) -> Option<(&'a str, &'a str)> {
+    // ASCII lowercasing keeps byte offsets, so the index is valid in `text`
+    let index = text.to_ascii_lowercase().find(close)?;
+    Some((&text[..index], &text[index + close.len()..]))
+}
+
+/// `key=value` attributes of a tag, keys lowercased
+fn attributes(tag: &str) -> Vec<(String, &str)> {
+    let mut attributes = Vec::new();
+    let mut rest = tag.split_once(char::is_whitespace).map_or("", |(_, rest)| rest);
+    
+    while let Some(equals) = rest.find('=') {
+        let key = rest[..equals].split_whitespace().last().unwrap_or_default().to_ascii_lowercase();
+        let value = rest[equals + 1..].trim_start();
+        let (value, after) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => match value[1..].find(quote) {
+                Some(end) => (&value[1..end + 1], &value[end + 2..]),
+                None => break,
+            },
+            _ => value.split_once(char::is_whitespace).unwrap_or((value, "")),
+        };
+        attributes.push((key, value));
+        rest = after;
+    }
+    
+    attributes
+}
+
+fn decode_text(text: &str) -> String {
+    html_escape::decode_html_entities(text.trim()).into_owned()
+}
+
+fn folder_path(bookmark: &Bookmark) -> Vec<&str> {
+    bookmark.folder.as_deref()
+        .map(|folder| folder.split(FOLDER_SEPARATOR).filter(|name| !name.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+fn indent(depth: usize) -> String {
+    "    ".repeat(depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const CHROME_EXPORT: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1700000000" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+    <DL><p>
+        <DT><A HREF="https://docs.rs/" ADD_DATE="1700000100" ICON="data:image/png;base64,AAAA">Docs.rs</A>
+        <DT><H3 ADD_DATE="1700000000">Rust &amp; Friends</H3>
+        <DL><p>
+            <DT><A HREF="https://crates.io/" ADD_DATE="1700000200">crates.io</A>
+            <DT><A ADD_DATE="1700000300">No link</A>
+            <DT><A HREF="not a url">Broken</A>
+        </DL><p>
+    </DL><p>
+    <DT><a href='https://example.com/?a=1&amp;b=2'></a>
+</DL><p>
+"#;
+
+    #[test]
+    fn test_parse_keeps_folders_and_skips_malformed_entries() {
+        let parsed = parse(CHROME_EXPORT);
+        assert_eq!(parsed.skipped, 2);
+        
+        let entries: Vec<(&str, &str, Option<&str>)> = parsed.bookmarks.iter()
+            .map(|b| (b.title.as_str(), b.url.as_str(), b.folder.as_deref()))
+            .collect();
+        assert_eq!(entries, vec![
+            ("Docs.rs", "https://docs.rs/", Some("Bookmarks bar")),
+            ("crates.io", "https://crates.io/", Some("Bookmarks bar/Rust & Friends")),
+            ("https://example.com/?a=1&b=2", "https://example.com/?a=1&b=2", None),
+        ]);
+        assert_eq!(parsed.bookmarks[0].created_at.timestamp(), 1_700_000_100);
+    }
+    
+    #[test]
+    fn test_render_round_trips() {
+        let bookmarks = parse(CHROME_EXPORT).bookmarks;
+        let reparsed = parse(&render(&bookmarks));
+        
+        assert_eq!(reparsed.skipped, 0);
+        let key = |b: &Bookmark| (b.title.clone(), b.url.clone(), b.folder.clone(), b.created_at.timestamp());
+        let mut expected: Vec<_> = bookmarks.iter().map(key).collect();
+        let mut actual: Vec<_> = reparsed.bookmarks.iter().map(key).collect();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+}
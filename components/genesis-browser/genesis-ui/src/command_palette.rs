@@ -0,0 +1,94 @@
+// Command palette: actions and open tabs, fuzzy-filtered by what's typed
+
+/// An entry in the palette, run against `T` when picked
+pub struct Command<T> {
+    pub name: String,
+    /// Shortcut shown next to the name, if the command has one
+    pub shortcut: Option<String>,
+    pub run: Box<dyn Fn(&mut T)>,
+}
+
+impl<T> Command<T> {
+    pub fn new(name: impl Into<String>, shortcut: Option<String>, run: impl Fn(&mut T) + 'static) -> Self {
+        Self {
+            name: name.into(),
+            shortcut,
+            run: Box::new(run),
+        }
+    }
+}
+
+/// How well `text` matches `query`, or `None` if it doesn't. Every query
+/// character has to appear in order, ignoring case; runs of characters and
+/// matches at the start of a word score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match = None;
+    
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = text[position..].iter().position(|&c| c == wanted)?;
+        let index = position + offset;
+        
+        score += 1;
+        if previous_match.map_or(false, |previous| previous + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+        // Skipped characters count against the match, a little
+        score -= offset.min(3) as i32;
+        
+        previous_match = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+/// Indices of the commands matching `query`, best first. An empty query
+/// keeps every command in its original order.
+pub fn filter_commands<T>(commands: &[Command<T>], query: &str) -> Vec<usize> {
+    let mut scored: Vec<(usize, i32)> = commands.iter()
+        .enumerate()
+        .filter_map(|(index, command)| fuzzy_score(query, &command.name).map(|score| (index, score)))
+        .collect();
+    // Stable, so equally good matches keep their order
+    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    scored.into_iter().map(|(index, _)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("nt", "New tab").is_some());
+        assert!(fuzzy_score("NEW", "new tab").is_some());
+        assert!(fuzzy_score("tn", "New tab").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        
+        // Word starts and runs beat scattered letters
+        assert!(fuzzy_score("ct", "Close tab") > fuzzy_score("ct", "Reopen closed tab"));
+        assert!(fuzzy_score("hist", "Clear history") > fuzzy_score("hist", "Show this tab"));
+    }
+    
+    #[test]
+    fn test_filter_commands() {
+        let commands: Vec<Command<Vec<&str>>> = ["New tab", "Close tab", "Toggle dark mode", "Clear history"]
+            .into_iter()
+            .map(|name| Command::new(name, None, move |log: &mut Vec<&str>| log.push(name)))
+            .collect();
+        
+        assert_eq!(filter_commands(&commands, ""), [0, 1, 2, 3]);
+        assert_eq!(filter_commands(&commands, "tab"), [0, 1]);
+        assert_eq!(filter_commands(&commands, "cl h"), [3]);
+        assert!(filter_commands(&commands, "zzz").is_empty());
+        
+        let mut log = Vec::new();
+        (commands[filter_commands(&commands, "dark")[0]].run)(&mut log);
+        assert_eq!(log, ["Toggle dark mode"]);
+    }
+}
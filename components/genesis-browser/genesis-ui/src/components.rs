@@ -101,5 +101,5 @@ pub struct SettingsPanel<'a> {
 
 pub struct DevToolsPanel {
     pub console_output: Vec<String>,
-    pub network_requests: Vec<(String, String, u64)>, // (method, url, size)
+    pub network_requests: Vec<crate::devtools::NetworkRequest>,
 }
\ No newline at end of file
@@ -0,0 +1,218 @@
+// User configuration loaded from config.toml at startup
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use genesis_dns::{DnsError, GenesisDnsResolver};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::enhanced_browser::{config_dir, BrowserUIState, DEFAULT_SEARCH_ENGINE};
+
+/// Config file inside the config directory, shared by all profiles
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Genesis node used when neither the config nor the command line names one
+pub const DEFAULT_GENESIS_NODE: &str = "http://localhost:3000";
+
+/// User-Agent the browser sends to sites and Genesis nodes
+pub const USER_AGENT: &str = "Genesis Browser/1.0 (Servo; Decentralized Web)";
+
+/// Browser chrome color scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+/// Settings read from `config.toml`; missing keys keep their defaults
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Genesis nodes in failover order
+    pub genesis_nodes: Vec<String>,
+    /// Resolve non-Genesis domains through traditional DNS
+    pub fallback: bool,
+    /// TLDs resolved through the Genesis nodes
+    pub genesis_tlds: Vec<String>,
+    /// IPFS gateways for content-hash domains, tried in order; `local`
+    /// stands for the gateway of an IPFS node on this machine
+    pub ipfs_gateways: Vec<String>,
+    /// RPC API of an IPFS node to pin content hashes on, e.g. `http://localhost:5001`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipfs_api_url: Option<String>,
+    /// Pin every content-hash domain visited on that node, for offline use
+    pub auto_pin: bool,
+    /// `socks5://` or `http://` proxy for Genesis node queries, e.g. `socks5://127.0.0.1:9050`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Search URL template; `{query}` is replaced
+    pub search_engine: String,
+    pub theme: Theme,
+    /// How fast tabs open and close; 5.0 takes a fifth of a second
+    pub animation_speed: f32,
+    /// Open and close tabs without animating
+    pub reduce_motion: bool,
+    pub window_width: u32,
+    pub window_height: u32,
+    pub vsync: bool,
+    /// Frame rate cap when vsync is off; 0 for none
+    pub max_fps: u32,
+    pub javascript: bool,
+    pub images: bool,
+    pub webgl: bool,
+    /// Run page content in sandboxed child processes
+    pub multiprocess: bool,
+    /// Larger click targets for tabs, buttons and window controls
+    pub accessibility: bool,
+    /// How much accessibility mode enlarges click targets, 1.0 to 2.0
+    pub hit_target_scale: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            genesis_nodes: vec![DEFAULT_GENESIS_NODE.to_string()],
+            fallback: false,
+            genesis_tlds: genesis_dns::DEFAULT_GENESIS_TLDS.iter().map(|tld| tld.to_string()).collect(),
+            ipfs_gateways: genesis_dns::DEFAULT_IPFS_GATEWAYS.iter().map(|gateway| gateway.to_string()).collect(),
+            ipfs_api_url: None,
+            auto_pin: false,
+            proxy: None,
+            search_engine: DEFAULT_SEARCH_ENGINE.to_string(),
+            theme: Theme::Light,
+            animation_speed: 5.0,
+            reduce_motion: false,
+            window_width: 1200,
+            window_height: 800,
+            vsync: true,
+            max_fps: 60,
+            javascript: true,
+            images: true,
+            webgl: true,
+            multiprocess: false,
+            accessibility: false,
+            hit_target_scale: 1.5,
+        }
+    }
+}
+
+impl Config {
+    /// `~/.config/genesis-browser/config.toml` on Linux
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join(CONFIG_FILE))
+    }
+    
+    /// Parse a config file
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        let config: Self = toml::from_str(&text)
+            .with_context(|| format!("Invalid config {}", path.display()))?;
+        
+        info!("⚙️ Loaded config from {}", path.display());
+        Ok(config)
+    }
+    
+    /// Load the config at the default path, or the defaults if there is none
+    pub fn load_default() -> anyhow::Result<Self> {
+        match Self::default_path().filter(|path| path.exists()) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+    
+    /// Write the default config to `path`, refusing to replace an existing file
+    pub fn init(path: &Path) -> anyhow::Result<()> {
+        if path.exists() {
+            anyhow::bail!("{} already exists", path.display());
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(&Self::default())?)?;
+        Ok(())
+    }
+    
+    /// First Genesis node, used where only one can be given
+    pub fn primary_node(&self) -> &str {
+        self.genesis_nodes.first().map_or(DEFAULT_GENESIS_NODE, String::as_str)
+    }
+    
+    /// A DNS resolver for the configured nodes, TLDs and IPFS gateways,
+    /// querying the nodes through the proxy with the browser's User-Agent
+    pub fn dns_resolver(&self) -> Result<GenesisDnsResolver, DnsError> {
+        let mut resolver = GenesisDnsResolver::new_with_nodes(self.genesis_nodes.clone(), self.fallback);
+        resolver.set_genesis_tlds(self.genesis_tlds.clone());
+        resolver.set_ipfs_gateways(self.ipfs_gateways.clone());
+        resolver.set_user_agent(USER_AGENT)?;
+        if let Some(proxy_url) = &self.proxy {
+            resolver.set_proxy(proxy_url)?;
+        }
+        Ok(resolver)
+    }
+    
+    /// Apply the content and search defaults to a fresh UI state
+    pub fn apply_to(&self, state: &BrowserUIState) {
+        state.javascript_enabled.set(self.javascript);
+        state.images_enabled.set(self.images);
+        state.webgl_enabled.set(self.webgl);
+        state.accessibility_mode.set(self.accessibility);
+        state.hit_target_scale.set(self.hit_target_scale);
+        state.search_engine_template.replace(self.search_engine.clone());
+        state.genesis_tlds.replace(self.genesis_tlds.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_partial_config_keeps_defaults() {
+        let config: Config = toml::from_str(r#"
+genesis_nodes = ["http://node1:3000", "http://node2:3000"]
+theme = "dark"
+images = false
+ipfs_gateways = ["local", "https://ipfs.io/ipfs/"]
+"#).unwrap();
+        
+        assert_eq!(config.primary_node(), "http://node1:3000");
+        assert_eq!(config.theme, Theme::Dark);
+        assert!(!config.images);
+        assert!(config.javascript);
+        assert_eq!(config.search_engine, DEFAULT_SEARCH_ENGINE);
+        assert_eq!(config.ipfs_gateways, ["local", "https://ipfs.io/ipfs/"]);
+        assert_eq!((config.window_width, config.window_height), (1200, 800));
+    }
+    
+    #[test]
+    fn test_dns_resolver_follows_config() {
+        let config = Config {
+            genesis_nodes: vec!["http://node1:3000".to_string()],
+            genesis_tlds: vec![".free".to_string()],
+            proxy: Some("socks5://127.0.0.1:9050".to_string()),
+            ..Config::default()
+        };
+        let resolver = config.dns_resolver().unwrap();
+        assert_eq!(resolver.genesis_node_urls(), ["http://node1:3000".to_string()]);
+        assert_eq!(resolver.genesis_tlds(), [".free".to_string()]);
+        assert_eq!(resolver.user_agent(), Some(USER_AGENT));
+        
+        // A bad proxy is an error rather than a silent direct connection
+        let config = Config { proxy: Some("ftp://127.0.0.1:21".to_string()), ..Config::default() };
+        assert!(matches!(config.dns_resolver(), Err(DnsError::InvalidProxy(_))));
+    }
+    
+    #[test]
+    fn test_init_writes_loadable_defaults() {
+        let path = std::env::temp_dir().join(format!("genesis-config-{}.toml", std::process::id()));
+        Config::init(&path).unwrap();
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+        
+        // An existing config is never overwritten
+        assert!(Config::init(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
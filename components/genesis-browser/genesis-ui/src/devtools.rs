@@ -0,0 +1,128 @@
+// Developer tools data: per-tab console messages and network requests
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Oldest console messages are dropped past this many per tab
+pub const MAX_CONSOLE_MESSAGES: usize = 1000;
+
+/// Oldest network requests are dropped past this many per tab
+pub const MAX_NETWORK_REQUESTS: usize = 500;
+
+/// Source of messages logged by the browser itself rather than the page
+pub const BROWSER_SOURCE: &str = "genesis-browser";
+
+/// Severity of a console message, as in the webview's `ConsoleLevel`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsoleLevel {
+    Log,
+    Info,
+    Warn,
+    Error,
+}
+
+impl ConsoleLevel {
+    pub const ALL: [ConsoleLevel; 4] = [Self::Log, Self::Info, Self::Warn, Self::Error];
+    
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Log => "Log",
+            Self::Info => "Info",
+            Self::Warn => "Warnings",
+            Self::Error => "Errors",
+        }
+    }
+}
+
+/// A console message from a page, mirroring the webview's `ConsoleMessage`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsoleMessage {
+    pub level: ConsoleLevel,
+    pub message: String,
+    pub source: String,
+    pub line: u32,
+}
+
+impl ConsoleMessage {
+    /// A message logged by the browser, e.g. a failed resolution
+    pub fn browser(level: ConsoleLevel, message: impl Into<String>) -> Self {
+        Self { level, message: message.into(), source: BROWSER_SOURCE.to_string(), line: 0 }
+    }
+}
+
+/// A finished request, mirroring the webview's `NetworkRequest`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkRequest {
+    /// HTTP method, or "DNS" for a domain resolution
+    pub method: String,
+    pub url: String,
+    /// `None` for DNS lookups and requests that got no response
+    pub status: Option<u16>,
+    /// Response body size in bytes
+    pub size: Option<u64>,
+    pub duration: Duration,
+    pub request_headers: Vec<(String, String)>,
+    pub response_headers: Vec<(String, String)>,
+    /// Why the request failed, if it did
+    pub error: Option<String>,
+}
+
+impl NetworkRequest {
+    /// Text of the status column
+    pub fn status_label(&self) -> String {
+        match (&self.error, self.status) {
+            (Some(_), _) => "(failed)".to_string(),
+            (None, Some(status)) => status.to_string(),
+            (None, None) if self.method == "DNS" => "resolved".to_string(),
+            (None, None) => "(pending)".to_string(),
+        }
+    }
+    
+    /// Text of the size column, e.g. "1.5 kB"
+    pub fn size_label(&self) -> String {
+        match self.size {
+            None => "—".to_string(),
+            Some(bytes) if bytes < 1000 => format!("{} B", bytes),
+            Some(bytes) if bytes < 1_000_000 => format!("{:.1} kB", bytes as f64 / 1000.0),
+            Some(bytes) => format!("{:.1} MB", bytes as f64 / 1_000_000.0),
+        }
+    }
+}
+
+/// Evaluates JavaScript typed into the console in the active tab, returning
+/// the result or the exception as a string. Set by whatever drives the
+/// webviews, e.g. by blocking on `GenesisWebView::execute_script`.
+pub type ScriptRunner = Box<dyn FnMut(&str) -> Result<String, String>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn request(method: &str, status: Option<u16>, size: Option<u64>) -> NetworkRequest {
+        NetworkRequest {
+            method: method.to_string(),
+            url: "https://a.genesis/".to_string(),
+            status,
+            size,
+            duration: Duration::from_millis(12),
+            request_headers: Vec::new(),
+            response_headers: Vec::new(),
+            error: None,
+        }
+    }
+    
+    #[test]
+    fn test_network_request_labels() {
+        assert_eq!(request("GET", Some(200), Some(512)).status_label(), "200");
+        assert_eq!(request("GET", Some(200), Some(512)).size_label(), "512 B");
+        assert_eq!(request("GET", Some(200), Some(1500)).size_label(), "1.5 kB");
+        assert_eq!(request("GET", None, None).status_label(), "(pending)");
+        assert_eq!(request("GET", None, None).size_label(), "—");
+        assert_eq!(request("DNS", None, None).status_label(), "resolved");
+        
+        let mut failed = request("DNS", None, None);
+        failed.error = Some("no results".to_string());
+        assert_eq!(failed.status_label(), "(failed)");
+    }
+}
@@ -0,0 +1,315 @@
+// Downloads streamed to disk, resumed with HTTP Range requests where the server allows
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use genesis_dns::GenesisDnsResolver;
+use reqwest::StatusCode;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::enhanced_browser::DownloadStatus;
+
+/// A download that receives nothing for this long fails
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Progress is reported at most this often
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Appended to a download's file name until it's complete
+pub const PARTIAL_SUFFIX: &str = ".part";
+
+/// Links with these extensions are saved instead of opened
+pub const DOWNLOAD_EXTENSIONS: [&str; 18] = [
+    "zip", "gz", "tgz", "xz", "7z", "rar", "tar", "exe", "msi", "dmg",
+    "deb", "rpm", "appimage", "iso", "pdf", "mp3", "mp4", "car",
+];
+
+/// Progress of a download, applied to its `Download` record by the UI
+#[derive(Clone, Debug)]
+pub struct DownloadUpdate {
+    pub id: String,
+    pub downloaded: u64,
+    /// Total size, once the server has sent it
+    pub size: Option<u64>,
+    pub status: DownloadStatus,
+}
+
+/// Whether a link points at a file to save rather than a page to show
+pub fn is_download_url(url: &str) -> bool {
+    let Ok(url) = Url::parse(url) else {
+        return false;
+    };
+    let name = url.path_segments().and_then(|segments| segments.last()).unwrap_or("");
+    name.rsplit_once('.').map_or(false, |(stem, extension)| {
+        !stem.is_empty() && DOWNLOAD_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+    })
+}
+
+/// Folder downloads are saved to: the user's download folder, or the
+/// temporary folder on systems without one
+pub fn default_download_dir() -> PathBuf {
+    dirs::download_dir().unwrap_or_else(std::env::temp_dir)
+}
+
+/// Name to save a download under: the URL's last path segment, without
+/// anything that would leave the download folder
+pub fn download_filename(url: &str) -> String {
+    let name = Url::parse(url).ok()
+        .and_then(|url| url.path_segments()?.last().map(|name| urlencoding::decode(name).map_or(name.to_string(), |name| name.into_owned())))
+        .unwrap_or_default();
+    let name: String = name.chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '\0') { '_' } else { c })
+        .collect();
+    let name = name.trim_start_matches('.').trim();
+    if name.is_empty() { "download".to_string() } else { name.to_string() }
+}
+
+/// Where a download is saved: `filename` in `dir`, numbered if a finished
+/// file of that name is already there
+fn destination(dir: &Path, filename: &str) -> PathBuf {
+    let (stem, extension) = match filename.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (filename, None),
+    };
+    (0..)
+        .map(|n| {
+            let stem = if n == 0 { stem.to_string() } else { format!("{} ({})", stem, n) };
+            dir.join(match extension {
+                Some(extension) => format!("{}.{}", stem, extension),
+                None => stem,
+            })
+        })
+        .find(|path| !path.exists())
+        .expect("ran out of file names")
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(PARTIAL_SUFFIX);
+    path.with_file_name(name)
+}
+
+/// The partial file a download of `url` into `dest_dir` writes to, so a
+/// cancelled download can be cleaned up
+pub fn partial_download_path(dest_dir: &Path, url: &str) -> PathBuf {
+    partial_path(&destination(dest_dir, &download_filename(url)))
+}
+
+/// Streams downloads to disk, reporting progress over a channel. Cheap to
+/// clone, so each download can run in its own task.
+#[derive(Clone)]
+pub struct DownloadManager {
+    resolver: Arc<tokio::sync::Mutex<GenesisDnsResolver>>,
+    updates: mpsc::Sender<DownloadUpdate>,
+    /// Called after every update, e.g. to repaint an idle window
+    on_update: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl DownloadManager {
+    pub fn new(resolver: Arc<tokio::sync::Mutex<GenesisDnsResolver>>, updates: mpsc::Sender<DownloadUpdate>) -> Self {
+        Self { resolver, updates, on_update: None }
+    }
+    
+    pub fn with_update_callback(mut self, on_update: Arc<dyn Fn() + Send + Sync>) -> Self {
+        self.on_update = Some(on_update);
+        self
+    }
+    
+    /// Download `url` into `dest_dir` for the `Download` record `id`. A
+    /// partial file left by an earlier attempt is resumed if the server
+    /// supports ranges. Ends with a `Completed` or `Failed` update.
+    pub async fn start_download(&self, id: &str, url: &str, dest_dir: &Path) -> anyhow::Result<PathBuf> {
+        let mut downloaded = 0;
+        let result = self.download(id, url, dest_dir, &mut downloaded).await;
+        let status = match &result {
+            Ok(path) => {
+                info!("✅ Downloaded {} to {}", url, path.display());
+                DownloadStatus::Completed
+            }
+            Err(e) => {
+                warn!("⚠️ Download of {} failed: {:#}", url, e);
+                DownloadStatus::Failed(format!("{:#}", e))
+            }
+        };
+        self.report(DownloadUpdate { id: id.to_string(), downloaded, size: None, status });
+        result
+    }
+    
+    async fn download(&self, id: &str, url: &str, dest_dir: &Path, downloaded: &mut u64) -> anyhow::Result<PathBuf> {
+        let filename = download_filename(url);
+        let mut url = Url::parse(url).with_context(|| format!("Invalid download address: {}", url))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("Can't download {} links", url.scheme());
+        }
+        let host = url.host_str().with_context(|| format!("{} has no host", url))?.to_string();
+        
+        // Genesis hosts are fetched from where they resolve to
+        let mut builder = reqwest::Client::builder();
+        let is_genesis = genesis_dns::has_genesis_tld(&host, self.resolver.lock().await.genesis_tlds());
+        if is_genesis {
+            let result = GenesisDnsResolver::resolve_shared(&self.resolver, &host).await
+                .with_context(|| format!("Failed to resolve {}", host))?;
+            if let (Some(address), Some(port)) = (result.ip_address, url.port_or_known_default()) {
+                builder = builder.resolve(&host, SocketAddr::new(address, port));
+            } else if let Some(content_hash) = &result.content_hash {
+                let resolver = self.resolver.lock().await.detached();
+                let gateway_url = resolver.ipfs_gateway_url(content_hash).await?;
+                url = Url::parse(&format!("{}{}", gateway_url.trim_end_matches('/'), url.path()))?;
+            } else {
+                anyhow::bail!("{} doesn't point anywhere to download from", host);
+            }
+        }
+        let client = builder.build()?;
+        
+        std::fs::create_dir_all(dest_dir)
+            .with_context(|| format!("Failed to create {}", dest_dir.display()))?;
+        let dest = destination(dest_dir, &filename);
+        let partial = partial_path(&dest);
+        let offset = tokio::fs::metadata(&partial).await.map_or(0, |metadata| metadata.len());
+        
+        let mut request = client.get(url.clone());
+        if offset > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+        }
+        let mut response = request.send().await.with_context(|| format!("Failed to fetch {}", url))?;
+        
+        let status = response.status();
+        if status == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+            // The partial file already holds the whole body
+            *downloaded = offset;
+            tokio::fs::rename(&partial, &dest).await?;
+            return Ok(dest);
+        }
+        if !status.is_success() {
+            anyhow::bail!("{} answered {}", url, status);
+        }
+        
+        // Servers that ignore the range send everything again
+        let resumed = offset > 0 && status == StatusCode::PARTIAL_CONTENT;
+        let mut file = if resumed {
+            info!("⬇ Resuming {} at {} bytes", url, offset);
+            tokio::fs::OpenOptions::new().append(true).open(&partial).await?
+        } else {
+            tokio::fs::File::create(&partial).await?
+        };
+        *downloaded = if resumed { offset } else { 0 };
+        let size = response.content_length().map(|length| length + *downloaded);
+        self.report_progress(id, *downloaded, size);
+        
+        let mut last_report = Instant::now();
+        loop {
+            let chunk = tokio::time::timeout(READ_TIMEOUT, response.chunk()).await
+                .map_err(|_| anyhow::anyhow!("Nothing received for {}s", READ_TIMEOUT.as_secs()))?
+                .with_context(|| format!("Failed to read {}", url))?;
+            let Some(chunk) = chunk else {
+                break;
+            };
+            file.write_all(&chunk).await
+                .with_context(|| format!("Failed to write {}", partial.display()))?;
+            *downloaded += chunk.len() as u64;
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                self.report_progress(id, *downloaded, size);
+                last_report = Instant::now();
+            }
+        }
+        file.flush().await?;
+        drop(file);
+        
+        tokio::fs::rename(&partial, &dest).await
+            .with_context(|| format!("Failed to move the download to {}", dest.display()))?;
+        Ok(dest)
+    }
+    
+    fn report_progress(&self, id: &str, downloaded: u64, size: Option<u64>) {
+        let fraction = size.filter(|&size| size > 0).map_or(0.0, |size| downloaded as f32 / size as f32);
+        self.report(DownloadUpdate {
+            id: id.to_string(),
+            downloaded,
+            size,
+            status: DownloadStatus::InProgress(fraction.min(1.0)),
+        });
+    }
+    
+    fn report(&self, update: DownloadUpdate) {
+        let _ = self.updates.send(update);
+        if let Some(on_update) = &self.on_update {
+            on_update();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    
+    #[test]
+    fn test_download_links_and_names() {
+        assert!(is_download_url("https://files.genesis/releases/browser-1.0.tar.gz"));
+        assert!(is_download_url("http://example.com/Manual.PDF?version=2"));
+        assert!(!is_download_url("https://example.com/index.html"));
+        assert!(!is_download_url("https://example.com/.zip"));
+        assert!(!is_download_url("genesis://welcome"));
+        
+        assert_eq!(download_filename("https://x.genesis/files/My%20Report.pdf"), "My Report.pdf");
+        assert_eq!(download_filename("https://x.genesis/a/..%2F..%2Fetc%2Fpasswd"), "_.._etc_passwd");
+        assert_eq!(download_filename("https://x.genesis/"), "download");
+    }
+    
+    #[tokio::test]
+    async fn test_partial_download_is_resumed() {
+        let body: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/files/data.bin", listener.local_addr().unwrap());
+        let served = body.clone();
+        let (range_sender, range_receiver) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = socket.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let request = String::from_utf8_lossy(&request).to_lowercase();
+            let start: usize = request.lines()
+                .find_map(|line| line.strip_prefix("range: bytes="))
+                .and_then(|range| range.trim_end_matches('-').parse().ok())
+                .unwrap_or(0);
+            let _ = range_sender.send(start);
+            let header = format!(
+                "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                served.len() - start, start, served.len() - 1, served.len()
+            );
+            socket.write_all(header.as_bytes()).await.unwrap();
+            socket.write_all(&served[start..]).await.unwrap();
+        });
+        
+        let dir = std::env::temp_dir().join(format!("genesis-downloads-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("data.bin.part"), &body[..40_000]).unwrap();
+        
+        let resolver = GenesisDnsResolver::new("http://127.0.0.1:9".to_string(), false);
+        let (sender, receiver) = mpsc::channel();
+        let manager = DownloadManager::new(Arc::new(tokio::sync::Mutex::new(resolver)), sender);
+        let path = manager.start_download("resume", &url, &dir).await.unwrap();
+        
+        assert_eq!(range_receiver.await.unwrap(), 40_000);
+        assert_eq!(path, dir.join("data.bin"));
+        assert_eq!(std::fs::read(&path).unwrap(), body);
+        assert!(!dir.join("data.bin.part").exists());
+        
+        let updates: Vec<DownloadUpdate> = receiver.try_iter().collect();
+        assert_eq!(updates[0].size, Some(100_000));
+        let last = updates.last().unwrap();
+        assert_eq!(last.downloaded, 100_000);
+        assert!(matches!(last.status, DownloadStatus::Completed));
+        
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -2,26 +2,295 @@
 // This module provides the complete browser interface with tabs, navigation, and Servo WebView
 
 use std::cell::{Cell, RefCell};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 
-use tracing::info;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+pub(crate) use genesis_util::fs::write_atomic;
+
+use crate::devtools::{ConsoleMessage, NetworkRequest, MAX_CONSOLE_MESSAGES, MAX_NETWORK_REQUESTS};
+use crate::feeds::FeedSubscription;
+use crate::internal_pages::LoadError;
+
+/// Number of recently closed tabs kept for "reopen closed tab"
+const MAX_CLOSED_TABS: usize = 25;
+
+/// Entries kept in each direction of a tab's back/forward history
+const MAX_TAB_HISTORY: usize = 50;
+
+/// Bookmark edits that can be undone
+const MAX_BOOKMARK_UNDO: usize = 50;
+
+/// Bookmarks file inside the profile directory
+const BOOKMARKS_FILE: &str = "bookmarks.json";
+
+/// Per-site overrides, keyed by host
+pub const SITE_SETTINGS_FILE: &str = "site-settings.json";
+
+/// Feed subscriptions inside the profile directory
+pub const FEEDS_FILE: &str = "feeds.json";
+
+/// Text of the genesis://notes scratchpad inside the profile directory
+pub const NOTES_FILE: &str = "notes.txt";
+
+/// Browser preferences set from prompts, inside the profile directory
+pub const PREFERENCES_FILE: &str = "preferences.json";
+
+/// Window size and position inside the profile directory
+pub const WINDOW_STATE_FILE: &str = "window-state.json";
+
+/// Session file inside the profile directory
+pub const SESSION_FILE: &str = "session.json";
+
+/// File in the profile directory holding the persisted DNS cache
+pub const DNS_CACHE_FILE: &str = "dns-cache.json";
+
+/// File in the profile directory holding the engine's cookie jar
+pub const COOKIES_FILE: &str = "cookies.json";
+
+/// Profile used when none is selected on the command line
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Directory holding the config file and every profile
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("genesis-browser"))
+}
+
+/// Directory holding all profiles
+pub fn profiles_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("profiles"))
+}
+
+/// Directory holding a profile's bookmarks, session, DNS cache and cookies
+pub fn profile_dir(name: &str) -> Option<PathBuf> {
+    profiles_dir().map(|dir| dir.join(name))
+}
+
+/// Directory holding the default profile
+pub fn default_profile_dir() -> Option<PathBuf> {
+    profile_dir(DEFAULT_PROFILE)
+}
+
+/// Profile names are plain directory names, so they can't escape the profiles directory
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Names of the profiles that exist on disk, sorted
+pub fn list_profiles() -> std::io::Result<Vec<String>> {
+    let Some(dir) = profiles_dir().filter(|dir| dir.exists()) else {
+        return Ok(Vec::new());
+    };
+    
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            names.extend(entry.file_name().to_str().map(str::to_string));
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Maximum number of omnibox suggestions
+pub const MAX_SUGGESTIONS: usize = 6;
+
+/// Search engine used for omnibox input that isn't a URL; `{query}` is replaced
+pub const DEFAULT_SEARCH_ENGINE: &str = "genesis://search?q={query}";
+
+/// Built-in search engines offered in settings
+pub const SEARCH_ENGINES: [(&str, &str); 4] = [
+    ("Genesis", DEFAULT_SEARCH_ENGINE),
+    ("DuckDuckGo", "https://duckduckgo.com/?q={query}"),
+    ("Google", "https://www.google.com/search?q={query}"),
+    ("Bing", "https://www.bing.com/search?q={query}"),
+];
+
+/// Whether omnibox input should be navigated to rather than searched for
+pub fn looks_like_url(input: &str) -> bool {
+    genesis_dns::normalize_url(input).is_ok()
+}
+
+/// Fill a search engine template with a URL-encoded query
+pub fn search_url(template: &str, query: &str) -> String {
+    let encoded: String = url::form_urlencoded::byte_serialize(query.trim().as_bytes()).collect();
+    template.replace("{query}", &encoded)
+}
+
+/// Markdown link to a page, e.g. `[Title](https://x.genesis/)`; pages
+/// without a title are labelled with their URL
+pub fn markdown_link(title: &str, url: &str) -> String {
+    let url = url.replace(' ', "%20").replace('(', "%28").replace(')', "%29");
+    let label = if title.trim().is_empty() { url.as_str() } else { title.trim() };
+    let label = label.replace('\\', "\\\\").replace('[', "\\[").replace(']', "\\]");
+    format!("[{}]({})", label, url)
+}
+
+/// Lowercase host of a web URL, with or without a scheme
+pub fn url_host(url: &str) -> Option<String> {
+    let with_scheme = if url.contains("://") {
+        url.trim().to_string()
+    } else {
+        format!("http://{}", url.trim())
+    };
+    url::Url::parse(&with_scheme).ok()?.host_str().map(str::to_lowercase)
+}
+
+/// Canonical form of a URL for duplicate-tab detection: `x.genesis`,
+/// `http://x.genesis/` and `https://X.genesis` all have the same key
+pub fn dedupe_key(url: &str) -> String {
+    match genesis_dns::normalize_url(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+            let host = parsed.host_str().unwrap_or_default();
+            let port = parsed.port().map(|port| format!(":{}", port)).unwrap_or_default();
+            let path = parsed.path().trim_end_matches('/');
+            let query = parsed.query().map(|query| format!("?{}", query)).unwrap_or_default();
+            format!("{}{}{}{}", host, port, path, query)
+        }
+        Ok(parsed) => parsed.as_str().trim_end_matches('/').to_string(),
+        Err(_) => url.trim().to_lowercase(),
+    }
+}
+
+/// Whether a URL points at a Genesis domain, judged by its TLD
+fn is_genesis_url(url: &str, genesis_tlds: &[String]) -> bool {
+    genesis_dns::normalize_url(url).ok()
+        .and_then(|url| url.host_str().map(|host| genesis_dns::has_genesis_tld(host, genesis_tlds)))
+        .unwrap_or(false)
+}
+
+/// How much accessibility mode may enlarge click targets
+pub const MIN_HIT_TARGET_SCALE: f32 = 1.0;
+pub const MAX_HIT_TARGET_SCALE: f32 = 2.0;
+
+/// Page zoom limits and the step used by Ctrl++ / Ctrl+-
+pub const MIN_ZOOM: f32 = 0.25;
+pub const MAX_ZOOM: f32 = 5.0;
+pub const ZOOM_STEP: f32 = 0.1;
 
 // Tab structure for multi-tab browsing
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BrowserTab {
     pub id: String,
     pub title: String,
     pub url: String,
     pub favicon: Option<String>,
+    #[serde(skip)]
     pub is_loading: bool,
     pub can_go_back: bool,
     pub can_go_forward: bool,
+    /// Pages behind the current one, most recent last
+    #[serde(default)]
+    pub back_history: Vec<String>,
+    /// Pages left by going back, the next one last
+    #[serde(default)]
+    pub forward_history: Vec<String>,
     pub is_genesis_domain: bool,
+    #[serde(skip)]
     pub load_progress: f32,
+    /// Page zoom factor, 1.0 is 100%
+    #[serde(default = "default_zoom")]
+    pub zoom: f32,
+    /// Pinned tabs stay leftmost and render as just their favicon
+    #[serde(default)]
+    pub pinned: bool,
+    /// The page is playing sound
+    #[serde(skip)]
+    pub is_audible: bool,
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Connection security of the loaded page, re-established on restore
+    #[serde(skip)]
+    pub security_state: SecurityState,
+    /// Console output of the page, shown in developer tools
+    #[serde(skip)]
+    pub console: Vec<ConsoleMessage>,
+    /// Requests made by the page, shown in developer tools
+    #[serde(skip)]
+    pub network: Vec<NetworkRequest>,
+    /// Feeds the page advertises, offered for subscription in the omnibox
+    #[serde(skip)]
+    pub feeds: Vec<String>,
+    /// Why the page failed to load, shown instead of its content until the
+    /// next navigation
+    #[serde(skip)]
+    pub error: Option<LoadError>,
+}
+
+/// Connection security shown at the left of the omnibox. Mirrors the
+/// webview's `SecurityStateChanged` states, plus a neutral state for tabs
+/// that haven't loaded anything yet and for internal pages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SecurityState {
+    #[default]
+    Neutral,
+    /// Resolved through the Genesis blockchain
+    Genesis,
+    /// HTTPS
+    Secure,
+    /// Plain HTTP
+    Insecure,
+    /// HTTPS with mixed content
+    Broken,
+}
+
+impl SecurityState {
+    /// State of a page at `url` once loaded; `is_genesis` when it resolved through Genesis DNS
+    pub fn for_url(url: &str, is_genesis: bool) -> Self {
+        if is_genesis {
+            Self::Genesis
+        } else if url.starts_with("genesis://") {
+            Self::Neutral
+        } else if url.starts_with("http://") {
+            Self::Insecure
+        } else {
+            // Web URLs without a scheme are loaded over HTTPS
+            Self::Secure
+        }
+    }
+    
+    /// Headline and explanation for the connection popup
+    pub fn description(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Neutral => ("Genesis page", "This page is part of the browser."),
+            Self::Genesis => ("Verified on Genesis blockchain", "This domain was resolved through the Genesis blockchain, not ICANN DNS."),
+            Self::Secure => ("Connection is secure", "Information you send to this site is encrypted."),
+            Self::Insecure => ("Not secure — HTTP", "Information you send to this site could be read or changed by others."),
+            Self::Broken => ("Not fully secure", "Parts of this page are loaded over an unencrypted connection."),
+        }
+    }
+}
+
+fn default_zoom() -> f32 {
+    1.0
+}
+
+/// Preferences kept in the profile rather than config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Preferences {
+    confirm_close_tabs: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self { confirm_close_tabs: true }
+    }
+}
+
+/// Open tabs as written to the session file
+#[derive(Serialize, Deserialize)]
+struct Session {
+    tabs: Vec<BrowserTab>,
+    active_tab_index: usize,
 }
 
 impl BrowserTab {
-    pub fn new(url: &str) -> Self {
+    /// A tab showing `url`; `genesis_tlds` decide whether it's a Genesis domain
+    pub fn new(url: &str, genesis_tlds: &[String]) -> Self {
         let id = uuid::Uuid::new_v4().to_string();
         Self {
             id,
@@ -31,28 +300,77 @@ impl BrowserTab {
             is_loading: false,
             can_go_back: false,
             can_go_forward: false,
-            is_genesis_domain: Self::check_genesis_domain(url),
+            back_history: Vec::new(),
+            forward_history: Vec::new(),
+            is_genesis_domain: Self::check_genesis_domain(url, genesis_tlds),
             load_progress: 0.0,
+            zoom: 1.0,
+            pinned: false,
+            is_audible: false,
+            is_muted: false,
+            security_state: SecurityState::Neutral,
+            console: Vec::new(),
+            network: Vec::new(),
+            feeds: Vec::new(),
+            error: None,
         }
     }
     
-    fn check_genesis_domain(url: &str) -> bool {
-        let genesis_tlds = [".genesis", ".free", ".web", ".defi", ".dao", "genesis://"];
-        genesis_tlds.iter().any(|tld| url.contains(tld))
+    fn update_navigation_state(&mut self) {
+        self.can_go_back = !self.back_history.is_empty();
+        self.can_go_forward = !self.forward_history.is_empty();
+    }
+    
+    fn check_genesis_domain(url: &str, genesis_tlds: &[String]) -> bool {
+        url.starts_with("genesis://") || is_genesis_url(url, genesis_tlds)
+    }
+}
+
+/// Settings a site overrides; `None` falls back to the global setting
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SiteSettings {
+    pub javascript: Option<bool>,
+    pub images: Option<bool>,
+    pub cookies: Option<bool>,
+    pub zoom: Option<f32>,
+}
+
+impl SiteSettings {
+    /// Whether every setting follows the globals
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
     }
 }
 
+/// Settings in effect for a page: its site's overrides over the globals
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentSettings {
+    pub javascript: bool,
+    pub images: bool,
+    pub cookies: bool,
+    /// `None` keeps the tab's current zoom
+    pub zoom: Option<f32>,
+}
+
 /// Browser UI State Management
 pub struct BrowserUIState {
     // Tab management
     pub tabs: RefCell<Vec<BrowserTab>>,
     pub active_tab_index: Cell<usize>,
+    pub closed_tabs: RefCell<Vec<BrowserTab>>,
     
     // Navigation
     pub url_input: RefCell<String>,
     pub search_input: RefCell<String>,
     pub navigation_history: RefCell<VecDeque<String>>,
     
+    // Find in page
+    pub find_query: RefCell<String>,
+    pub find_active: Cell<bool>,
+    pub find_match_count: Cell<usize>,
+    pub find_current_match: Cell<usize>,
+    
     // UI panels visibility
     pub show_dev_tools: Cell<bool>,
     pub show_downloads: Cell<bool>,
@@ -67,19 +385,56 @@ pub struct BrowserUIState {
     pub images_enabled: Cell<bool>,
     pub webgl_enabled: Cell<bool>,
     pub adblock_enabled: Cell<bool>,
+    /// Overrides of the settings above, by host
+    pub site_settings: RefCell<HashMap<String, SiteSettings>>,
+    /// Site settings changed since the last flush
+    pub site_settings_dirty: Cell<bool>,
+    /// Search URL template for non-URL omnibox input
+    pub search_engine_template: RefCell<String>,
+    /// TLDs marked as Genesis domains in tabs and suggestions
+    pub genesis_tlds: RefCell<Vec<String>>,
+    /// Switch to an open tab instead of loading the same URL twice
+    pub dedupe_tabs: Cell<bool>,
+    /// Larger click targets for tabs, buttons and window controls
+    pub accessibility_mode: Cell<bool>,
+    /// How much accessibility mode enlarges click targets
+    pub hit_target_scale: Cell<f32>,
+    /// Ask before closing a window with several tabs
+    pub confirm_close_tabs: Cell<bool>,
+    /// Preferences changed since the last flush
+    pub preferences_dirty: Cell<bool>,
     
     // Genesis features
     pub genesis_connected: Cell<bool>,
     pub genesis_node_status: RefCell<String>,
     
+    /// Profile the state is persisted to; `None` for an ephemeral profile that
+    /// never touches the disk
+    pub profile_dir: Option<PathBuf>,
+    
     // Collections
     pub bookmarks: RefCell<Vec<Bookmark>>,
+    /// Where bookmarks are flushed to, if persistence is enabled
+    pub bookmarks_path: RefCell<Option<PathBuf>>,
+    /// Bookmarks changed since the last flush
+    pub bookmarks_dirty: Cell<bool>,
+    /// Operations that revert the latest bookmark edits, newest last
+    pub bookmark_undo: RefCell<Vec<BookmarkOp>>,
+    /// Operations that redo undone bookmark edits, newest last
+    pub bookmark_redo: RefCell<Vec<BookmarkOp>>,
     pub downloads: RefCell<Vec<Download>>,
     pub history: RefCell<Vec<HistoryEntry>>,
+    pub feeds: RefCell<Vec<FeedSubscription>>,
+    /// Feed subscriptions changed since the last flush
+    pub feeds_dirty: Cell<bool>,
+    /// Text of the genesis://notes scratchpad
+    pub notes: RefCell<String>,
+    /// Notes edited since the last flush
+    pub notes_dirty: Cell<bool>,
     pub passwords: RefCell<Vec<SavedPassword>>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Bookmark {
     pub id: String,
     pub title: String,
@@ -88,6 +443,32 @@ pub struct Bookmark {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A bookmark edit, recorded as the inverse of what the user did
+#[derive(Clone, Debug)]
+pub enum BookmarkOp {
+    /// Put a bookmark back at its position, keeping its id
+    Add(usize, Bookmark),
+    /// Take out the bookmark with this id
+    Remove(Bookmark),
+}
+
+impl BookmarkOp {
+    /// Apply the edit, returning the one that reverts it; `None` if the
+    /// bookmark is already gone
+    fn apply(self, bookmarks: &mut Vec<Bookmark>) -> Option<BookmarkOp> {
+        match self {
+            Self::Add(index, bookmark) => {
+                bookmarks.insert(index.min(bookmarks.len()), bookmark.clone());
+                Some(Self::Remove(bookmark))
+            }
+            Self::Remove(bookmark) => {
+                let index = bookmarks.iter().position(|b| b.id == bookmark.id)?;
+                Some(Self::Add(index, bookmarks.remove(index)))
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Download {
     pub id: String,
@@ -102,6 +483,7 @@ pub struct Download {
 pub enum DownloadStatus {
     Pending,
     InProgress(f32),
+    Paused,
     Completed,
     Failed(String),
     Cancelled,
@@ -115,6 +497,16 @@ pub struct HistoryEntry {
     pub last_visit: chrono::DateTime<chrono::Utc>,
 }
 
+/// Omnibox autocomplete entry drawn from history or bookmarks
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub title: String,
+    pub url: String,
+    pub visit_count: u32,
+    pub is_bookmark: bool,
+    pub is_genesis_domain: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct SavedPassword {
     pub domain: String,
@@ -122,17 +514,33 @@ pub struct SavedPassword {
     pub encrypted_password: Vec<u8>,
 }
 
+/// An ephemeral profile: nothing is read from or written to disk. The
+/// browser loads the user's profile with `with_profile(default_profile_dir())`.
 impl Default for BrowserUIState {
     fn default() -> Self {
+        Self::with_profile(None)
+    }
+}
+
+impl BrowserUIState {
+    /// Create the state for a profile directory, or an ephemeral profile for `None`
+    pub fn with_profile(profile_dir: Option<PathBuf>) -> Self {
         // Create default tabs
-        let welcome_tab = BrowserTab::new("genesis://welcome");
+        let genesis_tlds: Vec<String> = genesis_dns::DEFAULT_GENESIS_TLDS.iter().map(|tld| tld.to_string()).collect();
+        let welcome_tab = BrowserTab::new("genesis://welcome", &genesis_tlds);
+        let bookmarks_path = profile_dir.as_ref().map(|dir| dir.join(BOOKMARKS_FILE));
         
-        Self {
+        let state = Self {
             tabs: RefCell::new(vec![welcome_tab]),
             active_tab_index: Cell::new(0),
+            closed_tabs: RefCell::new(Vec::new()),
             url_input: RefCell::new("genesis://welcome".to_string()),
             search_input: RefCell::new(String::new()),
             navigation_history: RefCell::new(VecDeque::with_capacity(100)),
+            find_query: RefCell::new(String::new()),
+            find_active: Cell::new(false),
+            find_match_count: Cell::new(0),
+            find_current_match: Cell::new(0),
             show_dev_tools: Cell::new(false),
             show_downloads: Cell::new(false),
             show_bookmarks: Cell::new(false),
@@ -144,17 +552,76 @@ impl Default for BrowserUIState {
             images_enabled: Cell::new(true),
             webgl_enabled: Cell::new(true),
             adblock_enabled: Cell::new(false),
+            site_settings: RefCell::new(HashMap::new()),
+            site_settings_dirty: Cell::new(false),
+            search_engine_template: RefCell::new(DEFAULT_SEARCH_ENGINE.to_string()),
+            genesis_tlds: RefCell::new(genesis_tlds),
+            dedupe_tabs: Cell::new(false),
+            accessibility_mode: Cell::new(false),
+            hit_target_scale: Cell::new(1.5),
+            confirm_close_tabs: Cell::new(true),
+            preferences_dirty: Cell::new(false),
             genesis_connected: Cell::new(false),
             genesis_node_status: RefCell::new("Connecting...".to_string()),
-            bookmarks: RefCell::new(Self::default_bookmarks()),
+            profile_dir,
+            bookmarks: RefCell::new(Vec::new()),
+            bookmarks_path: RefCell::new(bookmarks_path.clone()),
+            bookmarks_dirty: Cell::new(false),
+            bookmark_undo: RefCell::new(Vec::new()),
+            bookmark_redo: RefCell::new(Vec::new()),
             downloads: RefCell::new(Vec::new()),
             history: RefCell::new(Vec::new()),
+            feeds: RefCell::new(Vec::new()),
+            feeds_dirty: Cell::new(false),
+            notes: RefCell::new(String::new()),
+            notes_dirty: Cell::new(false),
             passwords: RefCell::new(Vec::new()),
+        };
+        
+        // Load saved bookmarks, seeding the defaults on first start
+        match bookmarks_path.filter(|path| path.exists()) {
+            Some(path) => {
+                if let Err(e) = state.load_bookmarks(&path) {
+                    warn!("⚠️ Failed to load bookmarks from {}: {}", path.display(), e);
+                    *state.bookmarks.borrow_mut() = Self::default_bookmarks();
+                }
+            }
+            None => *state.bookmarks.borrow_mut() = Self::default_bookmarks(),
+        }
+        
+        if let Some(path) = state.profile_file(SITE_SETTINGS_FILE).filter(|path| path.exists()) {
+            if let Err(e) = state.load_site_settings(&path) {
+                warn!("⚠️ Failed to load site settings from {}: {}", path.display(), e);
+            }
+        }
+        if let Some(path) = state.profile_file(FEEDS_FILE).filter(|path| path.exists()) {
+            if let Err(e) = state.load_feeds(&path) {
+                warn!("⚠️ Failed to load feeds from {}: {}", path.display(), e);
+            }
+        }
+        if let Some(path) = state.profile_file(NOTES_FILE).filter(|path| path.exists()) {
+            if let Err(e) = state.load_notes(&path) {
+                warn!("⚠️ Failed to load notes from {}: {}", path.display(), e);
+            }
+        }
+        if let Some(path) = state.profile_file(PREFERENCES_FILE).filter(|path| path.exists()) {
+            if let Err(e) = state.load_preferences(&path) {
+                warn!("⚠️ Failed to load preferences from {}: {}", path.display(), e);
+            }
         }
+        
+        state
     }
-}
-
-impl BrowserUIState {
+    
+    /// Where `file` lives in the profile, or `None` if nothing may be written:
+    /// the profile is ephemeral or private mode is on
+    pub fn profile_file(&self, file: &str) -> Option<PathBuf> {
+        if self.private_mode.get() {
+            return None;
+        }
+        self.profile_dir.as_ref().map(|dir| dir.join(file))
+    }
+    
     fn default_bookmarks() -> Vec<Bookmark> {
         vec![
             Bookmark {
@@ -190,7 +657,7 @@ impl BrowserUIState {
     
     /// Create a new tab
     pub fn create_tab(&self, url: &str) -> String {
-        let new_tab = BrowserTab::new(url);
+        let new_tab = BrowserTab::new(url, &self.genesis_tlds.borrow());
         let tab_id = new_tab.id.clone();
         
         self.tabs.borrow_mut().push(new_tab);
@@ -206,7 +673,8 @@ impl BrowserUIState {
         let mut tabs = self.tabs.borrow_mut();
         
         if tabs.len() > 1 && index < tabs.len() {
-            tabs.remove(index);
+            let closed = tabs.remove(index);
+            self.remember_closed(vec![closed]);
             
             // Adjust active tab if needed
             let current = self.active_tab_index.get();
@@ -223,6 +691,137 @@ impl BrowserUIState {
         }
     }
     
+    /// Save the open tabs and the active tab to a JSON file
+    pub fn save_session(&self, path: &Path) -> anyhow::Result<()> {
+        let session = Session {
+            tabs: self.tabs.borrow().clone(),
+            active_tab_index: self.active_tab_index.get(),
+        };
+        write_atomic(path, &serde_json::to_vec_pretty(&session)?)?;
+        info!("💾 Saved session with {} tabs", session.tabs.len());
+        Ok(())
+    }
+    
+    /// Replace the open tabs with a saved session
+    pub fn load_session(&self, path: &Path) -> anyhow::Result<()> {
+        let data = std::fs::read(path)?;
+        let session: Session = serde_json::from_slice(&data)?;
+        if session.tabs.is_empty() {
+            anyhow::bail!("session {} has no tabs", path.display());
+        }
+        
+        let active_index = session.active_tab_index.min(session.tabs.len() - 1);
+        info!("💾 Restored session with {} tabs", session.tabs.len());
+        *self.tabs.borrow_mut() = session.tabs;
+        self.switch_to_tab(active_index);
+        self.keep_pinned_first();
+        Ok(())
+    }
+    
+    /// Close every tab except the one at `index`
+    pub fn close_other_tabs(&self, index: usize) {
+        let mut tabs = self.tabs.borrow_mut();
+        if index >= tabs.len() {
+            return;
+        }
+        
+        let kept = tabs.remove(index);
+        let closed: Vec<BrowserTab> = tabs.drain(..).collect();
+        tabs.push(kept);
+        drop(tabs);
+        
+        self.active_tab_index.set(0);
+        info!("📑 Closed {} other tabs", closed.len());
+        self.remember_closed(closed);
+    }
+    
+    /// Close every tab after the one at `index`
+    pub fn close_tabs_to_right(&self, index: usize) {
+        let mut tabs = self.tabs.borrow_mut();
+        if index + 1 >= tabs.len() {
+            return;
+        }
+        
+        let closed: Vec<BrowserTab> = tabs.drain(index + 1..).collect();
+        drop(tabs);
+        
+        if self.active_tab_index.get() > index {
+            self.active_tab_index.set(index);
+        }
+        info!("📑 Closed {} tabs to the right of {}", closed.len(), index);
+        self.remember_closed(closed);
+    }
+    
+    /// Keep closed tabs for "reopen closed tab", dropping the oldest beyond the cap
+    fn remember_closed(&self, tabs: Vec<BrowserTab>) {
+        let mut closed_tabs = self.closed_tabs.borrow_mut();
+        closed_tabs.extend(tabs);
+        let overflow = closed_tabs.len().saturating_sub(MAX_CLOSED_TABS);
+        closed_tabs.drain(..overflow);
+    }
+    
+    /// Reopen the most recently closed tab and make it active.
+    /// Returns the id of the restored tab, or `None` if nothing was closed.
+    pub fn reopen_last_closed(&self) -> Option<String> {
+        let mut tab = self.closed_tabs.borrow_mut().pop()?;
+        tab.is_loading = false;
+        let tab_id = tab.id.clone();
+        
+        self.tabs.borrow_mut().push(tab);
+        let new_index = self.tabs.borrow().len() - 1;
+        self.switch_to_tab(new_index);
+        self.keep_pinned_first();
+        
+        info!("📑 Reopened closed tab: {}", tab_id);
+        Some(tab_id)
+    }
+    
+    /// Pin or unpin a tab; pinned tabs are moved to the front
+    pub fn toggle_pin(&self, index: usize) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.pinned = !tab.pinned;
+            info!("📌 Tab {} pinned: {}", index, tab.pinned);
+        }
+        self.keep_pinned_first();
+    }
+    
+    /// Move pinned tabs in front of the others, keeping the active tab selected
+    fn keep_pinned_first(&self) {
+        let mut tabs = self.tabs.borrow_mut();
+        let active_id = tabs.get(self.active_tab_index.get()).map(|tab| tab.id.clone());
+        
+        // Stable sort keeps the relative order within both groups
+        tabs.sort_by_key(|tab| !tab.pinned);
+        
+        if let Some(position) = active_id.and_then(|id| tabs.iter().position(|tab| tab.id == id)) {
+            self.active_tab_index.set(position);
+        }
+    }
+    
+    /// Index of the first tab showing `url`, compared in normalized form
+    pub fn find_tab_by_url(&self, url: &str) -> Option<usize> {
+        let wanted = dedupe_key(url);
+        self.tabs.borrow().iter().position(|tab| dedupe_key(&tab.url) == wanted)
+    }
+    
+    /// Move a tab to a new position, keeping the active tab selected.
+    /// Tabs can't be moved across the pinned/unpinned boundary.
+    pub fn reorder_tab(&self, from: usize, to: usize) -> bool {
+        let mut tabs = self.tabs.borrow_mut();
+        if from == to || from >= tabs.len() || to >= tabs.len() || tabs[from].pinned != tabs[to].pinned {
+            return false;
+        }
+        
+        let active_id = tabs.get(self.active_tab_index.get()).map(|tab| tab.id.clone());
+        let tab = tabs.remove(from);
+        tabs.insert(to, tab);
+        
+        if let Some(position) = active_id.and_then(|id| tabs.iter().position(|tab| tab.id == id)) {
+            self.active_tab_index.set(position);
+        }
+        true
+    }
+    
     /// Switch to a specific tab
     pub fn switch_to_tab(&self, index: usize) {
         let tabs = self.tabs.borrow();
@@ -242,8 +841,9 @@ impl BrowserUIState {
         tabs.get(index).cloned()
     }
     
-    /// Update tab info
-    pub fn update_tab(&self, index: usize, title: Option<String>, url: Option<String>, is_loading: Option<bool>) {
+    /// Update tab info. Starting a load resets the progress, finishing one
+    /// completes it; in between `progress` only ever moves the bar forward.
+    pub fn update_tab(&self, index: usize, title: Option<String>, url: Option<String>, is_loading: Option<bool>, progress: Option<f32>) {
         let mut tabs = self.tabs.borrow_mut();
         if let Some(tab) = tabs.get_mut(index) {
             if let Some(title) = title {
@@ -251,17 +851,179 @@ impl BrowserUIState {
             }
             if let Some(url) = url {
                 tab.url = url.clone();
-                tab.is_genesis_domain = BrowserTab::check_genesis_domain(&url);
+                tab.is_genesis_domain = BrowserTab::check_genesis_domain(&url, &self.genesis_tlds.borrow());
             }
             if let Some(loading) = is_loading {
                 tab.is_loading = loading;
-                if !loading {
-                    tab.load_progress = 1.0;
-                }
+                tab.load_progress = if loading { 0.0 } else { 1.0 };
+            }
+            if let Some(progress) = progress.filter(|_| tab.is_loading) {
+                tab.load_progress = progress.clamp(tab.load_progress, 1.0);
+            }
+        }
+    }
+    
+    /// Mark every web tab loading for a reload of all tabs, returning the
+    /// `(index, url)` of each. The browser's own pages aren't reloaded.
+    pub fn begin_reload_all(&self) -> Vec<(usize, String)> {
+        let targets: Vec<(usize, String)> = self.tabs.borrow().iter().enumerate()
+            .filter(|(_, tab)| !tab.url.starts_with("genesis://"))
+            .map(|(index, tab)| (index, tab.url.clone()))
+            .collect();
+        
+        for (index, url) in &targets {
+            self.update_tab(*index, None, Some(url.clone()), Some(true), None);
+            self.set_tab_error(*index, None);
+        }
+        targets
+    }
+    
+    /// Change the active tab's zoom by `delta`, clamped to the supported range
+    pub fn zoom_active_tab(&self, delta: f32) {
+        let index = self.active_tab_index.get();
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            // Round to whole steps so repeated zooming doesn't drift
+            let zoom = ((tab.zoom + delta) / ZOOM_STEP).round() * ZOOM_STEP;
+            tab.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+            info!("🔍 Zoom for tab {}: {:.0}%", index, tab.zoom * 100.0);
+        }
+    }
+    
+    /// Reset the active tab's zoom to 100%
+    pub fn reset_active_zoom(&self) {
+        let index = self.active_tab_index.get();
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.zoom = 1.0;
+        }
+    }
+    
+    /// Record whether a tab's page was resolved through Genesis DNS
+    pub fn set_tab_genesis_domain(&self, index: usize, is_genesis_domain: bool) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.is_genesis_domain = is_genesis_domain;
+        }
+    }
+    
+    /// Record whether a tab is playing sound, e.g. from `WebViewEvent::AudibleChanged`
+    pub fn set_tab_audible(&self, index: usize, is_audible: bool) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.is_audible = is_audible;
+        }
+    }
+    
+    /// Mute or unmute one tab
+    pub fn toggle_tab_muted(&self, index: usize) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.is_muted = !tab.is_muted;
+        }
+    }
+    
+    /// Mute or unmute every tab on the same host as the tab at `index`
+    pub fn set_site_muted(&self, index: usize, is_muted: bool) {
+        let mut tabs = self.tabs.borrow_mut();
+        let Some(host) = tabs.get(index).and_then(|tab| url_host(&tab.url)) else {
+            return;
+        };
+        for tab in tabs.iter_mut().filter(|tab| url_host(&tab.url).as_deref() == Some(host.as_str())) {
+            tab.is_muted = is_muted;
+        }
+    }
+    
+    /// Append a message to a tab's console, e.g. from `WebViewEvent::ConsoleMessage`
+    pub fn push_console_message(&self, index: usize, message: ConsoleMessage) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            if tab.console.len() >= MAX_CONSOLE_MESSAGES {
+                tab.console.remove(0);
+            }
+            tab.console.push(message);
+        }
+    }
+    
+    /// Empty a tab's console
+    pub fn clear_console(&self, index: usize) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.console.clear();
+        }
+    }
+    
+    /// Append a finished request to a tab's network log, e.g. from `WebViewEvent::NetworkRequest`
+    pub fn push_network_request(&self, index: usize, request: NetworkRequest) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            if tab.network.len() >= MAX_NETWORK_REQUESTS {
+                tab.network.remove(0);
             }
+            tab.network.push(request);
         }
     }
     
+    /// Empty a tab's network log
+    pub fn clear_network_log(&self, index: usize) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.network.clear();
+        }
+    }
+    
+    /// Set the connection security shown in a tab's omnibox
+    pub fn set_tab_security_state(&self, index: usize, security_state: SecurityState) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.security_state = security_state;
+        }
+    }
+    
+    /// Autocomplete suggestions for the omnibox: history and bookmarks whose
+    /// title or URL contains `query`, most visited first
+    pub fn suggest(&self, query: &str) -> Vec<Suggestion> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let matches = |title: &str, url: &str| {
+            title.to_lowercase().contains(&query) || url.to_lowercase().contains(&query)
+        };
+        let bookmarks = self.bookmarks.borrow();
+        let genesis_tlds = self.genesis_tlds.borrow();
+        
+        let mut suggestions: Vec<Suggestion> = self.history.borrow().iter()
+            .filter(|entry| matches(&entry.title, &entry.url))
+            .map(|entry| Suggestion {
+                title: entry.title.clone(),
+                url: entry.url.clone(),
+                visit_count: entry.visit_count,
+                is_bookmark: bookmarks.iter().any(|b| b.url == entry.url),
+                is_genesis_domain: is_genesis_url(&entry.url, &genesis_tlds),
+            })
+            .collect();
+        
+        // Bookmarks that were never visited rank after everything in history
+        for bookmark in bookmarks.iter().filter(|b| matches(&b.title, &b.url)) {
+            if !suggestions.iter().any(|s| s.url == bookmark.url) {
+                suggestions.push(Suggestion {
+                    title: bookmark.title.clone(),
+                    url: bookmark.url.clone(),
+                    visit_count: 0,
+                    is_bookmark: true,
+                    is_genesis_domain: is_genesis_url(&bookmark.url, &genesis_tlds),
+                });
+            }
+        }
+        
+        // Stable sort, so ties keep their history order
+        suggestions.sort_by(|a, b| b.visit_count.cmp(&a.visit_count));
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+    
+    /// Most visited pages for the new-tab page, skipping internal `genesis://` pages
+    pub fn top_sites(&self, limit: usize) -> Vec<HistoryEntry> {
+        let mut sites: Vec<HistoryEntry> = self.history.borrow().iter()
+            .filter(|entry| !entry.url.starts_with("genesis://"))
+            .cloned()
+            .collect();
+        sites.sort_by(|a, b| b.visit_count.cmp(&a.visit_count));
+        sites.truncate(limit);
+        sites
+    }
+    
     /// Add bookmark
     pub fn add_bookmark(&self, title: String, url: String, folder: Option<String>) {
         let bookmark = Bookmark {
@@ -272,18 +1034,366 @@ impl BrowserUIState {
             created_at: chrono::Utc::now(),
         };
         
-        self.bookmarks.borrow_mut().push(bookmark);
+        self.bookmarks.borrow_mut().push(bookmark.clone());
+        self.record_bookmark_op(BookmarkOp::Remove(bookmark));
+        self.bookmarks_dirty.set(true);
         info!("⭐ Added bookmark: {}", url);
     }
     
     /// Remove bookmark
     pub fn remove_bookmark(&self, id: &str) {
-        self.bookmarks.borrow_mut().retain(|b| b.id != id);
+        let mut bookmarks = self.bookmarks.borrow_mut();
+        if let Some(index) = bookmarks.iter().position(|b| b.id == id) {
+            let bookmark = bookmarks.remove(index);
+            drop(bookmarks);
+            self.record_bookmark_op(BookmarkOp::Add(index, bookmark));
+        }
+        self.bookmarks_dirty.set(true);
         info!("⭐ Removed bookmark: {}", id);
     }
     
+    /// Remember how to revert an edit the user just made. A new edit drops
+    /// whatever was undone before it.
+    fn record_bookmark_op(&self, undo: BookmarkOp) {
+        let mut stack = self.bookmark_undo.borrow_mut();
+        stack.push(undo);
+        let overflow = stack.len().saturating_sub(MAX_BOOKMARK_UNDO);
+        stack.drain(..overflow);
+        self.bookmark_redo.borrow_mut().clear();
+    }
+    
+    /// Revert the latest bookmark edit. Returns whether there was one.
+    pub fn undo_bookmark_op(&self) -> bool {
+        self.replay_bookmark_op(&self.bookmark_undo, &self.bookmark_redo, "Undid")
+    }
+    
+    /// Redo the latest undone bookmark edit. Returns whether there was one.
+    pub fn redo_bookmark_op(&self) -> bool {
+        self.replay_bookmark_op(&self.bookmark_redo, &self.bookmark_undo, "Redid")
+    }
+    
+    /// Apply the newest op of `from`, pushing its inverse onto `to`
+    fn replay_bookmark_op(&self, from: &RefCell<Vec<BookmarkOp>>, to: &RefCell<Vec<BookmarkOp>>, verb: &str) -> bool {
+        loop {
+            let Some(op) = from.borrow_mut().pop() else {
+                return false;
+            };
+            // Ops whose bookmark went away by other means are skipped
+            if let Some(inverse) = op.apply(&mut self.bookmarks.borrow_mut()) {
+                info!("⭐ {} a bookmark edit", verb);
+                to.borrow_mut().push(inverse);
+                self.bookmarks_dirty.set(true);
+                return true;
+            }
+        }
+    }
+    
+    /// Save bookmarks to a JSON file
+    pub fn save_bookmarks(&self, path: &Path) -> anyhow::Result<()> {
+        let data = serde_json::to_vec_pretty(&*self.bookmarks.borrow())?;
+        write_atomic(path, &data)?;
+        info!("⭐ Saved {} bookmarks to {}", self.bookmarks.borrow().len(), path.display());
+        Ok(())
+    }
+    
+    /// Replace the bookmarks with the ones saved in a JSON file
+    pub fn load_bookmarks(&self, path: &Path) -> anyhow::Result<()> {
+        let data = std::fs::read(path)?;
+        let bookmarks: Vec<Bookmark> = serde_json::from_slice(&data)?;
+        info!("⭐ Loaded {} bookmarks from {}", bookmarks.len(), path.display());
+        *self.bookmarks.borrow_mut() = bookmarks;
+        self.bookmark_undo.borrow_mut().clear();
+        self.bookmark_redo.borrow_mut().clear();
+        self.bookmarks_dirty.set(false);
+        Ok(())
+    }
+    
+    /// Add the bookmarks from a Netscape bookmark file (Chrome, Firefox and
+    /// Safari exports), skipping malformed entries and URLs already bookmarked.
+    /// Returns how many bookmarks were added.
+    pub fn import_bookmarks_html(&self, path: &Path) -> anyhow::Result<usize> {
+        let html = std::fs::read_to_string(path)?;
+        let parsed = crate::bookmarks_html::parse(&html);
+        
+        let mut bookmarks = self.bookmarks.borrow_mut();
+        let before = bookmarks.len();
+        let mut duplicates = 0;
+        for bookmark in parsed.bookmarks {
+            if bookmarks.iter().any(|existing| existing.url == bookmark.url) {
+                duplicates += 1;
+            } else {
+                bookmarks.push(bookmark);
+            }
+        }
+        let imported = bookmarks.len() - before;
+        
+        info!(
+            "⭐ Imported {} bookmarks from {} ({} skipped as malformed, {} already bookmarked)",
+            imported, path.display(), parsed.skipped, duplicates
+        );
+        if imported > 0 {
+            self.bookmarks_dirty.set(true);
+        }
+        Ok(imported)
+    }
+    
+    /// Write the bookmarks as a Netscape bookmark file other browsers can import
+    pub fn export_bookmarks_html(&self, path: &Path) -> anyhow::Result<()> {
+        let html = crate::bookmarks_html::render(&self.bookmarks.borrow());
+        write_atomic(path, html.as_bytes())?;
+        info!("⭐ Exported {} bookmarks to {}", self.bookmarks.borrow().len(), path.display());
+        Ok(())
+    }
+    
+    /// Write bookmarks to the profile if they changed since the last flush.
+    /// The UI calls this after mutations instead of saving on every change.
+    pub fn flush_bookmarks(&self) -> anyhow::Result<()> {
+        // Nothing is written in private mode, its changes go away with the window
+        if self.private_mode.get() {
+            return Ok(());
+        }
+        // Clear the flag first so a failing disk is reported once per change, not every frame
+        if !self.bookmarks_dirty.replace(false) {
+            return Ok(());
+        }
+        match self.bookmarks_path.borrow().as_deref() {
+            Some(path) => self.save_bookmarks(path),
+            None => Ok(()),
+        }
+    }
+    
+    /// Overrides saved for a host, all defaults if there are none
+    pub fn site_settings_for_host(&self, host: &str) -> SiteSettings {
+        self.site_settings.borrow().get(&host.to_lowercase()).cloned().unwrap_or_default()
+    }
+    
+    /// Replace a host's overrides; all-default settings remove the entry
+    pub fn set_site_settings(&self, host: &str, settings: SiteSettings) {
+        let host = host.to_lowercase();
+        let mut site_settings = self.site_settings.borrow_mut();
+        if settings.is_default() {
+            site_settings.remove(&host);
+        } else {
+            site_settings.insert(host, settings);
+        }
+        self.site_settings_dirty.set(true);
+    }
+    
+    /// Settings for a page at `url`, its site's overrides falling back to the globals
+    pub fn content_settings_for(&self, url: &str) -> ContentSettings {
+        let site = url_host(url).map(|host| self.site_settings_for_host(&host)).unwrap_or_default();
+        ContentSettings {
+            javascript: site.javascript.unwrap_or(self.javascript_enabled.get()),
+            images: site.images.unwrap_or(self.images_enabled.get()),
+            cookies: site.cookies.unwrap_or(true),
+            zoom: site.zoom.map(|zoom| zoom.clamp(MIN_ZOOM, MAX_ZOOM)),
+        }
+    }
+    
+    /// Replace the site settings with the ones saved in a JSON file
+    pub fn load_site_settings(&self, path: &Path) -> anyhow::Result<()> {
+        let data = std::fs::read(path)?;
+        let site_settings: HashMap<String, SiteSettings> = serde_json::from_slice(&data)?;
+        info!("🛡️ Loaded settings for {} sites from {}", site_settings.len(), path.display());
+        *self.site_settings.borrow_mut() = site_settings;
+        self.site_settings_dirty.set(false);
+        Ok(())
+    }
+    
+    /// Write site settings to the profile if they changed since the last flush
+    pub fn flush_site_settings(&self) -> anyhow::Result<()> {
+        if self.private_mode.get() || !self.site_settings_dirty.replace(false) {
+            return Ok(());
+        }
+        let Some(path) = self.profile_file(SITE_SETTINGS_FILE) else {
+            return Ok(());
+        };
+        let data = serde_json::to_vec_pretty(&*self.site_settings.borrow())?;
+        write_atomic(&path, &data)?;
+        Ok(())
+    }
+    
+    /// Remember a tab's current page before it navigates to `url`, dropping
+    /// its forward history. Reloads and navigations to the same page add nothing.
+    pub fn push_back_entry(&self, index: usize, url: &str) {
+        let mut tabs = self.tabs.borrow_mut();
+        let Some(tab) = tabs.get_mut(index) else {
+            return;
+        };
+        if tab.url == url || tab.url.is_empty() {
+            return;
+        }
+        let current = tab.url.clone();
+        tab.back_history.push(current);
+        if tab.back_history.len() > MAX_TAB_HISTORY {
+            tab.back_history.remove(0);
+        }
+        tab.forward_history.clear();
+        tab.update_navigation_state();
+    }
+    
+    /// Step a tab back in its history, returning the page to load
+    pub fn go_back(&self, index: usize) -> Option<String> {
+        let mut tabs = self.tabs.borrow_mut();
+        let tab = tabs.get_mut(index)?;
+        let target = tab.back_history.pop()?;
+        let current = tab.url.clone();
+        tab.forward_history.push(current);
+        tab.update_navigation_state();
+        Some(target)
+    }
+    
+    /// Step a tab forward in its history, returning the page to load
+    pub fn go_forward(&self, index: usize) -> Option<String> {
+        let mut tabs = self.tabs.borrow_mut();
+        let tab = tabs.get_mut(index)?;
+        let target = tab.forward_history.pop()?;
+        let current = tab.url.clone();
+        tab.back_history.push(current);
+        tab.update_navigation_state();
+        Some(target)
+    }
+    
+    /// Mark a tab's page as failed, e.g. from `WebViewEvent::LoadError`, or
+    /// clear the failure with `None` once it navigates again
+    pub fn set_tab_error(&self, index: usize, error: Option<LoadError>) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            if error.is_some() {
+                tab.is_loading = false;
+                tab.load_progress = 0.0;
+            }
+            tab.error = error;
+        }
+    }
+    
+    /// Record the feeds a tab's page advertises
+    pub fn set_tab_feeds(&self, index: usize, feeds: Vec<String>) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.feeds = feeds;
+        }
+    }
+    
+    pub fn is_subscribed(&self, feed_url: &str) -> bool {
+        self.feeds.borrow().iter().any(|feed| feed.url == feed_url)
+    }
+    
+    /// Subscribe to a feed found on `site_url`; false if already subscribed
+    pub fn subscribe_feed(&self, title: String, feed_url: String, site_url: String) -> bool {
+        if self.is_subscribed(&feed_url) {
+            return false;
+        }
+        info!("📰 Subscribed to {}", feed_url);
+        self.feeds.borrow_mut().push(FeedSubscription {
+            title,
+            url: feed_url,
+            site_url,
+            subscribed_at: chrono::Utc::now(),
+        });
+        self.feeds_dirty.set(true);
+        true
+    }
+    
+    pub fn unsubscribe_feed(&self, feed_url: &str) {
+        self.feeds.borrow_mut().retain(|feed| feed.url != feed_url);
+        self.feeds_dirty.set(true);
+    }
+    
+    /// Replace the feed subscriptions with the ones saved in a JSON file
+    pub fn load_feeds(&self, path: &Path) -> anyhow::Result<()> {
+        let data = std::fs::read(path)?;
+        let feeds: Vec<FeedSubscription> = serde_json::from_slice(&data)?;
+        info!("📰 Loaded {} feeds from {}", feeds.len(), path.display());
+        *self.feeds.borrow_mut() = feeds;
+        self.feeds_dirty.set(false);
+        Ok(())
+    }
+    
+    /// Write feed subscriptions to the profile if they changed since the last flush
+    pub fn flush_feeds(&self) -> anyhow::Result<()> {
+        if self.private_mode.get() || !self.feeds_dirty.replace(false) {
+            return Ok(());
+        }
+        let Some(path) = self.profile_file(FEEDS_FILE) else {
+            return Ok(());
+        };
+        let data = serde_json::to_vec_pretty(&*self.feeds.borrow())?;
+        write_atomic(&path, &data)?;
+        Ok(())
+    }
+    
+    /// Replace the notes with the text saved in a file
+    pub fn load_notes(&self, path: &Path) -> anyhow::Result<()> {
+        *self.notes.borrow_mut() = std::fs::read_to_string(path)?;
+        self.notes_dirty.set(false);
+        Ok(())
+    }
+    
+    /// Write the notes to a file
+    pub fn save_notes(&self, path: &Path) -> anyhow::Result<()> {
+        write_atomic(path, self.notes.borrow().as_bytes())
+    }
+    
+    /// Write the notes to the profile if they were edited since the last flush
+    pub fn flush_notes(&self) -> anyhow::Result<()> {
+        if self.private_mode.get() || !self.notes_dirty.replace(false) {
+            return Ok(());
+        }
+        let Some(path) = self.profile_file(NOTES_FILE) else {
+            return Ok(());
+        };
+        self.save_notes(&path)
+    }
+    
+    /// Change whether closing a window with several tabs asks first
+    pub fn set_confirm_close_tabs(&self, confirm: bool) {
+        if self.confirm_close_tabs.replace(confirm) != confirm {
+            self.preferences_dirty.set(true);
+        }
+    }
+    
+    /// Load preferences from a file
+    pub fn load_preferences(&self, path: &Path) -> anyhow::Result<()> {
+        let data = std::fs::read(path)?;
+        let preferences: Preferences = serde_json::from_slice(&data)?;
+        self.confirm_close_tabs.set(preferences.confirm_close_tabs);
+        self.preferences_dirty.set(false);
+        Ok(())
+    }
+    
+    /// Write preferences to the profile if they changed since the last flush
+    pub fn flush_preferences(&self) -> anyhow::Result<()> {
+        if self.private_mode.get() || !self.preferences_dirty.replace(false) {
+            return Ok(());
+        }
+        let Some(path) = self.profile_file(PREFERENCES_FILE) else {
+            return Ok(());
+        };
+        let preferences = Preferences { confirm_close_tabs: self.confirm_close_tabs.get() };
+        write_atomic(&path, &serde_json::to_vec_pretty(&preferences)?)?;
+        Ok(())
+    }
+    
+    /// Factor click targets are drawn at: 1.0 unless accessibility mode is on
+    pub fn hit_scale(&self) -> f32 {
+        if self.accessibility_mode.get() {
+            self.hit_target_scale.get().clamp(MIN_HIT_TARGET_SCALE, MAX_HIT_TARGET_SCALE)
+        } else {
+            1.0
+        }
+    }
+    
+    /// Set a tab's zoom, clamped to `MIN_ZOOM..=MAX_ZOOM`
+    pub fn set_tab_zoom(&self, index: usize, zoom: f32) {
+        if let Some(tab) = self.tabs.borrow_mut().get_mut(index) {
+            tab.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        }
+    }
+    
     /// Add to history
     pub fn add_to_history(&self, url: String, title: String) {
+        if self.private_mode.get() {
+            return;
+        }
         let mut history = self.history.borrow_mut();
         
         // Check if URL already exists
@@ -312,7 +1422,7 @@ impl BrowserUIState {
     }
     
     /// Clear browsing data
-    pub fn clear_browsing_data(&self, clear_history: bool, clear_downloads: bool, clear_passwords: bool) {
+    pub fn clear_browsing_data(&self, clear_history: bool, clear_downloads: bool, clear_passwords: bool, clear_cookies: bool) {
         if clear_history {
             self.history.borrow_mut().clear();
             self.navigation_history.borrow_mut().clear();
@@ -328,6 +1438,26 @@ impl BrowserUIState {
             self.passwords.borrow_mut().clear();
             info!("🧹 Cleared saved passwords");
         }
+        
+        if clear_cookies {
+            if let Some(dir) = &self.profile_dir {
+                match std::fs::remove_file(dir.join(COOKIES_FILE)) {
+                    Ok(()) => info!("🧹 Cleared cookies"),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => warn!("⚠️ Failed to clear cookies: {}", e),
+                }
+            }
+        }
+    }
+    
+    /// Forget what a private window collected in memory. Its last tab
+    /// closing ends the window, so nothing is left to reopen.
+    pub fn discard_private_data(&self) {
+        self.clear_browsing_data(true, true, true, false);
+        self.closed_tabs.borrow_mut().clear();
+        self.bookmark_undo.borrow_mut().clear();
+        self.bookmark_redo.borrow_mut().clear();
+        info!("🕶 Discarded private browsing data");
     }
     
     /// Add download
@@ -356,6 +1486,66 @@ impl BrowserUIState {
         }
     }
     
+    /// Record a download's total size once the server has sent it
+    pub fn set_download_size(&self, id: &str, size: u64) {
+        let mut downloads = self.downloads.borrow_mut();
+        if let Some(download) = downloads.iter_mut().find(|d| d.id == id) {
+            download.size = size;
+        }
+    }
+    
+    /// Recount find-in-page matches (case-insensitive) in the page text
+    pub fn update_find_matches(&self, page_text: &str) {
+        let query = self.find_query.borrow().to_lowercase();
+        let count = if query.is_empty() {
+            0
+        } else {
+            page_text.to_lowercase().matches(query.as_str()).count()
+        };
+        
+        self.find_match_count.set(count);
+        if self.find_current_match.get() >= count {
+            self.find_current_match.set(0);
+        }
+    }
+    
+    /// Move to the next find-in-page match, wrapping around
+    pub fn find_next(&self) {
+        let count = self.find_match_count.get();
+        if count > 0 {
+            self.find_current_match.set((self.find_current_match.get() + 1) % count);
+        }
+    }
+    
+    /// Move to the previous find-in-page match, wrapping around
+    pub fn find_previous(&self) {
+        let count = self.find_match_count.get();
+        if count > 0 {
+            self.find_current_match.set((self.find_current_match.get() + count - 1) % count);
+        }
+    }
+    
+    /// Close the find bar
+    pub fn close_find(&self) {
+        self.find_active.set(false);
+        self.find_current_match.set(0);
+    }
+    
+    /// Change a download's status, keeping its progress
+    pub fn set_download_status(&self, id: &str, status: DownloadStatus) {
+        let mut downloads = self.downloads.borrow_mut();
+        if let Some(download) = downloads.iter_mut().find(|d| d.id == id) {
+            info!("⬇ Download {} is now {:?}", id, status);
+            download.status = status;
+        }
+    }
+    
+    /// Remove finished downloads from the list
+    pub fn clear_completed_downloads(&self) {
+        self.downloads.borrow_mut().retain(|d| !matches!(d.status, DownloadStatus::Completed));
+        info!("🧹 Cleared completed downloads");
+    }
+    
     /// Toggle UI panel
     pub fn toggle_panel(&self, panel: &str) {
         match panel {
@@ -368,4 +1558,472 @@ impl BrowserUIState {
             _ => {}
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn state_with_history(entries: &[(&str, &str, u32)]) -> BrowserUIState {
+        let state = BrowserUIState::default();
+        state.bookmarks.borrow_mut().clear();
+        *state.history.borrow_mut() = entries.iter()
+            .map(|(url, title, visit_count)| HistoryEntry {
+                url: url.to_string(),
+                title: title.to_string(),
+                visit_count: *visit_count,
+                last_visit: chrono::Utc::now(),
+            })
+            .collect();
+        state
+    }
+    
+    #[test]
+    fn test_reload_all_skips_internal_pages() {
+        let state = BrowserUIState::with_profile(None);
+        state.create_tab("https://a.genesis/");
+        state.create_tab("genesis://settings");
+        state.create_tab("https://b.genesis/");
+        
+        let targets = state.begin_reload_all();
+        assert_eq!(targets, vec![(1, "https://a.genesis/".to_string()), (3, "https://b.genesis/".to_string())]);
+        
+        let tabs = state.tabs.borrow();
+        assert!(tabs[1].is_loading && tabs[3].is_loading);
+        assert!(!tabs[0].is_loading && !tabs[2].is_loading);
+    }
+    
+    #[test]
+    fn test_confirm_close_preference_persists() {
+        let dir = std::env::temp_dir().join(format!("genesis-preferences-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        
+        let state = BrowserUIState::with_profile(Some(dir.clone()));
+        assert!(state.confirm_close_tabs.get());
+        state.set_confirm_close_tabs(false);
+        state.flush_preferences().unwrap();
+        
+        let reloaded = BrowserUIState::with_profile(Some(dir.clone()));
+        assert!(!reloaded.confirm_close_tabs.get());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[test]
+    fn test_site_settings_override_globals() {
+        let dir = std::env::temp_dir().join(format!("genesis-site-settings-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        
+        let state = BrowserUIState::with_profile(Some(dir.clone()));
+        state.set_site_settings("Ads.genesis", SiteSettings { javascript: Some(false), zoom: Some(1.5), ..Default::default() });
+        
+        let site = state.content_settings_for("https://ads.genesis/page");
+        assert!(!site.javascript);
+        assert!(site.images);
+        assert_eq!(site.zoom, Some(1.5));
+        let other = state.content_settings_for("other.genesis");
+        assert!(other.javascript);
+        assert_eq!(other.zoom, None);
+        
+        // Saved to the profile and read back by the next window
+        state.flush_site_settings().unwrap();
+        let reloaded = BrowserUIState::with_profile(Some(dir.clone()));
+        assert_eq!(reloaded.site_settings_for_host("ads.genesis").javascript, Some(false));
+        
+        // Resetting every override forgets the site
+        state.set_site_settings("ads.genesis", SiteSettings::default());
+        assert!(state.site_settings.borrow().is_empty());
+        
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[test]
+    fn test_feed_subscriptions_persist() {
+        let dir = std::env::temp_dir().join(format!("genesis-feeds-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        
+        let state = BrowserUIState::with_profile(Some(dir.clone()));
+        let feed = "https://blog.genesis/feed.xml".to_string();
+        assert!(state.subscribe_feed("Blog".to_string(), feed.clone(), "https://blog.genesis/".to_string()));
+        assert!(!state.subscribe_feed("Blog".to_string(), feed.clone(), "https://blog.genesis/".to_string()));
+        assert_eq!(state.feeds.borrow().len(), 1);
+        
+        state.flush_feeds().unwrap();
+        let reloaded = BrowserUIState::with_profile(Some(dir.clone()));
+        assert!(reloaded.is_subscribed(&feed));
+        
+        reloaded.unsubscribe_feed(&feed);
+        assert!(!reloaded.is_subscribed(&feed));
+        
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[test]
+    fn test_notes_round_trip() {
+        let dir = std::env::temp_dir().join(format!("genesis-notes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(NOTES_FILE);
+        
+        let state = BrowserUIState::with_profile(None);
+        *state.notes.borrow_mut() = "Buy domain\nRead docs ✓".to_string();
+        state.save_notes(&path).unwrap();
+        
+        let reloaded = BrowserUIState::with_profile(None);
+        reloaded.notes_dirty.set(true);
+        reloaded.load_notes(&path).unwrap();
+        assert_eq!(*reloaded.notes.borrow(), "Buy domain\nRead docs ✓");
+        assert!(!reloaded.notes_dirty.get());
+        
+        // Edits reach the profile on flush, and are loaded at startup
+        let profile = BrowserUIState::with_profile(Some(dir.clone()));
+        assert_eq!(*profile.notes.borrow(), "Buy domain\nRead docs ✓");
+        profile.notes.borrow_mut().push_str("\nMore");
+        profile.notes_dirty.set(true);
+        profile.flush_notes().unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Buy domain\nRead docs ✓\nMore");
+        
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[test]
+    fn test_mute_site_mutes_tabs_on_the_same_host() {
+        let state = BrowserUIState::default();
+        state.create_tab("https://music.genesis/a");
+        state.create_tab("music.genesis/b");
+        state.create_tab("https://other.genesis/");
+        
+        state.set_site_muted(1, true);
+        let muted: Vec<bool> = state.tabs.borrow().iter().map(|tab| tab.is_muted).collect();
+        assert_eq!(muted, vec![false, true, true, false]);
+        
+        state.toggle_tab_muted(2);
+        assert!(!state.tabs.borrow()[2].is_muted);
+    }
+    
+    #[test]
+    fn test_console_drops_oldest_messages() {
+        use crate::devtools::ConsoleLevel;
+        
+        let state = BrowserUIState::default();
+        for i in 0..=MAX_CONSOLE_MESSAGES {
+            state.push_console_message(0, ConsoleMessage::browser(ConsoleLevel::Log, i.to_string()));
+        }
+        let console = state.tabs.borrow()[0].console.clone();
+        assert_eq!(console.len(), MAX_CONSOLE_MESSAGES);
+        assert_eq!(console[0].message, "1");
+        
+        state.clear_console(0);
+        assert!(state.tabs.borrow()[0].console.is_empty());
+    }
+    
+    #[test]
+    fn test_security_state_for_url() {
+        assert_eq!(BrowserTab::new("https://a.com", &[]).security_state, SecurityState::Neutral);
+        assert_eq!(SecurityState::for_url("https://a.com", false), SecurityState::Secure);
+        assert_eq!(SecurityState::for_url("a.com", false), SecurityState::Secure);
+        assert_eq!(SecurityState::for_url("http://a.com", false), SecurityState::Insecure);
+        assert_eq!(SecurityState::for_url("http://a.genesis", true), SecurityState::Genesis);
+        assert_eq!(SecurityState::for_url("genesis://welcome", false), SecurityState::Neutral);
+    }
+    
+    #[test]
+    fn test_reorder_tab_keeps_active_tab() {
+        let state = BrowserUIState::default();
+        let first = state.tabs.borrow()[0].id.clone();
+        state.create_tab("https://a.genesis");
+        state.create_tab("https://b.genesis");
+        state.switch_to_tab(0);
+        
+        assert!(state.reorder_tab(0, 2));
+        assert_eq!(state.tabs.borrow()[2].id, first);
+        assert_eq!(state.active_tab_index.get(), 2);
+        
+        assert!(state.reorder_tab(1, 0));
+        assert_eq!(state.active_tab_index.get(), 2);
+        assert!(!state.reorder_tab(1, 1));
+        assert!(!state.reorder_tab(0, 5));
+    }
+    
+    #[test]
+    fn test_reorder_tab_respects_pinned_boundary() {
+        let state = BrowserUIState::default();
+        state.create_tab("https://a.genesis");
+        state.create_tab("https://b.genesis");
+        state.toggle_pin(2);
+        
+        assert!(state.tabs.borrow()[0].pinned);
+        assert!(!state.reorder_tab(0, 1));
+        assert!(state.reorder_tab(1, 2));
+    }
+    
+    #[test]
+    fn test_dedupe_key() {
+        assert_eq!(dedupe_key("x.genesis"), dedupe_key("http://x.genesis"));
+        assert_eq!(dedupe_key("https://X.Genesis/"), dedupe_key("x.genesis"));
+        assert_eq!(dedupe_key(" x.genesis/docs/ "), dedupe_key("http://x.genesis/docs"));
+        assert_ne!(dedupe_key("x.genesis/docs"), dedupe_key("x.genesis"));
+        assert_ne!(dedupe_key("x.genesis?page=2"), dedupe_key("x.genesis"));
+        assert_ne!(dedupe_key("localhost:3000"), dedupe_key("localhost:8080"));
+        assert_eq!(dedupe_key("genesis://newtab/"), "genesis://newtab");
+    }
+    
+    #[test]
+    fn test_find_tab_by_url() {
+        let state = BrowserUIState::default();
+        state.create_tab("http://wiki.genesis/");
+        state.create_tab("https://dex.defi/swap");
+        
+        assert_eq!(state.find_tab_by_url("genesis://welcome"), Some(0));
+        assert_eq!(state.find_tab_by_url("wiki.genesis"), Some(1));
+        assert_eq!(state.find_tab_by_url("http://dex.defi/swap/"), Some(2));
+        assert_eq!(state.find_tab_by_url("dex.defi"), None);
+    }
+    
+    #[test]
+    fn test_tab_error_stops_loading_until_reload() {
+        let state = BrowserUIState::with_profile(None);
+        state.create_tab("https://down.genesis/");
+        state.update_tab(1, None, None, Some(true), Some(0.4));
+        state.set_tab_error(1, Some(LoadError::network("connection refused")));
+        {
+            let tabs = state.tabs.borrow();
+            assert_eq!(tabs[1].error, Some(LoadError::network("connection refused")));
+            assert!(!tabs[1].is_loading);
+        }
+        
+        let targets = state.begin_reload_all();
+        assert_eq!(targets, vec![(1, "https://down.genesis/".to_string())]);
+        assert_eq!(state.tabs.borrow()[1].error, None);
+    }
+    
+    #[test]
+    fn test_back_forward_history() {
+        let state = BrowserUIState::with_profile(None);
+        state.update_tab(0, None, Some("a.genesis".to_string()), None, None);
+        state.push_back_entry(0, "b.genesis");
+        state.update_tab(0, None, Some("b.genesis".to_string()), None, None);
+        // Reloading the same page isn't a new entry
+        state.push_back_entry(0, "b.genesis");
+        assert_eq!(state.tabs.borrow()[0].back_history, ["a.genesis"]);
+        assert!(state.tabs.borrow()[0].can_go_back);
+        
+        assert_eq!(state.go_back(0).as_deref(), Some("a.genesis"));
+        state.update_tab(0, None, Some("a.genesis".to_string()), None, None);
+        assert!(!state.tabs.borrow()[0].can_go_back);
+        assert!(state.tabs.borrow()[0].can_go_forward);
+        assert_eq!(state.go_back(0), None);
+    }
+    
+    #[test]
+    fn test_hit_scale_only_applies_in_accessibility_mode() {
+        let state = BrowserUIState::with_profile(None);
+        state.hit_target_scale.set(1.75);
+        assert_eq!(state.hit_scale(), 1.0);
+        
+        state.accessibility_mode.set(true);
+        assert_eq!(state.hit_scale(), 1.75);
+        state.hit_target_scale.set(10.0);
+        assert_eq!(state.hit_scale(), MAX_HIT_TARGET_SCALE);
+    }
+    
+    #[test]
+    fn test_load_progress_only_moves_forward() {
+        let state = BrowserUIState::default();
+        state.update_tab(0, None, None, Some(true), Some(0.4));
+        state.update_tab(0, None, None, None, Some(0.2));
+        assert_eq!(state.tabs.borrow()[0].load_progress, 0.4);
+        
+        state.update_tab(0, None, None, Some(false), None);
+        assert_eq!(state.tabs.borrow()[0].load_progress, 1.0);
+        // Late progress events of a finished load are ignored
+        state.update_tab(0, None, None, None, Some(0.5));
+        assert_eq!(state.tabs.borrow()[0].load_progress, 1.0);
+        
+        state.update_tab(0, None, None, Some(true), None);
+        assert_eq!(state.tabs.borrow()[0].load_progress, 0.0);
+    }
+    
+    #[test]
+    fn test_looks_like_url() {
+        for input in [
+            "example.com",
+            "https://example.com/search?q=rust",
+            "docs.rs/egui",
+            "localhost",
+            "localhost:3000/api",
+            "127.0.0.1",
+            "192.168.1.10:8080",
+            "[::1]:3000",
+            "wiki.genesis",
+            "dex.defi/swap",
+            "genesis://welcome",
+            "about:blank",
+            "view-source:wiki.genesis",
+        ] {
+            assert!(looks_like_url(input), "{} should be a URL", input);
+        }
+        
+        for input in ["", "   ", "rust egui", "genesis", "3.14", "what is .genesis", "file.", ".com", "foo:bar"] {
+            assert!(!looks_like_url(input), "{} should be a search", input);
+        }
+    }
+    
+    #[test]
+    fn test_markdown_link_escapes_title_and_url() {
+        assert_eq!(markdown_link("Genesis", "https://a.genesis/"), "[Genesis](https://a.genesis/)");
+        assert_eq!(markdown_link("[beta] docs", "https://a.genesis/x (1)"), "[\\[beta\\] docs](https://a.genesis/x%20%281%29)");
+        assert_eq!(markdown_link(" ", "https://a.genesis/"), "[https://a.genesis/](https://a.genesis/)");
+    }
+    
+    #[test]
+    fn test_search_url_encodes_query() {
+        assert_eq!(
+            search_url("https://duckduckgo.com/?q={query}", "rust & egui?"),
+            "https://duckduckgo.com/?q=rust+%26+egui%3F"
+        );
+        assert_eq!(search_url(DEFAULT_SEARCH_ENGINE, " café "), "genesis://search?q=caf%C3%A9");
+    }
+    
+    #[test]
+    fn test_suggest_ranks_by_visit_count() {
+        let state = state_with_history(&[
+            ("https://docs.rs", "Docs", 2),
+            ("https://dex.defi", "DEX", 9),
+            ("https://news.example.com", "News", 5),
+        ]);
+        
+        let urls: Vec<String> = state.suggest("d").into_iter().map(|s| s.url).collect();
+        assert_eq!(urls, vec!["https://dex.defi", "https://docs.rs"]);
+        
+        let suggestions = state.suggest("DEX");
+        assert_eq!(suggestions.len(), 1);
+        assert!(suggestions[0].is_genesis_domain);
+    }
+    
+    #[test]
+    fn test_bookmark_edits_undo_and_redo() {
+        let state = BrowserUIState::default();
+        state.bookmarks.borrow_mut().clear();
+        state.add_bookmark("Wiki".to_string(), "https://wiki.genesis".to_string(), None);
+        state.add_bookmark("Chat".to_string(), "https://chat.genesis".to_string(), None);
+        let wiki = state.bookmarks.borrow()[0].clone();
+        state.remove_bookmark(&wiki.id);
+        
+        let urls = |state: &BrowserUIState| -> Vec<String> {
+            state.bookmarks.borrow().iter().map(|b| b.url.clone()).collect()
+        };
+        assert_eq!(urls(&state), ["https://chat.genesis"]);
+        
+        // The removed bookmark comes back where it was, with the same id
+        assert!(state.undo_bookmark_op());
+        assert_eq!(urls(&state), ["https://wiki.genesis", "https://chat.genesis"]);
+        assert_eq!(state.bookmarks.borrow()[0].id, wiki.id);
+        
+        assert!(state.undo_bookmark_op());
+        assert_eq!(urls(&state), ["https://wiki.genesis"]);
+        assert!(state.redo_bookmark_op());
+        assert!(state.redo_bookmark_op());
+        assert_eq!(urls(&state), ["https://chat.genesis"]);
+        assert!(!state.redo_bookmark_op());
+        
+        assert!(state.undo_bookmark_op());
+        assert_eq!(state.bookmarks.borrow()[0].id, wiki.id);
+        
+        // A new edit can't be followed by a redo of older ones
+        state.add_bookmark("Docs".to_string(), "https://docs.genesis".to_string(), None);
+        assert!(!state.redo_bookmark_op());
+        
+        for i in 0..60 {
+            state.add_bookmark(format!("Site {}", i), format!("https://site{}.genesis", i), None);
+        }
+        assert_eq!(state.bookmark_undo.borrow().len(), MAX_BOOKMARK_UNDO);
+    }
+    
+    #[test]
+    fn test_suggest_includes_bookmarks_once() {
+        let state = state_with_history(&[("https://wiki.genesis", "Wiki", 3)]);
+        state.add_bookmark("Genesis Wiki".to_string(), "https://wiki.genesis".to_string(), None);
+        state.add_bookmark("Genesis Chat".to_string(), "https://chat.genesis".to_string(), None);
+        
+        let suggestions = state.suggest("genesis");
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].url, "https://wiki.genesis");
+        assert!(suggestions[0].is_bookmark);
+        assert_eq!(suggestions[1].url, "https://chat.genesis");
+        assert_eq!(suggestions[1].visit_count, 0);
+    }
+    
+    #[test]
+    fn test_genesis_domains_follow_configured_tlds() {
+        let state = state_with_history(&[("https://dex.defi", "DEX", 1), ("https://lab.test", "Lab", 1)]);
+        state.genesis_tlds.replace(vec![".test".to_string()]);
+        
+        let suggestions = state.suggest("https://");
+        assert!(!suggestions.iter().find(|s| s.url == "https://dex.defi").unwrap().is_genesis_domain);
+        assert!(suggestions.iter().find(|s| s.url == "https://lab.test").unwrap().is_genesis_domain);
+        
+        state.create_tab("https://lab.test");
+        assert!(state.get_active_tab().unwrap().is_genesis_domain);
+    }
+    
+    #[test]
+    fn test_top_sites() {
+        let state = state_with_history(&[
+            ("genesis://welcome", "Welcome", 50),
+            ("https://docs.rs", "Docs", 2),
+            ("https://dex.defi", "DEX", 9),
+            ("https://news.example.com", "News", 5),
+        ]);
+        
+        let urls: Vec<String> = state.top_sites(2).into_iter().map(|entry| entry.url).collect();
+        assert_eq!(urls, vec!["https://dex.defi", "https://news.example.com"]);
+        assert_eq!(state.top_sites(10).len(), 3);
+    }
+    
+    #[test]
+    fn test_suggest_limits_results() {
+        let entries: Vec<(String, u32)> = (0..10).map(|i| (format!("https://site{}.web", i), i)).collect();
+        let refs: Vec<(&str, &str, u32)> = entries.iter().map(|(url, n)| (url.as_str(), "Site", *n)).collect();
+        let state = state_with_history(&refs);
+        
+        assert_eq!(state.suggest("site").len(), MAX_SUGGESTIONS);
+        assert!(state.suggest("   ").is_empty());
+    }
+    
+    #[test]
+    fn test_profile_files() {
+        assert!(is_valid_profile_name("work"));
+        assert!(is_valid_profile_name("side_project-2"));
+        for name in ["", "..", "a/b", "a\\b", ".hidden"] {
+            assert!(!is_valid_profile_name(name), "{:?} should be rejected", name);
+        }
+        
+        let dir = std::env::temp_dir().join(format!("genesis-profile-{}", std::process::id()));
+        let state = BrowserUIState::with_profile(Some(dir.clone()));
+        assert_eq!(state.profile_file(SESSION_FILE), Some(dir.join(SESSION_FILE)));
+        
+        // Private mode writes nothing, and neither does an ephemeral profile
+        state.private_mode.set(true);
+        assert_eq!(state.profile_file(SESSION_FILE), None);
+        assert_eq!(BrowserUIState::with_profile(None).profile_file(SESSION_FILE), None);
+        assert!(BrowserUIState::with_profile(None).bookmarks_path.borrow().is_none());
+        assert_eq!(BrowserUIState::default().profile_file(SESSION_FILE), None);
+    }
+    
+    #[test]
+    fn test_private_mode_keeps_no_history() {
+        let state = BrowserUIState::with_profile(None);
+        state.private_mode.set(true);
+        state.add_to_history("https://wiki.genesis".to_string(), "Wiki".to_string());
+        assert!(state.history.borrow().is_empty());
+        assert!(state.navigation_history.borrow().is_empty());
+        
+        state.add_download("a.zip".to_string(), "https://files.genesis/a.zip".to_string(), 0);
+        let tab = state.create_tab("https://wiki.genesis");
+        let index = state.tabs.borrow().iter().position(|t| t.id == tab).unwrap();
+        assert!(state.close_tab(index));
+        assert_eq!(state.closed_tabs.borrow().len(), 1);
+        state.discard_private_data();
+        assert!(state.downloads.borrow().is_empty());
+        assert!(state.closed_tabs.borrow().is_empty());
+    }
+}
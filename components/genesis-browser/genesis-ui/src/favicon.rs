@@ -0,0 +1,119 @@
+// Favicon fetching for traditional web pages
+// Genesis domains show the Genesis badge instead and never hit the network here
+
+use std::time::Duration;
+
+use tracing::debug;
+use url::Url;
+
+/// Give up on a favicon request after this long
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Icons larger than this are ignored
+const MAX_FAVICON_BYTES: usize = 512 * 1024;
+
+/// Fetch the favicon for a page: `/favicon.ico` first, then the page's `<link rel="icon">`
+pub async fn fetch_favicon(url: &str) -> Option<Vec<u8>> {
+    let page_url = Url::parse(url).ok()?;
+    if !matches!(page_url.scheme(), "http" | "https") {
+        return None;
+    }
+    
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .ok()?;
+    
+    if let Some(icon) = fetch_bytes(&client, page_url.join("/favicon.ico").ok()?).await {
+        return Some(icon);
+    }
+    
+    let html = client.get(page_url.clone()).send().await.ok()?.text().await.ok()?;
+    let href = find_icon_link(&html)?;
+    fetch_bytes(&client, page_url.join(&href).ok()?).await
+}
+
+async fn fetch_bytes(client: &reqwest::Client, url: Url) -> Option<Vec<u8>> {
+    let response = client.get(url.clone()).send().await.ok()?;
+    if !response.status().is_success() {
+        debug!("Favicon request to {} returned {}", url, response.status());
+        return None;
+    }
+    
+    let bytes = response.bytes().await.ok()?;
+    if bytes.is_empty() || bytes.len() > MAX_FAVICON_BYTES {
+        return None;
+    }
+    Some(bytes.to_vec())
+}
+
+/// `href` of the first `<link rel="icon">` (or `shortcut icon`) in a page
+fn find_icon_link(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let mut rest = lower.as_str();
+    let mut offset = 0;
+    
+    while let Some(start) = rest.find("<link") {
+        let end = rest[start..].find('>').map_or(rest.len(), |end| start + end);
+        let tag = &rest[start..end];
+        
+        let is_icon = attribute(tag, "rel")
+            .map_or(false, |rel| rel.split_whitespace().any(|token| token == "icon"));
+        if is_icon {
+            // Take the href from the original text, URLs are case sensitive
+            let original = &html[offset + start..offset + end];
+            if let Some(href) = attribute(original, "href") {
+                return Some(href.to_string());
+            }
+        }
+        
+        offset += end;
+        rest = &rest[end..];
+    }
+    None
+}
+
+/// Value of an attribute inside a tag, quoted or not
+pub(crate) fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search_from = 0;
+    
+    loop {
+        let position = search_from + lower[search_from..].find(name)?;
+        search_from = position + name.len();
+        
+        // Must be a whole attribute name followed by `=`
+        let preceded_by_space = lower[..position].ends_with(char::is_whitespace);
+        let after = lower[search_from..].trim_start();
+        if !preceded_by_space || !after.starts_with('=') {
+            continue;
+        }
+        
+        let value_start = tag.len() - after.len() + 1;
+        let value = tag[value_start..].trim_start();
+        return match value.chars().next()? {
+            quote @ ('"' | '\'') => value[1..].split(quote).next(),
+            _ => value.split(|c: char| c.is_whitespace() || c == '>').next(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_find_icon_link() {
+        let html = r#"<html><head>
+            <link rel="stylesheet" href="/style.css">
+            <LINK REL="Shortcut Icon" HREF="/static/Favicon.png">
+        </head></html>"#;
+        assert_eq!(find_icon_link(html).as_deref(), Some("/static/Favicon.png"));
+        
+        let html = "<link href=icon.svg rel=icon>";
+        assert_eq!(find_icon_link(html).as_deref(), Some("icon.svg"));
+        
+        assert_eq!(find_icon_link(r#"<link rel="apple-touch-icon" href="/a.png">"#), None);
+        assert_eq!(find_icon_link("<p>no icons here</p>"), None);
+    }
+}
@@ -0,0 +1,121 @@
+// RSS/Atom feed discovery and subscriptions
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use url::Url;
+
+use crate::favicon::attribute;
+
+/// Give up on fetching a page for its feeds after this long
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `type`s of `<link rel="alternate">` tags that point at a feed
+const FEED_TYPES: [&str; 2] = ["application/rss+xml", "application/atom+xml"];
+
+/// A feed the user subscribed to from the omnibox
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeedSubscription {
+    /// Title of the page the feed was found on
+    pub title: String,
+    pub url: String,
+    /// Page that advertised the feed
+    pub site_url: String,
+    pub subscribed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Feeds a page advertises with `<link rel="alternate">`, resolved against
+/// the page's URL, in document order and without duplicates
+pub fn parse_feed_links(html: &str, base: &Url) -> Vec<Url> {
+    let lower = html.to_ascii_lowercase();
+    let mut feeds: Vec<Url> = Vec::new();
+    let mut offset = 0;
+    
+    while let Some(start) = lower[offset..].find("<link").map(|start| offset + start) {
+        let end = lower[start..].find('>').map_or(lower.len(), |end| start + end);
+        let tag = &lower[start..end];
+        offset = end;
+        
+        let is_alternate = attribute(tag, "rel")
+            .map_or(false, |rel| rel.split_whitespace().any(|token| token == "alternate"));
+        let is_feed = attribute(tag, "type")
+            .map_or(false, |kind| FEED_TYPES.contains(&kind.trim()));
+        if !is_alternate || !is_feed {
+            continue;
+        }
+        
+        // Take the href from the original text, URLs are case sensitive
+        let Some(href) = attribute(&html[start..end], "href") else {
+            continue;
+        };
+        let href = html_escape::decode_html_entities(href.trim());
+        if let Ok(url) = base.join(&href) {
+            if matches!(url.scheme(), "http" | "https") && !feeds.contains(&url) {
+                feeds.push(url);
+            }
+        }
+    }
+    feeds
+}
+
+/// Fetch a page and return the feeds it advertises. `address` is where
+/// Genesis DNS resolved the host to; without one the system resolver is used.
+pub async fn discover_feeds(url: &str, address: Option<IpAddr>) -> Vec<Url> {
+    let Ok(page_url) = Url::parse(url) else {
+        return Vec::new();
+    };
+    let (Some(host), Some(port)) = (page_url.host_str(), page_url.port_or_known_default()) else {
+        return Vec::new();
+    };
+    if !matches!(page_url.scheme(), "http" | "https") {
+        return Vec::new();
+    }
+    
+    let mut builder = reqwest::Client::builder().timeout(FETCH_TIMEOUT);
+    if let Some(address) = address {
+        builder = builder.resolve(host, SocketAddr::new(address, port));
+    }
+    let Ok(client) = builder.build() else {
+        return Vec::new();
+    };
+    let html = match client.get(page_url.clone()).send().await {
+        Ok(response) if response.status().is_success() => response.text().await.unwrap_or_default(),
+        Ok(response) => {
+            debug!("Feed discovery for {} got {}", page_url, response.status());
+            return Vec::new();
+        }
+        Err(e) => {
+            debug!("Feed discovery for {} failed: {}", page_url, e);
+            return Vec::new();
+        }
+    };
+    parse_feed_links(&html, &page_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_feed_links() {
+        let base = Url::parse("https://blog.genesis/posts/").unwrap();
+        let html = r#"<html><head>
+            <link rel="stylesheet" href="/style.css">
+            <link rel="alternate" type="application/rss+xml" title="Posts" href="/Feed.xml">
+            <LINK REL="Alternate" TYPE="application/atom+xml" HREF="atom.xml?a=1&amp;b=2">
+            <link rel="alternate" type="text/html" hreflang="fr" href="/fr/">
+            <link rel=alternate type=application/rss+xml href=/Feed.xml>
+        </head></html>"#;
+        
+        let feeds: Vec<String> = parse_feed_links(html, &base).iter().map(Url::to_string).collect();
+        assert_eq!(feeds, [
+            "https://blog.genesis/Feed.xml",
+            "https://blog.genesis/posts/atom.xml?a=1&b=2",
+        ]);
+        
+        assert!(parse_feed_links("<p>no feeds</p>", &base).is_empty());
+        assert!(parse_feed_links(r#"<link rel="alternate" type="application/rss+xml" href="javascript:alert(1)">"#, &base).is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+// Internal genesis:// pages, served by the browser instead of a DNS lookup
+
+use std::fmt::Write;
+
+use genesis_dns::DnsError;
+
+/// Scheme of pages the browser renders itself
+pub const INTERNAL_SCHEME: &str = "genesis://";
+
+/// An internal page with its own renderer
+#[derive(Debug, Clone, PartialEq)]
+pub enum InternalPage {
+    Welcome,
+    NewTab,
+    Settings,
+    /// Scratchpad kept in the profile
+    Notes,
+}
+
+/// Content of an internal page: a heading, paragraphs and links
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageTemplate {
+    pub title: String,
+    pub heading: String,
+    pub paragraphs: Vec<String>,
+    /// `(label, url)` pairs shown as buttons
+    pub links: Vec<(String, String)>,
+}
+
+impl InternalPage {
+    /// The page a URL points at; `None` for web URLs and unknown internal pages
+    pub fn parse(url: &str) -> Option<Self> {
+        if !url.starts_with(INTERNAL_SCHEME) {
+            return None;
+        }
+        let url = url::Url::parse(url).ok()?;
+        
+        match url.host_str()? {
+            "welcome" => Some(Self::Welcome),
+            "newtab" => Some(Self::NewTab),
+            "settings" => Some(Self::Settings),
+            "notes" => Some(Self::Notes),
+            _ => None,
+        }
+    }
+    
+    /// URL of the page, as shown in the omnibox
+    pub fn url(&self) -> String {
+        match self {
+            Self::Welcome => format!("{}welcome", INTERNAL_SCHEME),
+            Self::NewTab => format!("{}newtab", INTERNAL_SCHEME),
+            Self::Settings => format!("{}settings", INTERNAL_SCHEME),
+            Self::Notes => format!("{}notes", INTERNAL_SCHEME),
+        }
+    }
+    
+    /// Content of the page; the new-tab page adds its site tiles on top.
+    /// The welcome page lists `genesis_tlds`.
+    pub fn template(&self, genesis_tlds: &[String]) -> PageTemplate {
+        let link = |label: &str, url: &str| (label.to_string(), url.to_string());
+        
+        match self {
+            Self::Welcome => PageTemplate {
+                title: "Welcome".to_string(),
+                heading: "🌐 Welcome to Genesis Browser".to_string(),
+                paragraphs: vec![
+                    "Genesis domains resolve through the Genesis blockchain, with no ICANN and no central registry.".to_string(),
+                    match genesis_tlds.split_last() {
+                        Some((last, [])) => format!("Type a {} address to get started.", last),
+                        Some((last, rest)) => format!("Type a {} or {} address to get started.", rest.join(", "), last),
+                        None => "Type a Genesis address to get started.".to_string(),
+                    },
+                ],
+                links: vec![
+                    link("🔍 Domain Directory", "genesis://directory"),
+                    link("📖 Docs", "genesis://docs"),
+                    link("⚙️ Settings", "genesis://settings"),
+                ],
+            },
+            Self::NewTab => PageTemplate {
+                title: "New Tab".to_string(),
+                heading: "Genesis".to_string(),
+                paragraphs: Vec::new(),
+                links: Vec::new(),
+            },
+            Self::Settings => PageTemplate {
+                title: "Settings".to_string(),
+                heading: "⚙️ Settings".to_string(),
+                paragraphs: vec![
+                    "Genesis node, search engine, privacy and content settings are in the settings panel.".to_string(),
+                    "Defaults for new windows are read from config.toml.".to_string(),
+                ],
+                links: Vec::new(),
+            },
+            Self::Notes => PageTemplate {
+                title: "Notes".to_string(),
+                heading: "📝 Notes".to_string(),
+                paragraphs: Vec::new(),
+                links: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Explanation of a `DnsError::kind` for the couldn't-load page
+fn error_message(code: &str, domain: &str) -> String {
+    match code {
+        "unsupported_domain" | "no_results" => format!("{} wasn't found on Genesis. Check the address for typos, or whether the domain is registered.", domain),
+        "connection" | "all_nodes_failed" | "node" => "The Genesis node couldn't be reached. Check your connection or pick another node in settings.".to_string(),
+        "invalid_response" => "The Genesis node sent an answer the browser couldn't understand.".to_string(),
+        "integrity_check_failed" => format!("The content of {} isn't signed by its owner, so it was not loaded.", domain),
+        "invalid_domain_name" => format!("{} is not a valid domain name.", domain),
+        "invalid_proxy" => "The configured proxy is invalid.".to_string(),
+        _ => format!("Resolving {} failed.", domain),
+    }
+}
+
+/// Why a tab's page couldn't load
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadError {
+    /// The reported message
+    pub message: String,
+    /// `DnsError::kind` if the domain didn't resolve, `None` if its page
+    /// failed to load
+    pub code: Option<String>,
+}
+
+impl LoadError {
+    /// A failed lookup
+    pub fn dns(error: &DnsError) -> Self {
+        Self { message: error.to_string(), code: Some(error.kind().to_string()) }
+    }
+    
+    /// A page that failed to load after its domain resolved, e.g. on
+    /// `WebViewEvent::LoadError`
+    pub fn network(message: impl Into<String>) -> Self {
+        Self { message: message.into(), code: None }
+    }
+    
+    /// A failed load reported through `anyhow`, classified by the
+    /// `DnsError` behind it if there is one
+    pub fn from_anyhow(error: &anyhow::Error) -> Self {
+        match error.downcast_ref::<DnsError>() {
+            Some(dns_error) => Self::dns(dns_error),
+            None => Self::network(format!("{:#}", error)),
+        }
+    }
+}
+
+impl PageTemplate {
+    /// Page shown in place of a tab whose load failed
+    pub fn load_error(url: &str, error: &LoadError) -> Self {
+        let host = url::Url::parse(url).ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| url.to_string());
+        let explanation = match &error.code {
+            Some(code) => error_message(code, &host),
+            None => format!("{} didn't respond. Check your connection, proxy or firewall and try again.", host),
+        };
+        
+        Self {
+            title: format!("{} couldn't load", host),
+            heading: "⚠️ This page couldn't load".to_string(),
+            paragraphs: vec![explanation, error.message.clone()],
+            links: Vec::new(),
+        }
+    }
+    
+    /// Render the page as a standalone HTML document
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n",
+            html_escape::encode_text(&self.title),
+            html_escape::encode_text(&self.heading)
+        );
+        for paragraph in &self.paragraphs {
+            let _ = writeln!(html, "<p>{}</p>", html_escape::encode_text(paragraph));
+        }
+        for (label, url) in &self.links {
+            let _ = writeln!(
+                html,
+                "<a href=\"{}\">{}</a>",
+                html_escape::encode_double_quoted_attribute(url),
+                html_escape::encode_text(label)
+            );
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+    
+    /// Plain text of the page, for find-in-page
+    pub fn text(&self) -> String {
+        let mut lines = vec![self.heading.clone()];
+        lines.extend(self.paragraphs.iter().cloned());
+        lines.extend(self.links.iter().map(|(label, _)| label.clone()));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_internal_urls() {
+        assert_eq!(InternalPage::parse("genesis://welcome"), Some(InternalPage::Welcome));
+        assert_eq!(InternalPage::parse("genesis://newtab"), Some(InternalPage::NewTab));
+        assert_eq!(InternalPage::parse("genesis://settings"), Some(InternalPage::Settings));
+        assert_eq!(InternalPage::parse(&InternalPage::Notes.url()), Some(InternalPage::Notes));
+        assert_eq!(InternalPage::parse("genesis://docs"), None);
+        assert_eq!(InternalPage::parse("https://welcome.genesis"), None);
+    }
+    
+    #[test]
+    fn test_welcome_lists_configured_tlds() {
+        let tlds = |tlds: &[&str]| tlds.iter().map(|tld| tld.to_string()).collect::<Vec<_>>();
+        let paragraph = |genesis_tlds: &[String]| InternalPage::Welcome.template(genesis_tlds).paragraphs[1].clone();
+        
+        assert_eq!(
+            paragraph(&tlds(&genesis_dns::DEFAULT_GENESIS_TLDS)),
+            "Type a .genesis, .free, .web, .defi or .dao address to get started."
+        );
+        assert_eq!(paragraph(&tlds(&[".lab", ".node"])), "Type a .lab or .node address to get started.");
+        assert_eq!(paragraph(&tlds(&[".lab"])), "Type a .lab address to get started.");
+    }
+    
+    #[test]
+    fn test_load_error_tells_resolution_from_network() {
+        let resolution = PageTemplate::load_error(
+            "https://typo.genesis/",
+            &LoadError::dns(&DnsError::UnsupportedDomain("typo.genesis".to_string()))
+        );
+        assert!(resolution.paragraphs[0].starts_with("typo.genesis wasn't found on Genesis"));
+        
+        let unreachable = PageTemplate::load_error(
+            "https://site.genesis/",
+            &LoadError::dns(&DnsError::AllNodesFailed(vec!["connection refused".to_string()]))
+        );
+        assert!(unreachable.paragraphs[0].starts_with("The Genesis node couldn't be reached"));
+        
+        let network = PageTemplate::load_error("https://site.genesis/", &LoadError::network("Navigation timed out after 30s"));
+        assert!(network.paragraphs[0].starts_with("site.genesis didn't respond"));
+        assert_eq!(network.paragraphs[1], "Navigation timed out after 30s");
+    }
+}
@@ -1,16 +1,34 @@
 // Genesis UI - User interface components for Genesis Browser
 
+pub mod bookmarks_html;
 pub mod browser_ui;
+pub mod command_palette;
+pub mod config;
 pub mod enhanced_browser;
 pub mod components;
+pub mod devtools;
+pub mod downloads;
+pub mod favicon;
+pub mod feeds;
+pub mod internal_pages;
+pub mod page_source;
+
+// Encrypted password storage
+#[cfg(feature = "password-manager")]
+pub mod password_store;
 
 // Modern UI with egui
 #[cfg(feature = "modern-ui")]
 pub mod modern_browser;
+#[cfg(feature = "modern-ui")]
+pub mod shortcuts;
 
 // Re-export main types
 pub use browser_ui::GenesisBrowserUI; // Compatibility wrapper
-pub use enhanced_browser::{BrowserUIState, BrowserTab, Bookmark, Download, HistoryEntry};
+pub use config::{Config, Theme};
+pub use devtools::{ConsoleLevel, ConsoleMessage, ScriptRunner};
+pub use feeds::FeedSubscription;
+pub use enhanced_browser::{BrowserUIState, BrowserTab, Bookmark, Download, HistoryEntry, SecurityState};
 
 // Legacy components - deprecated
 #[deprecated = "Components are handled internally by modern UI"]
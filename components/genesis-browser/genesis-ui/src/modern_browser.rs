@@ -8,10 +8,348 @@ use egui::{
     CentralPanel, TopBottomPanel, SidePanel, ScrollArea, TextEdit,
     RichText, Color32, Ui, Vec2, Rounding, FontFamily, FontId, Stroke
 };
-use tracing::info;
+use tracing::{error, info, warn};
 
-use crate::enhanced_browser::BrowserUIState;
-use std::collections::HashMap;
+use crate::enhanced_browser::{
+    default_profile_dir, markdown_link, search_url, url_host, BrowserUIState, DownloadStatus, SecurityState,
+    SiteSettings, Suggestion,
+    DNS_CACHE_FILE, MAX_HIT_TARGET_SCALE, MIN_HIT_TARGET_SCALE, SEARCH_ENGINES, SESSION_FILE, WINDOW_STATE_FILE, ZOOM_STEP,
+};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use genesis_dns::{normalize_url, DnsError, DnsResult, GenesisDnsResolver, NodeHealth, ResolverType, UrlError};
+use genesis_util::window_state::{Monitor, WindowState};
+
+use crate::command_palette::{filter_commands, Command};
+use crate::config::{Config, Theme};
+use crate::devtools::{ConsoleLevel, ConsoleMessage, NetworkRequest, ScriptRunner};
+use crate::downloads::{default_download_dir, download_filename, is_download_url, partial_download_path, DownloadManager, DownloadUpdate};
+use crate::favicon::fetch_favicon;
+use crate::feeds::discover_feeds;
+use crate::internal_pages::{InternalPage, LoadError, PageTemplate};
+use crate::page_source::{fetch_source, source_target, view_source_url, PageSource};
+use crate::shortcuts::{shortcut_label, shortcuts, ShortcutAction, ShortcutCategory, ShortcutDef, Trigger};
+#[cfg(feature = "password-manager")]
+use crate::password_store::PasswordStore;
+
+/// How often open tabs are saved while browsing
+const SESSION_SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Pause between tabs in `reload_all_tabs`, so they don't all hit the Genesis node at once
+const RELOAD_ALL_STAGGER: Duration = Duration::from_millis(250);
+
+/// Feature list shown on Genesis domain pages
+const GENESIS_FEATURES: [&str; 5] = [
+    "✅ Decentralized DNS resolution",
+    "✅ Censorship resistant",
+    "✅ Community governed",
+    "✅ No ICANN dependency",
+    "✅ Built on 2M+ TPS Genesis blockchain",
+];
+
+/// Notes shown on traditional domain pages
+const TRADITIONAL_NOTES: [&str; 3] = [
+    "🔄 Using traditional DNS fallback",
+    "⚠️ Centralized infrastructure",
+    "🔒 Subject to censorship",
+];
+
+const GENESIS_HEADING: &str = "🌐 Genesis Blockchain Domain";
+const TRADITIONAL_HEADING: &str = "🌍 Traditional Web Domain";
+
+/// Height of the load progress line under the toolbar
+const PROGRESS_LINE_HEIGHT: f32 = 2.0;
+
+/// How long the progress line takes to fade out once a load completes
+const PROGRESS_FADE_SECS: f32 = 0.4;
+
+/// Progress shown as soon as a navigation starts
+const SIMULATED_PROGRESS_START: f32 = 0.1;
+
+/// The simulated progress approaches but never reaches this before the load ends
+const SIMULATED_PROGRESS_CEILING: f32 = 0.85;
+
+/// How quickly the simulated progress closes in on the ceiling, per second
+const SIMULATED_PROGRESS_RATE: f32 = 1.5;
+
+/// In fullscreen, moving the pointer this close to the top edge reveals the tab strip and toolbar
+const FULLSCREEN_REVEAL_EDGE: f32 = 4.0;
+
+/// Height of the tab strip and toolbar; revealed chrome hides once the pointer moves below it
+const FULLSCREEN_CHROME_HEIGHT: f32 = 75.0;
+
+/// Height of the toolbar; accessibility mode grows it to fit larger buttons
+const TOOLBAR_HEIGHT: f32 = 40.0;
+
+/// Range of the animation speed setting; 5 animates a tab in a fifth of a second
+const MIN_ANIMATION_SPEED: f32 = 1.0;
+const MAX_ANIMATION_SPEED: f32 = 20.0;
+
+/// Longest time step an animation advances by in one frame
+const MAX_ANIMATION_STEP: f32 = 0.05;
+
+/// How long a toast like "Copied!" stays up
+const TOAST_DURATION: Duration = Duration::from_millis(1500);
+
+/// Width of the tab overflow menu button, shown while the tab strip scrolls
+const TAB_OVERFLOW_BUTTON_WIDTH: f32 = 28.0;
+
+/// Width of a pinned tab, just enough for the favicon
+const PINNED_TAB_WIDTH: f32 = 40.0;
+
+/// Command-line flags a private window inherits from this process, so it
+/// reads the same config and reaches the node the same way
+const PRIVATE_WINDOW_FLAGS: [&str; 3] = ["--config", "--genesis-node", "--proxy"];
+
+/// Shortcuts shown on the new-tab page
+const GENESIS_SHORTCUTS: [(&str, &str); 3] = [
+    ("📂 .genesis directory", "genesis://directory"),
+    ("🌐 Explorer", "genesis://explorer"),
+    ("📖 Docs", "genesis://docs"),
+];
+
+/// Number of most-visited tiles on the new-tab page
+const TOP_SITES: usize = 8;
+
+/// How often the status bar re-reads the process memory
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How often the status bar re-checks the Genesis node
+const NODE_HEALTH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long to wait before retrying a favicon that failed to load
+const FAVICON_RETRY_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// Pause in typing after which the notes are saved
+const NOTES_SAVE_DELAY: Duration = Duration::from_secs(1);
+
+/// Frames plotted by the performance overlay
+const FRAME_HISTORY: usize = 120;
+
+/// Size of the performance overlay's frame time graph
+const FRAME_GRAPH_SIZE: Vec2 = Vec2::new(240.0, 48.0);
+
+/// Favicon of a host, as shown in the tab strip
+enum FaviconState {
+    Loading,
+    Loaded(egui::TextureHandle),
+    Failed(Instant),
+}
+
+/// Outcome of a background DNS lookup started by `navigate_to`
+struct DnsOutcome {
+    tab_id: String,
+    url: String,
+    host: String,
+    result: Result<DnsResult, DnsError>,
+    /// How long the resolution took, for the network log
+    elapsed: Duration,
+}
+
+/// Network log entry for a finished resolution; `error` if it failed
+fn dns_request(outcome: &DnsOutcome, error: Option<String>) -> NetworkRequest {
+    NetworkRequest {
+        method: "DNS".to_string(),
+        url: outcome.host.clone(),
+        status: None,
+        size: None,
+        duration: outcome.elapsed,
+        request_headers: Vec::new(),
+        response_headers: Vec::new(),
+        error,
+    }
+}
+
+/// Headers of an expanded network log entry
+fn render_request_details(ui: &mut Ui, request: &NetworkRequest) {
+    if let Some(error) = &request.error {
+        ui.colored_label(Color32::from_rgb(217, 48, 37), error);
+    }
+    for (title, headers) in [("Request headers", &request.request_headers), ("Response headers", &request.response_headers)] {
+        ui.label(RichText::new(title).strong());
+        if headers.is_empty() {
+            ui.label(RichText::new("None").weak());
+        }
+        for (name, value) in headers {
+            ui.label(RichText::new(format!("{}: {}", name, value)).monospace().small());
+        }
+    }
+}
+
+/// Speaker drawn over an audible tab's favicon, crossed out when muted
+fn paint_speaker_icon(painter: &egui::Painter, center: egui::Pos2, muted: bool, color: Color32) {
+    let center = center - Vec2::new(2.0, 0.0);
+    painter.rect_filled(
+        egui::Rect::from_min_max(center + Vec2::new(-5.0, -2.5), center + Vec2::new(-2.0, 2.5)),
+        0.0,
+        color
+    );
+    painter.add(egui::Shape::convex_polygon(
+        vec![
+            center + Vec2::new(-2.0, -2.5),
+            center + Vec2::new(2.0, -6.0),
+            center + Vec2::new(2.0, 6.0),
+            center + Vec2::new(-2.0, 2.5),
+        ],
+        color,
+        Stroke::NONE
+    ));
+    
+    let stroke = Stroke::new(1.2, color);
+    if muted {
+        painter.line_segment([center + Vec2::new(4.0, -3.0), center + Vec2::new(9.0, 3.0)], stroke);
+        painter.line_segment([center + Vec2::new(4.0, 3.0), center + Vec2::new(9.0, -3.0)], stroke);
+    } else {
+        // Two sound waves
+        for radius in [4.0, 7.0] {
+            let wave = (-3..=3)
+                .map(|step| {
+                    let angle = step as f32 * std::f32::consts::FRAC_PI_4 / 3.0;
+                    center + Vec2::new(2.0 + radius * angle.cos(), radius * angle.sin())
+                })
+                .collect();
+            painter.add(egui::Shape::line(wave, stroke));
+        }
+    }
+}
+
+/// Feed glyph: a dot in the lower left corner with two arcs around it
+fn paint_feed_icon(painter: &egui::Painter, center: egui::Pos2, color: Color32) {
+    let origin = center + Vec2::new(-5.0, 5.0);
+    painter.circle_filled(origin + Vec2::new(1.5, -1.5), 1.5, color);
+    
+    let stroke = Stroke::new(1.6, color);
+    for radius in [5.5, 9.5] {
+        let arc = (0..=6)
+            .map(|step| {
+                let angle = step as f32 * std::f32::consts::FRAC_PI_2 / 6.0;
+                origin + Vec2::new(radius * angle.sin(), -radius * angle.cos())
+            })
+            .collect();
+        painter.add(egui::Shape::line(arc, stroke));
+    }
+}
+
+/// Host part of a URL that needs DNS resolution.
+/// Internal `genesis://` pages are served by the browser itself and return `None`.
+fn navigation_host(url: &str) -> Option<String> {
+    let url = normalize_url(url).ok().filter(|url| url.scheme() != "genesis")?;
+    url.host_str().map(|host| host.to_string())
+}
+
+/// Password list in the settings panel
+#[cfg(feature = "password-manager")]
+#[derive(Default)]
+struct PasswordPanel {
+    /// Unlocked store, `None` until the master passphrase is entered
+    store: Option<PasswordStore>,
+    passphrase: String,
+    domain: String,
+    username: String,
+    password: String,
+    /// Decrypted password currently shown, by entry index
+    revealed: Option<(usize, String)>,
+    error: Option<String>,
+}
+
+/// The `PRIVATE_WINDOW_FLAGS` among `args`, with their values, given either
+/// as `--flag value` or `--flag=value`
+fn private_window_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut inherited = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if PRIVATE_WINDOW_FLAGS.contains(&arg.as_str()) {
+            inherited.push(arg);
+            inherited.extend(args.next());
+        } else if PRIVATE_WINDOW_FLAGS.iter().any(|flag| arg.strip_prefix(flag).map_or(false, |rest| rest.starts_with('='))) {
+            inherited.push(arg);
+        }
+    }
+    inherited
+}
+
+/// Resident memory of the browser process in bytes, `None` where the
+/// platform doesn't report it
+fn resident_memory_bytes() -> Option<u64> {
+    Some(genesis_util::memory::resident_memory_bytes()).filter(|bytes| *bytes > 0)
+}
+
+/// Whether the desktop asks apps to avoid animations, GNOME's `enable-animations` turned off
+#[cfg(target_os = "linux")]
+fn prefers_reduced_motion() -> bool {
+    std::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "enable-animations"])
+        .output()
+        .map_or(false, |output| output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "false")
+}
+
+/// Whether the desktop asks apps to avoid animations, Windows' "Show animations" turned off
+#[cfg(windows)]
+fn prefers_reduced_motion() -> bool {
+    let mut enabled: winapi::shared::minwindef::BOOL = 1;
+    // SAFETY: SPI_GETCLIENTAREAANIMATION writes a single BOOL to the pointer
+    let ok = unsafe {
+        winapi::um::winuser::SystemParametersInfoW(
+            winapi::um::winuser::SPI_GETCLIENTAREAANIMATION,
+            0,
+            &mut enabled as *mut _ as *mut std::ffi::c_void,
+            0,
+        )
+    };
+    ok != 0 && enabled == 0
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn prefers_reduced_motion() -> bool {
+    false
+}
+
+/// The DNS resolver for `config`, with the cache saved at `cache_path` loaded.
+/// Private windows don't pin what they visit on the IPFS node.
+///
+/// If the resolver can't be built, e.g. for an invalid proxy, the error is
+/// returned with a resolver that has no nodes, fallback or IPFS gateways, so
+/// nothing is looked up without the proxy the user asked for.
+#[cfg_attr(not(feature = "pinned-offline"), allow(unused_variables))]
+fn build_resolver(config: &Config, private: bool, cache_path: Option<&Path>) -> (GenesisDnsResolver, Option<String>) {
+    let mut resolver = match config.dns_resolver() {
+        Ok(resolver) => resolver,
+        Err(e) => {
+            error!("❌ Can't resolve anything: {}", e);
+            let mut resolver = GenesisDnsResolver::new_with_nodes(Vec::new(), false);
+            resolver.set_ipfs_gateways(Vec::new());
+            return (resolver, Some(e.to_string()));
+        }
+    };
+    #[cfg(feature = "pinned-offline")]
+    resolver.set_ipfs_api(config.ipfs_api_url.clone(), config.auto_pin && !private);
+    if let Some(path) = cache_path.filter(|path| path.exists()) {
+        match resolver.load_cache_from(path) {
+            Ok(loaded) => info!("📋 Loaded {} cached domains", loaded),
+            Err(e) => warn!("⚠️ Failed to load DNS cache: {}", e),
+        }
+    }
+    (resolver, None)
+}
+
+/// Repaint the window, if it exists yet, so a background result shows up while idle
+fn wake(ctx: &Option<egui::Context>) {
+    if let Some(ctx) = ctx {
+        ctx.request_repaint();
+    }
+}
+
+/// Host and page URL to fetch a favicon for; only plain http(s) pages have one
+fn favicon_source(url: &str) -> Option<(String, String)> {
+    let parsed = normalize_url(url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+    Some((parsed.host_str()?.to_string(), parsed.to_string()))
+}
 
 /// Smooth easing function for animations
 fn ease_out_cubic(t: f32) -> f32 {
@@ -35,76 +373,473 @@ enum TabAnimationType {
     Closing,
 }
 
+/// Section of the developer tools panel
+#[derive(Clone, Copy, PartialEq)]
+enum DevToolsTab {
+    Console,
+    Network,
+    Elements,
+}
+
+impl DevToolsTab {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Console => "Console",
+            Self::Network => "Network",
+            Self::Elements => "Elements",
+        }
+    }
+}
+
+/// Chrome colors for the light or dark theme
+#[derive(Clone, Copy)]
+struct ChromePalette {
+    tab_strip: Color32,
+    toolbar: Color32,
+    omnibox: Color32,
+    status_bar: Color32,
+    border: Color32,
+    tab_active: Color32,
+    tab_hovered: Color32,
+    tab_inactive: Color32,
+    text_active: Color32,
+    text_inactive: Color32,
+    icon: Color32,
+    icon_disabled: Color32,
+    hover_overlay: Color32,
+    progress: Color32,
+}
+
+impl ChromePalette {
+    /// Dark theme with a deep purple tab strip, so a private window can't
+    /// be mistaken for a normal one
+    fn private() -> Self {
+        Self {
+            tab_strip: Color32::from_rgb(53, 34, 84),
+            toolbar: Color32::from_rgb(59, 45, 82),
+            tab_active: Color32::from_rgb(59, 45, 82),
+            tab_hovered: Color32::from_rgba_unmultiplied(72, 50, 108, 240),
+            tab_inactive: Color32::from_rgba_unmultiplied(64, 42, 98, 220),
+            progress: Color32::from_rgb(197, 163, 255),
+            ..Self::dark()
+        }
+    }
+    
+    fn light() -> Self {
+        Self {
+            tab_strip: Color32::from_rgb(222, 225, 230),
+            toolbar: Color32::from_rgb(255, 255, 255),
+            omnibox: Color32::from_rgb(241, 243, 244),
+            status_bar: Color32::from_rgb(241, 243, 244),
+            border: Color32::from_rgb(218, 220, 224),
+            tab_active: Color32::from_rgb(255, 255, 255),
+            tab_hovered: Color32::from_rgba_unmultiplied(210, 213, 218, 240),
+            tab_inactive: Color32::from_rgba_unmultiplied(190, 194, 200, 220),
+            text_active: Color32::from_rgb(32, 33, 36),
+            text_inactive: Color32::from_rgba_unmultiplied(95, 99, 104, 200),
+            icon: Color32::from_rgb(95, 99, 104),
+            icon_disabled: Color32::from_rgb(180, 180, 180),
+            hover_overlay: Color32::from_rgba_premultiplied(0, 0, 0, 20),
+            progress: Color32::from_rgb(26, 115, 232),
+        }
+    }
+    
+    fn dark() -> Self {
+        Self {
+            tab_strip: Color32::from_rgb(32, 33, 36),
+            toolbar: Color32::from_rgb(53, 54, 58),
+            omnibox: Color32::from_rgb(32, 33, 36),
+            status_bar: Color32::from_rgb(41, 42, 45),
+            border: Color32::from_rgb(60, 64, 67),
+            tab_active: Color32::from_rgb(53, 54, 58),
+            tab_hovered: Color32::from_rgba_unmultiplied(48, 49, 52, 240),
+            tab_inactive: Color32::from_rgba_unmultiplied(41, 42, 45, 220),
+            text_active: Color32::from_rgb(232, 234, 237),
+            text_inactive: Color32::from_rgba_unmultiplied(154, 160, 166, 200),
+            icon: Color32::from_rgb(154, 160, 166),
+            icon_disabled: Color32::from_rgb(95, 99, 104),
+            hover_overlay: Color32::from_rgba_premultiplied(20, 20, 20, 20),
+            progress: Color32::from_rgb(138, 180, 248),
+        }
+    }
+}
+
 /// Modern Genesis Browser using egui
 pub struct ModernGenesisBrowser {
     ui_state: BrowserUIState,
     genesis_node_url: String,
+    /// Started without a profile: nothing outlives the window, which closes
+    /// with its last tab
+    private_window: bool,
+    
+    // DNS resolution, run on a Tokio runtime off the UI thread
+    dns_resolver: Arc<tokio::sync::Mutex<GenesisDnsResolver>>,
+    /// Settings the resolver is built from, kept to rebuild it for another node
+    resolver_config: Config,
+    /// Why the resolver couldn't be built from `resolver_config`; every lookup
+    /// fails with it rather than going out without the configured proxy
+    resolver_error: Option<String>,
+    runtime_handle: tokio::runtime::Handle,
+    /// Runtime owned by the browser when it wasn't started inside one
+    _runtime: Option<tokio::runtime::Runtime>,
+    dns_sender: mpsc::Sender<DnsOutcome>,
+    dns_receiver: mpsc::Receiver<DnsOutcome>,
+    /// In-flight lookups by tab id, aborted by the stop button
+    pending_navigations: HashMap<String, tokio::task::JoinHandle<()>>,
     
     // UI state
     url_input: String,
     search_query: String,
     
+    // Omnibox autocomplete
+    show_suggestions: bool,
+    selected_suggestion: Option<usize>,
+    
+    // Theme
+    dark_mode: bool,
+    
+    /// How fast tabs open and close; 5.0 takes a fifth of a second
+    animation_speed: f32,
+    /// Open and close tabs without animating
+    reduce_motion: bool,
+    
+    // Fullscreen, with whether the window was maximized before
+    fullscreen: bool,
+    fullscreen_chrome_revealed: bool,
+    restore_maximized: bool,
+    
     // Panel visibility
     show_devtools: bool,
+    devtools_tab: DevToolsTab,
+    /// Level shown in the console, `None` for all
+    console_filter: Option<ConsoleLevel>,
+    console_input: String,
+    script_runner: Option<ScriptRunner>,
+    /// Keep the network log when navigating
+    preserve_network_log: bool,
+    /// Request in the network log whose headers are shown
+    expanded_request: Option<usize>,
     show_downloads: bool,
     show_bookmarks: bool,
     show_history: bool,
     
+    // History panel search
+    history_filter: String,
+    
+    // Settings panel
+    node_url_input: String,
+    clear_history: bool,
+    clear_downloads: bool,
+    clear_passwords: bool,
+    clear_cookies: bool,
+    health_status: Option<String>,
+    health_receiver: Option<mpsc::Receiver<Result<(), DnsError>>>,
+    
+    // Periodic node health shown in the status bar
+    node_health: Option<NodeHealth>,
+    node_health_receiver: Option<mpsc::Receiver<NodeHealth>>,
+    /// When the last check started, `None` to check on the next frame
+    node_health_checked_at: Option<Instant>,
+    #[cfg(feature = "password-manager")]
+    password_panel: PasswordPanel,
+    
+    // Find in page
+    focus_find_field: bool,
+    
+    /// Short confirmation shown over the content, with when it appeared
+    toast: Option<(String, Instant)>,
+    
+    /// Host whose site settings window is open
+    site_settings_host: Option<String>,
+    
+    /// Keyboard shortcuts, dispatched and listed in the reference from here
+    shortcuts: Vec<ShortcutDef>,
+    show_shortcuts: bool,
+    
+    // Command palette, with what's typed and the highlighted row
+    show_command_palette: bool,
+    palette_query: String,
+    palette_selected: usize,
+    
+    // Confirm-on-quit prompt, with its "Don't ask again" box
+    show_close_confirm: bool,
+    dont_ask_close_again: bool,
+    /// The user agreed to close all tabs; the next close goes through
+    close_confirmed: bool,
+    
+    // Window geometry as last seen, saved on exit
+    window_state_path: Option<PathBuf>,
+    window_state: Option<WindowState>,
+    /// The restored position still has to be checked against the monitors
+    place_restored_window: bool,
+    
+    // Session restore
+    session_path: Option<PathBuf>,
+    /// Where the DNS cache is kept between runs
+    dns_cache_path: Option<PathBuf>,
+    /// Previous session waiting for the user to restore or dismiss it
+    pending_session_restore: bool,
+    last_session_save: Instant,
+    /// Last edit of genesis://notes not yet saved
+    notes_edited_at: Option<Instant>,
+    
     // Tab scrolling
     tab_scroll_offset: f32,
     ensure_last_tab_visible: bool,
+    /// Tab picked from the overflow menu, scrolled into view on the next frame
+    scroll_to_tab: Option<usize>,
     
     // Tab animations
     tab_animations: std::collections::HashMap<String, TabAnimation>,
     
+    // Favicons by host
+    favicons: HashMap<String, FaviconState>,
+    favicon_sender: mpsc::Sender<(String, Option<Vec<u8>>)>,
+    favicon_receiver: mpsc::Receiver<(String, Option<Vec<u8>>)>,
+    
+    // Feeds found on loaded pages, as (tab id, page URL, feed URLs)
+    feed_sender: mpsc::Sender<(String, String, Vec<String>)>,
+    feed_receiver: mpsc::Receiver<(String, String, Vec<String>)>,
+    show_feeds: bool,
+    
+    // Sources shown by `view-source:` tabs, by tab id: the URL fetched and
+    // its source, `None` while the fetch is running
+    page_sources: HashMap<String, (String, Option<PageSource>)>,
+    source_sender: mpsc::Sender<(String, String, anyhow::Result<PageSource>)>,
+    source_receiver: mpsc::Receiver<(String, String, anyhow::Result<PageSource>)>,
+    
+    // Downloads streamed to disk, with the tasks running them by download id
+    download_sender: mpsc::Sender<DownloadUpdate>,
+    download_receiver: mpsc::Receiver<DownloadUpdate>,
+    download_tasks: HashMap<String, tokio::task::JoinHandle<()>>,
+    
+    // Process memory for the status bar, with when it was read
+    resident_memory: Option<u64>,
+    memory_sampled_at: Instant,
+    /// Horizontal offset of tabs sliding into place after a reorder
+    tab_slide_offsets: HashMap<String, f32>,
+    
+    // Load progress line, by tab id: `None` while loading, then when the load ended
+    load_indicators: HashMap<String, Option<Instant>>,
+    
+    // Tab dragging
+    dragging_tab: Option<usize>,
+    /// Pointer movement since the dragged tab last changed position
+    drag_offset: f32,
+    
     // Performance metrics
     frame_time: f32,
     fps: f32,
     last_frame: std::time::Instant,
+    /// Recent frame times in seconds, oldest first, for the overlay's graph
+    frame_times: VecDeque<f32>,
+    show_performance_overlay: bool,
+    /// Shortest time between frames when vsync is off and `max_fps` is set
+    min_frame_interval: Option<Duration>,
+    /// Handed to background tasks so their results repaint an idle window
+    egui_ctx: Option<egui::Context>,
 }
 
 impl Default for ModernGenesisBrowser {
     fn default() -> Self {
-        Self::new("http://localhost:3000".to_string())
+        Self::from_config(&Config::default(), default_profile_dir())
     }
 }
 
 impl ModernGenesisBrowser {
     pub fn new(genesis_node_url: String) -> Self {
+        let config = Config {
+            genesis_nodes: vec![genesis_node_url],
+            ..Config::default()
+        };
+        Self::from_config(&config, default_profile_dir())
+    }
+    
+    /// Create the browser with settings from the user's config, persisting to
+    /// `profile_dir` or, for `None`, to an ephemeral profile
+    pub fn from_config(config: &Config, profile_dir: Option<PathBuf>) -> Self {
         info!("🎨 Creating Modern Genesis Browser with egui");
+        let genesis_node_url = config.primary_node().to_string();
+        
+        // Without a profile directory the whole session is private
+        let private = profile_dir.is_none();
+        let ui_state = BrowserUIState::with_profile(profile_dir);
+        ui_state.private_mode.set(private);
+        config.apply_to(&ui_state);
+        
+        let session_path = ui_state.profile_file(SESSION_FILE);
+        let window_state_path = ui_state.profile_file(WINDOW_STATE_FILE);
+        let pending_session_restore = session_path.as_ref().map_or(false, |path| path.exists());
+        
+        // Reuse the caller's runtime if there is one, otherwise bring our own
+        let (runtime_handle, runtime) = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => (handle, None),
+            Err(_) => {
+                let runtime = tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(1)
+                    .enable_all()
+                    .build()
+                    .expect("failed to start the DNS runtime");
+                (runtime.handle().clone(), Some(runtime))
+            }
+        };
+        let dns_cache_path = ui_state.profile_file(DNS_CACHE_FILE);
+        let (dns_resolver, resolver_error) = build_resolver(config, private, dns_cache_path.as_deref());
+        if let Some(e) = &resolver_error {
+            *ui_state.genesis_node_status.borrow_mut() = format!("DNS error: {}", e);
+        }
+        let (dns_sender, dns_receiver) = mpsc::channel();
+        let (favicon_sender, favicon_receiver) = mpsc::channel();
+        let (feed_sender, feed_receiver) = mpsc::channel();
+        let (source_sender, source_receiver) = mpsc::channel();
+        let (download_sender, download_receiver) = mpsc::channel();
+        let node_url_input = genesis_node_url.clone();
         
         Self {
-            ui_state: BrowserUIState::default(),
+            ui_state,
             genesis_node_url,
+            private_window: private,
+            dns_resolver: Arc::new(tokio::sync::Mutex::new(dns_resolver)),
+            resolver_config: config.clone(),
+            resolver_error,
+            runtime_handle,
+            _runtime: runtime,
+            dns_sender,
+            dns_receiver,
+            pending_navigations: HashMap::new(),
             url_input: "genesis://welcome".to_string(),
             search_query: String::new(),
+            show_suggestions: false,
+            selected_suggestion: None,
+            dark_mode: config.theme == Theme::Dark,
+            animation_speed: config.animation_speed,
+            reduce_motion: config.reduce_motion || prefers_reduced_motion(),
+            fullscreen: false,
+            fullscreen_chrome_revealed: false,
+            restore_maximized: false,
             show_devtools: false,
+            devtools_tab: DevToolsTab::Console,
+            console_filter: None,
+            console_input: String::new(),
+            script_runner: None,
+            preserve_network_log: false,
+            expanded_request: None,
             show_downloads: false,
             show_bookmarks: false,
             show_history: false,
+            history_filter: String::new(),
+            node_url_input,
+            clear_history: true,
+            clear_downloads: false,
+            clear_passwords: false,
+            clear_cookies: false,
+            health_status: None,
+            health_receiver: None,
+            node_health: None,
+            node_health_receiver: None,
+            node_health_checked_at: None,
+            #[cfg(feature = "password-manager")]
+            password_panel: PasswordPanel::default(),
+            focus_find_field: false,
+            toast: None,
+            site_settings_host: None,
+            shortcuts: shortcuts(),
+            show_shortcuts: false,
+            show_command_palette: false,
+            palette_query: String::new(),
+            palette_selected: 0,
+            show_close_confirm: false,
+            dont_ask_close_again: false,
+            close_confirmed: false,
+            window_state_path,
+            window_state: None,
+            place_restored_window: false,
+            session_path,
+            dns_cache_path,
+            pending_session_restore,
+            last_session_save: Instant::now(),
+            notes_edited_at: None,
             tab_scroll_offset: 0.0,
             ensure_last_tab_visible: false,
+            scroll_to_tab: None,
             tab_animations: HashMap::new(),
+            favicons: HashMap::new(),
+            favicon_sender,
+            favicon_receiver,
+            feed_sender,
+            feed_receiver,
+            show_feeds: false,
+            page_sources: HashMap::new(),
+            source_sender,
+            source_receiver,
+            download_sender,
+            download_receiver,
+            download_tasks: HashMap::new(),
+            resident_memory: resident_memory_bytes(),
+            memory_sampled_at: Instant::now(),
+            tab_slide_offsets: HashMap::new(),
+            load_indicators: HashMap::new(),
+            dragging_tab: None,
+            drag_offset: 0.0,
             frame_time: 0.0,
             fps: 144.0,
             last_frame: std::time::Instant::now(),
+            frame_times: VecDeque::with_capacity(FRAME_HISTORY),
+            show_performance_overlay: false,
+            min_frame_interval: (!config.vsync && config.max_fps > 0)
+                .then(|| Duration::from_secs_f32(1.0 / config.max_fps as f32)),
+            egui_ctx: None,
         }
     }
     
-    /// Run the modern browser
+    /// Evaluate console input with `runner`; without one the console reports
+    /// that no script engine is attached
+    pub fn set_script_runner(&mut self, runner: ScriptRunner) {
+        self.script_runner = Some(runner);
+    }
+    
+    /// Run the modern browser with the default config and profile
     pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+        Self::run_with_config(Config::default(), default_profile_dir())
+    }
+    
+    /// Run the modern browser with the user's config and profile
+    pub fn run_with_config(config: Config, profile_dir: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
         info!("🚀 Starting Modern Genesis Browser with egui");
         
+        // Reopen where the window was left; the first run uses the config's size
+        let saved_window = profile_dir.as_ref()
+            .map(|dir| dir.join(WINDOW_STATE_FILE))
+            .filter(|path| path.exists())
+            .and_then(|path| match WindowState::load(&path) {
+                Ok(state) => Some(state),
+                Err(e) => {
+                    warn!("⚠️ Failed to load window state from {}: {}", path.display(), e);
+                    None
+                }
+            });
+        let mut viewport = egui::ViewportBuilder::default();
+        match &saved_window {
+            Some(state) => {
+                viewport = viewport
+                    .with_inner_size([state.width, state.height])
+                    .with_maximized(state.maximized);
+                if let Some(position) = state.position {
+                    viewport = viewport.with_position(position);
+                }
+            }
+            None => viewport = viewport.with_inner_size([config.window_width as f32, config.window_height as f32]),
+        }
+        
         let options = eframe::NativeOptions {
-            viewport: egui::ViewportBuilder::default()
-                .with_inner_size([1400.0, 900.0])
+            viewport: viewport
                 .with_min_inner_size([800.0, 600.0])
-                .with_title("Genesis Browser")
+                .with_title(if profile_dir.is_none() { "Genesis Browser (Private)" } else { "Genesis Browser" })
                 .with_decorations(false) // Remove system title bar
                 .with_resizable(true)
                 .with_transparent(false)
                 .with_icon(eframe::icon_data::from_png_bytes(&[]).unwrap_or_default()),
-            vsync: false, // For maximum performance
+            vsync: config.vsync,
             multisampling: 4, // Anti-aliasing for smooth graphics
             depth_buffer: 24,
             ..Default::default()
@@ -113,7 +848,12 @@ impl ModernGenesisBrowser {
         eframe::run_native(
             "Genesis Browser",
             options,
-            Box::new(|_cc| Box::new(ModernGenesisBrowser::default())),
+            Box::new(move |_cc| {
+                let mut browser = ModernGenesisBrowser::from_config(&config, profile_dir);
+                browser.place_restored_window = saved_window.is_some();
+                browser.window_state = saved_window;
+                Box::new(browser)
+            }),
         )?;
         
         Ok(())
@@ -122,13 +862,190 @@ impl ModernGenesisBrowser {
 
 impl eframe::App for ModernGenesisBrowser {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Without vsync nothing else paces the frames
+        if let Some(interval) = self.min_frame_interval {
+            if let Some(remaining) = interval.checked_sub(self.last_frame.elapsed()) {
+                std::thread::sleep(remaining);
+            }
+        }
+        if self.egui_ctx.is_none() {
+            self.egui_ctx = Some(ctx.clone());
+        }
+        
         // Calculate FPS
         let now = std::time::Instant::now();
         self.frame_time = now.duration_since(self.last_frame).as_secs_f32();
         self.fps = 1.0 / self.frame_time.max(0.001);
         self.last_frame = now;
+        if self.frame_times.len() == FRAME_HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(self.frame_time);
+        
+        // Chrome light or dark theme with exact colors
+        ctx.set_visuals(self.chrome_visuals());
+        
+        self.handle_keyboard_shortcuts(ctx);
+        self.poll_dns_results();
+        self.poll_favicons(ctx);
+        self.poll_feeds();
+        self.poll_page_sources();
+        self.poll_downloads();
+        self.refresh_node_health();
+        self.advance_load_progress();
+        self.update_fullscreen_reveal(ctx);
+        self.track_window_state(ctx);
+        
+        // Main browser UI; fullscreen shows only the content unless the chrome is revealed
+        if !self.fullscreen || self.fullscreen_chrome_revealed {
+            self.render_top_panel(ctx);
+        }
+        self.render_session_restore_bar(ctx);
+        self.render_main_content(ctx);
+        if !self.fullscreen {
+            self.render_status_bar(ctx);
+        }
+        self.render_side_panels(ctx);
+        self.render_find_bar(ctx);
+        self.render_toast(ctx);
+        self.render_site_settings_window(ctx);
+        self.render_shortcuts_overlay(ctx);
+        self.render_command_palette(ctx);
+        self.render_performance_overlay(ctx);
+        self.render_close_confirm(ctx);
+        
+        if let Err(e) = self.ui_state.flush_bookmarks() {
+            warn!("⚠️ Failed to save bookmarks: {}", e);
+        }
+        if let Err(e) = self.ui_state.flush_site_settings() {
+            warn!("⚠️ Failed to save site settings: {}", e);
+        }
+        if let Err(e) = self.ui_state.flush_feeds() {
+            warn!("⚠️ Failed to save feeds: {}", e);
+        }
+        if let Err(e) = self.ui_state.flush_preferences() {
+            warn!("⚠️ Failed to save preferences: {}", e);
+        }
+        if self.notes_edited_at.map_or(false, |edited_at| edited_at.elapsed() >= NOTES_SAVE_DELAY) {
+            self.notes_edited_at = None;
+            if let Err(e) = self.ui_state.flush_notes() {
+                warn!("⚠️ Failed to save notes: {}", e);
+            }
+        }
+        
+        if self.last_session_save.elapsed() >= SESSION_SAVE_INTERVAL {
+            self.save_session();
+        }
+        
+        self.schedule_repaint(ctx);
+    }
+    
+    /// Hold back a window close that would drop several tabs until the user
+    /// confirms it; the prompt is shown by `render_close_confirm`
+    fn on_close_event(&mut self) -> bool {
+        if self.close_confirmed || !self.should_confirm_close() {
+            return true;
+        }
+        self.show_close_confirm = true;
+        wake(&self.egui_ctx);
+        false
+    }
+    
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_session();
+        if let (Some(path), Some(state)) = (&self.window_state_path, &self.window_state) {
+            if let Err(e) = state.save(path) {
+                warn!("⚠️ Failed to save window state: {}", e);
+            }
+        }
+        self.save_dns_cache();
+        if let Err(e) = self.ui_state.flush_bookmarks() {
+            warn!("⚠️ Failed to save bookmarks: {}", e);
+        }
+        if let Err(e) = self.ui_state.flush_site_settings() {
+            warn!("⚠️ Failed to save site settings: {}", e);
+        }
+        if let Err(e) = self.ui_state.flush_feeds() {
+            warn!("⚠️ Failed to save feeds: {}", e);
+        }
+        if let Err(e) = self.ui_state.flush_preferences() {
+            warn!("⚠️ Failed to save preferences: {}", e);
+        }
+        if let Err(e) = self.ui_state.flush_notes() {
+            warn!("⚠️ Failed to save notes: {}", e);
+        }
+    }
+}
+
+impl ModernGenesisBrowser {
+    /// Keep repainting while something moves; otherwise wait for input, a
+    /// background result or the next timed change
+    fn schedule_repaint(&self, ctx: &egui::Context) {
+        let animating = !self.tab_animations.is_empty()
+            || !self.tab_slide_offsets.is_empty()
+            || self.dragging_tab.is_some()
+            || !self.load_indicators.is_empty()
+            || self.health_receiver.is_some();
+        if animating {
+            ctx.request_repaint();
+            return;
+        }
+        
+        let mut next = MEMORY_SAMPLE_INTERVAL.saturating_sub(self.memory_sampled_at.elapsed());
+        if let Some(checked_at) = self.node_health_checked_at {
+            next = next.min(NODE_HEALTH_INTERVAL.saturating_sub(checked_at.elapsed()));
+        }
+        if let Some((_, shown_at)) = &self.toast {
+            next = next.min(TOAST_DURATION.saturating_sub(shown_at.elapsed()));
+        }
+        if let Some(edited_at) = self.notes_edited_at {
+            next = next.min(NOTES_SAVE_DELAY.saturating_sub(edited_at.elapsed()));
+        }
+        ctx.request_repaint_after(next);
+    }
+    
+    /// Colors for the current theme; private mode has its own
+    fn palette(&self) -> ChromePalette {
+        if self.ui_state.private_mode.get() {
+            ChromePalette::private()
+        } else if self.dark_mode {
+            ChromePalette::dark()
+        } else {
+            ChromePalette::light()
+        }
+    }
+    
+    /// Chrome-like visuals with exact colors for the current theme
+    fn chrome_visuals(&self) -> egui::Visuals {
+        if self.dark_mode {
+            let mut visuals = egui::Visuals::dark();
+            
+            // Chrome's dark theme colors
+            visuals.window_fill = Color32::from_rgb(53, 54, 58);
+            visuals.panel_fill = Color32::from_rgb(53, 54, 58);
+            visuals.faint_bg_color = Color32::from_rgb(32, 33, 36);
+            visuals.extreme_bg_color = Color32::from_rgb(32, 33, 36);
+            
+            // Dark button styling
+            visuals.widgets.noninteractive.bg_fill = Color32::from_rgb(53, 54, 58);
+            visuals.widgets.inactive.bg_fill = Color32::from_rgb(53, 54, 58);
+            visuals.widgets.hovered.bg_fill = Color32::from_rgb(66, 67, 71);
+            visuals.widgets.active.bg_fill = Color32::from_rgb(32, 33, 36);
+            
+            // Dark borders and strokes
+            visuals.widgets.noninteractive.bg_stroke = Stroke::new(1.0, Color32::from_rgb(60, 64, 67));
+            visuals.widgets.inactive.bg_stroke = Stroke::new(1.0, Color32::from_rgb(60, 64, 67));
+            visuals.widgets.hovered.bg_stroke = Stroke::new(1.0, Color32::from_rgb(95, 99, 104));
+            
+            // Light text on dark backgrounds
+            visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.0, Color32::from_rgb(232, 234, 237));
+            visuals.widgets.inactive.fg_stroke = Stroke::new(1.0, Color32::from_rgb(232, 234, 237));
+            
+            visuals.selection.bg_fill = Color32::from_rgb(138, 180, 248);
+            
+            return visuals;
+        }
         
-        // Set Chrome-like light theme with exact colors
         let mut visuals = egui::Visuals::light();
         
         // Exact Chrome colors from DevTools
@@ -155,42 +1072,690 @@ impl eframe::App for ModernGenesisBrowser {
         // Chrome selection colors
         visuals.selection.bg_fill = Color32::from_rgb(138, 180, 248);
         
-        ctx.set_visuals(visuals);
-        
-        // Request repaint for smooth animation
-        ctx.request_repaint();
+        visuals
+    }
+    
+    /// Save open tabs to the profile, unless the previous session is still on offer
+    fn save_session(&mut self) {
+        self.last_session_save = Instant::now();
+        if self.pending_session_restore || self.ui_state.private_mode.get() {
+            return;
+        }
         
-        // Main browser UI
-        self.render_top_panel(ctx);
-        self.render_main_content(ctx);
-        self.render_status_bar(ctx);
-        self.render_side_panels(ctx);
+        if let Some(path) = &self.session_path {
+            if let Err(e) = self.ui_state.save_session(path) {
+                warn!("⚠️ Failed to save session to {}: {}", path.display(), e);
+            }
+        }
     }
-}
-
-impl ModernGenesisBrowser {
-    /// Render custom title bar with tabs (exact Chrome replica)
-    fn render_top_panel(&mut self, ctx: &egui::Context) {
-        // Chrome tab area with exact height and color
-        TopBottomPanel::top("tab_area")
-            .exact_height(35.0) // Chrome's exact tab height
+    
+    /// Persist the DNS cache; skipped if a lookup is updating the resolver right then
+    fn save_dns_cache(&self) {
+        let Some(path) = self.dns_cache_path.as_ref().filter(|_| !self.ui_state.private_mode.get()) else {
+            return;
+        };
+        match self.dns_resolver.try_lock() {
+            Ok(resolver) => {
+                if let Err(e) = resolver.save_cache_to(path) {
+                    warn!("⚠️ Failed to save DNS cache: {}", e);
+                }
+            }
+            Err(_) => warn!("⚠️ DNS resolver busy, not saving its cache"),
+        }
+    }
+    
+    /// Closing loses several tabs: more than one is open, they won't be offered
+    /// back through a pending session restore and the window isn't private
+    fn should_confirm_close(&self) -> bool {
+        self.ui_state.confirm_close_tabs.get()
+            && self.ui_state.tabs.borrow().len() > 1
+            && !self.pending_session_restore
+            && !self.ui_state.private_mode.get()
+    }
+    
+    /// Close the window, asking first if that would close several tabs
+    fn request_close(&mut self, ctx: &egui::Context) {
+        if self.close_confirmed || !self.should_confirm_close() {
+            self.close_confirmed = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        } else {
+            self.show_close_confirm = true;
+        }
+    }
+    
+    /// Modal asking whether to close a window with several tabs
+    fn render_close_confirm(&mut self, ctx: &egui::Context) {
+        if !self.show_close_confirm {
+            return;
+        }
+        
+        let tab_count = self.ui_state.tabs.borrow().len();
+        let (mut close, mut cancel) = (false, false);
+        egui::Window::new("Close all tabs?")
+            .id(egui::Id::new("close_confirm"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
             .show(ctx, |ui| {
-                ui.style_mut().visuals.panel_fill = Color32::from_rgb(222, 225, 230); // Chrome's exact color
-                
-                // Get full panel rect
-                let panel_rect = ui.available_rect_before_wrap();
+                ui.label(format!("You are about to close {} tabs.", tab_count));
+                ui.checkbox(&mut self.dont_ask_close_again, "Don't ask again");
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    close = ui.button("Close all").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+        cancel |= ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape));
+        
+        if close {
+            if self.dont_ask_close_again {
+                self.ui_state.set_confirm_close_tabs(false);
+            }
+            info!("🚪 Closing window with {} tabs", tab_count);
+            self.show_close_confirm = false;
+            // The session is saved by `on_exit` on the way out
+            self.close_confirmed = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        } else if cancel {
+            self.show_close_confirm = false;
+            self.dont_ask_close_again = false;
+        }
+    }
+    
+    /// Offer to bring back the tabs from the last run
+    fn render_session_restore_bar(&mut self, ctx: &egui::Context) {
+        if !self.pending_session_restore {
+            return;
+        }
+        
+        TopBottomPanel::top("session_restore_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                ui.label("Restore the tabs from your last session?");
                 
-                // Draggable area (invisible background)
-                let drag_response = ui.allocate_rect(panel_rect, egui::Sense::click_and_drag());
-                if drag_response.dragged() {
-                    ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+                if ui.button("Restore").clicked() {
+                    if let Some(path) = &self.session_path {
+                        match self.ui_state.load_session(path) {
+                            Ok(()) => {
+                                self.tab_animations.clear();
+                                if let Some(tab) = self.ui_state.get_active_tab() {
+                                    self.url_input = tab.url;
+                                }
+                            }
+                            Err(e) => warn!("⚠️ Failed to restore session: {}", e),
+                        }
+                    }
+                    self.pending_session_restore = false;
+                }
+                if ui.button("Dismiss").clicked() {
+                    self.pending_session_restore = false;
+                }
+            });
+        });
+    }
+    
+    /// Dispatch shortcuts from the `shortcuts` table, which the shortcut
+    /// reference is rendered from as well
+    fn handle_keyboard_shortcuts(&mut self, ctx: &egui::Context) {
+        let text_focused = ctx.memory(|m| m.focus().is_some());
+        let mut fired = Vec::new();
+        
+        ctx.input_mut(|i| {
+            for shortcut in &self.shortcuts {
+                if !self.shortcut_applies(shortcut.action) {
+                    continue;
+                }
+                let position = shortcut.keys.iter().position(|trigger| {
+                    if text_focused && !trigger.works_while_typing() {
+                        return false;
+                    }
+                    match *trigger {
+                        Trigger::Key(modifiers, key) => i.consume_key(modifiers, key),
+                        Trigger::Char(c) => {
+                            let typed = c.to_string();
+                            let before = i.events.len();
+                            i.events.retain(|event| !matches!(event, egui::Event::Text(text) if *text == typed));
+                            i.events.len() != before
+                        }
+                        Trigger::Pointer(button) => i.pointer.button_pressed(button),
+                    }
+                });
+                if let Some(position) = position {
+                    fired.push((shortcut.action, position));
+                }
+            }
+        });
+        
+        for (action, position) in fired {
+            self.run_shortcut(ctx, action, position);
+        }
+    }
+    
+    /// Shortcuts that would do nothing right now are left to other handlers
+    fn shortcut_applies(&self, action: ShortcutAction) -> bool {
+        match action {
+            // The close prompt handles its own Escape
+            ShortcutAction::Escape => !self.show_close_confirm
+                && (self.show_command_palette || self.show_shortcuts || self.ui_state.find_active.get() || self.fullscreen),
+            ShortcutAction::UndoBookmark | ShortcutAction::RedoBookmark => self.show_bookmarks,
+            _ => true,
+        }
+    }
+    
+    /// Perform a shortcut; `position` is the index of the trigger that fired it
+    fn run_shortcut(&mut self, ctx: &egui::Context, action: ShortcutAction, position: usize) {
+        let tab_count = self.ui_state.tabs.borrow().len();
+        let active_index = self.ui_state.active_tab_index.get();
+        
+        match action {
+            ShortcutAction::NewTab => self.handle_new_tab(),
+            ShortcutAction::NewPrivateWindow => self.open_private_window(),
+            ShortcutAction::CloseTab => {
+                let active_is_pinned = self.ui_state.get_active_tab().map_or(false, |tab| tab.pinned);
+                if !active_is_pinned {
+                    self.start_closing_tab(active_index);
+                }
+            }
+            ShortcutAction::ReopenClosedTab => self.handle_reopen_closed_tab(),
+            ShortcutAction::NextTab if tab_count > 0 => self.switch_tab((active_index + 1) % tab_count),
+            ShortcutAction::PreviousTab if tab_count > 0 => self.switch_tab((active_index + tab_count - 1) % tab_count),
+            ShortcutAction::NextTab | ShortcutAction::PreviousTab => {}
+            ShortcutAction::JumpToTab => {
+                if position < tab_count {
+                    self.switch_tab(position);
+                }
+            }
+            ShortcutAction::Back => self.go_back(),
+            ShortcutAction::Forward => self.go_forward(),
+            ShortcutAction::ReloadAll => self.reload_all_tabs(),
+            ShortcutAction::ToggleHistory => self.show_history = !self.show_history,
+            ShortcutAction::UndoBookmark => {
+                self.ui_state.undo_bookmark_op();
+            }
+            ShortcutAction::RedoBookmark => {
+                self.ui_state.redo_bookmark_op();
+            }
+            ShortcutAction::OpenNotes => self.open_notes(),
+            ShortcutAction::Find => {
+                self.ui_state.find_active.set(true);
+                self.focus_find_field = true;
+            }
+            ShortcutAction::CopyUrl => self.copy_active_url(ctx, false),
+            ShortcutAction::ViewSource => self.view_active_source(),
+            ShortcutAction::ZoomIn => self.ui_state.zoom_active_tab(ZOOM_STEP),
+            ShortcutAction::ZoomOut => self.ui_state.zoom_active_tab(-ZOOM_STEP),
+            ShortcutAction::ZoomReset => self.ui_state.reset_active_zoom(),
+            ShortcutAction::ToggleFullscreen => self.set_fullscreen(ctx, !self.fullscreen),
+            ShortcutAction::Escape => {
+                if self.show_command_palette {
+                    self.show_command_palette = false;
+                } else if self.show_shortcuts {
+                    self.show_shortcuts = false;
+                } else if self.ui_state.find_active.get() {
+                    self.ui_state.close_find();
+                } else if self.fullscreen {
+                    self.set_fullscreen(ctx, false);
+                }
+            }
+            ShortcutAction::ShowShortcuts => self.show_shortcuts = !self.show_shortcuts,
+            ShortcutAction::CommandPalette => self.toggle_command_palette(),
+            ShortcutAction::TogglePerformanceOverlay => self.show_performance_overlay = !self.show_performance_overlay,
+        }
+    }
+    
+    fn toggle_command_palette(&mut self) {
+        self.show_command_palette = !self.show_command_palette;
+        self.palette_query.clear();
+        self.palette_selected = 0;
+    }
+    
+    /// Entries of the command palette: the actions, then every open tab
+    fn palette_commands(&self) -> Vec<Command<Self>> {
+        let label = |action| shortcut_label(&self.shortcuts, action);
+        let theme = if self.dark_mode { "Switch to the light theme" } else { "Switch to the dark theme" };
+        let mut commands = vec![
+            Command::new("New tab", label(ShortcutAction::NewTab), |browser: &mut Self| browser.handle_new_tab()),
+            Command::new("Close tab", label(ShortcutAction::CloseTab), |browser: &mut Self| {
+                if !browser.ui_state.get_active_tab().map_or(false, |tab| tab.pinned) {
+                    browser.start_closing_tab(browser.ui_state.active_tab_index.get());
+                }
+            }),
+            Command::new("Reopen closed tab", label(ShortcutAction::ReopenClosedTab), |browser: &mut Self| browser.handle_reopen_closed_tab()),
+            Command::new("New private window", label(ShortcutAction::NewPrivateWindow), |browser: &mut Self| browser.open_private_window()),
+            Command::new(theme, None, |browser: &mut Self| browser.dark_mode = !browser.dark_mode),
+            Command::new("Open bookmarks", None, |browser: &mut Self| browser.show_bookmarks = true),
+            Command::new("Open history", label(ShortcutAction::ToggleHistory), |browser: &mut Self| browser.show_history = true),
+            Command::new("Open notes", label(ShortcutAction::OpenNotes), |browser: &mut Self| browser.open_notes()),
+            Command::new("Open downloads", None, |browser: &mut Self| browser.show_downloads = true),
+            Command::new("Clear history", None, |browser: &mut Self| {
+                browser.ui_state.clear_browsing_data(true, false, false, false);
+                browser.toast = Some(("History cleared".to_string(), Instant::now()));
+            }),
+            Command::new("Find in page", label(ShortcutAction::Find), |browser: &mut Self| {
+                browser.ui_state.find_active.set(true);
+                browser.focus_find_field = true;
+            }),
+            Command::new("View page source", label(ShortcutAction::ViewSource), |browser: &mut Self| browser.view_active_source()),
+            Command::new("Keyboard shortcuts", label(ShortcutAction::ShowShortcuts), |browser: &mut Self| browser.show_shortcuts = true),
+        ];
+        for (index, tab) in self.ui_state.tabs.borrow().iter().enumerate() {
+            commands.push(Command::new(
+                format!("Go to tab: {}", tab.title),
+                None,
+                move |browser: &mut Self| browser.switch_tab(index),
+            ));
+        }
+        commands
+    }
+    
+    /// Ctrl+Shift+P overlay listing `palette_commands`. It keeps the keyboard
+    /// while open: typing filters, the arrows pick, Enter runs and Escape closes.
+    fn render_command_palette(&mut self, ctx: &egui::Context) {
+        if !self.show_command_palette {
+            return;
+        }
+        
+        let commands = self.palette_commands();
+        let matches = filter_commands(&commands, &self.palette_query);
+        let (up, down, enter) = ctx.input_mut(|i| (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+        ));
+        if !matches.is_empty() {
+            if down {
+                self.palette_selected = (self.palette_selected + 1) % matches.len();
+            }
+            if up {
+                self.palette_selected = (self.palette_selected + matches.len() - 1) % matches.len();
+            }
+        }
+        self.palette_selected = self.palette_selected.min(matches.len().saturating_sub(1));
+        let mut chosen = if enter { matches.get(self.palette_selected).copied() } else { None };
+        
+        egui::Window::new("Command palette")
+            .id(egui::Id::new("command_palette"))
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .fixed_size([420.0, 0.0])
+            .anchor(egui::Align2::CENTER_TOP, Vec2::new(0.0, 80.0))
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.palette_query)
+                        .hint_text("Type a command or tab name")
+                        .desired_width(f32::INFINITY)
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.palette_selected = 0;
+                }
+                ui.separator();
+                
+                ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    if matches.is_empty() {
+                        ui.label(RichText::new("No matching commands").color(Color32::GRAY));
+                    }
+                    for (row, &index) in matches.iter().enumerate() {
+                        let command = &commands[index];
+                        let selected = row == self.palette_selected;
+                        let response = ui.horizontal(|ui| {
+                            let response = ui.selectable_label(selected, &command.name);
+                            if let Some(shortcut) = &command.shortcut {
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    ui.label(RichText::new(shortcut).small().monospace().color(Color32::GRAY));
+                                });
+                            }
+                            response
+                        }).inner;
+                        if selected && (up || down) {
+                            response.scroll_to_me(None);
+                        }
+                        if response.clicked() {
+                            chosen = Some(index);
+                        }
+                    }
+                });
+            });
+        
+        if let Some(index) = chosen {
+            self.show_command_palette = false;
+            info!("🔍 Running \"{}\" from the command palette", commands[index].name);
+            (commands[index].run)(self);
+        }
+    }
+    
+    /// Reference of every shortcut, grouped by category
+    fn render_shortcuts_overlay(&mut self, ctx: &egui::Context) {
+        if !self.show_shortcuts {
+            return;
+        }
+        
+        let mut open = true;
+        egui::Window::new("⌨ Keyboard shortcuts")
+            .id(egui::Id::new("shortcuts_overlay"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                for category in ShortcutCategory::ALL {
+                    ui.label(RichText::new(category.label()).strong());
+                    egui::Grid::new(("shortcuts", category.label()))
+                        .num_columns(2)
+                        .min_col_width(160.0)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for shortcut in self.shortcuts.iter().filter(|shortcut| shortcut.category == category) {
+                                ui.label(RichText::new(shortcut.keys_label()).monospace());
+                                ui.label(shortcut.description);
+                                ui.end_row();
+                            }
+                        });
+                    ui.add_space(8.0);
+                }
+            });
+        if !open {
+            self.show_shortcuts = false;
+        }
+    }
+    
+    /// Enter or leave borderless fullscreen. Leaving restores the window to
+    /// maximized or normal, whichever it was before.
+    fn set_fullscreen(&mut self, ctx: &egui::Context, fullscreen: bool) {
+        if fullscreen == self.fullscreen {
+            return;
+        }
+        
+        if fullscreen {
+            self.restore_maximized = ctx.input(|i| i.viewport().maximized.unwrap_or(false));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(true));
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(false));
+            // Some window managers drop the maximized state on the way out
+            ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(self.restore_maximized));
+        }
+        self.fullscreen = fullscreen;
+        self.fullscreen_chrome_revealed = false;
+        info!("🖥️ Fullscreen {}", if fullscreen { "on" } else { "off" });
+    }
+    
+    /// Note the window's geometry for the next start. On the first frame a
+    /// restored window is moved back on-screen if the monitors changed.
+    fn track_window_state(&mut self, ctx: &egui::Context) {
+        let (inner, outer, maximized, monitor_size) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (viewport.inner_rect, viewport.outer_rect, viewport.maximized.unwrap_or(false), viewport.monitor_size)
+        });
+        let monitor_size = monitor_size.map(|size| [size.x, size.y]);
+        
+        if std::mem::take(&mut self.place_restored_window) {
+            // egui only knows the monitor the window opened on, so a different
+            // monitor than last time is taken to mean the old one is gone
+            if let (Some(saved), Some(size)) = (&self.window_state, monitor_size) {
+                let placed = saved.placed_on(&[Monitor { position: [0.0, 0.0], size }]);
+                if saved.monitor_size != Some(size) && placed != *saved {
+                    info!("🪟 Saved window position is off-screen, moving it back");
+                    if let Some(position) = placed.position {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position.into()));
+                    }
+                    ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(Vec2::new(placed.width, placed.height)));
+                }
+            }
+        }
+        
+        // Fullscreen and maximized windows keep the size to return to
+        if self.fullscreen {
+            return;
+        }
+        let (Some(inner), Some(outer)) = (inner, outer) else {
+            return;
+        };
+        self.window_state = Some(match self.window_state.take() {
+            Some(previous) if maximized => WindowState { maximized: true, monitor_size, ..previous },
+            _ => WindowState {
+                width: inner.width(),
+                height: inner.height(),
+                position: Some([outer.min.x, outer.min.y]),
+                maximized,
+                monitor_size,
+            },
+        });
+    }
+    
+    /// In fullscreen, show the tab strip and toolbar while the pointer is at the top edge
+    fn update_fullscreen_reveal(&mut self, ctx: &egui::Context) {
+        if !self.fullscreen {
+            return;
+        }
+        match ctx.input(|i| i.pointer.hover_pos()).map(|pos| pos.y) {
+            Some(y) if y <= FULLSCREEN_REVEAL_EDGE => self.fullscreen_chrome_revealed = true,
+            // Keep the toolbar while the omnibox is being typed in
+            Some(y) if y > FULLSCREEN_CHROME_HEIGHT - TOOLBAR_HEIGHT + self.toolbar_height() && !ctx.wants_keyboard_input() => {
+                self.fullscreen_chrome_revealed = false;
+            }
+            _ => {}
+        }
+    }
+    
+    /// Toolbar height, taller in accessibility mode so the enlarged buttons fit
+    fn toolbar_height(&self) -> f32 {
+        TOOLBAR_HEIGHT.max(32.0 * self.ui_state.hit_scale() + 8.0)
+    }
+    
+    /// Copy the active tab's address, or a Markdown link to it, to the clipboard
+    fn copy_active_url(&mut self, ctx: &egui::Context, as_markdown: bool) {
+        let Some(tab) = self.ui_state.get_active_tab() else {
+            return;
+        };
+        
+        let text = if as_markdown { markdown_link(&tab.title, &tab.url) } else { tab.url };
+        ctx.output_mut(|o| o.copied_text = text);
+        self.toast = Some(("Copied!".to_string(), Instant::now()));
+    }
+    
+    /// Window with a site's overrides of the JavaScript, image, cookie and zoom settings
+    fn render_site_settings_window(&mut self, ctx: &egui::Context) {
+        let Some(host) = self.site_settings_host.clone() else {
+            return;
+        };
+        
+        let mut settings = self.ui_state.site_settings_for_host(&host);
+        let original = settings.clone();
+        let active_zoom = self.ui_state.get_active_tab().map_or(1.0, |tab| tab.zoom);
+        let mut open = true;
+        
+        egui::Window::new(format!("Site settings — {}", host))
+            .id(egui::Id::new("site_settings"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("site_settings_grid").num_columns(2).show(ui, |ui| {
+                    for (label, value) in [
+                        ("JavaScript", &mut settings.javascript),
+                        ("Images", &mut settings.images),
+                        ("Cookies", &mut settings.cookies),
+                    ] {
+                        ui.label(label);
+                        let selected = match *value {
+                            None => "Default",
+                            Some(true) => "Allow",
+                            Some(false) => "Block",
+                        };
+                        egui::ComboBox::from_id_source(("site_setting", label))
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(value, None, "Default");
+                                ui.selectable_value(value, Some(true), "Allow");
+                                ui.selectable_value(value, Some(false), "Block");
+                            });
+                        ui.end_row();
+                    }
+                    
+                    ui.label("Zoom");
+                    let mut remember_zoom = settings.zoom.is_some();
+                    let zoom = settings.zoom.unwrap_or(active_zoom);
+                    if ui.checkbox(&mut remember_zoom, format!("Always {:.0}%", zoom * 100.0)).changed() {
+                        settings.zoom = remember_zoom.then_some(active_zoom);
+                    }
+                    ui.end_row();
+                });
+                
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Reset all").clicked() {
+                        settings = SiteSettings::default();
+                    }
+                    ui.label(RichText::new("Applies from the next page load").small().weak());
+                });
+            });
+        
+        if settings != original {
+            self.ui_state.set_site_settings(&host, settings);
+        }
+        if !open {
+            self.site_settings_host = None;
+        }
+    }
+    
+    /// Show the current toast near the bottom of the window until it expires
+    fn render_toast(&mut self, ctx: &egui::Context) {
+        if self.toast.as_ref().map_or(false, |(_, shown_at)| shown_at.elapsed() >= TOAST_DURATION) {
+            self.toast = None;
+        }
+        let Some((message, _)) = &self.toast else {
+            return;
+        };
+        
+        egui::Area::new(egui::Id::new("toast"))
+            .anchor(egui::Align2::CENTER_BOTTOM, Vec2::new(0.0, -48.0))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(message);
+                });
+            });
+    }
+    
+    /// FPS, frame time and a graph of the last `FRAME_HISTORY` frames in the
+    /// top right corner. Keeps the window repainting while it's shown, so the
+    /// numbers are live rather than the gaps between input events.
+    fn render_performance_overlay(&mut self, ctx: &egui::Context) {
+        if !self.show_performance_overlay {
+            return;
+        }
+        ctx.request_repaint();
+        let tab_count = self.ui_state.tabs.borrow().len();
+        
+        egui::Area::new(egui::Id::new("performance_overlay"))
+            .anchor(egui::Align2::RIGHT_TOP, Vec2::new(-8.0, TOOLBAR_HEIGHT * 2.0))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    let small = |text: String| RichText::new(text).monospace().small();
+                    ui.label(small(format!("{:.0} FPS", self.fps)));
+                    ui.label(small(format!("{:.1} ms / frame", self.frame_time * 1000.0)));
+                    ui.label(small(format!("{} tabs", tab_count)));
+                    
+                    let (rect, _) = ui.allocate_exact_size(FRAME_GRAPH_SIZE, egui::Sense::hover());
+                    let painter = ui.painter_at(rect);
+                    painter.rect_filled(rect, Rounding::same(2.0), ui.visuals().extreme_bg_color);
+                    
+                    // Scaled so a 60 FPS frame sits halfway up, unless slower frames need the room
+                    let budget = 1.0 / 60.0;
+                    let slowest = self.frame_times.iter().copied().fold(budget * 2.0, f32::max);
+                    let y = |frame_time: f32| rect.bottom() - rect.height() * (frame_time / slowest).min(1.0);
+                    painter.hline(rect.x_range(), y(budget), Stroke::new(1.0, Color32::from_gray(120)));
+                    
+                    let step = rect.width() / (FRAME_HISTORY - 1) as f32;
+                    let offset = FRAME_HISTORY - self.frame_times.len();
+                    let points: Vec<egui::Pos2> = self.frame_times.iter()
+                        .enumerate()
+                        .map(|(i, &frame_time)| egui::pos2(rect.left() + (offset + i) as f32 * step, y(frame_time)))
+                        .collect();
+                    painter.add(egui::Shape::line(points, Stroke::new(1.5, ui.visuals().selection.bg_fill)));
+                });
+            });
+    }
+    
+    /// Make a tab active and show its URL in the omnibox
+    fn switch_tab(&mut self, index: usize) {
+        self.ui_state.switch_to_tab(index);
+        if let Some(active_tab) = self.ui_state.get_active_tab() {
+            self.url_input = active_tab.url;
+        }
+    }
+    
+    /// Start the closing animation; the tab is removed once it finishes
+    fn start_closing_tab(&mut self, index: usize) {
+        let tabs = self.ui_state.tabs.borrow();
+        if index >= tabs.len() {
+            return;
+        }
+        
+        // close_tab refuses to close the last tab, so don't animate it away either
+        let closing = self.tab_animations.values()
+            .filter(|anim| anim.anim_type == TabAnimationType::Closing)
+            .count();
+        if tabs.len() <= closing + 1 {
+            // A private window goes away with its last tab, and its data with it
+            if self.private_window && closing == 0 {
+                drop(tabs);
+                self.close_private_window();
+            }
+            return;
+        }
+        
+        let tab_id = tabs[index].id.clone();
+        drop(tabs);
+        
+        if self.reduce_motion {
+            self.tab_animations.remove(&tab_id);
+            self.ui_state.close_tab(index);
+            self.switch_tab(self.ui_state.active_tab_index.get());
+            return;
+        }
+        
+        // Only start animation if not already animating
+        if !self.tab_animations.contains_key(&tab_id) {
+            self.tab_animations.insert(
+                tab_id,
+                TabAnimation {
+                    progress: 0.0,
+                    anim_type: TabAnimationType::Closing,
+                    target_width: 0.0,
+                }
+            );
+        }
+    }
+    
+    /// Render custom title bar with tabs (exact Chrome replica)
+    fn render_top_panel(&mut self, ctx: &egui::Context) {
+        let palette = self.palette();
+        
+        // Chrome tab area with exact height and color
+        TopBottomPanel::top("tab_area")
+            .exact_height(35.0) // Chrome's exact tab height
+            .show(ctx, |ui| {
+                ui.style_mut().visuals.panel_fill = palette.tab_strip; // Chrome's exact color
+                
+                // Get full panel rect
+                let panel_rect = ui.available_rect_before_wrap();
+                
+                // Draggable area (invisible background)
+                let drag_response = ui.allocate_rect(panel_rect, egui::Sense::click_and_drag());
+                if drag_response.dragged() {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
                 }
                 if drag_response.double_clicked() {
                     ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(!ctx.input(|i| i.viewport().maximized.unwrap_or(false))));
                 }
                 
                 // Fixed layout with absolute positioning
-                let controls_width = 120.0;
+                let controls_width = 120.0 * self.ui_state.hit_scale();
                 let new_tab_width = 36.0;
                 let scroll_button_width = 32.0; // Width for scroll buttons
                 let left_padding = 8.0;
@@ -198,19 +1763,23 @@ impl ModernGenesisBrowser {
                 // Check if we need scroll (pre-calculate)
                 let available_width = panel_rect.width() - controls_width - new_tab_width - left_padding;
                 let tabs_borrow = self.ui_state.tabs.borrow();
-                let tab_count = tabs_borrow.len();
+                let pinned_count = tabs_borrow.iter().filter(|tab| tab.pinned).count();
+                let tab_count = tabs_borrow.len() - pinned_count;
+                let pinned_width = pinned_count as f32 * PINNED_TAB_WIDTH;
+                let dynamic_available_width = available_width - pinned_width;
                 let min_tab_width = 120.0;
                 let dynamic_tab_width = if tab_count == 0 {
                     240.0
                 } else {
-                    (available_width / tab_count as f32).clamp(min_tab_width, 240.0)
+                    (dynamic_available_width / tab_count as f32).clamp(min_tab_width, 240.0)
                 };
-                let needs_scroll_buttons = dynamic_tab_width <= min_tab_width && (tab_count as f32 * min_tab_width) > available_width;
+                let needs_scroll_buttons = dynamic_tab_width <= min_tab_width && (tab_count as f32 * min_tab_width) > dynamic_available_width;
                 drop(tabs_borrow);
                 
                 // Adjust tab container width if scroll buttons are needed
                 let tabs_width = if needs_scroll_buttons {
-                    available_width - (scroll_button_width * 2.0) // Space for both scroll buttons
+                    // Space for both scroll buttons and the overflow menu
+                    available_width - (scroll_button_width * 2.0) - TAB_OVERFLOW_BUTTON_WIDTH
                 } else {
                     available_width
                 };
@@ -223,18 +1792,19 @@ impl ModernGenesisBrowser {
                         Vec2::new(scroll_button_width, 35.0)
                     );
                     let left_response = ui.allocate_rect(left_scroll_rect, egui::Sense::click());
+                    left_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Scroll tabs left"));
                     
                     // Draw left arrow
                     let left_center = left_scroll_rect.center();
                     if left_response.hovered() {
-                        ui.painter().rect_filled(left_scroll_rect, 4.0, Color32::from_rgba_premultiplied(0, 0, 0, 20));
+                        ui.painter().rect_filled(left_scroll_rect, 4.0, palette.hover_overlay);
                     }
                     ui.painter().text(
                         left_center,
                         egui::Align2::CENTER_CENTER,
                         "◀",
                         egui::FontId::new(14.0, egui::FontFamily::Proportional),
-                        if self.tab_scroll_offset > 0.0 { Color32::from_rgb(95, 99, 104) } else { Color32::from_rgb(180, 180, 180) }
+                        if self.tab_scroll_offset > 0.0 { palette.icon } else { palette.icon_disabled }
                     );
                     
                     if left_response.clicked() && self.tab_scroll_offset > 0.0 {
@@ -247,23 +1817,22 @@ impl ModernGenesisBrowser {
                         Vec2::new(scroll_button_width, 35.0)
                     );
                     let right_response = ui.allocate_rect(right_scroll_rect, egui::Sense::click());
+                    right_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Scroll tabs right"));
                     
                     // Draw right arrow
                     let right_center = right_scroll_rect.center();
                     if right_response.hovered() {
-                        ui.painter().rect_filled(right_scroll_rect, 4.0, Color32::from_rgba_premultiplied(0, 0, 0, 20));
+                        ui.painter().rect_filled(right_scroll_rect, 4.0, palette.hover_overlay);
                     }
                     
-                    let tabs = self.ui_state.tabs.borrow();
-                    let max_scroll = (tabs.len() as f32 * min_tab_width - tabs_width).max(0.0);
-                    drop(tabs);
+                    let max_scroll = (pinned_width + tab_count as f32 * min_tab_width - tabs_width).max(0.0);
                     
                     ui.painter().text(
                         right_center,
                         egui::Align2::CENTER_CENTER,
                         "▶",
                         egui::FontId::new(14.0, egui::FontFamily::Proportional),
-                        if self.tab_scroll_offset < max_scroll { Color32::from_rgb(95, 99, 104) } else { Color32::from_rgb(180, 180, 180) }
+                        if self.tab_scroll_offset < max_scroll { palette.icon } else { palette.icon_disabled }
                     );
                     
                     if right_response.clicked() && self.tab_scroll_offset < max_scroll {
@@ -298,14 +1867,15 @@ impl ModernGenesisBrowser {
                 let new_tab_pos = egui::Pos2::new(actual_x, panel_rect.top() + 3.5);
                 let new_tab_rect = egui::Rect::from_min_size(new_tab_pos, Vec2::new(28.0, 28.0));
                 let new_tab_response = ui.allocate_rect(new_tab_rect, egui::Sense::click());
+                new_tab_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "New tab"));
                 
                 // Draw new tab button
                 if new_tab_response.hovered() {
-                    ui.painter().rect_filled(new_tab_rect, 4.0, Color32::from_rgba_premultiplied(0, 0, 0, 20));
+                    ui.painter().rect_filled(new_tab_rect, 4.0, palette.hover_overlay);
                 }
                 
                 let center = new_tab_rect.center();
-                let stroke = Stroke::new(1.5, Color32::from_rgb(95, 99, 104));
+                let stroke = Stroke::new(1.5, palette.icon);
                 ui.painter().line_segment([center - Vec2::new(6.0, 0.0), center + Vec2::new(6.0, 0.0)], stroke);
                 ui.painter().line_segment([center - Vec2::new(0.0, 6.0), center + Vec2::new(0.0, 6.0)], stroke);
                 
@@ -313,6 +1883,15 @@ impl ModernGenesisBrowser {
                     self.handle_new_tab();
                 }
                 
+                // Overflow menu listing every tab, right of the new tab button
+                if needs_scroll_buttons {
+                    let overflow_rect = egui::Rect::from_min_size(
+                        egui::Pos2::new(new_tab_rect.right() + 4.0, new_tab_rect.top()),
+                        Vec2::new(TAB_OVERFLOW_BUTTON_WIDTH, 28.0)
+                    );
+                    self.render_tab_overflow_menu(ui, overflow_rect);
+                }
+                
                 // Window controls (absolutely positioned, fixed)
                 let controls_rect = egui::Rect::from_min_size(
                     egui::Pos2::new(panel_rect.right() - controls_width, panel_rect.top()),
@@ -324,20 +1903,66 @@ impl ModernGenesisBrowser {
             });
         
         // Chrome toolbar area (address bar)
-        TopBottomPanel::top("toolbar_area")
-            .exact_height(40.0) // Chrome's exact toolbar height
+        let toolbar = TopBottomPanel::top("toolbar_area")
+            .exact_height(self.toolbar_height()) // Chrome's exact toolbar height
             .show(ctx, |ui| {
-                ui.style_mut().visuals.panel_fill = Color32::from_rgb(255, 255, 255);
+                ui.style_mut().visuals.panel_fill = palette.toolbar;
                 
                 // Add subtle border at bottom
                 let rect = ui.available_rect_before_wrap();
                 ui.painter().line_segment(
                     [rect.left_bottom(), rect.right_bottom()],
-                    Stroke::new(1.0, Color32::from_rgb(218, 220, 224))
+                    Stroke::new(1.0, palette.border)
                 );
                 
                 self.render_chrome_navigation_bar(ui);
             });
+        
+        self.render_progress_line(ctx, toolbar.response.rect);
+    }
+    
+    /// Move loading tabs' progress along and note when each load ends.
+    /// Webview progress events don't reach the UI yet, so the progress
+    /// eases toward a ceiling over the simulated navigation instead.
+    fn advance_load_progress(&mut self) {
+        let dt = self.frame_time.min(0.1);
+        let step = 1.0 - (-dt * SIMULATED_PROGRESS_RATE).exp();
+        let tabs: Vec<(String, bool, f32)> = self.ui_state.tabs.borrow().iter()
+            .map(|tab| (tab.id.clone(), tab.is_loading, tab.load_progress))
+            .collect();
+        
+        for (index, (id, is_loading, progress)) in tabs.iter().enumerate() {
+            if *is_loading {
+                self.load_indicators.insert(id.clone(), None);
+                let progress = progress + (SIMULATED_PROGRESS_CEILING - progress).max(0.0) * step;
+                self.ui_state.update_tab(index, None, None, None, Some(progress));
+            } else if let Some(finished) = self.load_indicators.get_mut(id) {
+                finished.get_or_insert_with(Instant::now);
+            }
+        }
+        
+        self.load_indicators.retain(|id, finished| {
+            tabs.iter().any(|(tab_id, _, _)| tab_id == id)
+                && finished.map_or(true, |at| at.elapsed().as_secs_f32() < PROGRESS_FADE_SECS)
+        });
+    }
+    
+    /// Thin Chrome-style progress line along the bottom of the toolbar
+    fn render_progress_line(&self, ctx: &egui::Context, toolbar_rect: egui::Rect) {
+        let Some(tab) = self.ui_state.get_active_tab() else {
+            return;
+        };
+        let Some(finished) = self.load_indicators.get(&tab.id) else {
+            return;
+        };
+        
+        let opacity = finished.map_or(1.0, |at| 1.0 - at.elapsed().as_secs_f32() / PROGRESS_FADE_SECS);
+        let rect = egui::Rect::from_min_size(
+            egui::Pos2::new(toolbar_rect.left(), toolbar_rect.bottom() - PROGRESS_LINE_HEIGHT),
+            Vec2::new(toolbar_rect.width() * tab.load_progress, PROGRESS_LINE_HEIGHT)
+        );
+        ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("load_progress")))
+            .rect_filled(rect, 0.0, self.palette().progress.gamma_multiply(opacity.clamp(0.0, 1.0)));
     }
     
     
@@ -350,20 +1975,16 @@ impl ModernGenesisBrowser {
         let min_tab_width = 120.0; // Minimum tab width before scrolling
         let tab_height = 35.0;
         
-        // Request continuous repaints for smooth animations
-        if !self.tab_animations.is_empty() {
-            ui.ctx().request_repaint();
-        }
-        
-        // Update animations
-        let delta_time = ui.input(|i| i.unstable_dt);
-        let animation_speed = 5.0; // Higher = faster animation
+        // Update animations; the first frame after idling would otherwise jump ahead
+        let delta_time = ui.input(|i| i.unstable_dt).min(MAX_ANIMATION_STEP);
+        // Animations still running when reduce motion is switched on finish right away
+        let step = if self.reduce_motion { 1.0 } else { delta_time * self.animation_speed };
         
         let mut completed_animations = Vec::new();
         let mut tabs_to_close = Vec::new();
         
         for (id, anim) in self.tab_animations.iter_mut() {
-            anim.progress = (anim.progress + delta_time * animation_speed).min(1.0);
+            anim.progress = (anim.progress + step).min(1.0);
             if anim.progress >= 1.0 {
                 if anim.anim_type == TabAnimationType::Closing {
                     completed_animations.push(id.clone());
@@ -379,6 +2000,13 @@ impl ModernGenesisBrowser {
             self.tab_animations.remove(&id);
         }
         
+        // Tabs pushed aside by a drag glide back to their slot
+        let slide_decay = (step * 2.0).min(1.0);
+        self.tab_slide_offsets.retain(|_, offset| {
+            *offset -= *offset * slide_decay;
+            offset.abs() > 0.5
+        });
+        
         // Actually close tabs after animation completes
         for tab_id in tabs_to_close {
             let tabs = self.ui_state.tabs.borrow();
@@ -390,20 +2018,24 @@ impl ModernGenesisBrowser {
             }
         }
         
+        self.request_favicons();
+        
         let tabs = self.ui_state.tabs.borrow();
-        let tab_count = tabs.len();
+        let pinned_width = tabs.iter().filter(|tab| tab.pinned).count() as f32 * PINNED_TAB_WIDTH;
+        let tab_count = tabs.iter().filter(|tab| !tab.pinned).count();
         let active_index = self.ui_state.active_tab_index.get();
         
-        // Calculate dynamic tab width - shrink tabs until minimum width
+        // Calculate dynamic tab width - shrink tabs until minimum width.
+        // Pinned tabs have a fixed width and don't take part in this.
         let tab_width = if tab_count == 0 {
             max_tab_width
         } else {
-            let width_per_tab = container_width / tab_count as f32;
+            let width_per_tab = (container_width - pinned_width) / tab_count as f32;
             width_per_tab.clamp(min_tab_width, max_tab_width)
         };
         
         // Calculate if we need scrolling (only when tabs are at minimum width and still don't fit)
-        let total_tabs_width = tab_count as f32 * tab_width;
+        let total_tabs_width = pinned_width + tab_count as f32 * tab_width;
         let needs_scroll = tab_width <= min_tab_width && total_tabs_width > container_width;
         
         // Handle mouse wheel scrolling
@@ -426,6 +2058,19 @@ impl ModernGenesisBrowser {
             self.ensure_last_tab_visible = false;
         }
         
+        // Bring a tab picked from the overflow menu into view
+        if let Some(index) = self.scroll_to_tab.take().filter(|&index| needs_scroll && index < tabs.len()) {
+            let start: f32 = tabs[..index].iter()
+                .map(|tab| if tab.pinned { PINNED_TAB_WIDTH } else { tab_width })
+                .sum();
+            let end = start + if tabs[index].pinned { PINNED_TAB_WIDTH } else { tab_width };
+            if start < self.tab_scroll_offset {
+                self.tab_scroll_offset = start;
+            } else if end > self.tab_scroll_offset + container_width {
+                self.tab_scroll_offset = end - container_width;
+            }
+        }
+        
         // Always ensure scroll doesn't leave empty space at the end
         if needs_scroll {
             let max_valid_scroll = (total_tabs_width - container_width).max(0.0);
@@ -458,55 +2103,86 @@ impl ModernGenesisBrowser {
             
             // Render all tabs with dynamic width and animations
             for (index, tab) in tabs.iter().enumerate() {
+                let full_width = if tab.pinned { PINNED_TAB_WIDTH } else { tab_width };
+                
                 // Check if this tab has an animation
                 let animated_width = if let Some(anim) = self.tab_animations.get(&tab.id) {
                     // Smooth easing function
                     let eased_progress = ease_out_cubic(anim.progress);
                     if anim.anim_type == TabAnimationType::Opening {
-                        tab_width * eased_progress // Grow from 0 to full width
+                        full_width * eased_progress // Grow from 0 to full width
                     } else {
-                        tab_width * (1.0 - eased_progress) // Shrink from full width to 0
+                        full_width * (1.0 - eased_progress) // Shrink from full width to 0
                     }
                 } else {
-                    tab_width
+                    full_width
+                };
+                
+                // The dragged tab follows the cursor, the others slide into place
+                let x_offset = if self.dragging_tab == Some(index) {
+                    self.drag_offset
+                } else {
+                    self.tab_slide_offsets.get(&tab.id).copied().unwrap_or(0.0)
                 };
                 
                 if animated_width > 1.0 { // Only render if width > 1px
-                    self.render_single_chrome_tab(ui, tab, index, active_index, animated_width, &mut tab_actions);
+                    self.render_single_chrome_tab(ui, tab, index, active_index, animated_width, x_offset, &mut tab_actions);
                 }
             }
         });
         
         drop(tabs);
         
+        if let Some(dragged) = self.dragging_tab {
+            if ui.input(|i| i.pointer.primary_down()) {
+                self.drag_offset += ui.input(|i| i.pointer.delta().x);
+                self.drag_past_neighbor(dragged, tab_width);
+            } else {
+                self.dragging_tab = None;
+                self.drag_offset = 0.0;
+            }
+        }
+        
         // Process tab actions
         for (action, index) in tab_actions {
             match action {
                 "switch" => {
-                    self.ui_state.switch_to_tab(index);
-                    if let Some(active_tab) = self.ui_state.get_active_tab() {
-                        self.url_input = active_tab.url;
-                    }
+                    self.switch_tab(index);
+                }
+                "drag_start" => {
+                    self.dragging_tab = Some(index);
+                    self.drag_offset = 0.0;
+                    self.switch_tab(index);
                 }
                 "close" => {
                     // Add closing animation, don't close immediately
-                    let tabs = self.ui_state.tabs.borrow();
-                    if index < tabs.len() {
-                        let tab_id = tabs[index].id.clone();
-                        // Only start animation if not already animating
-                        if !self.tab_animations.contains_key(&tab_id) {
-                            self.tab_animations.insert(
-                                tab_id,
-                                TabAnimation {
-                                    progress: 0.0,
-                                    anim_type: TabAnimationType::Closing,
-                                    target_width: 0.0,
-                                }
-                            );
-                        }
+                    self.start_closing_tab(index);
+                }
+                "close_others" => {
+                    self.ui_state.close_other_tabs(index);
+                    self.switch_tab(self.ui_state.active_tab_index.get());
+                }
+                "close_right" => {
+                    self.ui_state.close_tabs_to_right(index);
+                    self.switch_tab(self.ui_state.active_tab_index.get());
+                }
+                "duplicate" => {
+                    let source = self.ui_state.tabs.borrow().get(index).cloned();
+                    if let Some(source) = source {
+                        self.open_tab(&source.url);
+                        let new_index = self.ui_state.active_tab_index.get();
+                        self.ui_state.update_tab(new_index, Some(source.title), None, None, None);
                     }
-                    drop(tabs);
-                    // Don't close immediately - wait for animation to complete
+                }
+                "pin" => {
+                    self.ui_state.toggle_pin(index);
+                }
+                "mute" => {
+                    self.ui_state.toggle_tab_muted(index);
+                }
+                "mute_site" => {
+                    let is_muted = self.ui_state.tabs.borrow().get(index).map_or(false, |tab| tab.is_muted);
+                    self.ui_state.set_site_muted(index, !is_muted);
                 }
                 _ => {}
             }
@@ -522,39 +2198,312 @@ impl ModernGenesisBrowser {
         }
     }
     
-    /// Handle new tab creation
-    fn handle_new_tab(&mut self) {
-        self.ui_state.create_tab("genesis://newtab");
+    /// Swap the dragged tab with a neighbor once it's dragged past the neighbor's midpoint
+    fn drag_past_neighbor(&mut self, dragged: usize, tab_width: f32) {
+        let width_of = |tab: &crate::enhanced_browser::BrowserTab| {
+            if tab.pinned { PINNED_TAB_WIDTH } else { tab_width }
+        };
+        
+        let (neighbor, direction) = if self.drag_offset > 0.0 {
+            (dragged + 1, 1.0)
+        } else if self.drag_offset < 0.0 && dragged > 0 {
+            (dragged - 1, -1.0)
+        } else {
+            return;
+        };
         
-        // Add opening animation for the new tab
         let tabs = self.ui_state.tabs.borrow();
-        if let Some(new_tab) = tabs.last() {
-            self.tab_animations.insert(
-                new_tab.id.clone(),
-                TabAnimation {
-                    progress: 0.0,
-                    anim_type: TabAnimationType::Opening,
-                    target_width: 240.0,
-                }
-            );
-        }
+        let (Some(dragged_tab), Some(neighbor_tab)) = (tabs.get(dragged), tabs.get(neighbor)) else {
+            return;
+        };
+        let dragged_width = width_of(dragged_tab);
+        let neighbor_width = width_of(neighbor_tab);
+        let neighbor_id = neighbor_tab.id.clone();
+        drop(tabs);
         
-        // Auto-scroll to show the new tab
-        let tab_width = 240.0;
-        let total_tabs_width = tabs.len() as f32 * tab_width;
+        if self.drag_offset.abs() < neighbor_width / 2.0 || !self.ui_state.reorder_tab(dragged, neighbor) {
+            return;
+        }
+        
+        // The neighbor jumps into the dragged tab's old slot and slides there from where it was
+        self.dragging_tab = Some(neighbor);
+        self.drag_offset -= direction * neighbor_width;
+        *self.tab_slide_offsets.entry(neighbor_id).or_insert(0.0) += direction * dragged_width;
+    }
+    
+    /// Start fetching favicons for traditional pages that don't have one yet
+    fn request_favicons(&mut self) {
+        let sources: Vec<(String, String)> = self.ui_state.tabs.borrow().iter()
+            .filter(|tab| !tab.is_genesis_domain)
+            .filter_map(|tab| favicon_source(&tab.url))
+            .collect();
+        
+        for (host, page_url) in sources {
+            let should_fetch = match self.favicons.get(&host) {
+                None => true,
+                Some(FaviconState::Failed(at)) => at.elapsed() >= FAVICON_RETRY_COOLDOWN,
+                Some(_) => false,
+            };
+            if !should_fetch {
+                continue;
+            }
+            
+            self.favicons.insert(host.clone(), FaviconState::Loading);
+            let sender = self.favicon_sender.clone();
+            let egui_ctx = self.egui_ctx.clone();
+            self.runtime_handle.spawn(async move {
+                let icon = fetch_favicon(&page_url).await;
+                let _ = sender.send((host, icon));
+                wake(&egui_ctx);
+            });
+        }
+    }
+    
+    /// Look for feeds on a page that just resolved, fetching it from `address`
+    fn request_feeds(&self, tab_id: String, url: &str, address: Option<std::net::IpAddr>) {
+        let Some((_, page_url)) = favicon_source(url) else {
+            return;
+        };
+        let url = url.to_string();
+        let sender = self.feed_sender.clone();
+        let egui_ctx = self.egui_ctx.clone();
+        self.runtime_handle.spawn(async move {
+            let feeds = discover_feeds(&page_url, address).await;
+            if !feeds.is_empty() {
+                let _ = sender.send((tab_id, url, feeds.into_iter().map(String::from).collect()));
+                wake(&egui_ctx);
+            }
+        });
+    }
+    
+    /// Show discovered feeds on tabs still showing the page they were found on
+    fn poll_feeds(&mut self) {
+        while let Ok((tab_id, url, feeds)) = self.feed_receiver.try_recv() {
+            let index = self.ui_state.tabs.borrow().iter()
+                .position(|tab| tab.id == tab_id && tab.url == url);
+            if let Some(index) = index {
+                info!("📰 {} advertises {} feeds", url, feeds.len());
+                self.ui_state.set_tab_feeds(index, feeds);
+            }
+        }
+    }
+    
+    /// Turn fetched favicons into textures
+    fn poll_favicons(&mut self, ctx: &egui::Context) {
+        while let Ok((host, icon)) = self.favicon_receiver.try_recv() {
+            let decoded = icon.and_then(|bytes| image::load_from_memory(&bytes).ok());
+            let state = match decoded {
+                Some(image) => {
+                    let image = image.thumbnail(32, 32).to_rgba8();
+                    let size = [image.width() as usize, image.height() as usize];
+                    let pixels = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+                    FaviconState::Loaded(ctx.load_texture(format!("favicon-{}", host), pixels, egui::TextureOptions::LINEAR))
+                }
+                None => {
+                    warn!("⚠️ No favicon for {}, retrying in {}s", host, FAVICON_RETRY_COOLDOWN.as_secs());
+                    FaviconState::Failed(Instant::now())
+                }
+            };
+            self.favicons.insert(host, state);
+        }
+    }
+    
+    /// Handle new tab creation
+    fn handle_new_tab(&mut self) {
+        self.open_tab("genesis://newtab");
+    }
+    
+    /// Start another browser process with `--private`: its own in-memory
+    /// profile that writes nothing to disk, with this window's config, node
+    /// and proxy flags
+    fn open_private_window(&mut self) {
+        let args = private_window_args(std::env::args().skip(1));
+        let spawned = std::env::current_exe()
+            .and_then(|exe| std::process::Command::new(exe).arg("--private").args(args).spawn());
+        match spawned {
+            Ok(_) => info!("🕶 Opened a private window"),
+            Err(e) => {
+                warn!("⚠️ Failed to open a private window: {}", e);
+                self.toast = Some(("Couldn't open a private window".to_string(), Instant::now()));
+            }
+        }
+    }
+    
+    /// Drop the private window's data and close it, without the prompt for
+    /// closing several tabs
+    fn close_private_window(&mut self) {
+        self.ui_state.discard_private_data();
+        self.page_sources.clear();
+        self.favicons.clear();
+        self.close_confirmed = true;
+        if let Some(ctx) = &self.egui_ctx {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    }
+    
+    /// Open a tab at the end of the strip with the opening animation.
+    /// Returns the id of the new tab.
+    fn open_tab(&mut self, url: &str) -> String {
+        let tab_id = self.ui_state.create_tab(url);
+        self.start_opening_animation(tab_id.clone());
         
         // Scroll to the end to show the new tab (if needed)
         // We'll use a flag to trigger scroll in the next frame
         self.ensure_last_tab_visible = true;
+        self.url_input = url.to_string();
+        
+        tab_id
+    }
+    
+    /// Grow a new tab into the strip, unless motion is reduced
+    fn start_opening_animation(&mut self, tab_id: String) {
+        if self.reduce_motion {
+            return;
+        }
+        self.tab_animations.insert(
+            tab_id,
+            TabAnimation {
+                progress: 0.0,
+                anim_type: TabAnimationType::Opening,
+                target_width: 240.0,
+            }
+        );
+    }
+    
+    /// Open a tab without switching away from the current one
+    fn open_background_tab(&mut self, url: &str) {
+        if self.ui_state.dedupe_tabs.get() && self.ui_state.find_tab_by_url(url).is_some() {
+            return;
+        }
+        
+        let active_index = self.ui_state.active_tab_index.get();
+        self.open_tab(url);
+        self.switch_tab(active_index);
+    }
+    
+    /// Bring back the most recently closed tab with the opening animation
+    fn handle_reopen_closed_tab(&mut self) {
+        let Some(tab_id) = self.ui_state.reopen_last_closed() else {
+            return;
+        };
+        
+        self.start_opening_animation(tab_id);
+        if let Some(active_tab) = self.ui_state.get_active_tab() {
+            self.url_input = active_tab.url;
+        }
+        self.ensure_last_tab_visible = true;
     }
     
     /// Render a single Chrome-style tab
-    fn render_single_chrome_tab(&self, ui: &mut egui::Ui, tab: &crate::enhanced_browser::BrowserTab, index: usize, active_index: usize, tab_width: f32, tab_actions: &mut Vec<(&str, usize)>) {
+    /// Chevron button opening a list of every tab, for when they don't all fit
+    fn render_tab_overflow_menu(&mut self, ui: &mut Ui, rect: egui::Rect) {
+        let palette = self.palette();
+        let response = ui.allocate_rect(rect, egui::Sense::click());
+        response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "All tabs"));
+        if response.hovered() {
+            ui.painter().rect_filled(rect, 4.0, palette.hover_overlay);
+        }
+        let center = rect.center();
+        let stroke = Stroke::new(1.5, palette.icon);
+        ui.painter().line_segment([center + Vec2::new(-4.0, -2.0), center + Vec2::new(0.0, 2.0)], stroke);
+        ui.painter().line_segment([center + Vec2::new(0.0, 2.0), center + Vec2::new(4.0, -2.0)], stroke);
+        
+        let popup_id = ui.make_persistent_id("tab_overflow_menu");
+        if response.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+        }
+        
+        let tabs = self.ui_state.tabs.borrow().clone();
+        let active_index = self.ui_state.active_tab_index.get();
+        let mut action = None;
+        egui::popup::popup_below_widget(ui, popup_id, &response, |ui| {
+            ui.set_min_width(280.0);
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                for (index, tab) in tabs.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let (favicon_rect, _) = ui.allocate_exact_size(Vec2::new(16.0, 16.0), egui::Sense::hover());
+                        self.paint_favicon(ui.painter(), favicon_rect, tab);
+                        
+                        let title = RichText::new(&tab.title);
+                        let title = if index == active_index { title.strong() } else { title };
+                        let close_width = if tab.pinned { 0.0 } else { 24.0 };
+                        let row = ui.add_sized(
+                            Vec2::new(ui.available_width() - close_width, 20.0),
+                            egui::SelectableLabel::new(index == active_index, title)
+                        );
+                        if row.clicked() {
+                            action = Some(("switch", index));
+                        }
+                        if !tab.pinned && ui.small_button("✕").on_hover_text("Close tab").clicked() {
+                            action = Some(("close", index));
+                        }
+                    });
+                }
+            });
+        });
+        
+        match action {
+            Some(("switch", index)) => {
+                self.switch_tab(index);
+                self.scroll_to_tab = Some(index);
+                ui.memory_mut(|mem| mem.close_popup());
+            }
+            Some((_, index)) => self.start_closing_tab(index),
+            None => {}
+        }
+    }
+    
+    /// Paint a tab's favicon: the Genesis badge, the site's icon once loaded, or a placeholder
+    fn paint_favicon(&self, painter: &egui::Painter, rect: egui::Rect, tab: &crate::enhanced_browser::BrowserTab) {
+        let favicon = favicon_source(&tab.url).and_then(|(host, _)| match self.favicons.get(&host) {
+            Some(FaviconState::Loaded(texture)) => Some(texture.id()),
+            _ => None,
+        });
+        
+        if tab.is_genesis_domain {
+            painter.circle_filled(
+                rect.center(),
+                8.0,
+                Color32::from_rgb(34, 197, 94)
+            );
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "G",
+                FontId::new(10.0, FontFamily::Proportional),
+                Color32::WHITE
+            );
+        } else if let Some(texture_id) = favicon {
+            painter.image(
+                texture_id,
+                rect,
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                Color32::WHITE
+            );
+        } else {
+            painter.circle_filled(
+                rect.center(),
+                8.0,
+                self.palette().icon
+            );
+        }
+    }
+    
+    fn render_single_chrome_tab(&self, ui: &mut egui::Ui, tab: &crate::enhanced_browser::BrowserTab, index: usize, active_index: usize, tab_width: f32, x_offset: f32, tab_actions: &mut Vec<(&str, usize)>) {
+        let palette = self.palette();
+        
         let is_active = index == active_index;
         let tab_height = 35.0;
         
-        let tab_response = ui.allocate_response(Vec2::new(tab_width, tab_height), egui::Sense::click());
-        let rect = tab_response.rect;
+        let tab_response = ui.allocate_response(Vec2::new(tab_width, tab_height), egui::Sense::click_and_drag());
+        tab_response.widget_info(|| egui::WidgetInfo::selected(egui::WidgetType::SelectableLabel, is_active, &tab.title));
+        let rect = tab_response.rect.translate(Vec2::new(x_offset, 0.0));
+        // Accessibility mode grows the buttons' hit areas, not what's drawn
+        let hit_scale = self.ui_state.hit_scale();
+        
+        if tab_response.drag_started() {
+            tab_actions.push(("drag_start", index));
+        }
         
         // Chrome tab shape with slanted edges
         let mut points = Vec::new();
@@ -567,17 +2516,17 @@ impl ModernGenesisBrowser {
         
         // Tab colors with transparency for inactive tabs
         let bg_color = if is_active {
-            Color32::from_rgb(255, 255, 255) // Solid white for active
+            palette.tab_active // Solid toolbar color for active
         } else if tab_response.hovered() {
-            Color32::from_rgba_unmultiplied(210, 213, 218, 240) // Slightly darker on hover
+            palette.tab_hovered // Between active and inactive on hover
         } else {
-            Color32::from_rgba_unmultiplied(190, 194, 200, 220) // Slightly darker for inactive
+            palette.tab_inactive // Blends into the tab strip for inactive
         };
         
         let text_color = if is_active {
-            Color32::from_rgb(32, 33, 36) // Full opacity for active
+            palette.text_active // Full opacity for active
         } else {
-            Color32::from_rgba_unmultiplied(95, 99, 104, 200) // Slightly transparent for inactive
+            palette.text_inactive // Slightly transparent for inactive
         };
         
         // Draw tab background
@@ -592,36 +2541,46 @@ impl ModernGenesisBrowser {
             // Right separator line (very subtle)
             ui.painter().line_segment(
                 [points[2], points[3]],
-                Stroke::new(1.0, Color32::from_rgba_premultiplied(0, 0, 0, 20))
+                Stroke::new(1.0, palette.hover_overlay)
             );
         }
         
-        // Favicon area (16x16 Chrome standard)
-        let favicon_rect = egui::Rect::from_min_size(
-            rect.left_top() + Vec2::new(16.0, 9.0),
-            Vec2::new(16.0, 16.0)
-        );
+        // Favicon area (16x16 Chrome standard), centered on pinned tabs
+        let favicon_rect = if tab.pinned {
+            egui::Rect::from_center_size(rect.center(), Vec2::new(16.0, 16.0))
+        } else {
+            egui::Rect::from_min_size(
+                rect.left_top() + Vec2::new(16.0, 9.0),
+                Vec2::new(16.0, 16.0)
+            )
+        };
         
-        // Draw favicon (placeholder)
-        if tab.is_genesis_domain {
-            ui.painter().circle_filled(
-                favicon_rect.center(),
-                8.0,
-                Color32::from_rgb(34, 197, 94)
-            );
-            ui.painter().text(
-                favicon_rect.center(),
-                egui::Align2::CENTER_CENTER,
-                "G",
-                FontId::new(10.0, FontFamily::Proportional),
-                Color32::WHITE
-            );
+        // Tabs playing sound show a speaker instead of the favicon; clicking it toggles mute
+        let mute_clicked = if tab.is_audible || tab.is_muted {
+            let audio_label = if tab.is_muted { "Unmute tab" } else { "Mute tab" };
+            let audio_rect = favicon_rect.expand(8.0 * (hit_scale - 1.0));
+            let audio_response = ui.interact(audio_rect, ui.id().with(("tab_audio", index)), egui::Sense::click())
+                .on_hover_text(audio_label);
+            audio_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, audio_label));
+            if audio_response.hovered() {
+                ui.painter().circle_filled(favicon_rect.center(), 10.0, palette.hover_overlay);
+            }
+            paint_speaker_icon(ui.painter(), favicon_rect.center(), tab.is_muted, palette.icon);
+            audio_response.clicked()
         } else {
-            ui.painter().circle_filled(
-                favicon_rect.center(),
-                8.0,
-                Color32::from_rgb(95, 99, 104)
-            );
+            self.paint_favicon(ui.painter(), favicon_rect, tab);
+            false
+        };
+        
+        // Pinned tabs show only the favicon, without title or close button
+        if tab.pinned {
+            let tab_response = Self::add_tab_context_menu(tab_response, tab, index, tab_actions);
+            if mute_clicked {
+                tab_actions.push(("mute", index));
+            } else if tab_response.clicked() {
+                tab_actions.push(("switch", index));
+            }
+            return;
         }
         
         // Tab title with Chrome's exact font size (dynamic truncation based on tab width)
@@ -643,14 +2602,15 @@ impl ModernGenesisBrowser {
         );
         
         // Close button (overlay - doesn't affect layout)
-        let close_rect = egui::Rect::from_min_size(
-            rect.right_top() + Vec2::new(-28.0, 7.0),
-            Vec2::new(20.0, 20.0)
+        let close_rect = egui::Rect::from_center_size(
+            rect.right_top() + Vec2::new(-18.0, 17.0),
+            Vec2::splat((20.0 * hit_scale).min(tab_height))
         );
         
         // Use interact instead of allocate_rect to avoid affecting layout
         let close_id = ui.id().with(("tab_close", index));
         let close_response = ui.interact(close_rect, close_id, egui::Sense::click());
+        close_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, format!("Close {}", tab.title)));
         
         // Draw close button hover background
         if close_response.hovered() {
@@ -664,7 +2624,7 @@ impl ModernGenesisBrowser {
         // Draw X icon (always visible)
         let x_size = 6.0;
         let center = close_rect.center();
-        let stroke = Stroke::new(1.5, Color32::from_rgb(95, 99, 104)); // Gray color when not hovered
+        let stroke = Stroke::new(1.5, palette.icon); // Gray color when not hovered
         
         ui.painter().line_segment(
             [center - Vec2::new(x_size, x_size), center + Vec2::new(x_size, x_size)],
@@ -675,7 +2635,16 @@ impl ModernGenesisBrowser {
             stroke
         );
         
-        if close_response.clicked() {
+        // Right-click menu
+        let tab_response = Self::add_tab_context_menu(tab_response, tab, index, tab_actions);
+        
+        if mute_clicked {
+            tab_actions.push(("mute", index));
+            return;
+        }
+        
+        // Middle-clicking the tab body closes it like the X button
+        if close_response.clicked() || tab_response.clicked_by(egui::PointerButton::Middle) {
             tab_actions.push(("close", index));
             return;
         }
@@ -685,18 +2654,59 @@ impl ModernGenesisBrowser {
         }
     }
     
+    /// Attach Chrome's tab right-click menu to a tab
+    fn add_tab_context_menu(
+        tab_response: egui::Response,
+        tab: &crate::enhanced_browser::BrowserTab,
+        index: usize,
+        tab_actions: &mut Vec<(&str, usize)>,
+    ) -> egui::Response {
+        tab_response.context_menu(|ui| {
+            if ui.button("Duplicate").clicked() {
+                tab_actions.push(("duplicate", index));
+                ui.close_menu();
+            }
+            if ui.button(if tab.pinned { "Unpin" } else { "Pin" }).clicked() {
+                tab_actions.push(("pin", index));
+                ui.close_menu();
+            }
+            if ui.button(if tab.is_muted { "Unmute site" } else { "Mute site" }).clicked() {
+                tab_actions.push(("mute_site", index));
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.button("Close").clicked() {
+                tab_actions.push(("close", index));
+                ui.close_menu();
+            }
+            if ui.button("Close other tabs").clicked() {
+                tab_actions.push(("close_others", index));
+                ui.close_menu();
+            }
+            if ui.button("Close tabs to the right").clicked() {
+                tab_actions.push(("close_right", index));
+                ui.close_menu();
+            }
+        })
+    }
+    
     /// Chrome-style window controls
     fn render_chrome_window_controls(&mut self, ui: &mut Ui, ctx: &egui::Context) {
+        let palette = self.palette();
+        
+        let button_size = Vec2::new(40.0 * self.ui_state.hit_scale(), 35.0);
+        
         ui.horizontal(|ui| {
             ui.spacing_mut().item_spacing = Vec2::new(0.0, 0.0);
             
             // Minimize button
-            let min_response = ui.allocate_response(Vec2::new(40.0, 35.0), egui::Sense::click());
+            let min_response = ui.allocate_response(button_size, egui::Sense::click());
+            min_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Minimize"));
             if min_response.hovered() {
                 ui.painter().rect_filled(
                     min_response.rect,
                     0.0,
-                    Color32::from_rgba_premultiplied(0, 0, 0, 20)
+                    palette.hover_overlay
                 );
             }
             
@@ -704,7 +2714,7 @@ impl ModernGenesisBrowser {
             let min_center = min_response.rect.center();
             ui.painter().line_segment(
                 [min_center - Vec2::new(5.0, 0.0), min_center + Vec2::new(5.0, 0.0)],
-                Stroke::new(1.0, Color32::from_rgb(95, 99, 104))
+                Stroke::new(1.0, palette.icon)
             );
             
             if min_response.clicked() {
@@ -712,12 +2722,13 @@ impl ModernGenesisBrowser {
             }
             
             // Maximize button
-            let max_response = ui.allocate_response(Vec2::new(40.0, 35.0), egui::Sense::click());
+            let max_response = ui.allocate_response(button_size, egui::Sense::click());
+            max_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Maximize"));
             if max_response.hovered() {
                 ui.painter().rect_filled(
                     max_response.rect,
                     0.0,
-                    Color32::from_rgba_premultiplied(0, 0, 0, 20)
+                    palette.hover_overlay
                 );
             }
             
@@ -726,7 +2737,7 @@ impl ModernGenesisBrowser {
             ui.painter().rect_stroke(
                 egui::Rect::from_center_size(max_center, Vec2::new(10.0, 10.0)),
                 0.0,
-                Stroke::new(1.0, Color32::from_rgb(95, 99, 104))
+                Stroke::new(1.0, palette.icon)
             );
             
             if max_response.clicked() {
@@ -734,7 +2745,8 @@ impl ModernGenesisBrowser {
             }
             
             // Close button
-            let close_response = ui.allocate_response(Vec2::new(40.0, 35.0), egui::Sense::click());
+            let close_response = ui.allocate_response(button_size, egui::Sense::click());
+            close_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Close window"));
             if close_response.hovered() {
                 ui.painter().rect_filled(
                     close_response.rect,
@@ -748,7 +2760,7 @@ impl ModernGenesisBrowser {
             let x_color = if close_response.hovered() {
                 Color32::WHITE
             } else {
-                Color32::from_rgb(95, 99, 104)
+                palette.icon
             };
             
             let x_size = 5.0;
@@ -762,13 +2774,17 @@ impl ModernGenesisBrowser {
             );
             
             if close_response.clicked() {
-                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                self.request_close(ctx);
             }
         });
     }
     
     /// Chrome navigation bar with exact styling
     fn render_chrome_navigation_bar(&mut self, ui: &mut Ui) {
+        let palette = self.palette();
+        let button_size = Vec2::splat(32.0 * self.ui_state.hit_scale());
+        let hover_radius = button_size.x / 2.0;
+        
         ui.horizontal(|ui| {
             ui.add_space(8.0);
             
@@ -776,74 +2792,115 @@ impl ModernGenesisBrowser {
             ui.scope(|ui| {
                 ui.spacing_mut().item_spacing = Vec2::new(4.0, 0.0);
                 
+                let (can_go_back, can_go_forward) = self.ui_state.get_active_tab()
+                    .map_or((false, false), |tab| (tab.can_go_back, tab.can_go_forward));
+                let nav_sense = |enabled: bool| if enabled { egui::Sense::click() } else { egui::Sense::hover() };
+                let nav_info = |label: &str, enabled: bool| {
+                    let mut info = egui::WidgetInfo::labeled(egui::WidgetType::Button, label);
+                    info.enabled = enabled;
+                    info
+                };
+                
                 // Back button
-                let back_response = ui.allocate_response(Vec2::new(32.0, 32.0), egui::Sense::click());
-                if back_response.hovered() {
+                let back_response = ui.allocate_response(button_size, nav_sense(can_go_back));
+                back_response.widget_info(|| nav_info("Back", can_go_back));
+                if can_go_back && back_response.hovered() {
                     ui.painter().circle_filled(
                         back_response.rect.center(),
-                        16.0,
-                        Color32::from_rgba_premultiplied(60, 64, 67, 20)
+                        hover_radius,
+                        palette.hover_overlay
                     );
                 }
                 
-                // Draw back arrow
+                // Draw back arrow, grayed out with nothing to go back to
                 let center = back_response.rect.center();
-                let arrow_color = Color32::from_rgb(95, 99, 104);
+                let arrow_color = palette.icon;
+                let back_color = if can_go_back { arrow_color } else { palette.icon_disabled };
                 ui.painter().line_segment(
                     [center - Vec2::new(4.0, 0.0), center + Vec2::new(4.0, 0.0)],
-                    Stroke::new(2.0, arrow_color)
+                    Stroke::new(2.0, back_color)
                 );
                 ui.painter().line_segment(
                     [center - Vec2::new(4.0, 0.0), center - Vec2::new(0.0, 4.0)],
-                    Stroke::new(2.0, arrow_color)
+                    Stroke::new(2.0, back_color)
                 );
                 ui.painter().line_segment(
                     [center - Vec2::new(4.0, 0.0), center - Vec2::new(0.0, -4.0)],
-                    Stroke::new(2.0, arrow_color)
+                    Stroke::new(2.0, back_color)
                 );
+                if back_response.clicked() {
+                    self.go_back();
+                }
                 
                 // Forward button
-                let forward_response = ui.allocate_response(Vec2::new(32.0, 32.0), egui::Sense::click());
-                if forward_response.hovered() {
+                let forward_response = ui.allocate_response(button_size, nav_sense(can_go_forward));
+                forward_response.widget_info(|| nav_info("Forward", can_go_forward));
+                if can_go_forward && forward_response.hovered() {
                     ui.painter().circle_filled(
                         forward_response.rect.center(),
-                        16.0,
-                        Color32::from_rgba_premultiplied(60, 64, 67, 20)
+                        hover_radius,
+                        palette.hover_overlay
                     );
                 }
                 
                 // Draw forward arrow
                 let center = forward_response.rect.center();
+                let forward_color = if can_go_forward { arrow_color } else { palette.icon_disabled };
                 ui.painter().line_segment(
                     [center - Vec2::new(4.0, 0.0), center + Vec2::new(4.0, 0.0)],
-                    Stroke::new(2.0, arrow_color)
+                    Stroke::new(2.0, forward_color)
                 );
                 ui.painter().line_segment(
                     [center + Vec2::new(4.0, 0.0), center + Vec2::new(0.0, 4.0)],
-                    Stroke::new(2.0, arrow_color)
+                    Stroke::new(2.0, forward_color)
                 );
                 ui.painter().line_segment(
                     [center + Vec2::new(4.0, 0.0), center + Vec2::new(0.0, -4.0)],
-                    Stroke::new(2.0, arrow_color)
+                    Stroke::new(2.0, forward_color)
                 );
+                if forward_response.clicked() {
+                    self.go_forward();
+                }
                 
-                // Reload button
-                let reload_response = ui.allocate_response(Vec2::new(32.0, 32.0), egui::Sense::click());
+                // Reload button, turns into a stop button while loading
+                let is_loading = self.ui_state.get_active_tab().map_or(false, |tab| tab.is_loading);
+                let reload_response = ui.allocate_response(button_size, egui::Sense::click());
+                reload_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, if is_loading { "Stop" } else { "Reload" }));
                 if reload_response.hovered() {
                     ui.painter().circle_filled(
                         reload_response.rect.center(),
-                        16.0,
-                        Color32::from_rgba_premultiplied(60, 64, 67, 20)
+                        hover_radius,
+                        palette.hover_overlay
                     );
                 }
                 
-                // Draw reload icon (circular arrow)
                 let center = reload_response.rect.center();
-                ui.painter().circle_stroke(
-                    center,
-                    6.0,
-                    Stroke::new(2.0, arrow_color)
-                );
+                if is_loading {
+                    // Draw stop icon (X)
+                    ui.painter().line_segment(
+                        [center - Vec2::new(5.0, 5.0), center + Vec2::new(5.0, 5.0)],
+                        Stroke::new(2.0, arrow_color)
+                    );
+                    ui.painter().line_segment(
+                        [center - Vec2::new(5.0, -5.0), center + Vec2::new(5.0, -5.0)],
+                        Stroke::new(2.0, arrow_color)
+                    );
+                } else {
+                    // Draw reload icon (circular arrow)
+                    ui.painter().circle_stroke(
+                        center,
+                        6.0,
+                        Stroke::new(2.0, arrow_color)
+                    );
+                }
+                
+                if reload_response.clicked() {
+                    if is_loading {
+                        self.stop_loading();
+                    } else {
+                        self.reload();
+                    }
+                }
             });
             
             ui.add_space(8.0);
@@ -858,7 +2915,7 @@ impl ModernGenesisBrowser {
             ui.painter().rect(
                 url_rect,
                 16.0, // Chrome's border radius
-                Color32::from_rgb(241, 243, 244),
+                palette.omnibox,
                 Stroke::NONE
             );
             
@@ -866,29 +2923,83 @@ impl ModernGenesisBrowser {
             ui.allocate_ui_at_rect(url_rect, |ui| {
                 ui.add_space(12.0);
                 ui.horizontal_centered(|ui| {
+                    self.render_security_icon(ui);
+                    
+                    let feeds = self.ui_state.get_active_tab().map(|tab| tab.feeds).unwrap_or_default();
+                    let feed_icon_width = if feeds.is_empty() { 0.0 } else { 28.0 };
                     let url_response = ui.add_sized(
-                        Vec2::new(url_rect.width() - 24.0, 28.0),
+                        Vec2::new(url_rect.width() - 52.0 - feed_icon_width, 28.0),
                         TextEdit::singleline(&mut self.url_input)
                             .font(FontId::new(14.0, FontFamily::Proportional))
-                            .hint_text("Search Google or type a URL")
+                            .hint_text("Search or type a URL")
                     );
+                    url_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::TextEdit, "Address and search bar"));
+                    if !feeds.is_empty() {
+                        self.render_feed_icon(ui, &feeds);
+                    }
+                    
+                    if url_response.changed() {
+                        self.show_suggestions = true;
+                        self.selected_suggestion = None;
+                    }
+                    
+                    let suggestions = if self.show_suggestions {
+                        self.ui_state.suggest(&self.url_input)
+                    } else {
+                        Vec::new()
+                    };
+                    
+                    if !suggestions.is_empty() && url_response.has_focus() {
+                        let (down, up) = ui.input_mut(|i| (
+                            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                        ));
+                        if down {
+                            self.selected_suggestion = Some(match self.selected_suggestion {
+                                Some(index) => (index + 1).min(suggestions.len() - 1),
+                                None => 0,
+                            });
+                        }
+                        if up {
+                            self.selected_suggestion = self.selected_suggestion.and_then(|index| index.checked_sub(1));
+                        }
+                    }
                     
                     if url_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                        let url = self.url_input.clone();
+                        let url = self.selected_suggestion
+                            .and_then(|index| suggestions.get(index))
+                            .map_or_else(|| self.url_input.clone(), |suggestion| suggestion.url.clone());
+                        self.show_suggestions = false;
                         self.navigate_to(&url);
+                    } else if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        self.show_suggestions = false;
+                    } else if !suggestions.is_empty() {
+                        if let Some(url) = self.render_suggestions(ui.ctx(), url_rect, &suggestions) {
+                            self.show_suggestions = false;
+                            self.navigate_to(&url);
+                        } else if url_response.clicked_elsewhere() && !ui.ctx().is_pointer_over_area() {
+                            self.show_suggestions = false;
+                        }
                     }
                 });
             });
             
             ui.add_space(8.0);
             
+            if self.ui_state.private_mode.get() {
+                ui.label(RichText::new("🕶").size(18.0).color(palette.progress))
+                    .on_hover_text("Private: history, cookies and caches aren't saved");
+                ui.add_space(4.0);
+            }
+            
             // Chrome menu button (three dots)
-            let menu_response = ui.allocate_response(Vec2::new(32.0, 32.0), egui::Sense::click());
+            let menu_response = ui.allocate_response(button_size, egui::Sense::click());
+            menu_response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Menu"));
             if menu_response.hovered() {
                 ui.painter().circle_filled(
                     menu_response.rect.center(),
-                    16.0,
-                    Color32::from_rgba_premultiplied(60, 64, 67, 20)
+                    hover_radius,
+                    palette.hover_overlay
                 );
             }
             
@@ -898,91 +3009,457 @@ impl ModernGenesisBrowser {
                 ui.painter().circle_filled(
                     center + Vec2::new(0.0, i as f32 * 6.0),
                     2.0,
-                    Color32::from_rgb(95, 99, 104)
+                    palette.icon
                 );
             }
             
+            let menu_popup_id = ui.make_persistent_id("chrome_menu");
             if menu_response.clicked() {
-                self.show_devtools = !self.show_devtools;
+                ui.memory_mut(|mem| mem.toggle_popup(menu_popup_id));
             }
             
+            egui::popup::popup_below_widget(ui, menu_popup_id, &menu_response, |ui| {
+                ui.set_min_width(180.0);
+                
+                let theme_label = if self.dark_mode { "☀ Light theme" } else { "🌙 Dark theme" };
+                if ui.button(theme_label).clicked() {
+                    self.dark_mode = !self.dark_mode;
+                }
+                
+                if ui.button("🕘 History").clicked() {
+                    self.show_history = !self.show_history;
+                }
+                
+                if ui.button("📰 Feeds").clicked() {
+                    self.show_feeds = !self.show_feeds;
+                }
+                
+                if ui.button("🔧 Developer tools").clicked() {
+                    self.show_devtools = !self.show_devtools;
+                }
+                
+                // Shortcut hints come from the table the input is handled from
+                let with_shortcut = |label: &str, action: ShortcutAction| match shortcut_label(&self.shortcuts, action) {
+                    Some(keys) => format!("{} ({})", label, keys),
+                    None => label.to_string(),
+                };
+                let reload_all_label = with_shortcut("🔄 Reload all tabs", ShortcutAction::ReloadAll);
+                let copy_url_label = with_shortcut("📋 Copy URL", ShortcutAction::CopyUrl);
+                let view_source_label = with_shortcut("📄 View page source", ShortcutAction::ViewSource);
+                let fullscreen_label = with_shortcut(
+                    if self.fullscreen { "🗗 Exit fullscreen" } else { "⛶ Fullscreen" },
+                    ShortcutAction::ToggleFullscreen
+                );
+                let shortcuts_label = with_shortcut("⌨ Keyboard shortcuts", ShortcutAction::ShowShortcuts);
+                let palette_label = with_shortcut("🔍 Command palette", ShortcutAction::CommandPalette);
+                let performance_label = with_shortcut("📈 Performance overlay", ShortcutAction::TogglePerformanceOverlay);
+                let private_label = with_shortcut("🕶 New private window", ShortcutAction::NewPrivateWindow);
+                let notes_label = with_shortcut("📝 Notes", ShortcutAction::OpenNotes);
+                
+                if ui.button(reload_all_label).clicked() {
+                    self.reload_all_tabs();
+                }
+                
+                if ui.button(copy_url_label).clicked() {
+                    let ctx = ui.ctx().clone();
+                    self.copy_active_url(&ctx, false);
+                }
+                if ui.button("📝 Copy as Markdown").clicked() {
+                    let ctx = ui.ctx().clone();
+                    self.copy_active_url(&ctx, true);
+                }
+                if ui.button(view_source_label).clicked() {
+                    self.view_active_source();
+                }
+                
+                if ui.button(fullscreen_label).clicked() {
+                    let ctx = ui.ctx().clone();
+                    self.set_fullscreen(&ctx, !self.fullscreen);
+                }
+                
+                if ui.button(shortcuts_label).clicked() {
+                    self.show_shortcuts = true;
+                }
+                
+                if ui.button(palette_label).clicked() {
+                    self.toggle_command_palette();
+                }
+                
+                if ui.button(performance_label).clicked() {
+                    self.show_performance_overlay = !self.show_performance_overlay;
+                }
+                
+                if ui.button(private_label).clicked() {
+                    self.open_private_window();
+                }
+                
+                if ui.button(notes_label).clicked() {
+                    self.open_notes();
+                }
+                
+                if ui.button("⚙ Settings").clicked() {
+                    self.ui_state.toggle_panel("settings");
+                }
+                
+                ui.separator();
+                ui.menu_button("🔎 Search engine", |ui| {
+                    let mut template = self.ui_state.search_engine_template.borrow_mut();
+                    for (name, engine) in SEARCH_ENGINES {
+                        if ui.radio(*template == engine, name).clicked() {
+                            *template = engine.to_string();
+                        }
+                    }
+                    ui.label(RichText::new("Custom ({query} is replaced):").small());
+                    ui.text_edit_singleline(&mut *template);
+                });
+            });
+            
             ui.add_space(8.0);
         });
     }
     
-    /// Render main content area
-    fn render_main_content(&mut self, ctx: &egui::Context) {
-        CentralPanel::default().show(ctx, |ui| {
-            if let Some(tab) = self.ui_state.get_active_tab() {
-                let _content_rect = ui.available_rect_before_wrap();
-                
-                ui.vertical_centered(|ui| {
-                    ui.add_space(50.0);
-                    
-                    ui.heading(
-                        RichText::new(&tab.title)
-                            .size(24.0)
-                            .color(if tab.is_genesis_domain { 
-                                Color32::from_rgb(34, 197, 94) 
-                            } else { 
-                                Color32::GRAY 
-                            })
-                    );
-                    
-                    ui.add_space(20.0);
-                    
-                    ui.label(
-                        RichText::new(&tab.url)
-                            .size(16.0)
-                            .color(Color32::from_rgb(156, 163, 175))
-                    );
-                    
-                    ui.add_space(40.0);
-                    
-                    if tab.is_genesis_domain {
-                        ui.group(|ui| {
-                            ui.vertical(|ui| {
-                                ui.label(
-                                    RichText::new("🌐 Genesis Blockchain Domain")
-                                        .size(18.0)
-                                        .color(Color32::from_rgb(34, 197, 94))
-                                        .strong()
-                                );
-                                
-                                ui.separator();
-                                
-                                ui.label("✅ Decentralized DNS resolution");
-                                ui.label("✅ Censorship resistant");
-                                ui.label("✅ Community governed");
-                                ui.label("✅ No ICANN dependency");
-                                ui.label("✅ Built on 2M+ TPS Genesis blockchain");
-                                
-                                ui.separator();
-                                
-                                ui.horizontal(|ui| {
-                                    if ui.button("📖 Learn More").clicked() {
-                                        self.navigate_to("genesis://docs");
-                                    }
-                                    if ui.button("🌐 Explore Genesis").clicked() {
-                                        self.navigate_to("genesis://explorer");
-                                    }
-                                });
-                            });
-                        });
-                    } else {
-                        ui.group(|ui| {
-                            ui.vertical(|ui| {
-                                ui.label(
-                                    RichText::new("🌍 Traditional Web Domain")
-                                        .size(18.0)
-                                        .color(Color32::GRAY)
-                                );
+    /// Feed icon at the end of the omnibox for pages that advertise feeds.
+    /// Clicking it subscribes; pages with several feeds offer a choice.
+    fn render_feed_icon(&mut self, ui: &mut Ui, feeds: &[String]) {
+        let palette = self.palette();
+        let subscribed = feeds.iter().all(|feed| self.ui_state.is_subscribed(feed));
+        let response = ui.allocate_response(Vec2::new(24.0, 24.0), egui::Sense::click());
+        response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Subscribe to feed"));
+        if response.hovered() {
+            ui.painter().circle_filled(response.rect.center(), 12.0, palette.hover_overlay);
+        }
+        let color = if subscribed { palette.icon } else { Color32::from_rgb(242, 133, 0) };
+        paint_feed_icon(ui.painter(), response.rect.center(), color);
+        let response = response.on_hover_text(if subscribed { "Subscribed to this page's feed" } else { "Subscribe to this page's feed" });
+        
+        let popup_id = ui.make_persistent_id("feed_choice");
+        let mut chosen = None;
+        if response.clicked() {
+            if feeds.len() == 1 {
+                chosen = Some(feeds[0].clone());
+            } else {
+                ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+            }
+        }
+        egui::popup::popup_below_widget(ui, popup_id, &response, |ui| {
+            ui.set_min_width(240.0);
+            for feed in feeds {
+                let label = if self.ui_state.is_subscribed(feed) { format!("✔ {}", feed) } else { feed.clone() };
+                if ui.selectable_label(false, label).clicked() {
+                    chosen = Some(feed.clone());
+                }
+            }
+        });
+        
+        let (Some(feed), Some(tab)) = (chosen, self.ui_state.get_active_tab()) else {
+            return;
+        };
+        let message = if self.ui_state.subscribe_feed(tab.title, feed, tab.url) {
+            "Subscribed to feed"
+        } else {
+            "Already subscribed"
+        };
+        self.toast = Some((message.to_string(), Instant::now()));
+    }
+    
+    /// Security icon at the start of the omnibox; clicking it describes the connection
+    fn render_security_icon(&mut self, ui: &mut Ui) {
+        let palette = self.palette();
+        let state = self.ui_state.get_active_tab().map_or(SecurityState::Neutral, |tab| tab.security_state);
+        let response = ui.allocate_response(Vec2::new(24.0, 24.0), egui::Sense::click());
+        response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Site information"));
+        if response.hovered() {
+            ui.painter().circle_filled(response.rect.center(), 12.0, palette.hover_overlay);
+        }
+        
+        let center = response.rect.center();
+        let painter = ui.painter();
+        match state {
+            SecurityState::Genesis => {
+                // Green shield with a "G"
+                let green = Color32::from_rgb(34, 197, 94);
+                let shield = vec![
+                    center + Vec2::new(-6.0, -7.0),
+                    center + Vec2::new(6.0, -7.0),
+                    center + Vec2::new(6.0, 1.0),
+                    center + Vec2::new(0.0, 8.0),
+                    center + Vec2::new(-6.0, 1.0),
+                ];
+                painter.add(egui::Shape::convex_polygon(shield, green, Stroke::NONE));
+                painter.text(center - Vec2::new(0.0, 1.0), egui::Align2::CENTER_CENTER, "G",
+                    FontId::new(10.0, FontFamily::Proportional), Color32::WHITE);
+            }
+            SecurityState::Secure => {
+                // Padlock: shackle over a body
+                painter.circle_stroke(center - Vec2::new(0.0, 2.0), 3.5, Stroke::new(1.5, palette.icon));
+                painter.rect_filled(
+                    egui::Rect::from_center_size(center + Vec2::new(0.0, 3.0), Vec2::new(10.0, 7.0)),
+                    1.5,
+                    palette.icon
+                );
+            }
+            SecurityState::Insecure | SecurityState::Broken => {
+                // Warning triangle
+                let red = Color32::from_rgb(217, 48, 37);
+                let triangle = vec![
+                    center + Vec2::new(0.0, -7.0),
+                    center + Vec2::new(7.0, 6.0),
+                    center + Vec2::new(-7.0, 6.0),
+                ];
+                painter.add(egui::Shape::convex_polygon(triangle, red, Stroke::NONE));
+                painter.text(center + Vec2::new(0.0, 1.0), egui::Align2::CENTER_CENTER, "!",
+                    FontId::new(9.0, FontFamily::Proportional), Color32::WHITE);
+            }
+            SecurityState::Neutral => {
+                // Info circle
+                painter.circle_stroke(center, 6.0, Stroke::new(1.5, palette.icon));
+                painter.text(center, egui::Align2::CENTER_CENTER, "i",
+                    FontId::new(9.0, FontFamily::Proportional), palette.icon);
+            }
+        }
+        
+        let popup_id = ui.make_persistent_id("security_popup");
+        if response.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+        }
+        egui::popup::popup_below_widget(ui, popup_id, &response, |ui| {
+            ui.set_min_width(240.0);
+            let (headline, details) = state.description();
+            ui.label(RichText::new(headline).strong());
+            ui.label(RichText::new(details).small());
+            
+            let host = self.ui_state.get_active_tab()
+                .filter(|tab| InternalPage::parse(&tab.url).is_none())
+                .and_then(|tab| url_host(&tab.url));
+            if let Some(host) = host {
+                ui.separator();
+                if ui.button("⚙ Site settings").clicked() {
+                    self.site_settings_host = Some(host);
+                }
+            }
+        });
+    }
+    
+    /// Render the new-tab page: most visited sites and Genesis shortcuts
+    fn render_new_tab_page(&mut self, ui: &mut Ui, zoom: f32) {
+        let top_sites = self.ui_state.top_sites(TOP_SITES);
+        let tile_size = Vec2::new(150.0, 72.0) * zoom;
+        let mut open = None;
+        
+        ui.vertical_centered(|ui| {
+            ui.add_space(60.0);
+            ui.heading(RichText::new("Genesis").size(32.0 * zoom).color(Color32::from_rgb(34, 197, 94)));
+            ui.add_space(30.0);
+            
+            if top_sites.is_empty() {
+                ui.label(
+                    RichText::new("Start browsing — the sites you visit most will show up here")
+                        .color(Color32::GRAY)
+                );
+            } else {
+                ui.horizontal_wrapped(|ui| {
+                    ui.set_max_width(4.0 * (tile_size.x + ui.spacing().item_spacing.x));
+                    for site in &top_sites {
+                        let host = url::Url::parse(&site.url).ok()
+                            .and_then(|url| url.host_str().map(|host| host.to_string()))
+                            .unwrap_or_else(|| site.url.clone());
+                        let label = format!("{}\n{}", site.title, host);
+                        let response = ui.add_sized(tile_size, egui::Button::new(label))
+                            .on_hover_text(&site.url);
+                        if response.clicked() {
+                            open = Some((site.url.clone(), false));
+                        } else if response.clicked_by(egui::PointerButton::Middle) {
+                            open = Some((site.url.clone(), true));
+                        }
+                    }
+                });
+            }
+            
+            ui.add_space(30.0);
+            ui.horizontal_wrapped(|ui| {
+                for (label, url) in GENESIS_SHORTCUTS {
+                    let response = ui.button(label);
+                    if response.clicked() {
+                        open = Some((url.to_string(), false));
+                    } else if response.clicked_by(egui::PointerButton::Middle) {
+                        open = Some((url.to_string(), true));
+                    }
+                }
+            });
+        });
+        
+        match open {
+            Some((url, true)) => self.open_background_tab(&url),
+            Some((url, false)) => self.navigate_to(&url),
+            None => {}
+        }
+    }
+    
+    /// Render the autocomplete dropdown under the omnibox, returning the clicked URL
+    fn render_suggestions(&self, ctx: &egui::Context, url_rect: egui::Rect, suggestions: &[Suggestion]) -> Option<String> {
+        let palette = self.palette();
+        let mut clicked = None;
+        
+        egui::Area::new(egui::Id::new("omnibox_suggestions"))
+            .fixed_pos(url_rect.left_bottom() + Vec2::new(0.0, 4.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(url_rect.width());
+                    
+                    for (index, suggestion) in suggestions.iter().enumerate() {
+                        let selected = self.selected_suggestion == Some(index);
+                        let response = ui.horizontal(|ui| {
+                            // Green "G" marker for Genesis domains, as on tabs
+                            let (marker_rect, _) = ui.allocate_exact_size(Vec2::new(16.0, 16.0), egui::Sense::hover());
+                            if suggestion.is_genesis_domain {
+                                ui.painter().circle_filled(marker_rect.center(), 8.0, Color32::from_rgb(34, 197, 94));
+                                ui.painter().text(
+                                    marker_rect.center(),
+                                    egui::Align2::CENTER_CENTER,
+                                    "G",
+                                    FontId::new(10.0, FontFamily::Proportional),
+                                    Color32::WHITE
+                                );
+                            } else {
+                                let icon = if suggestion.is_bookmark { "⭐" } else { "🕘" };
+                                ui.painter().text(
+                                    marker_rect.center(),
+                                    egui::Align2::CENTER_CENTER,
+                                    icon,
+                                    FontId::new(12.0, FontFamily::Proportional),
+                                    palette.icon
+                                );
+                            }
+                            
+                            let clicked = ui.selectable_label(
+                                selected,
+                                RichText::new(&suggestion.title).color(palette.text_active)
+                            ).clicked();
+                            ui.label(RichText::new(&suggestion.url).small().color(palette.text_inactive));
+                            clicked
+                        });
+                        
+                        if response.inner {
+                            clicked = Some(suggestion.url.clone());
+                        }
+                    }
+                });
+            });
+        
+        clicked
+    }
+    
+    /// Render main content area
+    fn render_main_content(&mut self, ctx: &egui::Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            if let Some(tab) = self.ui_state.get_active_tab() {
+                let _content_rect = ui.available_rect_before_wrap();
+                
+                // Page zoom scales every font in the content area
+                let zoom = tab.zoom;
+                for font_id in ui.style_mut().text_styles.values_mut() {
+                    font_id.size *= zoom;
+                }
+                
+                if let Some(error) = &tab.error {
+                    self.render_load_error(ui, &tab.url, error, zoom);
+                    return;
+                }
+                
+                if source_target(&tab.url).is_some() {
+                    self.render_page_source(ui, &tab, zoom);
+                    return;
+                }
+                
+                match InternalPage::parse(&tab.url) {
+                    Some(InternalPage::NewTab) => {
+                        self.render_new_tab_page(ui, zoom);
+                        return;
+                    }
+                    Some(InternalPage::Notes) => {
+                        self.render_notes_page(ui, zoom);
+                        return;
+                    }
+                    Some(page) => {
+                        let template = page.template(&self.ui_state.genesis_tlds.borrow());
+                        self.render_internal_page(ui, &template, zoom);
+                        return;
+                    }
+                    None => {}
+                }
+                
+                ui.vertical_centered(|ui| {
+                    ui.add_space(50.0);
+                    
+                    ui.heading(
+                        RichText::new(&tab.title)
+                            .size(24.0 * zoom)
+                            .color(if tab.is_genesis_domain { 
+                                Color32::from_rgb(34, 197, 94) 
+                            } else { 
+                                Color32::GRAY 
+                            })
+                    );
+                    
+                    ui.add_space(20.0);
+                    
+                    ui.label(
+                        RichText::new(&tab.url)
+                            .size(16.0 * zoom)
+                            .color(Color32::from_rgb(156, 163, 175))
+                    );
+                    
+                    ui.add_space(40.0);
+                    
+                    if tab.is_genesis_domain {
+                        ui.group(|ui| {
+                            ui.vertical(|ui| {
+                                ui.label(
+                                    RichText::new(GENESIS_HEADING)
+                                        .size(18.0 * zoom)
+                                        .color(Color32::from_rgb(34, 197, 94))
+                                        .strong()
+                                );
+                                
+                                ui.separator();
+                                
+                                for feature in GENESIS_FEATURES {
+                                    ui.label(feature);
+                                }
+                                
+                                ui.separator();
+                                
+                                ui.horizontal(|ui| {
+                                    // Middle-click opens the page in a background tab
+                                    for (label, url) in [
+                                        ("📖 Learn More", "genesis://docs"),
+                                        ("🌐 Explore Genesis", "genesis://explorer"),
+                                    ] {
+                                        let response = ui.button(label);
+                                        if response.clicked_by(egui::PointerButton::Middle) {
+                                            self.open_background_tab(url);
+                                        } else if response.clicked() {
+                                            self.navigate_to(url);
+                                        }
+                                    }
+                                });
+                            });
+                        });
+                    } else {
+                        ui.group(|ui| {
+                            ui.vertical(|ui| {
+                                ui.label(
+                                    RichText::new(TRADITIONAL_HEADING)
+                                        .size(18.0 * zoom)
+                                        .color(Color32::GRAY)
+                                );
                                 
                                 ui.separator();
                                 
-                                ui.label("🔄 Using traditional DNS fallback");
-                                ui.label("⚠️ Centralized infrastructure");
-                                ui.label("🔒 Subject to censorship");
+                                for note in TRADITIONAL_NOTES {
+                                    ui.label(note);
+                                }
                                 
                                 ui.separator();
                                 
@@ -1017,12 +3494,343 @@ impl ModernGenesisBrowser {
         });
     }
     
+    /// Render an internal page from its template
+    fn render_internal_page(&mut self, ui: &mut Ui, template: &PageTemplate, zoom: f32) {
+        let mut open = None;
+        
+        ui.vertical_centered(|ui| {
+            ui.add_space(60.0);
+            ui.heading(
+                RichText::new(&template.heading)
+                    .size(24.0 * zoom)
+                    .color(Color32::from_rgb(34, 197, 94))
+            );
+            ui.add_space(20.0);
+            
+            for paragraph in &template.paragraphs {
+                ui.label(RichText::new(paragraph).size(14.0 * zoom));
+                ui.add_space(6.0);
+            }
+            
+            if !template.links.is_empty() {
+                ui.add_space(30.0);
+                ui.horizontal_wrapped(|ui| {
+                    for (label, url) in &template.links {
+                        let response = ui.button(label);
+                        if response.clicked_by(egui::PointerButton::Middle) {
+                            open = Some((url.clone(), true));
+                        } else if response.clicked() {
+                            open = Some((url.clone(), false));
+                        }
+                    }
+                });
+            }
+        });
+        
+        match open {
+            Some((url, true)) => self.open_background_tab(&url),
+            Some((url, false)) => self.navigate_to(&url),
+            None => {}
+        }
+    }
+    
+    /// Render genesis://notes: the whole page is one text field, saved by
+    /// `update` once typing pauses for `NOTES_SAVE_DELAY`
+    fn render_notes_page(&mut self, ui: &mut Ui, zoom: f32) {
+        ui.heading(
+            RichText::new(InternalPage::Notes.template(&[]).heading)
+                .size(20.0 * zoom)
+                .color(Color32::from_rgb(34, 197, 94))
+        );
+        ui.add_space(8.0);
+        
+        let changed = ScrollArea::vertical().show(ui, |ui| {
+            let mut notes = self.ui_state.notes.borrow_mut();
+            ui.add(
+                TextEdit::multiline(&mut *notes)
+                    .hint_text("Jot down addresses, ideas, anything…")
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(30)
+                    .font(egui::TextStyle::Monospace)
+            ).changed()
+        }).inner;
+        
+        if changed {
+            self.ui_state.notes_dirty.set(true);
+            self.notes_edited_at = Some(Instant::now());
+        }
+    }
+    
+    /// Switch to the tab showing genesis://notes, or open it in a new one
+    fn open_notes(&mut self) {
+        let url = InternalPage::Notes.url();
+        match self.ui_state.find_tab_by_url(&url) {
+            Some(index) => self.switch_tab(index),
+            None => {
+                self.open_tab(&url);
+            }
+        }
+    }
+    
+    /// Render the placeholder of a tab whose page failed to load, with a way to retry
+    fn render_load_error(&mut self, ui: &mut Ui, url: &str, error: &LoadError, zoom: f32) {
+        self.render_internal_page(ui, &PageTemplate::load_error(url, error), zoom);
+        
+        ui.vertical_centered(|ui| {
+            ui.add_space(30.0);
+            if ui.button(RichText::new("🔄 Reload").size(14.0 * zoom)).clicked() {
+                self.reload();
+            }
+        });
+    }
+    
+    /// Source of a `view-source:` tab as numbered, monospace lines. Only the
+    /// rows in view are laid out, so long documents stay responsive.
+    fn render_page_source(&mut self, ui: &mut Ui, tab: &crate::enhanced_browser::BrowserTab, zoom: f32) {
+        let requested = self.page_sources.get(&tab.id).map_or(false, |(url, _)| *url == tab.url);
+        if !requested {
+            // Restored and background tabs are fetched once they're shown
+            self.request_page_source(tab.id.clone(), tab.url.clone());
+        }
+        let Some(source) = self.page_sources.get(&tab.id).and_then(|(_, source)| source.as_ref()) else {
+            ui.centered_and_justified(|ui| {
+                ui.spinner();
+            });
+            return;
+        };
+        
+        if source.truncated {
+            ui.label(
+                RichText::new(format!("Only the first {} MB are shown", crate::page_source::MAX_SOURCE_BYTES / (1024 * 1024)))
+                    .color(Color32::GRAY)
+            );
+        }
+        
+        let font = FontId::monospace(13.0 * zoom);
+        let row_height = ui.fonts(|fonts| fonts.row_height(&font));
+        let number_width = source.lines.len().to_string().len();
+        ScrollArea::both()
+            .auto_shrink([false, false])
+            .show_rows(ui, row_height, source.lines.len(), |ui, rows| {
+                for index in rows {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(format!("{:>width$}", index + 1, width = number_width))
+                                .font(font.clone())
+                                .color(Color32::GRAY)
+                        );
+                        ui.add(egui::Label::new(RichText::new(&source.lines[index]).font(font.clone())).wrap(false));
+                    });
+                }
+            });
+    }
+    
+    /// Fetch the source for a `view-source:` tab, resolving Genesis hosts first.
+    /// The result is applied by `poll_page_sources`.
+    fn request_page_source(&mut self, tab_id: String, url: String) {
+        let Some(target) = source_target(&url).map(str::to_string) else {
+            return;
+        };
+        self.page_sources.insert(tab_id.clone(), (url.clone(), None));
+        if let Some(index) = self.ui_state.tabs.borrow().iter().position(|tab| tab.id == tab_id) {
+            self.ui_state.update_tab(index, None, None, Some(true), Some(SIMULATED_PROGRESS_START));
+        }
+        
+        let resolver = self.dns_resolver.clone();
+        let sender = self.source_sender.clone();
+        let egui_ctx = self.egui_ctx.clone();
+        self.runtime_handle.spawn(async move {
+            let address = match navigation_host(&target) {
+                Some(host) => GenesisDnsResolver::resolve_shared(&resolver, &host).await
+                    .ok()
+                    .and_then(|result| result.ip_address),
+                None => None,
+            };
+            let source = fetch_source(&target, address).await;
+            let _ = sender.send((tab_id, url, source));
+            wake(&egui_ctx);
+        });
+    }
+    
+    /// Show fetched sources on tabs still pointing at them; a failed fetch
+    /// gets the couldn't-load page
+    fn poll_page_sources(&mut self) {
+        while let Ok((tab_id, url, source)) = self.source_receiver.try_recv() {
+            let index = self.ui_state.tabs.borrow().iter()
+                .position(|tab| tab.id == tab_id && tab.url == url);
+            let Some(index) = index else {
+                self.page_sources.remove(&tab_id);
+                continue;
+            };
+            
+            match source {
+                Ok(source) => {
+                    info!("📄 Fetched {} lines of source for {}", source.lines.len(), url);
+                    self.page_sources.insert(tab_id, (url, Some(source)));
+                }
+                Err(e) => {
+                    warn!("⚠️ Failed to fetch the source of {}: {:#}", url, e);
+                    self.ui_state.set_tab_error(index, Some(LoadError::from_anyhow(&e)));
+                }
+            }
+            self.ui_state.update_tab(index, None, None, Some(false), None);
+        }
+    }
+    
+    /// Start or restart the download `id` in the background. A restart picks
+    /// up from the partial file the last attempt left.
+    fn start_download(&mut self, id: String, url: String) {
+        let egui_ctx = self.egui_ctx.clone();
+        let manager = DownloadManager::new(self.dns_resolver.clone(), self.download_sender.clone())
+            .with_update_callback(Arc::new(move || wake(&egui_ctx)));
+        self.ui_state.set_download_status(&id, DownloadStatus::Pending);
+        
+        let task_id = id.clone();
+        let task = self.runtime_handle.spawn(async move {
+            let _ = manager.start_download(&task_id, &url, &default_download_dir()).await;
+        });
+        if let Some(previous) = self.download_tasks.insert(id, task) {
+            previous.abort();
+        }
+    }
+    
+    /// Stop a running download. A paused download keeps its partial file to
+    /// resume from; a cancelled one has it deleted.
+    fn stop_download(&mut self, id: &str, status: DownloadStatus) {
+        if let Some(task) = self.download_tasks.remove(id) {
+            task.abort();
+        }
+        if matches!(status, DownloadStatus::Cancelled) {
+            let url = self.ui_state.downloads.borrow().iter()
+                .find(|download| download.id == id)
+                .map(|download| download.url.clone());
+            if let Some(url) = url {
+                let _ = std::fs::remove_file(partial_download_path(&default_download_dir(), &url));
+            }
+        }
+        self.ui_state.set_download_status(id, status);
+    }
+    
+    /// Apply download progress. Updates from downloads that were paused or
+    /// cancelled since are dropped.
+    fn poll_downloads(&mut self) {
+        while let Ok(update) = self.download_receiver.try_recv() {
+            if !self.download_tasks.contains_key(&update.id) {
+                continue;
+            }
+            if matches!(update.status, DownloadStatus::Completed | DownloadStatus::Failed(_)) {
+                self.download_tasks.remove(&update.id);
+            }
+            if let Some(size) = update.size {
+                self.ui_state.set_download_size(&update.id, size);
+            }
+            self.ui_state.update_download(&update.id, update.downloaded, update.status);
+        }
+    }
+    
+    /// Open the source of the active page in a new tab
+    fn view_active_source(&mut self) {
+        let Some(tab) = self.ui_state.get_active_tab() else {
+            return;
+        };
+        if navigation_host(&tab.url).is_none() {
+            return;
+        }
+        self.open_tab(&view_source_url(&tab.url));
+    }
+    
+    /// Text of the rendered page, searched by find-in-page until Servo content is wired up
+    fn page_text(&self, tab: &crate::enhanced_browser::BrowserTab) -> String {
+        if let Some(error) = &tab.error {
+            return PageTemplate::load_error(&tab.url, error).text();
+        }
+        if let Some((_, Some(source))) = self.page_sources.get(&tab.id).filter(|(url, _)| *url == tab.url) {
+            return source.text();
+        }
+        match InternalPage::parse(&tab.url) {
+            Some(InternalPage::Notes) => return self.ui_state.notes.borrow().clone(),
+            Some(page) => return page.template(&self.ui_state.genesis_tlds.borrow()).text(),
+            None => {}
+        }
+        let mut lines = vec![tab.title.clone(), tab.url.clone()];
+        if tab.is_genesis_domain {
+            lines.push(GENESIS_HEADING.to_string());
+            lines.extend(GENESIS_FEATURES.iter().map(|line| line.to_string()));
+        } else {
+            lines.push(TRADITIONAL_HEADING.to_string());
+            lines.extend(TRADITIONAL_NOTES.iter().map(|line| line.to_string()));
+        }
+        lines.join("\n")
+    }
+    
+    /// Render the find-in-page bar below the toolbar
+    fn render_find_bar(&mut self, ctx: &egui::Context) {
+        if !self.ui_state.find_active.get() {
+            return;
+        }
+        
+        if let Some(tab) = self.ui_state.get_active_tab() {
+            self.ui_state.update_find_matches(&self.page_text(&tab));
+        }
+        
+        egui::Area::new(egui::Id::new("find_bar"))
+            .anchor(egui::Align2::RIGHT_TOP, Vec2::new(-16.0, 80.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        let mut query = self.ui_state.find_query.borrow_mut();
+                        let response = ui.add_sized(
+                            Vec2::new(200.0, 24.0),
+                            TextEdit::singleline(&mut *query).hint_text("Find in page")
+                        );
+                        drop(query);
+                        
+                        if self.focus_find_field {
+                            response.request_focus();
+                            self.focus_find_field = false;
+                        }
+                        if response.changed() {
+                            self.ui_state.find_current_match.set(0);
+                        }
+                        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                            if ui.input(|i| i.modifiers.shift) {
+                                self.ui_state.find_previous();
+                            } else {
+                                self.ui_state.find_next();
+                            }
+                            response.request_focus();
+                        }
+                        
+                        let count = self.ui_state.find_match_count.get();
+                        let current = if count == 0 { 0 } else { self.ui_state.find_current_match.get() + 1 };
+                        ui.label(
+                            RichText::new(format!("{}/{}", current, count))
+                                .size(12.0)
+                                .color(Color32::GRAY)
+                        );
+                        
+                        if ui.small_button("▲").clicked() {
+                            self.ui_state.find_previous();
+                        }
+                        if ui.small_button("▼").clicked() {
+                            self.ui_state.find_next();
+                        }
+                        if ui.small_button("✕").clicked() {
+                            self.ui_state.close_find();
+                        }
+                    });
+                });
+            });
+    }
+    
     /// Render status bar
     fn render_status_bar(&mut self, ctx: &egui::Context) {
+        let palette = self.palette();
+        
         TopBottomPanel::bottom("status_bar")
             .exact_height(22.0) // Chrome's exact status bar height
             .show(ctx, |ui| {
-                ui.style_mut().visuals.panel_fill = Color32::from_rgb(241, 243, 244);
+                ui.style_mut().visuals.panel_fill = palette.status_bar;
                 
                 ui.horizontal(|ui| {
                     ui.add_space(8.0);
@@ -1031,15 +3839,15 @@ impl ModernGenesisBrowser {
                         if tab.is_loading {
                             ui.spinner();
                             ui.label(
-                                RichText::new("Loading...")
+                                RichText::new(format!("Loading... {:.0}%", tab.load_progress * 100.0))
                                     .size(11.0)
-                                    .color(Color32::from_rgb(95, 99, 104))
+                                    .color(palette.icon)
                             );
                         } else {
                             ui.label(
                                 RichText::new(&tab.url)
                                     .size(11.0)
-                                    .color(Color32::from_rgb(95, 99, 104))
+                                    .color(palette.icon)
                             );
                         }
                         
@@ -1055,19 +3863,52 @@ impl ModernGenesisBrowser {
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.add_space(8.0);
                         
-                        ui.label(
-                            RichText::new(format!("{}%", 100))
-                                .size(11.0)
-                                .color(Color32::from_rgb(95, 99, 104))
-                        );
-                        
-                        ui.separator();
+                        if self.memory_sampled_at.elapsed() >= MEMORY_SAMPLE_INTERVAL {
+                            self.resident_memory = resident_memory_bytes();
+                            self.memory_sampled_at = Instant::now();
+                        }
+                        if let Some(bytes) = self.resident_memory {
+                            ui.label(
+                                RichText::new(format!("{} MB", bytes / (1024 * 1024)))
+                                    .size(11.0)
+                                    .color(palette.icon)
+                            ).on_hover_text("Browser memory usage");
+                            
+                            ui.separator();
+                        }
                         
-                        ui.label(
+                        if let Some(tab) = self.ui_state.get_active_tab() {
+                            let percent = (tab.zoom * 100.0).round() as i32;
+                            if percent != 100 {
+                                ui.label(
+                                    RichText::new(format!("{}%", percent))
+                                        .size(11.0)
+                                        .color(palette.icon)
+                                );
+                                
+                                ui.separator();
+                            }
+                        }
+                        
+                        ui.label(
                             RichText::new(&*self.ui_state.genesis_node_status.borrow())
                                 .size(11.0)
-                                .color(Color32::from_rgb(95, 99, 104))
+                                .color(palette.icon)
                         );
+                        
+                        if let Some(health) = &self.node_health {
+                            let color = if health.online { Color32::from_rgb(34, 197, 94) } else { Color32::from_rgb(234, 67, 53) };
+                            let version = health.version.as_deref().unwrap_or("unknown");
+                            let height = health.block_height.map_or("unknown".to_string(), |height| height.to_string());
+                            ui.label(RichText::new("●").size(11.0).color(color)).on_hover_text(format!(
+                                "{}\n{}\nVersion: {}\nBlock height: {}\nLatency: {} ms",
+                                self.genesis_node_url,
+                                if health.online { "Online" } else { "Offline" },
+                                version,
+                                height,
+                                health.latency_ms
+                            ));
+                        }
                     });
                 });
             });
@@ -1114,6 +3955,18 @@ impl ModernGenesisBrowser {
                     add_bookmark_clicked = true;
                 }
                 
+                // Ctrl+Z / Ctrl+Y go through the shortcut table
+                ui.horizontal(|ui| {
+                    let can_undo = !self.ui_state.bookmark_undo.borrow().is_empty();
+                    let can_redo = !self.ui_state.bookmark_redo.borrow().is_empty();
+                    if ui.add_enabled(can_undo, egui::Button::new("↶ Undo")).clicked() {
+                        self.ui_state.undo_bookmark_op();
+                    }
+                    if ui.add_enabled(can_redo, egui::Button::new("↷ Redo")).clicked() {
+                        self.ui_state.redo_bookmark_op();
+                    }
+                });
+                
                 for (action, data) in bookmark_actions {
                     match action {
                         "navigate" => {
@@ -1140,44 +3993,901 @@ impl ModernGenesisBrowser {
                 ui.heading("⬇ Downloads");
                 ui.separator();
                 
-                ui.centered_and_justified(|ui| {
-                    ui.label("No downloads yet");
+                let mut status_changes = Vec::new();
+                let mut clear_completed = false;
+                
+                {
+                    let downloads = self.ui_state.downloads.borrow();
+                    if downloads.is_empty() {
+                        ui.label(RichText::new("No downloads yet").color(Color32::GRAY));
+                    } else {
+                        ScrollArea::vertical().show(ui, |ui| {
+                            for download in downloads.iter() {
+                                ui.label(RichText::new(&download.filename).strong());
+                                
+                                let progress = match download.status {
+                                    DownloadStatus::InProgress(fraction) => fraction.clamp(0.0, 1.0),
+                                    DownloadStatus::Completed => 1.0,
+                                    _ if download.size > 0 => (download.downloaded as f32 / download.size as f32).clamp(0.0, 1.0),
+                                    _ => 0.0,
+                                };
+                                ui.add(egui::ProgressBar::new(progress).show_percentage());
+                                
+                                let status = match &download.status {
+                                    DownloadStatus::Pending => "Waiting...".to_string(),
+                                    DownloadStatus::InProgress(fraction) => format!("Downloading - {:.0}%", fraction * 100.0),
+                                    DownloadStatus::Paused => "Paused".to_string(),
+                                    DownloadStatus::Completed => "Completed".to_string(),
+                                    DownloadStatus::Failed(reason) => format!("Failed: {}", reason),
+                                    DownloadStatus::Cancelled => "Cancelled".to_string(),
+                                };
+                                ui.label(RichText::new(status).small().color(Color32::GRAY));
+                                
+                                ui.horizontal(|ui| {
+                                    match &download.status {
+                                        DownloadStatus::Pending | DownloadStatus::InProgress(_) => {
+                                            if ui.small_button("⏸ Pause").clicked() {
+                                                status_changes.push((download.id.clone(), DownloadStatus::Paused));
+                                            }
+                                            if ui.small_button("✕ Cancel").clicked() {
+                                                status_changes.push((download.id.clone(), DownloadStatus::Cancelled));
+                                            }
+                                        }
+                                        DownloadStatus::Paused => {
+                                            if ui.small_button("▶ Resume").clicked() {
+                                                status_changes.push((download.id.clone(), DownloadStatus::Pending));
+                                            }
+                                            if ui.small_button("✕ Cancel").clicked() {
+                                                status_changes.push((download.id.clone(), DownloadStatus::Cancelled));
+                                            }
+                                        }
+                                        DownloadStatus::Failed(_) | DownloadStatus::Cancelled => {
+                                            if ui.small_button("🔄 Retry").clicked() {
+                                                status_changes.push((download.id.clone(), DownloadStatus::Pending));
+                                            }
+                                        }
+                                        DownloadStatus::Completed => {}
+                                    }
+                                });
+                                ui.separator();
+                            }
+                        });
+                    }
+                }
+                
+                ui.separator();
+                if ui.button("🧹 Clear completed").clicked() {
+                    clear_completed = true;
+                }
+                
+                for (id, status) in status_changes {
+                    match status {
+                        DownloadStatus::Pending => {
+                            let url = self.ui_state.downloads.borrow().iter()
+                                .find(|download| download.id == id)
+                                .map(|download| download.url.clone());
+                            if let Some(url) = url {
+                                self.start_download(id, url);
+                            }
+                        }
+                        status => self.stop_download(&id, status),
+                    }
+                }
+                if clear_completed {
+                    self.ui_state.clear_completed_downloads();
+                }
+            });
+        }
+        
+        if self.show_history {
+            SidePanel::right("history_panel").show(ctx, |ui| {
+                ui.heading("🕘 History");
+                ui.separator();
+                
+                ui.add(
+                    TextEdit::singleline(&mut self.history_filter)
+                        .hint_text("Search history")
+                        .desired_width(f32::INFINITY)
+                );
+                ui.separator();
+                
+                let filter = self.history_filter.to_lowercase();
+                let mut entries: Vec<_> = self.ui_state.history.borrow().iter()
+                    .filter(|entry| {
+                        filter.is_empty()
+                            || entry.title.to_lowercase().contains(&filter)
+                            || entry.url.to_lowercase().contains(&filter)
+                    })
+                    .cloned()
+                    .collect();
+                entries.sort_by(|a, b| b.last_visit.cmp(&a.last_visit));
+                
+                let mut navigate_to = None;
+                let mut clear_history = false;
+                
+                ScrollArea::vertical().max_height(ui.available_height() - 40.0).show(ui, |ui| {
+                    if entries.is_empty() {
+                        ui.label(RichText::new("No matching history").color(Color32::GRAY));
+                    }
+                    
+                    for entry in &entries {
+                        ui.horizontal(|ui| {
+                            if ui.link(&entry.title).clicked() {
+                                navigate_to = Some(entry.url.clone());
+                            }
+                            
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.label(
+                                    RichText::new(format!("{}×", entry.visit_count))
+                                        .small()
+                                        .color(Color32::GRAY)
+                                );
+                            });
+                        });
+                        
+                        ui.label(
+                            RichText::new(format!("{} · {}", entry.url, entry.last_visit.format("%Y-%m-%d %H:%M")))
+                                .small()
+                                .color(Color32::GRAY)
+                        );
+                        ui.separator();
+                    }
                 });
+                
+                ui.separator();
+                if ui.button("🧹 Clear history").clicked() {
+                    clear_history = true;
+                }
+                
+                if let Some(url) = navigate_to {
+                    self.navigate_to(&url);
+                }
+                if clear_history {
+                    self.ui_state.clear_browsing_data(true, false, false, false);
+                }
             });
         }
         
+        if self.show_feeds {
+            self.render_feeds_panel(ctx);
+        }
+        
+        if self.ui_state.show_settings.get() {
+            self.render_settings_panel(ctx);
+        }
+        
         if self.show_devtools {
-            SidePanel::right("devtools_panel").show(ctx, |ui| {
-                ui.heading("🔧 Developer Tools");
+            self.render_devtools_panel(ctx);
+        }
+    }
+    
+    /// Feed subscriptions, newest first
+    fn render_feeds_panel(&mut self, ctx: &egui::Context) {
+        SidePanel::right("feeds_panel").show(ctx, |ui| {
+            ui.heading("📰 Feeds");
+            ui.separator();
+            
+            let mut feeds = self.ui_state.feeds.borrow().clone();
+            feeds.reverse();
+            let mut navigate_to = None;
+            let mut unsubscribe = None;
+            
+            ScrollArea::vertical().show(ui, |ui| {
+                if feeds.is_empty() {
+                    ui.label(RichText::new("No feeds yet. Pages with a feed show a feed icon in the address bar.").color(Color32::GRAY));
+                }
+                
+                for feed in &feeds {
+                    ui.horizontal(|ui| {
+                        if ui.link(&feed.title).on_hover_text(&feed.site_url).clicked() {
+                            navigate_to = Some(feed.url.clone());
+                        }
+                        
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("🗑").on_hover_text("Unsubscribe").clicked() {
+                                unsubscribe = Some(feed.url.clone());
+                            }
+                        });
+                    });
+                    
+                    ui.label(
+                        RichText::new(format!("{} · since {}", feed.url, feed.subscribed_at.format("%Y-%m-%d")))
+                            .small()
+                            .color(Color32::GRAY)
+                    );
+                    ui.separator();
+                }
+            });
+            
+            if let Some(url) = navigate_to {
+                self.navigate_to(&url);
+            }
+            if let Some(url) = unsubscribe {
+                self.ui_state.unsubscribe_feed(&url);
+            }
+        });
+    }
+    
+    /// Render the developer tools panel
+    fn render_devtools_panel(&mut self, ctx: &egui::Context) {
+        SidePanel::right("devtools_panel").show(ctx, |ui| {
+            ui.heading("🔧 Developer Tools");
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                for tab in [DevToolsTab::Console, DevToolsTab::Network, DevToolsTab::Elements] {
+                    if ui.selectable_label(self.devtools_tab == tab, tab.label()).clicked() {
+                        self.devtools_tab = tab;
+                    }
+                }
+            });
+            
+            ui.separator();
+            
+            match self.devtools_tab {
+                DevToolsTab::Console => self.render_console(ui),
+                DevToolsTab::Network => self.render_network_log(ui),
+                DevToolsTab::Elements => {
+                    ui.label(RichText::new("Nothing to show yet").weak());
+                }
+            }
+        });
+    }
+    
+    /// Console of the active tab: filtered messages and a script input line
+    fn render_console(&mut self, ui: &mut Ui) {
+        let active_index = self.ui_state.active_tab_index.get();
+        
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("console_filter")
+                .selected_text(self.console_filter.map_or("All levels", ConsoleLevel::label))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.console_filter, None, "All levels");
+                    for level in ConsoleLevel::ALL {
+                        ui.selectable_value(&mut self.console_filter, Some(level), level.label());
+                    }
+                });
+            if ui.button("🗑 Clear").clicked() {
+                self.ui_state.clear_console(active_index);
+            }
+        });
+        
+        ui.separator();
+        
+        let messages = self.ui_state.get_active_tab().map(|tab| tab.console).unwrap_or_default();
+        let filter = self.console_filter;
+        ScrollArea::vertical()
+            .max_height(ui.available_height() - 36.0)
+            .stick_to_bottom(true)
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for message in messages.iter().filter(|message| filter.map_or(true, |level| message.level == level)) {
+                    let color = match message.level {
+                        ConsoleLevel::Error => Color32::from_rgb(217, 48, 37),
+                        ConsoleLevel::Warn => Color32::from_rgb(234, 179, 8),
+                        ConsoleLevel::Info | ConsoleLevel::Log => Color32::GRAY,
+                    };
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(RichText::new(&message.message).monospace().color(color));
+                        ui.label(RichText::new(format!("{}:{}", message.source, message.line)).small().weak());
+                    });
+                }
+            });
+        
+        ui.separator();
+        
+        let input = ui.add(
+            TextEdit::singleline(&mut self.console_input)
+                .font(egui::TextStyle::Monospace)
+                .hint_text("> Evaluate JavaScript")
+                .desired_width(f32::INFINITY)
+        );
+        if input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            let script = std::mem::take(&mut self.console_input);
+            self.evaluate_in_console(active_index, script.trim());
+            input.request_focus();
+        }
+    }
+    
+    /// Network log of the active tab; clicking a request shows its headers
+    fn render_network_log(&mut self, ui: &mut Ui) {
+        let active_index = self.ui_state.active_tab_index.get();
+        
+        ui.horizontal(|ui| {
+            if ui.button("🗑 Clear").clicked() {
+                self.ui_state.clear_network_log(active_index);
+                self.expanded_request = None;
+            }
+            ui.checkbox(&mut self.preserve_network_log, "Preserve log");
+        });
+        
+        ui.separator();
+        
+        let requests = self.ui_state.get_active_tab().map(|tab| tab.network).unwrap_or_default();
+        if requests.is_empty() {
+            ui.label(RichText::new("Requests show up here as the page loads").weak());
+            return;
+        }
+        
+        let red = Color32::from_rgb(217, 48, 37);
+        let cell = |ui: &mut Ui, width: f32, text: RichText| {
+            ui.add_sized(Vec2::new(width, 18.0), egui::Label::new(text).truncate(true));
+        };
+        let url_width = (ui.available_width() - 260.0).max(80.0);
+        
+        ui.horizontal(|ui| {
+            cell(ui, 52.0, RichText::new("Method").strong());
+            cell(ui, url_width, RichText::new("URL").strong());
+            cell(ui, 64.0, RichText::new("Status").strong());
+            cell(ui, 60.0, RichText::new("Size").strong());
+            cell(ui, 56.0, RichText::new("Time").strong());
+        });
+        
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for (index, request) in requests.iter().enumerate() {
+                    let failed = request.error.is_some() || request.status.map_or(false, |status| status >= 400);
+                    let color = if failed { red } else { ui.visuals().text_color() };
+                    
+                    let row = ui.horizontal(|ui| {
+                        cell(ui, 52.0, RichText::new(&request.method).monospace().color(color));
+                        cell(ui, url_width, RichText::new(&request.url).color(color));
+                        cell(ui, 64.0, RichText::new(request.status_label()).color(color));
+                        cell(ui, 60.0, RichText::new(request.size_label()));
+                        cell(ui, 56.0, RichText::new(format!("{} ms", request.duration.as_millis())));
+                    });
+                    let row = row.response.interact(egui::Sense::click());
+                    if row.hovered() {
+                        ui.painter().rect_filled(row.rect, 2.0, self.palette().hover_overlay);
+                    }
+                    if row.clicked() {
+                        self.expanded_request = if self.expanded_request == Some(index) { None } else { Some(index) };
+                    }
+                    
+                    if self.expanded_request == Some(index) {
+                        ui.indent(("network_request", index), |ui| render_request_details(ui, request));
+                    }
+                }
+            });
+    }
+    
+    /// Run console input in a tab, logging it and its result
+    fn evaluate_in_console(&mut self, index: usize, script: &str) {
+        if script.is_empty() {
+            return;
+        }
+        self.ui_state.push_console_message(index, ConsoleMessage::browser(ConsoleLevel::Log, format!("> {}", script)));
+        
+        let result = if !self.ui_state.javascript_enabled.get() {
+            Err("JavaScript is disabled in settings".to_string())
+        } else if let Some(runner) = self.script_runner.as_mut() {
+            runner(script)
+        } else {
+            Err("No script engine is attached to this window".to_string())
+        };
+        let message = match result {
+            Ok(value) => ConsoleMessage::browser(ConsoleLevel::Log, value),
+            Err(e) => ConsoleMessage::browser(ConsoleLevel::Error, e),
+        };
+        self.ui_state.push_console_message(index, message);
+    }
+    
+    /// Render the settings panel
+    fn render_settings_panel(&mut self, ctx: &egui::Context) {
+        if let Some(result) = self.health_receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            self.health_status = Some(match result {
+                Ok(()) => "✅ Genesis node is reachable".to_string(),
+                Err(e) => format!("❌ {}", e),
+            });
+            self.health_receiver = None;
+        }
+        
+        SidePanel::right("settings_panel").show(ctx, |ui| {
+            ui.heading("⚙ Settings");
+            ui.separator();
+            
+            ScrollArea::vertical().show(ui, |ui| {
+                ui.label(RichText::new("Content").strong());
+                let javascript_was_enabled = self.ui_state.javascript_enabled.get();
+                for (label, flag) in [
+                    ("Enable JavaScript", &self.ui_state.javascript_enabled),
+                    ("Load images", &self.ui_state.images_enabled),
+                    ("Enable WebGL", &self.ui_state.webgl_enabled),
+                    ("Block ads", &self.ui_state.adblock_enabled),
+                    ("Private mode", &self.ui_state.private_mode),
+                    ("Switch to already open tabs", &self.ui_state.dedupe_tabs),
+                ] {
+                    let mut value = flag.get();
+                    if ui.checkbox(&mut value, label).changed() {
+                        flag.set(value);
+                    }
+                }
+                let mut confirm_close = self.ui_state.confirm_close_tabs.get();
+                if ui.checkbox(&mut confirm_close, "Ask before closing multiple tabs").changed() {
+                    self.ui_state.set_confirm_close_tabs(confirm_close);
+                }
+                if self.ui_state.javascript_enabled.get() != javascript_was_enabled {
+                    // Webviews pick this up through `set_javascript_enabled` once Servo is wired in
+                    info!("📜 JavaScript {}", if javascript_was_enabled { "disabled" } else { "enabled" });
+                }
+                
                 ui.separator();
+                ui.label(RichText::new("Motion").strong());
+                ui.checkbox(&mut self.reduce_motion, "Reduce motion");
+                ui.add_enabled(
+                    !self.reduce_motion,
+                    egui::Slider::new(&mut self.animation_speed, MIN_ANIMATION_SPEED..=MAX_ANIMATION_SPEED).text("Animation speed")
+                );
                 
+                ui.separator();
+                ui.label(RichText::new("Accessibility").strong());
+                let mut accessibility = self.ui_state.accessibility_mode.get();
+                if ui.checkbox(&mut accessibility, "Larger click targets").changed() {
+                    self.ui_state.accessibility_mode.set(accessibility);
+                }
+                let mut scale = self.ui_state.hit_target_scale.get();
+                let slider = egui::Slider::new(&mut scale, MIN_HIT_TARGET_SCALE..=MAX_HIT_TARGET_SCALE)
+                    .text("Target size")
+                    .fixed_decimals(2);
+                if ui.add_enabled(accessibility, slider).changed() {
+                    self.ui_state.hit_target_scale.set(scale);
+                }
+                
+                ui.separator();
+                ui.label(RichText::new("Genesis node").strong());
+                ui.add(
+                    TextEdit::singleline(&mut self.node_url_input)
+                        .hint_text("http://localhost:3000")
+                        .desired_width(f32::INFINITY)
+                );
                 ui.horizontal(|ui| {
-                    ui.selectable_label(true, "Console");
-                    ui.selectable_label(false, "Network");
-                    ui.selectable_label(false, "Elements");
+                    let url_changed = self.node_url_input.trim() != self.genesis_node_url;
+                    if ui.add_enabled(url_changed, egui::Button::new("Apply")).clicked() {
+                        self.set_genesis_node_url(self.node_url_input.trim().to_string());
+                    }
+                    
+                    let testing = self.health_receiver.is_some();
+                    if ui.add_enabled(!testing, egui::Button::new("Test Genesis connection")).clicked() {
+                        self.test_genesis_connection();
+                    }
+                    if testing {
+                        ui.spinner();
+                    }
                 });
+                if let Some(status) = &self.health_status {
+                    ui.label(RichText::new(status).small());
+                }
                 
                 ui.separator();
+                ui.label(RichText::new("Privacy").strong());
+                ui.checkbox(&mut self.clear_history, "Browsing history");
+                ui.checkbox(&mut self.clear_downloads, "Downloads");
+                ui.checkbox(&mut self.clear_passwords, "Saved passwords");
+                ui.checkbox(&mut self.clear_cookies, "Cookies");
+                let anything_selected = self.clear_history || self.clear_downloads
+                    || self.clear_passwords || self.clear_cookies;
+                if ui.add_enabled(anything_selected, egui::Button::new("🧹 Clear browsing data")).clicked() {
+                    self.ui_state.clear_browsing_data(
+                        self.clear_history,
+                        self.clear_downloads,
+                        self.clear_passwords,
+                        self.clear_cookies,
+                    );
+                    #[cfg(feature = "password-manager")]
+                    if self.clear_passwords {
+                        self.password_panel = PasswordPanel::default();
+                    }
+                }
                 
-                ScrollArea::vertical().show(ui, |ui| {
-                    ui.monospace("genesis-browser: console initialized");
-                    ui.monospace("dns-resolver: genesis domains ready");
-                    ui.monospace("servo-engine: webrender active");
-                });
+                #[cfg(feature = "password-manager")]
+                self.render_password_settings(ui);
+            });
+        });
+    }
+    
+    /// Saved password list, unlocked with the master passphrase
+    #[cfg(feature = "password-manager")]
+    fn render_password_settings(&mut self, ui: &mut Ui) {
+        ui.separator();
+        ui.label(RichText::new("🔑 Passwords").strong());
+        
+        let panel = &mut self.password_panel;
+        let Some(store) = panel.store.as_mut() else {
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut panel.passphrase)
+                        .password(true)
+                        .hint_text("Master passphrase")
+                );
+                if ui.add_enabled(!panel.passphrase.is_empty(), egui::Button::new("Unlock")).clicked() {
+                    let entries = self.ui_state.passwords.borrow().clone();
+                    panel.store = Some(PasswordStore::from_entries(&panel.passphrase, entries));
+                    panel.passphrase.clear();
+                }
+            });
+            return;
+        };
+        
+        let mut remove = None;
+        for (index, entry) in store.entries().iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} · {}", entry.domain, entry.username));
+                let shown = panel.revealed.as_ref()
+                    .filter(|(revealed, _)| *revealed == index)
+                    .map(|(_, password)| password.clone());
+                match shown {
+                    Some(password) => {
+                        ui.monospace(password);
+                        if ui.small_button("Hide").clicked() {
+                            panel.revealed = None;
+                        }
+                    }
+                    None => {
+                        if ui.small_button("Show").clicked() {
+                            match store.get_password(&entry.domain, &entry.username) {
+                                Ok(Some(password)) => {
+                                    panel.revealed = Some((index, password));
+                                    panel.error = None;
+                                }
+                                Ok(None) => {}
+                                Err(e) => panel.error = Some(e.to_string()),
+                            }
+                        }
+                    }
+                }
+                if ui.small_button("🗑").clicked() {
+                    remove = Some((entry.domain.clone(), entry.username.clone()));
+                }
             });
         }
+        if store.entries().is_empty() {
+            ui.label(RichText::new("No saved passwords").color(Color32::GRAY));
+        }
+        
+        ui.add(TextEdit::singleline(&mut panel.domain).hint_text("Site"));
+        ui.add(TextEdit::singleline(&mut panel.username).hint_text("Username"));
+        ui.add(TextEdit::singleline(&mut panel.password).password(true).hint_text("Password"));
+        
+        let mut changed = false;
+        let mut lock = false;
+        ui.horizontal(|ui| {
+            let complete = !panel.domain.is_empty() && !panel.username.is_empty() && !panel.password.is_empty();
+            if ui.add_enabled(complete, egui::Button::new("➕ Save password")).clicked() {
+                match store.add_password(&panel.domain, &panel.username, &panel.password) {
+                    Ok(()) => {
+                        panel.password.clear();
+                        panel.error = None;
+                        changed = true;
+                    }
+                    Err(e) => panel.error = Some(e.to_string()),
+                }
+            }
+            lock = ui.button("🔒 Lock").clicked();
+        });
+        
+        if let Some((domain, username)) = remove {
+            if let Some(store) = panel.store.as_mut() {
+                store.remove_password(&domain, &username);
+            }
+            panel.revealed = None;
+            changed = true;
+        }
+        if changed {
+            if let Some(store) = &panel.store {
+                *self.ui_state.passwords.borrow_mut() = store.entries().to_vec();
+            }
+        }
+        if let Some(error) = &panel.error {
+            ui.label(RichText::new(error).small().color(Color32::from_rgb(239, 68, 68)));
+        }
+        if lock {
+            *panel = PasswordPanel::default();
+        }
     }
     
-    /// Navigate to a URL
-    fn navigate_to(&mut self, url: &str) {
-        info!("🔍 Modern UI navigating to: {}", url);
+    /// Point DNS resolution at a different Genesis node. The other configured
+    /// nodes stay behind it for failover, and every other resolver setting is kept.
+    fn set_genesis_node_url(&mut self, url: String) {
+        info!("🔗 Using Genesis node {}", url);
+        let nodes = &mut self.resolver_config.genesis_nodes;
+        nodes.retain(|node| *node != url);
+        nodes.insert(0, url.clone());
+        let (resolver, resolver_error) = build_resolver(&self.resolver_config, self.private_window, self.dns_cache_path.as_deref());
+        self.dns_resolver = Arc::new(tokio::sync::Mutex::new(resolver));
+        self.resolver_error = resolver_error;
+        self.genesis_node_url = url;
+        self.health_status = None;
+        self.node_health = None;
+        self.node_health_receiver = None;
+        self.node_health_checked_at = None;
+    }
+    
+    /// Apply the last node health check and start the next one every
+    /// `NODE_HEALTH_INTERVAL`
+    fn refresh_node_health(&mut self) {
+        if let Some(health) = self.node_health_receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            if self.node_health.as_ref().map(|previous| previous.online) != Some(health.online) {
+                info!("🩺 Genesis node {} is {}", self.genesis_node_url, health);
+            }
+            self.ui_state.genesis_connected.set(health.online);
+            *self.ui_state.genesis_node_status.borrow_mut() = format!("Genesis node {}", health);
+            self.node_health = Some(health);
+            self.node_health_receiver = None;
+        }
+        
+        // The status bar keeps showing why there's no resolver
+        let due = self.node_health_checked_at.map_or(true, |checked_at| checked_at.elapsed() >= NODE_HEALTH_INTERVAL);
+        if !due || self.node_health_receiver.is_some() || self.resolver_error.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let resolver = self.dns_resolver.clone();
+        let egui_ctx = self.egui_ctx.clone();
+        self.runtime_handle.spawn(async move {
+            let resolver = resolver.lock().await.detached();
+            let _ = sender.send(resolver.check_node_health().await);
+            wake(&egui_ctx);
+        });
+        self.node_health_receiver = Some(receiver);
+        self.node_health_checked_at = Some(Instant::now());
+    }
+    
+    /// Ping the Genesis node's health endpoint in the background
+    fn test_genesis_connection(&mut self) {
+        let (sender, receiver) = mpsc::channel();
+        let resolver = self.dns_resolver.clone();
+        let resolver_error = self.resolver_error.clone();
+        let egui_ctx = self.egui_ctx.clone();
+        self.runtime_handle.spawn(async move {
+            let resolver = resolver.lock().await.detached();
+            let health = match resolver_error {
+                Some(e) => Err(DnsError::InvalidProxy(e)),
+                None => resolver.check_health().await,
+            };
+            let _ = sender.send(health);
+            wake(&egui_ctx);
+        });
+        self.health_receiver = Some(receiver);
+        self.health_status = None;
+    }
+    
+    /// Navigate to a URL, resolving its host in the background
+    fn navigate_to(&mut self, input: &str) {
+        let url = match normalize_url(input) {
+            Ok(url) => url.to_string(),
+            Err(UrlError::SearchQuery(query)) => search_url(&self.ui_state.search_engine_template.borrow(), &query),
+            Err(UrlError::Empty) => return,
+            Err(e) => {
+                warn!("⚠️ {}", e);
+                self.toast = Some((e.to_string(), Instant::now()));
+                return;
+            }
+        };
+        let url = url.as_str();
+        
+        // Files are saved rather than opened
+        if is_download_url(url) {
+            let id = self.ui_state.add_download(download_filename(url), url.to_string(), 0);
+            self.start_download(id, url.to_string());
+            self.show_downloads = true;
+            return;
+        }
         
         let active_index = self.ui_state.active_tab_index.get();
-        self.ui_state.update_tab(active_index, Some(url.to_string()), Some(url.to_string()), Some(true));
+        if self.ui_state.dedupe_tabs.get() {
+            if let Some(index) = self.ui_state.find_tab_by_url(url).filter(|&index| index != active_index) {
+                info!("📑 {} is already open, switching to tab {}", url, index);
+                self.switch_tab(index);
+                return;
+            }
+        }
+        
+        self.ui_state.push_back_entry(active_index, url);
+        self.load_in_active_tab(url);
+    }
+    
+    /// Go back in the active tab's history. Forwarded to the tab's webview
+    /// `go_back` once Servo is wired in; until then the page is loaded again.
+    fn go_back(&mut self) {
+        if let Some(url) = self.ui_state.go_back(self.ui_state.active_tab_index.get()) {
+            info!("⬅️ Going back to {}", url);
+            self.load_in_active_tab(&url);
+        }
+    }
+    
+    /// Go forward in the active tab's history, the counterpart of `go_back`
+    fn go_forward(&mut self) {
+        if let Some(url) = self.ui_state.go_forward(self.ui_state.active_tab_index.get()) {
+            info!("➡️ Going forward to {}", url);
+            self.load_in_active_tab(&url);
+        }
+    }
+    
+    /// Load the active tab's page again. Becomes the webview's `reload` once
+    /// Servo is wired in.
+    fn reload(&mut self) {
+        if let Some(tab) = self.ui_state.get_active_tab() {
+            self.load_in_active_tab(&tab.url);
+        }
+    }
+    
+    /// Load a URL in the active tab without touching its back/forward history
+    fn load_in_active_tab(&mut self, url: &str) {
+        let active_index = self.ui_state.active_tab_index.get();
+        info!("🔍 Modern UI navigating to: {}", url);
+        self.ui_state.update_tab(active_index, Some(url.to_string()), Some(url.to_string()), Some(true), Some(SIMULATED_PROGRESS_START));
+        self.ui_state.set_tab_security_state(active_index, SecurityState::Neutral);
+        self.ui_state.set_tab_feeds(active_index, Vec::new());
+        self.ui_state.set_tab_error(active_index, None);
+        // A new document starts with an empty console
+        self.ui_state.clear_console(active_index);
+        
+        // Per-site overrides of the global settings
+        let content = self.ui_state.content_settings_for(url);
+        if let Some(zoom) = content.zoom {
+            self.ui_state.set_tab_zoom(active_index, zoom);
+        }
+        if content.javascript != self.ui_state.javascript_enabled.get() {
+            // Applied through `set_javascript_enabled` on the tab's webview once Servo is wired in
+            info!("📜 JavaScript {} on {} by site settings", if content.javascript { "enabled" } else { "disabled" }, url);
+        }
+        if !self.preserve_network_log {
+            self.ui_state.clear_network_log(active_index);
+            self.expanded_request = None;
+        }
         self.url_input = url.to_string();
         
         self.ui_state.add_to_history(url.to_string(), url.to_string());
         
-        self.ui_state.update_tab(active_index, None, None, Some(false));
+        if let Some(page) = InternalPage::parse(url) {
+            // Internal pages are rendered by the browser, no DNS involved
+            if page == InternalPage::Settings {
+                self.ui_state.show_settings.set(true);
+            }
+            let title = page.template(&self.ui_state.genesis_tlds.borrow()).title;
+            self.ui_state.update_tab(active_index, Some(title), None, Some(false), None);
+            return;
+        }
+        if source_target(url).is_some() {
+            // Fetched again when `render_page_source` next shows the tab
+            if let Some(tab) = self.ui_state.get_active_tab() {
+                self.page_sources.remove(&tab.id);
+            }
+            return;
+        }
+        let Some(host) = navigation_host(url) else {
+            // Other internal pages don't need DNS either
+            self.ui_state.update_tab(active_index, None, None, Some(false), None);
+            return;
+        };
+        let Some(tab) = self.ui_state.get_active_tab() else {
+            return;
+        };
+        
+        *self.ui_state.genesis_node_status.borrow_mut() = format!("Resolving {}...", host);
+        self.start_lookup(tab.id, url.to_string(), host, Duration::ZERO);
+    }
+    
+    /// Resolve `host` for a tab after `delay`, replacing the tab's pending lookup.
+    /// The outcome is applied by `poll_dns_results`.
+    fn start_lookup(&mut self, tab_id: String, url: String, host: String, delay: Duration) {
+        let resolver = self.dns_resolver.clone();
+        let resolver_error = self.resolver_error.clone();
+        let sender = self.dns_sender.clone();
+        let egui_ctx = self.egui_ctx.clone();
+        let lookup_tab_id = tab_id.clone();
+        let lookup = self.runtime_handle.spawn(async move {
+            tokio::time::sleep(delay).await;
+            let started = Instant::now();
+            let result = match resolver_error {
+                Some(e) => Err(DnsError::InvalidProxy(e)),
+                None => GenesisDnsResolver::resolve_shared(&resolver, &host).await,
+            };
+            let elapsed = started.elapsed();
+            let _ = sender.send(DnsOutcome { tab_id: lookup_tab_id, url, host, result, elapsed });
+            wake(&egui_ctx);
+        });
+        if let Some(previous) = self.pending_navigations.insert(tab_id, lookup) {
+            previous.abort();
+        }
+    }
+    
+    /// Reload every web tab, `RELOAD_ALL_STAGGER` apart so they don't all hit
+    /// the Genesis node at once. Each tab shows its own result or error page.
+    fn reload_all_tabs(&mut self) {
+        let targets = self.ui_state.begin_reload_all();
+        info!("🔄 Reloading {} tabs", targets.len());
+        
+        let mut delay = Duration::ZERO;
+        for (index, url) in targets {
+            let Some(tab_id) = self.ui_state.tabs.borrow().get(index).map(|tab| tab.id.clone()) else {
+                continue;
+            };
+            let Some(host) = navigation_host(&url) else {
+                self.ui_state.update_tab(index, None, None, Some(false), None);
+                continue;
+            };
+            self.ui_state.update_tab(index, None, None, None, Some(SIMULATED_PROGRESS_START));
+            self.start_lookup(tab_id, url, host, delay);
+            delay += RELOAD_ALL_STAGGER;
+        }
+        
+        if let Some(tab) = self.ui_state.get_active_tab() {
+            self.url_input = tab.url;
+        }
+        *self.ui_state.genesis_node_status.borrow_mut() = "Reloading all tabs...".to_string();
+    }
+    
+    /// Stop loading the active tab, aborting its pending resolution
+    fn stop_loading(&mut self) {
+        let active_index = self.ui_state.active_tab_index.get();
+        let Some(tab) = self.ui_state.get_active_tab() else {
+            return;
+        };
+        
+        if let Some(lookup) = self.pending_navigations.remove(&tab.id) {
+            lookup.abort();
+        }
+        self.ui_state.update_tab(active_index, None, None, Some(false), None);
+        *self.ui_state.genesis_node_status.borrow_mut() = "Stopped by user".to_string();
+        info!("⏹️ Stopped loading {}", tab.url);
+    }
+    
+    /// Apply finished DNS lookups to their tabs
+    fn poll_dns_results(&mut self) {
+        while let Ok(outcome) = self.dns_receiver.try_recv() {
+            // Keep the handle of a newer lookup for the same tab
+            if self.pending_navigations.get(&outcome.tab_id).map_or(false, |lookup| lookup.is_finished()) {
+                self.pending_navigations.remove(&outcome.tab_id);
+            }
+            
+            let index = self.ui_state.tabs.borrow().iter()
+                .position(|tab| tab.id == outcome.tab_id && tab.url == outcome.url);
+            // The tab was closed or navigated elsewhere in the meantime
+            let Some(index) = index else {
+                continue;
+            };
+            
+            let error = outcome.result.as_ref().err().map(|e| e.to_string());
+            self.ui_state.push_network_request(index, dns_request(&outcome, error));
+            
+            match outcome.result {
+                Ok(result) => {
+                    let is_genesis = matches!(
+                        result.resolver_type,
+                        ResolverType::Genesis | ResolverType::IPFS | ResolverType::Hybrid
+                    );
+                    info!("✅ {} resolved to {}", outcome.host, result);
+                    
+                    self.ui_state.set_tab_genesis_domain(index, is_genesis);
+                    self.ui_state.set_tab_error(index, None);
+                    self.request_feeds(outcome.tab_id.clone(), &outcome.url, result.ip_address);
+                    self.ui_state.set_tab_security_state(index, SecurityState::for_url(&outcome.url, is_genesis));
+                    self.ui_state.update_tab(index, Some(outcome.host.clone()), None, Some(false), None);
+                    self.ui_state.genesis_connected.set(is_genesis || self.ui_state.genesis_connected.get());
+                    self.ui_state.push_console_message(index, ConsoleMessage::browser(
+                        ConsoleLevel::Info,
+                        format!("{} resolved to {}", outcome.host, result)
+                    ));
+                    *self.ui_state.genesis_node_status.borrow_mut() = format!("{} → {}", outcome.host, result);
+                }
+                Err(e) => {
+                    warn!("⚠️ Failed to resolve {}: {}", outcome.host, e);
+                    self.ui_state.push_console_message(index, ConsoleMessage::browser(
+                        ConsoleLevel::Error,
+                        format!("Failed to resolve {}: {}", outcome.host, e)
+                    ));
+                    
+                    // The tab keeps its address so Reload retries the lookup
+                    let error = LoadError::dns(&e);
+                    let title = PageTemplate::load_error(&outcome.url, &error).title;
+                    self.ui_state.set_tab_error(index, Some(error));
+                    self.ui_state.update_tab(index, Some(title), None, Some(false), None);
+                    *self.ui_state.genesis_node_status.borrow_mut() = format!("DNS error: {}", e);
+                }
+            }
+        }
     }
 }
\ No newline at end of file
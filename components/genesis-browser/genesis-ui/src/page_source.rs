@@ -0,0 +1,115 @@
+// Raw page source for `view-source:` URLs
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::Context;
+
+/// Prefix that turns a page URL into a view of its raw response
+pub const VIEW_SOURCE_PREFIX: &str = "view-source:";
+
+/// Give up on fetching the source after this long
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Bodies are cut off after this many bytes
+pub const MAX_SOURCE_BYTES: usize = 5 * 1024 * 1024;
+
+/// The page a `view-source:` URL shows the source of
+pub fn source_target(url: &str) -> Option<&str> {
+    url.strip_prefix(VIEW_SOURCE_PREFIX)
+        .map(str::trim)
+        .filter(|target| !target.is_empty())
+}
+
+/// `view-source:` URL of a page; a URL that already is one is kept as it is
+pub fn view_source_url(url: &str) -> String {
+    if url.starts_with(VIEW_SOURCE_PREFIX) {
+        url.to_string()
+    } else {
+        format!("{}{}", VIEW_SOURCE_PREFIX, url)
+    }
+}
+
+/// A fetched response body, split into lines so the view only lays out
+/// the rows that are on screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageSource {
+    pub lines: Vec<String>,
+    /// The body was longer than `MAX_SOURCE_BYTES`
+    pub truncated: bool,
+}
+
+impl PageSource {
+    pub fn from_bytes(body: &[u8], truncated: bool) -> Self {
+        let text = String::from_utf8_lossy(body);
+        Self {
+            lines: text.lines().map(str::to_string).collect(),
+            truncated,
+        }
+    }
+    
+    /// The source as one string, for find-in-page
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// Fetch the raw body of `url`. `address` is where Genesis DNS resolved the
+/// host to; without one the system resolver is used.
+pub async fn fetch_source(url: &str, address: Option<IpAddr>) -> anyhow::Result<PageSource> {
+    let page_url = genesis_dns::normalize_url(url)?;
+    if !matches!(page_url.scheme(), "http" | "https") {
+        anyhow::bail!("Can't show the source of {} pages", page_url.scheme());
+    }
+    let (Some(host), Some(port)) = (page_url.host_str(), page_url.port_or_known_default()) else {
+        anyhow::bail!("{} has no host", page_url);
+    };
+    
+    let mut builder = reqwest::Client::builder().timeout(FETCH_TIMEOUT);
+    if let Some(address) = address {
+        builder = builder.resolve(host, SocketAddr::new(address, port));
+    }
+    let client = builder.build()?;
+    let mut response = client.get(page_url.clone()).send().await
+        .with_context(|| format!("Failed to fetch {}", page_url))?;
+    
+    // Error pages have a source too, so any status is shown
+    let mut body = Vec::new();
+    let mut truncated = false;
+    while let Some(chunk) = response.chunk().await.with_context(|| format!("Failed to read {}", page_url))? {
+        let room = MAX_SOURCE_BYTES - body.len();
+        if chunk.len() > room {
+            body.extend_from_slice(&chunk[..room]);
+            truncated = true;
+            break;
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(PageSource::from_bytes(&body, truncated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_view_source_urls() {
+        assert_eq!(view_source_url("http://x.genesis/"), "view-source:http://x.genesis/");
+        assert_eq!(view_source_url("view-source:http://x.genesis/"), "view-source:http://x.genesis/");
+        assert_eq!(source_target("view-source:http://x.genesis/"), Some("http://x.genesis/"));
+        assert_eq!(source_target("view-source: "), None);
+        assert_eq!(source_target("http://x.genesis/"), None);
+    }
+    
+    #[test]
+    fn test_source_is_split_into_lines() {
+        let source = PageSource::from_bytes(b"<html>\r\n<body>\n\n</body>\n", false);
+        assert_eq!(source.lines, ["<html>", "<body>", "", "</body>"]);
+        assert_eq!(source.text(), "<html>\n<body>\n\n</body>");
+        
+        // Invalid UTF-8 is shown rather than rejected
+        let source = PageSource::from_bytes(b"caf\xe9", true);
+        assert_eq!(source.lines, ["caf\u{fffd}"]);
+        assert!(source.truncated);
+    }
+}
@@ -0,0 +1,165 @@
+// Encrypted password storage
+// Passwords are encrypted with AES-256-GCM under a key derived from the master passphrase with Argon2
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use tracing::info;
+
+use crate::enhanced_browser::SavedPassword;
+
+/// Length of the per-password Argon2 salt
+const SALT_LEN: usize = 16;
+
+/// Length of the AES-GCM nonce
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PasswordError {
+    #[error("Key derivation failed: {0}")]
+    KeyDerivation(String),
+    
+    #[error("Encryption failed")]
+    Encryption,
+    
+    #[error("Decryption failed, wrong master passphrase?")]
+    Decryption,
+    
+    #[error("Stored password is corrupted")]
+    Corrupted,
+}
+
+/// Saved passwords unlocked with a master passphrase.
+///
+/// Each `encrypted_password` is `salt || nonce || ciphertext`, so entries
+/// can be stored and moved around on their own.
+pub struct PasswordStore {
+    passphrase: String,
+    entries: Vec<SavedPassword>,
+}
+
+impl PasswordStore {
+    /// Create an empty store
+    pub fn new(passphrase: &str) -> Self {
+        Self::from_entries(passphrase, Vec::new())
+    }
+    
+    /// Wrap previously saved passwords
+    pub fn from_entries(passphrase: &str, entries: Vec<SavedPassword>) -> Self {
+        Self {
+            passphrase: passphrase.to_string(),
+            entries,
+        }
+    }
+    
+    /// The saved passwords, still encrypted
+    pub fn entries(&self) -> &[SavedPassword] {
+        &self.entries
+    }
+    
+    /// Encrypt and save a password, replacing any existing one for the same login
+    pub fn add_password(&mut self, domain: &str, username: &str, plaintext: &str) -> Result<(), PasswordError> {
+        let encrypted_password = self.encrypt(plaintext)?;
+        
+        match self.entries.iter_mut().find(|e| e.domain == domain && e.username == username) {
+            Some(entry) => entry.encrypted_password = encrypted_password,
+            None => self.entries.push(SavedPassword {
+                domain: domain.to_string(),
+                username: username.to_string(),
+                encrypted_password,
+            }),
+        }
+        
+        info!("🔑 Saved password for {} on {}", username, domain);
+        Ok(())
+    }
+    
+    /// Decrypt the password saved for a login, `None` if there isn't one
+    pub fn get_password(&self, domain: &str, username: &str) -> Result<Option<String>, PasswordError> {
+        self.entries.iter()
+            .find(|e| e.domain == domain && e.username == username)
+            .map(|entry| self.decrypt(&entry.encrypted_password))
+            .transpose()
+    }
+    
+    /// Remove the password saved for a login
+    pub fn remove_password(&mut self, domain: &str, username: &str) {
+        self.entries.retain(|e| !(e.domain == domain && e.username == username));
+    }
+    
+    fn derive_key(&self, salt: &[u8]) -> Result<Key<Aes256Gcm>, PasswordError> {
+        let mut key = Key::<Aes256Gcm>::default();
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| PasswordError::KeyDerivation(e.to_string()))?;
+        Ok(key)
+    }
+    
+    fn encrypt(&self, plaintext: &str) -> Result<Vec<u8>, PasswordError> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        
+        let cipher = Aes256Gcm::new(&self.derive_key(&salt)?);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher.encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| PasswordError::Encryption)?;
+        
+        let mut blob = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+        blob.extend_from_slice(&salt);
+        blob.extend_from_slice(&nonce);
+        blob.extend_from_slice(&ciphertext);
+        Ok(blob)
+    }
+    
+    fn decrypt(&self, blob: &[u8]) -> Result<String, PasswordError> {
+        if blob.len() < SALT_LEN + NONCE_LEN {
+            return Err(PasswordError::Corrupted);
+        }
+        let (salt, rest) = blob.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        
+        // GCM authentication fails for a wrong key, so we never return garbage
+        let cipher = Aes256Gcm::new(&self.derive_key(salt)?);
+        let plaintext = cipher.decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| PasswordError::Decryption)?;
+        String::from_utf8(plaintext).map_err(|_| PasswordError::Corrupted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_round_trip() {
+        let mut store = PasswordStore::new("correct horse");
+        store.add_password("wiki.genesis", "alice", "hunter2").unwrap();
+        
+        let entry = &store.entries()[0];
+        assert!(!entry.encrypted_password.windows(7).any(|w| w == b"hunter2"));
+        assert_eq!(store.get_password("wiki.genesis", "alice").unwrap().as_deref(), Some("hunter2"));
+        assert_eq!(store.get_password("wiki.genesis", "bob").unwrap(), None);
+        
+        store.add_password("wiki.genesis", "alice", "n3w").unwrap();
+        assert_eq!(store.entries().len(), 1);
+        assert_eq!(store.get_password("wiki.genesis", "alice").unwrap().as_deref(), Some("n3w"));
+    }
+    
+    #[test]
+    fn test_wrong_passphrase_fails() {
+        let mut store = PasswordStore::new("correct horse");
+        store.add_password("dex.defi", "alice", "hunter2").unwrap();
+        
+        let locked = PasswordStore::from_entries("battery staple", store.entries().to_vec());
+        assert!(matches!(locked.get_password("dex.defi", "alice"), Err(PasswordError::Decryption)));
+        
+        let truncated = PasswordStore::from_entries("correct horse", vec![SavedPassword {
+            domain: "dex.defi".to_string(),
+            username: "alice".to_string(),
+            encrypted_password: vec![0; 8],
+        }]);
+        assert!(matches!(truncated.get_password("dex.defi", "alice"), Err(PasswordError::Corrupted)));
+    }
+}
+
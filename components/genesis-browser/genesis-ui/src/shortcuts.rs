@@ -0,0 +1,280 @@
+// Keyboard shortcuts: the table the modern UI dispatches input from and
+// renders its shortcut reference from
+
+use egui::{Key, Modifiers, PointerButton};
+
+/// Section of the shortcut reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutCategory {
+    Tabs,
+    Navigation,
+    Page,
+    View,
+}
+
+impl ShortcutCategory {
+    pub const ALL: [ShortcutCategory; 4] = [Self::Tabs, Self::Navigation, Self::Page, Self::View];
+    
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tabs => "Tabs",
+            Self::Navigation => "Navigation",
+            Self::Page => "Page",
+            Self::View => "View",
+        }
+    }
+}
+
+/// What a shortcut does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    NewTab,
+    NewPrivateWindow,
+    CloseTab,
+    ReopenClosedTab,
+    NextTab,
+    PreviousTab,
+    /// Switch to the tab numbered by the trigger's position in `keys`
+    JumpToTab,
+    Back,
+    Forward,
+    ReloadAll,
+    ToggleHistory,
+    /// Undo or redo the last bookmark edit, while the bookmarks panel is open
+    UndoBookmark,
+    RedoBookmark,
+    OpenNotes,
+    Find,
+    CopyUrl,
+    ViewSource,
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+    ToggleFullscreen,
+    /// Close the command palette, the shortcut reference, the find bar or
+    /// fullscreen, in that order
+    Escape,
+    ShowShortcuts,
+    CommandPalette,
+    TogglePerformanceOverlay,
+}
+
+/// Input that fires a shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    Key(Modifiers, Key),
+    /// A typed character, for keys egui has no name for such as "?"
+    Char(char),
+    Pointer(PointerButton),
+}
+
+impl Trigger {
+    /// Keys without Ctrl edit text, so they're left to a focused text field,
+    /// and so do Ctrl+Z and Ctrl+Y. Escape and F11 always work, as do the
+    /// mouse buttons.
+    pub fn works_while_typing(&self) -> bool {
+        match self {
+            Self::Key(modifiers, key) => {
+                (modifiers.command && !matches!(key, Key::Z | Key::Y)) || matches!(key, Key::Escape | Key::F11)
+            }
+            Self::Char(_) => false,
+            Self::Pointer(_) => true,
+        }
+    }
+    
+    /// How the trigger is written in the reference, e.g. "Ctrl+Shift+T"
+    pub fn label(&self) -> String {
+        match self {
+            Self::Key(modifiers, key) => {
+                let mut label = String::new();
+                if modifiers.command {
+                    label.push_str(if cfg!(target_os = "macos") { "Cmd+" } else { "Ctrl+" });
+                }
+                if modifiers.alt {
+                    label.push_str("Alt+");
+                }
+                if modifiers.shift {
+                    label.push_str("Shift+");
+                }
+                label.push_str(&key_label(*key));
+                label
+            }
+            Self::Char(c) => c.to_string(),
+            Self::Pointer(PointerButton::Extra1) => "Mouse back button".to_string(),
+            Self::Pointer(PointerButton::Extra2) => "Mouse forward button".to_string(),
+            Self::Pointer(button) => format!("{:?} mouse button", button),
+        }
+    }
+}
+
+fn key_label(key: Key) -> String {
+    let label = match key {
+        Key::ArrowLeft => "Left",
+        Key::ArrowRight => "Right",
+        Key::PlusEquals => "=",
+        Key::Minus => "-",
+        Key::Escape => "Esc",
+        Key::Num0 => "0",
+        Key::Num1 => "1",
+        Key::Num2 => "2",
+        Key::Num3 => "3",
+        Key::Num4 => "4",
+        Key::Num5 => "5",
+        Key::Num6 => "6",
+        Key::Num7 => "7",
+        Key::Num8 => "8",
+        Key::Num9 => "9",
+        _ => return format!("{:?}", key),
+    };
+    label.to_string()
+}
+
+/// A shortcut with the inputs that fire it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortcutDef {
+    pub keys: Vec<Trigger>,
+    pub description: &'static str,
+    pub category: ShortcutCategory,
+    pub action: ShortcutAction,
+}
+
+impl ShortcutDef {
+    /// The triggers as shown in the reference; long runs such as the tab
+    /// numbers are shortened to their ends
+    pub fn keys_label(&self) -> String {
+        let labels: Vec<String> = self.keys.iter().map(Trigger::label).collect();
+        match labels.as_slice() {
+            [first, .., last] if labels.len() > 2 => format!("{} … {}", first, last),
+            _ => labels.join(" / "),
+        }
+    }
+}
+
+/// Every shortcut, in the order input is checked. Shift variants come first
+/// so Ctrl+Shift+T is not read as Ctrl+T.
+pub fn shortcuts() -> Vec<ShortcutDef> {
+    use ShortcutAction as Action;
+    use ShortcutCategory as Category;
+    
+    let ctrl = Modifiers::COMMAND;
+    let ctrl_shift = Modifiers::COMMAND | Modifiers::SHIFT;
+    let def = |keys: Vec<Trigger>, description, category, action| ShortcutDef { keys, description, category, action };
+    let numbers = [
+        Key::Num1, Key::Num2, Key::Num3, Key::Num4, Key::Num5,
+        Key::Num6, Key::Num7, Key::Num8, Key::Num9,
+    ];
+    
+    vec![
+        def(vec![Trigger::Key(ctrl_shift, Key::T)], "Reopen the last closed tab", Category::Tabs, Action::ReopenClosedTab),
+        def(vec![Trigger::Key(ctrl, Key::T)], "New tab", Category::Tabs, Action::NewTab),
+        def(vec![Trigger::Key(ctrl_shift, Key::N)], "New private window", Category::Tabs, Action::NewPrivateWindow),
+        def(vec![Trigger::Key(ctrl, Key::W)], "Close the tab", Category::Tabs, Action::CloseTab),
+        def(vec![Trigger::Key(ctrl_shift, Key::Tab)], "Previous tab", Category::Tabs, Action::PreviousTab),
+        def(vec![Trigger::Key(ctrl, Key::Tab)], "Next tab", Category::Tabs, Action::NextTab),
+        def(
+            numbers.iter().map(|key| Trigger::Key(ctrl, *key)).collect(),
+            "Go to tab 1 to 9",
+            Category::Tabs,
+            Action::JumpToTab,
+        ),
+        def(
+            vec![Trigger::Key(Modifiers::ALT, Key::ArrowLeft), Trigger::Pointer(PointerButton::Extra1)],
+            "Back",
+            Category::Navigation,
+            Action::Back,
+        ),
+        def(
+            vec![Trigger::Key(Modifiers::ALT, Key::ArrowRight), Trigger::Pointer(PointerButton::Extra2)],
+            "Forward",
+            Category::Navigation,
+            Action::Forward,
+        ),
+        def(vec![Trigger::Key(ctrl_shift, Key::R)], "Reload all tabs", Category::Navigation, Action::ReloadAll),
+        def(vec![Trigger::Key(ctrl, Key::H)], "Show or hide history", Category::Navigation, Action::ToggleHistory),
+        def(vec![Trigger::Key(ctrl, Key::Z)], "Undo a bookmark edit", Category::Navigation, Action::UndoBookmark),
+        def(vec![Trigger::Key(ctrl, Key::Y)], "Redo a bookmark edit", Category::Navigation, Action::RedoBookmark),
+        def(vec![Trigger::Key(ctrl_shift, Key::O)], "Open the notes scratchpad", Category::Navigation, Action::OpenNotes),
+        def(vec![Trigger::Key(ctrl, Key::F)], "Find in page", Category::Page, Action::Find),
+        def(vec![Trigger::Key(ctrl_shift, Key::C)], "Copy the page address", Category::Page, Action::CopyUrl),
+        def(vec![Trigger::Key(ctrl, Key::U)], "View the page source in a new tab", Category::Page, Action::ViewSource),
+        def(
+            vec![Trigger::Key(ctrl_shift, Key::PlusEquals), Trigger::Key(ctrl, Key::PlusEquals)],
+            "Zoom in",
+            Category::Page,
+            Action::ZoomIn,
+        ),
+        def(vec![Trigger::Key(ctrl, Key::Minus)], "Zoom out", Category::Page, Action::ZoomOut),
+        def(vec![Trigger::Key(ctrl, Key::Num0)], "Reset zoom", Category::Page, Action::ZoomReset),
+        def(vec![Trigger::Key(Modifiers::NONE, Key::F11)], "Fullscreen", Category::View, Action::ToggleFullscreen),
+        def(
+            vec![Trigger::Key(Modifiers::NONE, Key::Escape)],
+            "Close this reference or the find bar, or leave fullscreen",
+            Category::View,
+            Action::Escape,
+        ),
+        def(vec![Trigger::Char('?')], "Show keyboard shortcuts", Category::View, Action::ShowShortcuts),
+        def(vec![Trigger::Key(ctrl_shift, Key::P)], "Search actions and open tabs", Category::View, Action::CommandPalette),
+        def(
+            vec![Trigger::Key(ctrl_shift, Key::I)],
+            "Show or hide the performance overlay",
+            Category::View,
+            Action::TogglePerformanceOverlay,
+        ),
+    ]
+}
+
+/// Label of the first trigger of an action, for menu entries
+pub fn shortcut_label(shortcuts: &[ShortcutDef], action: ShortcutAction) -> Option<String> {
+    shortcuts.iter()
+        .find(|shortcut| shortcut.action == action)
+        .and_then(|shortcut| shortcut.keys.first())
+        .map(Trigger::label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_shortcut_table_is_consistent() {
+        let shortcuts = shortcuts();
+        let triggers: Vec<Trigger> = shortcuts.iter().flat_map(|shortcut| shortcut.keys.clone()).collect();
+        for (index, trigger) in triggers.iter().enumerate() {
+            assert!(!triggers[..index].contains(trigger), "{} is bound twice", trigger.label());
+            
+            // A Shift variant checked after its plain key would never fire
+            if let Trigger::Key(modifiers, key) = trigger {
+                if modifiers.shift {
+                    let plain = Trigger::Key(Modifiers { shift: false, ..*modifiers }, *key);
+                    assert!(!triggers[..index].contains(&plain), "{} is checked too late", trigger.label());
+                }
+            }
+        }
+        for category in ShortcutCategory::ALL {
+            assert!(shortcuts.iter().any(|shortcut| shortcut.category == category));
+        }
+    }
+    
+    #[test]
+    fn test_shortcut_labels() {
+        let shortcuts = shortcuts();
+        let modifier = if cfg!(target_os = "macos") { "Cmd" } else { "Ctrl" };
+        assert_eq!(
+            shortcut_label(&shortcuts, ShortcutAction::ReloadAll),
+            Some(format!("{}+Shift+R", modifier))
+        );
+        
+        let jump = shortcuts.iter().find(|shortcut| shortcut.action == ShortcutAction::JumpToTab).unwrap();
+        assert_eq!(jump.keys_label(), format!("{0}+1 … {0}+9", modifier));
+        let back = shortcuts.iter().find(|shortcut| shortcut.action == ShortcutAction::Back).unwrap();
+        assert_eq!(back.keys_label(), "Alt+Left / Mouse back button");
+    }
+    
+    #[test]
+    fn test_text_editing_keys_stay_with_text_fields() {
+        assert!(Trigger::Key(Modifiers::COMMAND, Key::T).works_while_typing());
+        assert!(!Trigger::Key(Modifiers::COMMAND, Key::Z).works_while_typing());
+        assert!(!Trigger::Key(Modifiers::COMMAND, Key::Y).works_while_typing());
+        assert!(!Trigger::Char('?').works_while_typing());
+    }
+}
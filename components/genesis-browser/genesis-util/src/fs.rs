@@ -0,0 +1,14 @@
+// File helpers for the profile directory
+
+use std::path::Path;
+
+/// Write a file atomically by writing a temporary file and renaming it
+pub fn write_atomic(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, data)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
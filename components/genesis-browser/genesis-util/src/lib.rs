@@ -0,0 +1,5 @@
+// Genesis Util - Helpers shared by the Genesis Browser front-ends
+
+pub mod fs;
+pub mod memory;
+pub mod window_state;
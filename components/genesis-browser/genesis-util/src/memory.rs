@@ -0,0 +1,35 @@
+// Process memory reporting, for the status bar and kiosk monitoring
+
+/// Resident set size of this process in bytes, 0 where unsupported
+#[cfg(target_os = "linux")]
+pub fn resident_memory_bytes() -> u64 {
+    // statm fields are in pages: size resident shared text lib data dt
+    let Ok(statm) = std::fs::read_to_string("/proc/self/statm") else {
+        return 0;
+    };
+    let resident_pages: u64 = statm.split_whitespace()
+        .nth(1)
+        .and_then(|pages| pages.parse().ok())
+        .unwrap_or(0);
+    
+    // SAFETY: sysconf has no preconditions
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    resident_pages * u64::try_from(page_size).unwrap_or(0)
+}
+
+/// Resident set size of this process in bytes, 0 where unsupported
+#[cfg(not(target_os = "linux"))]
+pub fn resident_memory_bytes() -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_resident_memory_is_reported() {
+        assert!(resident_memory_bytes() > 0);
+    }
+}
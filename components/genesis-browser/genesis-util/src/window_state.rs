@@ -0,0 +1,120 @@
+// Window geometry saved in the profile so the browser reopens where it was
+// left, in the same file for the egui and Servo front-ends
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fs::write_atomic;
+
+/// Part of a restored window that must be on a monitor for it to stay where it was
+const MIN_VISIBLE: f32 = 48.0;
+
+/// Size, position and maximized state of the main window, in logical pixels
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    pub width: f32,
+    pub height: f32,
+    /// Top-left corner of the window frame; `None` lets the window manager choose
+    pub position: Option<[f32; 2]>,
+    pub maximized: bool,
+    /// Size of the monitor the window was on, to notice when the layout changed
+    #[serde(default)]
+    pub monitor_size: Option<[f32; 2]>,
+}
+
+/// A monitor's area on the desktop, in logical pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Monitor {
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+}
+
+impl WindowState {
+    /// Read the saved state
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let data = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+    
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        write_atomic(path, &serde_json::to_vec_pretty(self)?)
+    }
+    
+    /// The state moved onto `monitors` if it would open off-screen, e.g. on a
+    /// monitor that was disconnected since. The window moves into the first
+    /// monitor and shrinks to fit it; positions still visible are kept.
+    pub fn placed_on(&self, monitors: &[Monitor]) -> Self {
+        let (Some([x, y]), Some(primary)) = (self.position, monitors.first()) else {
+            return self.clone();
+        };
+        let visible = monitors.iter().any(|monitor| {
+            let [left, top] = monitor.position;
+            let [width, height] = monitor.size;
+            x + self.width.min(MIN_VISIBLE) <= left + width
+                && x + self.width >= left + MIN_VISIBLE.min(self.width)
+                && y >= top
+                && y + MIN_VISIBLE.min(self.height) <= top + height
+        });
+        if visible {
+            return self.clone();
+        }
+        
+        let [left, top] = primary.position;
+        let [monitor_width, monitor_height] = primary.size;
+        let width = self.width.min(monitor_width);
+        let height = self.height.min(monitor_height);
+        Self {
+            width,
+            height,
+            position: Some([
+                x.clamp(left, left + monitor_width - width),
+                y.clamp(top, top + monitor_height - height),
+            ]),
+            maximized: self.maximized,
+            monitor_size: Some(primary.size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn state(x: f32, y: f32) -> WindowState {
+        WindowState { width: 1200.0, height: 800.0, position: Some([x, y]), maximized: false, monitor_size: None }
+    }
+    
+    #[test]
+    fn test_placed_on_keeps_visible_windows() {
+        let monitors = [
+            Monitor { position: [0.0, 0.0], size: [1920.0, 1080.0] },
+            Monitor { position: [1920.0, 0.0], size: [2560.0, 1440.0] },
+        ];
+        assert_eq!(state(100.0, 50.0).placed_on(&monitors), state(100.0, 50.0));
+        // On the second monitor, and partly off the right edge of the first
+        assert_eq!(state(2500.0, 200.0).placed_on(&monitors), state(2500.0, 200.0));
+        assert_eq!(state(1000.0, 0.0).placed_on(&monitors), state(1000.0, 0.0));
+    }
+    
+    #[test]
+    fn test_placed_on_moves_windows_from_missing_monitors() {
+        let laptop = [Monitor { position: [0.0, 0.0], size: [1440.0, 900.0] }];
+        
+        let moved = state(2500.0, 200.0).placed_on(&laptop);
+        assert_eq!(moved.position, Some([240.0, 100.0]));
+        assert_eq!((moved.width, moved.height), (1200.0, 800.0));
+        
+        // Too big for the monitor: shrunk to fit, in its corner
+        let mut big = state(-3000.0, -100.0);
+        big.width = 2560.0;
+        let moved = big.placed_on(&laptop);
+        assert_eq!(moved.position, Some([0.0, 0.0]));
+        assert_eq!(moved.width, 1440.0);
+        
+        // Nothing to place without a saved position or known monitors
+        let unplaced = WindowState { position: None, ..state(0.0, 0.0) };
+        assert_eq!(unplaced.placed_on(&laptop), unplaced);
+        assert_eq!(state(5000.0, 0.0).placed_on(&[]), state(5000.0, 0.0));
+    }
+}
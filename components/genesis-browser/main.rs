@@ -2,6 +2,8 @@ use clap::{Parser, Subcommand};
 use tracing::{info, error};
 use tracing_subscriber;
 
+use genesis_ui::Config;
+
 // Import our local packages
 use genesis_dns;
 use genesis_integration;
@@ -21,13 +23,41 @@ struct Cli {
     #[arg(short, long)]
     verbose: bool,
     
-    /// Genesis node address
-    #[arg(long, default_value = "http://localhost:3000")]
-    genesis_node: String,
+    /// Config file, defaults to ~/.config/genesis-browser/config.toml
+    #[arg(long)]
+    config: Option<std::path::PathBuf>,
+    
+    /// Genesis node address, overriding the config's node list
+    #[arg(long)]
+    genesis_node: Option<String>,
     
     /// Enable traditional DNS fallback
     #[arg(long)]
     fallback: bool,
+    
+    /// Proxy for all traffic, e.g. socks5://127.0.0.1:9050
+    #[arg(long)]
+    proxy: Option<String>,
+    
+    /// Profile holding bookmarks, session and caches
+    #[arg(long, default_value = genesis_ui::enhanced_browser::DEFAULT_PROFILE)]
+    profile: String,
+    
+    /// Open a private window: an ephemeral profile that writes nothing to disk
+    #[arg(long)]
+    private: bool,
+    
+    /// Run page content in sandboxed child processes
+    #[arg(long)]
+    multiprocess: bool,
+    
+    /// Accessibility mode: larger click targets for tabs, buttons and window controls
+    #[arg(long)]
+    accessible: bool,
+    
+    /// Accept JSON commands on this Unix socket (headless mode only)
+    #[arg(long)]
+    control_socket: Option<std::path::PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -39,12 +69,12 @@ enum Commands {
         url: Option<String>,
         
         /// Window width
-        #[arg(long, default_value_t = 1200)]
-        width: u32,
+        #[arg(long)]
+        width: Option<u32>,
         
         /// Window height
-        #[arg(long, default_value_t = 800)]
-        height: u32,
+        #[arg(long)]
+        height: Option<u32>,
     },
     
     /// Test Genesis DNS resolution
@@ -53,8 +83,109 @@ enum Commands {
         domain: String,
     },
     
+    /// Resolve a domain through the Genesis node and print the record
+    Resolve {
+        /// Domain to resolve
+        domain: String,
+        
+        /// Print the record as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Measure resolution latency of the Genesis node, bypassing the cache
+    Benchmark {
+        /// Domain to resolve
+        domain: String,
+        
+        /// Number of resolutions
+        #[arg(short = 'n', long, default_value_t = 100)]
+        count: usize,
+        
+        /// Resolutions in flight at once
+        #[arg(short, long, default_value_t = 1)]
+        concurrency: usize,
+    },
+    
     /// Show Genesis browser information
     Info,
+    
+    /// Inspect or clear the persisted DNS cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    
+    /// Import or export the profile's bookmarks as a Netscape bookmark file
+    Bookmarks {
+        #[command(subcommand)]
+        action: BookmarkAction,
+    },
+    
+    /// Manage browser profiles
+    Profiles {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+    
+    /// Manage the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    
+    /// Save a page as a PNG (not supported yet: the engine can't read pages back)
+    #[cfg(feature = "screenshot")]
+    Screenshot {
+        /// Page to capture
+        #[arg(long)]
+        url: String,
+        
+        /// Where to write the PNG
+        #[arg(long)]
+        out: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Print entry counts and the hit/miss ratio
+    Stats,
+    
+    /// List cached domains and when they expire
+    List {
+        /// Only show entries whose TTL has run out
+        #[arg(long)]
+        expired_only: bool,
+    },
+    
+    /// Delete the cache file
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum BookmarkAction {
+    /// Add the bookmarks from an HTML export of another browser
+    Import {
+        file: std::path::PathBuf,
+    },
+    
+    /// Write all bookmarks to an HTML file
+    Export {
+        file: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileAction {
+    /// List the profiles that exist on disk
+    List,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Write a config file with the default settings
+    Init,
 }
 
 #[tokio::main]
@@ -76,23 +207,88 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Print banner
     print_banner();
     
+    // Command line flags override the config file
+    // `config init` runs before there is a config to load
+    let config_path = cli.config.clone().or_else(Config::default_path);
+    let mut config = match &cli.config {
+        _ if matches!(cli.command, Some(Commands::Config { .. })) => Config::default(),
+        Some(path) => Config::load(path)?,
+        None => Config::load_default()?,
+    };
+    if let Some(node) = &cli.genesis_node {
+        config.genesis_nodes = vec![node.clone()];
+    }
+    if cli.fallback {
+        config.fallback = true;
+    }
+    if cli.proxy.is_some() {
+        config.proxy = cli.proxy.clone();
+    }
+    if cli.multiprocess {
+        config.multiprocess = true;
+    }
+    if cli.accessible {
+        config.accessibility = true;
+    }
+    
+    if cli.control_socket.is_some() && !cfg!(feature = "remote-control") {
+        return Err("--control-socket needs a build with the remote-control feature".into());
+    }
+    
+    if !genesis_ui::enhanced_browser::is_valid_profile_name(&cli.profile) {
+        return Err(format!("Invalid profile name {:?}, use letters, digits, '-' and '_'", cli.profile).into());
+    }
+    let profile_dir = if cli.private {
+        None
+    } else {
+        genesis_ui::enhanced_browser::profile_dir(&cli.profile)
+    };
+    
     // Execute command
     match cli.command {
         Some(Commands::Start { url, width, height }) => {
             info!("Starting Genesis Browser...");
-            start_browser(&cli.genesis_node, url, width, height, cli.fallback).await?;
+            config.window_width = width.unwrap_or(config.window_width);
+            config.window_height = height.unwrap_or(config.window_height);
+            start_browser(config, profile_dir, url, cli.control_socket).await?;
         },
         Some(Commands::Test { domain }) => {
             info!("Testing DNS resolution for: {}", domain);
-            test_dns_resolution(&cli.genesis_node, &domain).await?;
+            resolve_domain(&config, &domain, false).await?;
+        },
+        Some(Commands::Resolve { domain, json }) => {
+            resolve_domain(&config, &domain, json).await?;
+        },
+        Some(Commands::Benchmark { domain, count, concurrency }) => {
+            benchmark_resolution(&config, &domain, count, concurrency).await?;
         },
         Some(Commands::Info) => {
-            show_info(&cli.genesis_node).await?;
+            show_info(&config).await?;
+        },
+        Some(Commands::Cache { action }) => {
+            manage_dns_cache(&config, profile_dir.as_deref(), action)?;
+        },
+        Some(Commands::Bookmarks { action }) => {
+            manage_bookmarks(profile_dir, action)?;
+        },
+        Some(Commands::Profiles { action }) => {
+            manage_profiles(action)?;
+        },
+        Some(Commands::Config { action }) => {
+            manage_config(config_path, action)?;
+        },
+        #[cfg(feature = "screenshot")]
+        Some(Commands::Screenshot { url, out }) => {
+            return Err(format!(
+                "Can't capture {} to {}: screenshots aren't supported yet, the engine can't read pages back",
+                url,
+                out.display()
+            ).into());
         },
         None => {
             // Default: start browser
             info!("Starting Genesis Browser with default settings...");
-            start_browser(&cli.genesis_node, None, 1200, 800, cli.fallback).await?;
+            start_browser(config, profile_dir, None, cli.control_socket).await?;
         }
     }
     
@@ -118,34 +314,26 @@ fn print_banner() {
 }
 
 async fn start_browser(
-    genesis_node: &str,
+    config: Config,
+    profile_dir: Option<std::path::PathBuf>,
     startup_url: Option<String>, 
-    width: u32, 
-    height: u32,
-    fallback: bool
+    control_socket: Option<std::path::PathBuf>
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let genesis_node = config.primary_node();
+    let fallback = config.fallback;
+    let (width, height) = (config.window_width, config.window_height);
     info!("Connecting to Genesis node at: {}", genesis_node);
     
     // Check Genesis node connectivity
-    let client = reqwest::Client::new();
-    match client.get(&format!("{}/health", genesis_node)).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                info!("✅ Connected to Genesis node");
-            } else {
-                error!("❌ Genesis node returned error: {}", response.status());
-                if !fallback {
-                    return Err("Genesis node not available and fallback disabled".into());
-                }
-            }
-        },
-        Err(e) => {
-            error!("❌ Failed to connect to Genesis node: {}", e);
-            if !fallback {
-                return Err("Genesis node not available and fallback disabled".into());
-            }
-            info!("🔄 Continuing with traditional DNS fallback");
+    let health = config.dns_resolver()?.check_node_health().await;
+    if health.online {
+        info!("✅ Connected to Genesis node ({})", health);
+    } else {
+        error!("❌ Genesis node {} is offline", genesis_node);
+        if !fallback {
+            return Err("Genesis node not available and fallback disabled".into());
         }
+        info!("🔄 Continuing with traditional DNS fallback");
     }
     
     // Check if Servo integration is available
@@ -155,23 +343,33 @@ async fn start_browser(
         info!("Window size: {}x{}", width, height);
         
         // Create browser configuration
-        let config = genesis_integration::BrowserConfig {
+        let browser_config = genesis_integration::BrowserConfig {
             enable_genesis_dns: true,
             enable_traditional_fallback: fallback,
             genesis_node_url: genesis_node.to_string(),
-            user_agent: "Genesis Browser/1.0 (Servo; Decentralized Web)".to_string(),
-            enable_javascript: true,
-            enable_webgl: true,
+            user_agent: genesis_ui::config::USER_AGENT.to_string(),
+            enable_javascript: config.javascript,
+            enable_images: config.images,
+            enable_webgl: config.webgl,
+            multiprocess: config.multiprocess,
+            genesis_tlds: config.genesis_tlds.clone(),
+            window_size: (width, height),
+            proxy_url: config.proxy.clone(),
+            ipfs_gateways: config.ipfs_gateways.clone(),
+            ipfs_api_url: config.ipfs_api_url.clone(),
+            auto_pin: config.auto_pin,
+            private_mode: profile_dir.is_none(),
+            ..Default::default()
         };
         
         // Initialize Servo-based browser engine
-        let browser_engine = genesis_integration::GenesisBrowserEngine::new(config).await?;
+        let browser_engine = genesis_integration::GenesisBrowserEngine::new(browser_config).await?;
         
         info!("🌐 Genesis Browser with Servo Engine running...");
-        info!("Supported domains: .genesis, .free, .web, .defi, .dao");
+        info!("Supported domains: {}", config.genesis_tlds.join(", "));
         
         // Run the browser GUI
-        run_genesis_browser_gui(browser_engine, startup_url).await?;
+        run_genesis_browser_gui(browser_engine, config, profile_dir, startup_url, control_socket).await?;
     }
     
     // Fallback to enhanced UI if Servo is not available
@@ -179,15 +377,19 @@ async fn start_browser(
     {
         info!("🚀 Starting Genesis Browser Enhanced UI...");
         info!("Window size: {}x{}", width, height);
+        if control_socket.is_some() {
+            tracing::warn!("⚠️ Remote control needs the Servo engine, ignoring --control-socket");
+        }
         
         // Use the enhanced browser UI with tab support
-        let ui_state = genesis_ui::BrowserUIState::default();
+        let ui_state = genesis_ui::BrowserUIState::with_profile(profile_dir.clone());
+        config.apply_to(&ui_state);
         
         // Set initial URL if provided
         if let Some(url) = startup_url.as_ref() {
             info!("🔍 Will open startup URL: {}", url);
             ui_state.url_input.replace(url.clone());
-            ui_state.update_tab(0, None, Some(url.clone()), Some(false));
+            ui_state.update_tab(0, None, Some(url.clone()), Some(false), None);
         }
         
         // Update Genesis connection status
@@ -207,13 +409,13 @@ async fn start_browser(
         info!("  ⭐ Bookmarks management");
         info!("  ⬇ Downloads tracking");
         info!("  🔧 Developer tools");
-        info!("  🌐 Genesis DNS: .genesis, .free, .web, .defi, .dao");
+        info!("  🌐 Genesis DNS: {}", config.genesis_tlds.join(", "));
         
         // Use modern UI if available, otherwise fallback
         #[cfg(feature = "modern-ui")]
         {
             info!("🎨 Starting Modern Genesis Browser UI (egui)");
-            genesis_ui::ModernGenesisBrowser::run()?;
+            genesis_ui::ModernGenesisBrowser::run_with_config(config.clone(), profile_dir)?;
         }
         
         #[cfg(not(feature = "modern-ui"))]
@@ -227,36 +429,236 @@ async fn start_browser(
     Ok(())
 }
 
-async fn test_dns_resolution(
-    genesis_node: &str,
-    domain: &str
+async fn resolve_domain(
+    config: &Config,
+    domain: &str,
+    json: bool
 ) -> Result<(), Box<dyn std::error::Error>> {
-    info!("Testing DNS resolution for domain: {}", domain);
+    let mut resolver = config.dns_resolver()?;
     
-    // TODO: Implement Genesis DNS resolution test
-    // For now, just check if domain ends with Genesis TLDs
-    let genesis_tlds = [".genesis", ".free", ".web", ".defi"];
-    let is_genesis_domain = genesis_tlds.iter().any(|tld| domain.ends_with(tld));
+    // A DnsError propagates out of main, which exits non-zero
+    let result = resolver.resolve(domain).await?;
     
-    if is_genesis_domain {
-        info!("🎯 Genesis domain detected: {}", domain);
-        info!("🔍 Attempting Genesis blockchain DNS lookup...");
-        
-        // Mock resolution for now
-        info!("✅ Resolved {} to Genesis blockchain", domain);
-    } else {
-        info!("🌐 Traditional domain: {}", domain);
-        info!("🔍 Would fallback to traditional DNS");
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+    
+    println!("Domain:   {}", result.original_domain);
+    println!("Resolver: {}", result.resolver_type.as_str());
+    if result.ip_addresses.is_empty() {
+        if let Some(ip) = result.ip_address {
+            println!("Address:  {}", ip);
+        }
+    }
+    for ip in &result.ip_addresses {
+        println!("Address:  {}", ip);
+    }
+    if let Some(hash) = &result.content_hash {
+        println!("Content:  ipfs://{}", hash);
+    }
+    println!("TTL:      {}s", result.ttl);
+    
+    Ok(())
+}
+
+/// Width of the longest histogram bar in characters
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Number of histogram buckets between the fastest and slowest sample
+const HISTOGRAM_BUCKETS: usize = 10;
+
+async fn benchmark_resolution(
+    config: &Config,
+    domain: &str,
+    count: usize,
+    concurrency: usize
+) -> Result<(), Box<dyn std::error::Error>> {
+    use futures::stream::{self, StreamExt};
+    use std::time::{Duration, Instant};
+    
+    if count == 0 || concurrency == 0 {
+        return Err("--count and --concurrency must be at least 1".into());
+    }
+    
+    let mut resolver = config.dns_resolver()?;
+    // One request per sample, so retries don't show up as latency
+    resolver.set_retry_policy(0, 0);
+    
+    println!("Resolving {} {} times against {} ({} at a time)",
+        domain, count, config.primary_node(), concurrency);
+    
+    let resolver = &resolver;
+    let started = Instant::now();
+    let samples: Vec<Result<Duration, genesis_dns::DnsError>> = stream::iter(0..count)
+        .map(|_| async move {
+            let started = Instant::now();
+            resolver.resolve_uncached(domain).await.map(|_| started.elapsed())
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+    let elapsed = started.elapsed();
+    
+    let mut latencies: Vec<Duration> = samples.iter().filter_map(|sample| sample.as_ref().ok().copied()).collect();
+    latencies.sort();
+    
+    println!("Success:  {}/{} ({:.1}%)", latencies.len(), count, latencies.len() as f64 * 100.0 / count as f64);
+    println!("Total:    {:.2?} ({:.1} resolutions/s)", elapsed, count as f64 / elapsed.as_secs_f64());
+    if let Some(Err(e)) = samples.iter().find(|sample| sample.is_err()) {
+        println!("Error:    {}", e);
+    }
+    let (Some(&min), Some(&max)) = (latencies.first(), latencies.last()) else {
+        return Err(format!("Every resolution of {} failed", domain).into());
+    };
+    
+    let mean = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+    println!("Min:      {:.2?}", min);
+    println!("Max:      {:.2?}", max);
+    println!("Mean:     {:.2?}", mean);
+    println!("p50:      {:.2?}", percentile(&latencies, 0.50));
+    println!("p95:      {:.2?}", percentile(&latencies, 0.95));
+    println!();
+    print_histogram(&latencies, min, max);
+    
+    Ok(())
+}
+
+/// Nearest-rank percentile of sorted, non-empty samples
+fn percentile(sorted: &[std::time::Duration], p: f64) -> std::time::Duration {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn print_histogram(sorted: &[std::time::Duration], min: std::time::Duration, max: std::time::Duration) {
+    let span = (max - min).as_secs_f64();
+    let mut buckets = [0usize; HISTOGRAM_BUCKETS];
+    for latency in sorted {
+        let offset = if span > 0.0 { (*latency - min).as_secs_f64() / span } else { 0.0 };
+        buckets[((offset * HISTOGRAM_BUCKETS as f64) as usize).min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+    
+    let largest = buckets.iter().copied().max().unwrap_or(1);
+    for (index, &bucket) in buckets.iter().enumerate() {
+        let lower = min + (max - min).mul_f64(index as f64 / HISTOGRAM_BUCKETS as f64);
+        let bar = "█".repeat(bucket * HISTOGRAM_WIDTH / largest);
+        println!("{:>10.2?} | {:<width$} {}", lower, bar, bucket, width = HISTOGRAM_WIDTH);
+    }
+}
+
+fn manage_dns_cache(
+    config: &Config,
+    profile_dir: Option<&std::path::Path>,
+    action: CacheAction
+) -> Result<(), Box<dyn std::error::Error>> {
+    let path = profile_dir
+        .ok_or("No profile directory, the private profile keeps no cache")?
+        .join(genesis_ui::enhanced_browser::DNS_CACHE_FILE);
+    if !path.exists() {
+        println!("No DNS cache at {}", path.display());
+        return Ok(());
+    }
+    
+    let mut resolver = genesis_dns::GenesisDnsResolver::new(config.primary_node().to_string(), false);
+    match action {
+        CacheAction::Stats => {
+            resolver.load_cache_from(&path)?;
+            let stats = resolver.cache_stats();
+            println!("Cache file:  {}", path.display());
+            println!("Entries:     {} ({} Genesis, {} traditional)",
+                stats.total_entries, stats.genesis_domains, stats.traditional_domains);
+            println!("Hits:        {}", stats.hits);
+            println!("Misses:      {}", stats.misses);
+            println!("Hit ratio:   {:.1}%", stats.hit_ratio() * 100.0);
+            println!("Evictions:   {}", stats.evictions);
+        },
+        CacheAction::List { expired_only } => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs() as i64;
+            let mut entries: Vec<_> = genesis_dns::read_cache_file(&path)?.into_values()
+                .filter(|result| !expired_only || resolver.is_cache_expired(result))
+                .collect();
+            entries.sort_by(|a, b| a.domain.cmp(&b.domain));
+            
+            for result in entries {
+                let expires_in = (result.timestamp + result.ttl) as i64 - now;
+                let expiry = if expires_in >= 0 {
+                    format!("expires in {}s", expires_in)
+                } else {
+                    format!("expired {}s ago", -expires_in)
+                };
+                println!("{:<40} {:<12} {}", result.domain, result.resolver_type.as_str(), expiry);
+            }
+        },
+        CacheAction::Clear => {
+            std::fs::remove_file(&path)?;
+            println!("Removed {}", path.display());
+        },
+    }
+    
+    Ok(())
+}
+
+fn manage_bookmarks(
+    profile_dir: Option<std::path::PathBuf>,
+    action: BookmarkAction
+) -> Result<(), Box<dyn std::error::Error>> {
+    let profile_dir = profile_dir.ok_or("No profile directory, the private profile keeps no bookmarks")?;
+    let state = genesis_ui::BrowserUIState::with_profile(Some(profile_dir));
+    
+    match action {
+        BookmarkAction::Import { file } => {
+            let imported = state.import_bookmarks_html(&file)?;
+            state.flush_bookmarks()?;
+            println!("Imported {} bookmarks from {}", imported, file.display());
+        },
+        BookmarkAction::Export { file } => {
+            state.export_bookmarks_html(&file)?;
+            println!("Exported {} bookmarks to {}", state.bookmarks.borrow().len(), file.display());
+        },
     }
     
     Ok(())
 }
 
-async fn show_info(genesis_node: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn manage_profiles(action: ProfileAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        ProfileAction::List => {
+            let profiles = genesis_ui::enhanced_browser::list_profiles()?;
+            if profiles.is_empty() {
+                println!("No profiles yet, '{}' is created on first start", genesis_ui::enhanced_browser::DEFAULT_PROFILE);
+            }
+            for profile in profiles {
+                println!("{}", profile);
+            }
+        },
+    }
+    
+    Ok(())
+}
+
+fn manage_config(
+    path: Option<std::path::PathBuf>,
+    action: ConfigAction
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        ConfigAction::Init => {
+            let path = path.ok_or("No profile directory on this system")?;
+            Config::init(&path)?;
+            println!("Wrote default config to {}", path.display());
+        },
+    }
+    
+    Ok(())
+}
+
+async fn show_info(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let genesis_node = config.primary_node();
     info!("=== Genesis Browser Information ===");
     info!("Version: {}", env!("CARGO_PKG_VERSION"));
     info!("Genesis Node: {}", genesis_node);
-    info!("Supported TLDs: .genesis, .free, .web, .defi");
+    info!("Supported TLDs: {}", config.genesis_tlds.join(", "));
     info!("Features:");
     info!("  ✅ Decentralized DNS");
     info!("  ✅ Blockchain integration");
@@ -264,18 +666,17 @@ async fn show_info(genesis_node: &str) -> Result<(), Box<dyn std::error::Error>>
     info!("  ✅ Community governance");
     info!("  ✅ Servo browser engine (ready - enable with --features servo-integration)");
     
-    // Try to get Genesis node info
-    let client = reqwest::Client::new();
-    match client.get(&format!("{}/info", genesis_node)).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                info!("✅ Genesis node is online");
-                // TODO: Parse and show node info
-            }
-        },
-        Err(_) => {
-            info!("❌ Genesis node is offline");
+    let health = config.dns_resolver()?.check_node_health().await;
+    if health.online {
+        info!("✅ Genesis node is online");
+        info!("  Version: {}", health.version.as_deref().unwrap_or("unknown"));
+        match health.block_height {
+            Some(height) => info!("  Block height: {}", height),
+            None => info!("  Block height: unknown"),
         }
+        info!("  Latency: {} ms", health.latency_ms);
+    } else {
+        info!("❌ Genesis node is offline");
     }
     
     Ok(())
@@ -284,7 +685,10 @@ async fn show_info(genesis_node: &str) -> Result<(), Box<dyn std::error::Error>>
 /// Run Genesis Browser with GUI
 async fn run_genesis_browser_gui(
     mut browser_engine: genesis_integration::GenesisBrowserEngine,
-    startup_url: Option<String>
+    config: Config,
+    profile_dir: Option<std::path::PathBuf>,
+    startup_url: Option<String>,
+    control_socket: Option<std::path::PathBuf>
 ) -> Result<(), Box<dyn std::error::Error>> {
     use winit::event_loop::{EventLoop, ControlFlow};
     use winit::event::{Event, WindowEvent};
@@ -295,12 +699,15 @@ async fn run_genesis_browser_gui(
     match EventLoop::new() {
         Ok(event_loop) => {
             info!("✅ GUI mode available - starting enhanced UI with Servo");
+            if let Some(path) = &control_socket {
+                tracing::warn!("⚠️ Remote control is only available in headless mode, not listening on {}", path.display());
+            }
             
             info!("🎨 Modern Genesis Browser UI with egui starting...");
             info!("✨ Features:");
             info!("  📑 Multiple tabs with modern styling");
             info!("  ⬅➡ Navigation controls (back/forward/reload)");
-            info!("  🌐 Genesis DNS: .genesis, .free, .web, .defi, .dao");
+            info!("  🌐 Genesis DNS: {}", config.genesis_tlds.join(", "));
             info!("  🖱️  Mouse interaction and smooth animations");
             info!("  ⌨️  Keyboard shortcuts: Ctrl+T (new tab), Ctrl+W (close)");
             info!("  🚀 144 FPS GPU-accelerated rendering");
@@ -315,13 +722,13 @@ async fn run_genesis_browser_gui(
             #[cfg(feature = "modern-ui")]
             {
                 info!("🎨 Starting Modern Genesis Browser UI (egui)");
-                return Ok(genesis_ui::ModernGenesisBrowser::run()?);
+                return Ok(genesis_ui::ModernGenesisBrowser::run_with_config(config, profile_dir)?);
             }
             
             #[cfg(not(feature = "modern-ui"))]
             {
                 // Fallback to enhanced UI if modern-ui feature is not enabled
-                let mut enhanced_ui = genesis_ui::GenesisBrowserUI::new("http://localhost:3000".to_string())?;
+                let mut enhanced_ui = genesis_ui::GenesisBrowserUI::new(config.primary_node().to_string())?;
                 return enhanced_ui.run().await;
             }
         },
@@ -330,29 +737,50 @@ async fn run_genesis_browser_gui(
             info!("⚠️ GUI not available: {}", e);
             info!("🖥️ Falling back to headless mode");
             
+            // Ctrl-C asks the run loop to shut down
+            let (shutdown_sender, shutdown_receiver) = tokio::sync::oneshot::channel();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    let _ = shutdown_sender.send(());
+                }
+            });
+            
+            // Bind before starting so a busy socket fails fast
+            #[cfg(feature = "remote-control")]
+            let control_server = match &control_socket {
+                Some(path) => Some(genesis_integration::ControlServer::bind(path).await?),
+                None => None,
+            };
+            
             // Start browser engine in headless mode
             browser_engine.start().await?;
             
             // Navigate to startup URL if provided
-            if let Some(url) = startup_url {
-                info!("🔍 Opening startup URL in headless mode: {}", url);
-                browser_engine.navigate(&url).await?;
-            } else {
-                info!("🔍 Opening welcome page in headless mode");
-                browser_engine.navigate("http://welcome.genesis").await?;
+            let url = match startup_url {
+                Some(url) => {
+                    info!("🔍 Opening startup URL in headless mode: {}", url);
+                    url
+                }
+                None => {
+                    info!("🔍 Opening welcome page in headless mode");
+                    "http://welcome.genesis".to_string()
+                }
+            };
+            if let Err(e) = browser_engine.navigate(&url).await {
+                browser_engine.stop().await?;
+                return Err(e.into());
             }
             
-            info!("🚀 Genesis Browser running in headless mode");
-            info!("📊 Testing Genesis DNS resolution...");
-            
-            // Run browser in headless mode for demonstration
-            for i in 1..=5 {
-                info!("🔄 Headless browser loop iteration {}/5", i);
-                browser_engine.run().await?;
-                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            info!("🚀 Genesis Browser running in headless mode, press Ctrl-C to quit");
+            #[cfg(feature = "remote-control")]
+            match control_server {
+                Some(server) => browser_engine.run_with_control(shutdown_receiver, server).await?,
+                None => browser_engine.run_until_shutdown(shutdown_receiver).await?,
             }
+            #[cfg(not(feature = "remote-control"))]
+            browser_engine.run_until_shutdown(shutdown_receiver).await?;
             
-            info!("✅ Genesis Browser headless demo completed!");
+            info!("✅ Genesis Browser shut down cleanly");
         }
     }
     